@@ -2,6 +2,7 @@ use std::path::Path;
 use std::time::Duration;
 
 use source_stack_desktop_tauri_lib::core::document_parser::ResumeDocumentParser;
+use source_stack_desktop_tauri_lib::core::field_extractor;
 use source_stack_desktop_tauri_lib::core::models::ParsedCandidate;
 use source_stack_desktop_tauri_lib::core::ocr::TesseractCliOcrService;
 use source_stack_desktop_tauri_lib::core::pdf::PdfTextExtractor;
@@ -42,11 +43,26 @@ async fn main() -> anyhow::Result<()> {
     let candidate = ParsedCandidate {
         drive_file_id: None,
         source_file: Some(file_name),
+        mime_type: None,
         name: parsed.name,
+        email_domain: parsed
+            .email
+            .as_deref()
+            .and_then(field_extractor::email_domain),
         email: parsed.email,
         phone: parsed.phone,
+        phone_extension: parsed.phone_extension,
         linked_in: parsed.linked_in,
         git_hub: parsed.git_hub,
+        skills: parsed.skills,
+        headline: parsed.headline,
+        notice_period: parsed.notice_period,
+        field_sources: parsed.field_sources,
+        name_candidates: parsed.name_candidates,
+        ocr_used: parsed.ocr_used,
+        parse_duration_ms: None,
+        custom: parsed.custom,
+        education: parsed.education,
         confidence: parsed.confidence,
         errors: parsed.errors,
     };
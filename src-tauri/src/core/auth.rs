@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 use std::fs;
 use std::io::{ErrorKind, Read, Write};
-use std::net::TcpListener;
+use std::net::{TcpListener, TcpStream};
 use std::path::{Path, PathBuf};
 use std::thread;
 use std::time::{Duration, Instant};
@@ -10,16 +10,21 @@ use anyhow::Context;
 use base64::engine::general_purpose::URL_SAFE_NO_PAD;
 use base64::Engine;
 use chrono::{DateTime, Utc};
+use futures::future::BoxFuture;
 use rand::distr::Alphanumeric;
 use rand::Rng;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use tokio::sync::Mutex;
+use tokio_util::sync::CancellationToken;
 use url::Url;
 use uuid::Uuid;
 
+use super::auth_provider::AuthProvider;
+use super::credential_store::{write_private_file, CredentialStore};
 use super::errors::{AuthErrorCode, CoreError};
+use super::events::{EventSink, AUTH_STATUS_CHANGED};
 use super::models::{
     resolve_env_value, AuthStatus, GoogleSignInResult, ManualAuthChallenge,
     ManualAuthCompleteRequest, RuntimeSettings,
@@ -27,7 +32,7 @@ use super::models::{
 use super::settings_store::app_data_root;
 
 const TOKEN_KEYRING_SERVICE: &str = "com.sourcestack.desktop.google";
-const TOKEN_KEYRING_USERNAME: &str = "default";
+const TOKEN_FALLBACK_FILE: &str = "google-auth-token.enc.json";
 const TOKEN_CACHE_FILE: &str = "google-auth-token.json";
 
 const DEFAULT_AUTH_AUTHORIZE: &str = "https://accounts.google.com/o/oauth2/v2/auth";
@@ -36,7 +41,14 @@ const DEFAULT_USERINFO: &str = "https://www.googleapis.com/oauth2/v2/userinfo";
 const DEFAULT_WEB_REDIRECT_PATH: &str = "/api/auth/callback/google";
 
 const MANUAL_SESSION_TTL_SECONDS: i64 = 10 * 60;
+/// Custom URL scheme a web redirect page can hand the callback back on, e.g.
+/// `sourcestack://callback?code=...&state=...`.
+pub const DEEP_LINK_SCHEME: &str = "sourcestack";
 const LOOPBACK_WAIT_SECONDS: u64 = 90;
+const CALLBACK_READ_TIMEOUT: Duration = Duration::from_secs(10);
+const CALLBACK_MAX_REQUEST_BYTES: usize = 64 * 1024;
+/// Access tokens this close to expiry are refreshed before use.
+const TOKEN_REFRESH_MARGIN: Duration = Duration::from_secs(5 * 60);
 
 const SCOPES: &[&str] = &[
     "openid",
@@ -113,6 +125,23 @@ struct ManualAuthSession {
     expires_at: DateTime<Utc>,
 }
 
+/// Google's `prompt` for the authorize URL. `Consent` guarantees a refresh token, so it is
+/// used until one is stored; later sign-ins only ask which account to use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SignInPrompt {
+    Consent,
+    SelectAccount,
+}
+
+impl SignInPrompt {
+    fn as_str(self) -> &'static str {
+        match self {
+            SignInPrompt::Consent => "consent",
+            SignInPrompt::SelectAccount => "select_account",
+        }
+    }
+}
+
 #[derive(Debug)]
 struct OAuthCallback {
     code: String,
@@ -123,6 +152,11 @@ pub struct GoogleAuthService {
     client: Client,
     endpoints: AuthEndpoints,
     manual_sessions: Mutex<HashMap<String, ManualAuthSession>>,
+    event_sink: std::sync::RwLock<Option<EventSink>>,
+    token_cache_path: PathBuf,
+    token_store: CredentialStore,
+    /// The loopback wait of the sign-in in progress, tagged with an attempt id.
+    interactive_sign_in: std::sync::Mutex<Option<(Uuid, CancellationToken)>>,
 }
 
 impl GoogleAuthService {
@@ -131,6 +165,23 @@ impl GoogleAuthService {
             client,
             endpoints: AuthEndpoints::default(),
             manual_sessions: Mutex::new(HashMap::new()),
+            event_sink: std::sync::RwLock::new(None),
+            token_cache_path: token_cache_path(),
+            token_store: CredentialStore::new(TOKEN_KEYRING_SERVICE, TOKEN_FALLBACK_FILE),
+            interactive_sign_in: std::sync::Mutex::new(None),
+        }
+    }
+
+    /// Keeps every credential under the directory of `token_cache_path`, never touching
+    /// the OS keychain.
+    #[cfg(test)]
+    pub(crate) fn with_token_cache_path(client: Client, token_cache_path: PathBuf) -> Self {
+        Self {
+            token_store: CredentialStore::without_keyring(
+                token_cache_path.with_file_name(TOKEN_FALLBACK_FILE),
+            ),
+            token_cache_path,
+            ..Self::new(client)
         }
     }
 
@@ -140,6 +191,33 @@ impl GoogleAuthService {
             client,
             endpoints,
             manual_sessions: Mutex::new(HashMap::new()),
+            event_sink: std::sync::RwLock::new(None),
+            token_cache_path: token_cache_path(),
+            token_store: CredentialStore::new(TOKEN_KEYRING_SERVICE, TOKEN_FALLBACK_FILE),
+            interactive_sign_in: std::sync::Mutex::new(None),
+        }
+    }
+
+    /// Stops the browser sign-in that is waiting for its loopback callback, which then fails
+    /// with a cancelled error. Returns whether a sign-in was waiting.
+    pub fn cancel_sign_in(&self) -> bool {
+        let pending = self
+            .interactive_sign_in
+            .lock()
+            .ok()
+            .and_then(|mut pending| pending.take());
+        match pending {
+            Some((_, cancel)) => {
+                cancel.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn set_event_sink(&self, sink: EventSink) {
+        if let Ok(mut guard) = self.event_sink.write() {
+            *guard = Some(sink);
         }
     }
 
@@ -229,7 +307,7 @@ impl GoogleAuthService {
                 &code,
                 &session.code_verifier,
                 &session.redirect_uri,
-                None,
+                self.load_token().ok().flatten(),
             )
             .await?;
         self.save_token(&token)?;
@@ -246,6 +324,50 @@ impl GoogleAuthService {
         })
     }
 
+    /// Finishes a manual sign-in from a deep-link callback, finding the session by the
+    /// `state` the link carries. The paste flow keeps working for the same session.
+    pub async fn complete_deep_link_sign_in(
+        &self,
+        settings: &RuntimeSettings,
+        url: &str,
+    ) -> anyhow::Result<AuthStatus> {
+        let state = parse_state_from_callback_url(url).ok_or_else(|| {
+            CoreError::auth(
+                AuthErrorCode::InvalidCallback,
+                "Sign-in link is missing its state.",
+            )
+        })?;
+        let session_id = self
+            .manual_session_id_for_state(&state)
+            .await
+            .ok_or_else(|| {
+                CoreError::auth(
+                    AuthErrorCode::SessionNotFound,
+                    "Sign-in link does not match a pending sign-in. Start sign-in again.",
+                )
+            })?;
+
+        let status = self
+            .complete_manual_sign_in(
+                settings,
+                ManualAuthCompleteRequest {
+                    session_id,
+                    callback_url_or_code: url.to_string(),
+                },
+            )
+            .await?;
+        self.emit_status_changed(status.clone());
+        Ok(status)
+    }
+
+    pub async fn manual_session_id_for_state(&self, state: &str) -> Option<String> {
+        let sessions = self.manual_sessions.lock().await;
+        sessions
+            .values()
+            .find(|session| session.state == state)
+            .map(|session| session.session_id.clone())
+    }
+
     pub fn sign_out(&self) -> anyhow::Result<()> {
         self.clear_token()?;
         let mut sessions = self.manual_sessions.blocking_lock();
@@ -254,23 +376,25 @@ impl GoogleAuthService {
     }
 
     pub fn status(&self) -> anyhow::Result<AuthStatus> {
-        if let Some(token) = self.load_token()? {
-            return Ok(AuthStatus {
-                signed_in: true,
-                email: token.email,
-                name: token.name,
-                picture: token.picture,
-                expires_at: Some(token.expires_at_utc),
-            });
-        }
+        Ok(auth_status_for(self.load_token()?.as_ref()))
+    }
 
-        Ok(AuthStatus {
-            signed_in: false,
-            email: None,
-            name: None,
-            picture: None,
-            expires_at: None,
-        })
+    /// Whether a stored refresh token lets jobs get fresh access tokens without a browser.
+    /// Only inspects the cached token; nothing is refreshed.
+    pub fn has_refresh_token(&self) -> anyhow::Result<bool> {
+        Ok(self.load_token()?.is_some_and(|token| {
+            token
+                .refresh_token
+                .is_some_and(|refresh_token| !refresh_token.trim().is_empty())
+        }))
+    }
+
+    fn sign_in_prompt(&self) -> SignInPrompt {
+        if self.has_refresh_token().unwrap_or(false) {
+            SignInPrompt::SelectAccount
+        } else {
+            SignInPrompt::Consent
+        }
     }
 
     pub async fn get_access_token_non_interactive(
@@ -283,16 +407,18 @@ impl GoogleAuthService {
             CoreError::auth(AuthErrorCode::SignInRequired, "Google sign-in required.")
         })?;
 
-        if !cached.is_expiring_within(Duration::from_secs(5 * 60)) {
+        if !cached.is_expiring_within(TOKEN_REFRESH_MARGIN) {
             return Ok(cached.access_token);
         }
 
-        let refresh_token = cached.refresh_token.clone().ok_or_else(|| {
-            CoreError::auth(
+        let Some(refresh_token) = cached.refresh_token.clone() else {
+            self.emit_status_changed(auth_status_for(Some(&cached)));
+            return Err(CoreError::auth(
                 AuthErrorCode::ReauthRequired,
                 "Google session expired. Sign in again.",
             )
-        })?;
+            .into());
+        };
 
         match self.refresh_token(settings, &refresh_token).await {
             Ok(mut refreshed) => {
@@ -322,6 +448,22 @@ impl GoogleAuthService {
         }
     }
 
+    fn emit_status_changed(&self, status: AuthStatus) {
+        let sink = self
+            .event_sink
+            .read()
+            .ok()
+            .and_then(|guard| guard.as_ref().cloned());
+        let Some(sink) = sink else {
+            return;
+        };
+
+        match serde_json::to_value(&status) {
+            Ok(payload) => sink(AUTH_STATUS_CHANGED, payload),
+            Err(err) => eprintln!("google auth: failed to serialize auth status event: {err}"),
+        }
+    }
+
     fn validate_settings(&self, settings: &RuntimeSettings) -> anyhow::Result<()> {
         if settings.google_client_id.trim().is_empty() {
             return Err(CoreError::MissingGoogleClientId.into());
@@ -334,14 +476,14 @@ impl GoogleAuthService {
         match self.load_token_from_keyring() {
             Ok(Some(token)) => Ok(Some(token)),
             Ok(None) => {
-                let token = load_token_from_file_path(&token_cache_path())?;
+                let token = load_token_from_file_path(&self.token_cache_path)?;
                 if let Some(ref value) = token {
                     let _ = self.save_token_to_keyring(value);
                 }
                 Ok(token)
             }
             Err(keyring_error) => {
-                if let Some(token) = load_token_from_file_path(&token_cache_path())? {
+                if let Some(token) = load_token_from_file_path(&self.token_cache_path)? {
                     eprintln!(
                         "google auth: keychain read failed, using local token cache: {keyring_error}"
                     );
@@ -355,7 +497,7 @@ impl GoogleAuthService {
 
     fn save_token(&self, token: &GoogleTokenEnvelope) -> anyhow::Result<()> {
         let keyring_result = self.save_token_to_keyring(token);
-        let file_result = save_token_to_file_path(&token_cache_path(), token);
+        let file_result = save_token_to_file_path(&self.token_cache_path, token);
 
         match (&keyring_result, &file_result) {
             (Ok(()), _) | (_, Ok(())) => {
@@ -365,18 +507,19 @@ impl GoogleAuthService {
                 if let Err(err) = file_result {
                     eprintln!("google auth: local token cache write failed: {err}");
                 }
+                self.emit_status_changed(auth_status_for(Some(token)));
                 Ok(())
             }
             (Err(keyring_error), Err(file_error)) => Err(anyhow::anyhow!(
                 "failed to persist Google auth token in keychain and {}: {keyring_error}; {file_error}",
-                token_cache_path().display()
+                self.token_cache_path.display()
             )),
         }
     }
 
     fn clear_token(&self) -> anyhow::Result<()> {
         let keyring_result = self.clear_token_from_keyring();
-        let file_result = clear_token_file_path(&token_cache_path());
+        let file_result = clear_token_file_path(&self.token_cache_path);
 
         match (&keyring_result, &file_result) {
             (Ok(()), _) | (_, Ok(())) => {
@@ -386,21 +529,19 @@ impl GoogleAuthService {
                 if let Err(err) = file_result {
                     eprintln!("google auth: local token cache delete failed: {err}");
                 }
+                self.emit_status_changed(auth_status_for(None));
                 Ok(())
             }
             (Err(keyring_error), Err(file_error)) => Err(anyhow::anyhow!(
                 "failed to clear Google auth token from keychain and {}: {keyring_error}; {file_error}",
-                token_cache_path().display()
+                self.token_cache_path.display()
             )),
         }
     }
 
     fn load_token_from_keyring(&self) -> anyhow::Result<Option<GoogleTokenEnvelope>> {
-        let entry = keyring::Entry::new(TOKEN_KEYRING_SERVICE, TOKEN_KEYRING_USERNAME)?;
-        let raw = match entry.get_password() {
-            Ok(value) => value,
-            Err(keyring::Error::NoEntry) => return Ok(None),
-            Err(err) => return Err(err.into()),
+        let Some(raw) = self.token_store.load()? else {
+            return Ok(None);
         };
 
         let token = serde_json::from_str::<GoogleTokenEnvelope>(&raw)?;
@@ -408,18 +549,12 @@ impl GoogleAuthService {
     }
 
     fn save_token_to_keyring(&self, token: &GoogleTokenEnvelope) -> anyhow::Result<()> {
-        let entry = keyring::Entry::new(TOKEN_KEYRING_SERVICE, TOKEN_KEYRING_USERNAME)?;
         let json = serde_json::to_string(token)?;
-        entry.set_password(&json)?;
-        Ok(())
+        self.token_store.save(&json)
     }
 
     fn clear_token_from_keyring(&self) -> anyhow::Result<()> {
-        let entry = keyring::Entry::new(TOKEN_KEYRING_SERVICE, TOKEN_KEYRING_USERNAME)?;
-        match entry.delete_credential() {
-            Ok(_) | Err(keyring::Error::NoEntry) => Ok(()),
-            Err(err) => Err(err.into()),
-        }
+        self.token_store.clear()
     }
 
     async fn refresh_token(
@@ -516,10 +651,30 @@ impl GoogleAuthService {
             )
         })?;
 
+        let attempt = Uuid::new_v4();
+        let cancel = CancellationToken::new();
+        if let Ok(mut pending) = self.interactive_sign_in.lock() {
+            // Only one browser sign-in waits at a time; a newer one replaces the old wait.
+            if let Some((_, previous)) = pending.replace((attempt, cancel.clone())) {
+                previous.cancel();
+            }
+        }
+
         let callback = tokio::task::spawn_blocking(move || {
-            wait_for_oauth_callback(listener, port, Duration::from_secs(LOOPBACK_WAIT_SECONDS))
+            wait_for_oauth_callback(
+                listener,
+                port,
+                Duration::from_secs(LOOPBACK_WAIT_SECONDS),
+                &cancel,
+            )
         })
-        .await??;
+        .await;
+        if let Ok(mut pending) = self.interactive_sign_in.lock() {
+            if pending.as_ref().is_some_and(|(id, _)| *id == attempt) {
+                *pending = None;
+            }
+        }
+        let callback = callback??;
 
         if callback.state != session.state {
             return Err(CoreError::auth(
@@ -534,7 +689,7 @@ impl GoogleAuthService {
             &callback.code,
             &session.code_verifier,
             &session.redirect_uri,
-            None,
+            self.load_token().ok().flatten(),
         )
         .await
     }
@@ -575,6 +730,7 @@ impl GoogleAuthService {
             &state,
             &challenge,
             redirect_uri.as_str(),
+            self.sign_in_prompt(),
         )?
         .to_string();
 
@@ -588,13 +744,15 @@ impl GoogleAuthService {
         })
     }
 
+    /// `previous_token` lends its refresh token when Google sends none, which happens on
+    /// `select_account` sign-ins, but only if the same account signed in again.
     async fn exchange_authorization_code(
         &self,
         settings: &RuntimeSettings,
         code: &str,
         code_verifier: &str,
         redirect_uri: &str,
-        fallback_refresh_token: Option<String>,
+        previous_token: Option<GoogleTokenEnvelope>,
     ) -> anyhow::Result<GoogleTokenEnvelope> {
         let mut form = vec![
             ("client_id", settings.google_client_id.clone()),
@@ -639,12 +797,16 @@ impl GoogleAuthService {
         let payload = serde_json::from_str::<TokenResponse>(&body)?;
         let expires_at = Utc::now() + chrono::Duration::seconds(payload.expires_in);
         let profile = self.fetch_user_profile(&payload.access_token).await.ok();
+        let email = profile.as_ref().and_then(|value| value.email.clone());
+        let fallback_refresh_token = previous_token
+            .filter(|previous| previous.email.is_some() && previous.email == email)
+            .and_then(|previous| previous.refresh_token);
 
         Ok(GoogleTokenEnvelope {
             access_token: payload.access_token,
             refresh_token: payload.refresh_token.or(fallback_refresh_token),
             expires_at_utc: expires_at,
-            email: profile.as_ref().and_then(|value| value.email.clone()),
+            email,
             name: profile.as_ref().and_then(|value| value.name.clone()),
             picture: profile.and_then(|value| value.picture),
         })
@@ -675,6 +837,94 @@ impl GoogleAuthService {
     }
 }
 
+// Each method forwards to the inherent one of the same name, which takes precedence.
+impl AuthProvider for GoogleAuthService {
+    fn sign_in<'a>(
+        &'a self,
+        settings: &'a RuntimeSettings,
+    ) -> BoxFuture<'a, anyhow::Result<GoogleSignInResult>> {
+        Box::pin(GoogleAuthService::sign_in(self, settings))
+    }
+
+    fn begin_manual_sign_in<'a>(
+        &'a self,
+        settings: &'a RuntimeSettings,
+    ) -> BoxFuture<'a, anyhow::Result<ManualAuthChallenge>> {
+        Box::pin(GoogleAuthService::begin_manual_sign_in(self, settings))
+    }
+
+    fn complete_manual_sign_in<'a>(
+        &'a self,
+        settings: &'a RuntimeSettings,
+        request: ManualAuthCompleteRequest,
+    ) -> BoxFuture<'a, anyhow::Result<AuthStatus>> {
+        Box::pin(GoogleAuthService::complete_manual_sign_in(
+            self, settings, request,
+        ))
+    }
+
+    fn complete_deep_link_sign_in<'a>(
+        &'a self,
+        settings: &'a RuntimeSettings,
+        url: &'a str,
+    ) -> BoxFuture<'a, anyhow::Result<AuthStatus>> {
+        Box::pin(GoogleAuthService::complete_deep_link_sign_in(
+            self, settings, url,
+        ))
+    }
+
+    fn cancel_sign_in(&self) -> bool {
+        GoogleAuthService::cancel_sign_in(self)
+    }
+
+    fn sign_out(&self) -> anyhow::Result<()> {
+        GoogleAuthService::sign_out(self)
+    }
+
+    fn status(&self) -> anyhow::Result<AuthStatus> {
+        GoogleAuthService::status(self)
+    }
+
+    fn has_refresh_token(&self) -> anyhow::Result<bool> {
+        GoogleAuthService::has_refresh_token(self)
+    }
+
+    fn get_access_token_non_interactive<'a>(
+        &'a self,
+        settings: &'a RuntimeSettings,
+    ) -> BoxFuture<'a, anyhow::Result<String>> {
+        Box::pin(GoogleAuthService::get_access_token_non_interactive(
+            self, settings,
+        ))
+    }
+
+    fn set_event_sink(&self, sink: EventSink) {
+        GoogleAuthService::set_event_sink(self, sink)
+    }
+}
+
+/// A cached token reports signed out once it is about to expire with no refresh token, since
+/// only a new sign-in gets the app another access token.
+fn auth_status_for(token: Option<&GoogleTokenEnvelope>) -> AuthStatus {
+    match token {
+        Some(token) => AuthStatus {
+            signed_in: token.refresh_token.is_some()
+                || !token.is_expiring_within(TOKEN_REFRESH_MARGIN),
+            email: token.email.clone(),
+            name: token.name.clone(),
+            picture: token.picture.clone(),
+            expires_at: Some(token.expires_at_utc),
+        },
+        None => AuthStatus {
+            signed_in: false,
+            email: None,
+            name: None,
+            picture: None,
+            expires_at: None,
+        },
+    }
+}
+
 fn token_cache_path() -> PathBuf {
     app_data_root().join(TOKEN_CACHE_FILE)
 }
@@ -702,23 +952,8 @@ fn save_token_to_file_path(path: &Path, token: &GoogleTokenEnvelope) -> anyhow::
     }
 
     let json = serde_json::to_string(token)?;
-    fs::write(path, json)
-        .with_context(|| format!("failed to write token cache {}", path.display()))?;
-
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::PermissionsExt;
-
-        let permissions = fs::Permissions::from_mode(0o600);
-        fs::set_permissions(path, permissions).with_context(|| {
-            format!(
-                "failed to secure token cache permissions {}",
-                path.display()
-            )
-        })?;
-    }
-
-    Ok(())
+    write_private_file(path, json.as_bytes())
+        .with_context(|| format!("failed to write token cache {}", path.display()))
 }
 
 fn clear_token_file_path(path: &Path) -> anyhow::Result<()> {
@@ -737,6 +972,7 @@ fn build_authorize_url(
     state: &str,
     challenge: &str,
     redirect_uri: &str,
+    prompt: SignInPrompt,
 ) -> anyhow::Result<Url> {
     let scope = SCOPES.join(" ");
     let url = Url::parse_with_params(
@@ -747,7 +983,7 @@ fn build_authorize_url(
             ("response_type", "code"),
             ("scope", scope.as_str()),
             ("access_type", "offline"),
-            ("prompt", "consent"),
+            ("prompt", prompt.as_str()),
             ("state", state),
             ("code_challenge", challenge),
             ("code_challenge_method", "S256"),
@@ -834,6 +1070,7 @@ fn wait_for_oauth_callback(
     listener: TcpListener,
     port: u16,
     timeout: Duration,
+    cancel: &CancellationToken,
 ) -> anyhow::Result<OAuthCallback> {
     listener.set_nonblocking(true)?;
     let deadline = Instant::now() + timeout;
@@ -841,17 +1078,13 @@ fn wait_for_oauth_callback(
     loop {
         match listener.accept() {
             Ok((mut stream, _)) => {
-                let mut buffer = [0u8; 16_384];
-                let read = stream
-                    .read(&mut buffer)
-                    .context("failed to read OAuth callback request")?;
-                let request = String::from_utf8_lossy(&buffer[..read]);
-
-                let path = request
-                    .lines()
-                    .next()
-                    .and_then(|line| line.split_whitespace().nth(1))
-                    .unwrap_or("/");
+                // A connection that stalls or floods us is dropped; the real browser
+                // redirect can still arrive before the deadline.
+                let Ok(request_line) = read_callback_request_line(&mut stream) else {
+                    continue;
+                };
+
+                let path = request_line.split_whitespace().nth(1).unwrap_or("/");
 
                 let callback_url = format!("http://127.0.0.1:{port}{path}");
                 let callback = parse_callback_url_or_code(&callback_url, "")?;
@@ -872,6 +1105,13 @@ fn wait_for_oauth_callback(
                 });
             }
             Err(err) if err.kind() == ErrorKind::WouldBlock => {
+                if cancel.is_cancelled() {
+                    return Err(CoreError::auth(
+                        AuthErrorCode::SignInCancelled,
+                        "Google sign-in was cancelled.",
+                    )
+                    .into());
+                }
                 if Instant::now() >= deadline {
                     return Err(CoreError::auth(
                         AuthErrorCode::LoopbackTimeout,
@@ -892,6 +1132,41 @@ fn wait_for_oauth_callback(
     }
 }
 
+/// Reads from a loopback callback connection until the HTTP request line is complete.
+/// Gives up after `CALLBACK_READ_TIMEOUT` or once `CALLBACK_MAX_REQUEST_BYTES` have
+/// arrived without a line break.
+fn read_callback_request_line(stream: &mut TcpStream) -> anyhow::Result<String> {
+    // Accepted sockets inherit the listener's non-blocking mode on some platforms.
+    stream.set_nonblocking(false)?;
+    let deadline = Instant::now() + CALLBACK_READ_TIMEOUT;
+    let mut request = Vec::new();
+    let mut chunk = [0u8; 4096];
+
+    loop {
+        if let Some(end) = request.iter().position(|byte| *byte == b'\n') {
+            let line = String::from_utf8_lossy(&request[..end]);
+            return Ok(line.trim_end_matches('\r').to_string());
+        }
+        if request.len() >= CALLBACK_MAX_REQUEST_BYTES {
+            anyhow::bail!("OAuth callback request line exceeds {CALLBACK_MAX_REQUEST_BYTES} bytes");
+        }
+
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            anyhow::bail!("timed out reading OAuth callback request");
+        }
+        stream.set_read_timeout(Some(remaining))?;
+
+        let read = stream
+            .read(&mut chunk)
+            .context("failed to read OAuth callback request")?;
+        if read == 0 {
+            anyhow::bail!("OAuth callback connection closed before the request line ended");
+        }
+        request.extend_from_slice(&chunk[..read]);
+    }
+}
+
 fn parse_state_from_callback_url(input: &str) -> Option<String> {
     let parsed = Url::parse(input).ok()?;
     for (k, v) in parsed.query_pairs() {
@@ -912,7 +1187,10 @@ fn parse_callback_url_or_code(input: &str, expected_state: &str) -> anyhow::Resu
         .into());
     }
 
-    if trimmed.starts_with("http://") || trimmed.starts_with("https://") {
+    if trimmed.starts_with("http://")
+        || trimmed.starts_with("https://")
+        || trimmed.starts_with(&format!("{DEEP_LINK_SCHEME}://"))
+    {
         let parsed = Url::parse(trimmed).map_err(|_| {
             CoreError::auth(AuthErrorCode::InvalidCallback, "Invalid callback URL.")
         })?;
@@ -1027,6 +1305,7 @@ mod tests {
             max_retries: 3,
             retry_delay_seconds: 1.0,
             job_retention_hours: 24,
+            ..RuntimeSettings::default()
         }
     }
 
@@ -1076,6 +1355,61 @@ mod tests {
         assert!(load_token_from_file_path(&token_path).unwrap().is_none());
     }
 
+    #[test]
+    fn loopback_callback_assembles_request_line_split_across_writes() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let client = thread::spawn(move || {
+            let mut stream = std::net::TcpStream::connect(("127.0.0.1", port)).unwrap();
+            stream
+                .write_all(b"GET /callback?code=4%2F0Ab-long-code")
+                .unwrap();
+            stream.flush().unwrap();
+            thread::sleep(Duration::from_millis(50));
+            stream
+                .write_all(b"&state=abc123 HTTP/1.1\r\nHost: 127.0.0.1\r\n\r\n")
+                .unwrap();
+            let mut response = String::new();
+            let _ = stream.read_to_string(&mut response);
+            response
+        });
+
+        let callback = wait_for_oauth_callback(
+            listener,
+            port,
+            Duration::from_secs(5),
+            &CancellationToken::new(),
+        )
+        .unwrap();
+        assert_eq!(callback.code, "4/0Ab-long-code");
+        assert_eq!(callback.state, "abc123");
+        assert!(client.join().unwrap().starts_with("HTTP/1.1 200 OK"));
+    }
+
+    #[test]
+    fn cancelling_sign_in_unblocks_the_loopback_wait() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let cancel = CancellationToken::new();
+        let canceller = cancel.clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            canceller.cancel();
+        });
+
+        let started = Instant::now();
+        let err =
+            wait_for_oauth_callback(listener, port, Duration::from_secs(90), &cancel).unwrap_err();
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert!(matches!(
+            err.downcast_ref::<CoreError>(),
+            Some(CoreError::Auth {
+                code: AuthErrorCode::SignInCancelled,
+                ..
+            })
+        ));
+    }
+
     #[test]
     fn parse_callback_state_mismatch_is_rejected() {
         let err = parse_callback_url_or_code(
@@ -1098,6 +1432,116 @@ mod tests {
         assert!(challenge.expires_at > Utc::now());
     }
 
+    #[tokio::test]
+    async fn deep_link_finds_the_manual_session_by_state() {
+        let service = GoogleAuthService::new(Client::new());
+        let challenge = service
+            .begin_manual_sign_in(&test_settings())
+            .await
+            .unwrap();
+        let state = parse_state_from_callback_url(&challenge.authorize_url).unwrap();
+
+        assert_eq!(
+            service.manual_session_id_for_state(&state).await,
+            Some(challenge.session_id)
+        );
+        let code = parse_callback_url_or_code(
+            &format!("{DEEP_LINK_SCHEME}://callback?code=abc123&state={state}"),
+            &state,
+        )
+        .unwrap();
+        assert_eq!(code, "abc123");
+
+        let err = service
+            .complete_deep_link_sign_in(
+                &test_settings(),
+                "sourcestack://callback?code=abc123&state=someone-elses",
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<CoreError>(),
+            Some(CoreError::Auth {
+                code: AuthErrorCode::SessionNotFound,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn prompt_asks_for_consent_only_until_a_refresh_token_is_stored() {
+        let temp_dir = tempdir().unwrap();
+        let service = GoogleAuthService::with_token_cache_path(
+            Client::new(),
+            temp_dir.path().join("google-auth-token.json"),
+        );
+        let prompt = |service: &GoogleAuthService| {
+            let session = service.create_manual_session(&test_settings()).unwrap();
+            Url::parse(&session.authorize_url)
+                .unwrap()
+                .query_pairs()
+                .find(|(key, _)| key == "prompt")
+                .map(|(_, value)| value.into_owned())
+        };
+
+        assert_eq!(prompt(&service).as_deref(), Some("consent"));
+
+        service
+            .save_token(&GoogleTokenEnvelope {
+                refresh_token: None,
+                ..example_token()
+            })
+            .unwrap();
+        assert_eq!(prompt(&service).as_deref(), Some("consent"));
+
+        service.save_token(&example_token()).unwrap();
+        assert_eq!(prompt(&service).as_deref(), Some("select_account"));
+    }
+
+    #[tokio::test]
+    async fn select_account_sign_in_keeps_the_same_accounts_refresh_token() {
+        let no_refresh = MockResponse {
+            path: "/token",
+            status: 200,
+            body: r#"{"access_token":"new-access","expires_in":3600}"#,
+            content_type: "application/json",
+        };
+        let server = Arc::new(MockAuthServer::start(vec![
+            no_refresh.clone(),
+            MockResponse::userinfo_success(),
+            no_refresh,
+            MockResponse::userinfo_success(),
+        ]));
+        let endpoints = AuthEndpoints {
+            authorize: server.url("/authorize"),
+            token: server.url("/token"),
+            userinfo: server.url("/userinfo"),
+        };
+        let service = GoogleAuthService::with_endpoints(Client::new(), endpoints);
+        let settings = test_settings();
+        let exchange = |previous: GoogleTokenEnvelope| {
+            service.exchange_authorization_code(
+                &settings,
+                "code123",
+                "verifier123",
+                "http://127.0.0.1:5000/callback/",
+                Some(previous),
+            )
+        };
+
+        let same_account = exchange(example_token()).await.unwrap();
+        assert_eq!(same_account.access_token, "new-access");
+        assert_eq!(same_account.refresh_token.as_deref(), Some("refresh-token"));
+
+        let other_account = exchange(GoogleTokenEnvelope {
+            email: Some("someone-else@example.com".to_string()),
+            ..example_token()
+        })
+        .await
+        .unwrap();
+        assert_eq!(other_account.refresh_token, None);
+    }
+
     #[tokio::test]
     async fn complete_manual_rejects_expired_session() {
         let service = GoogleAuthService::new(Client::new());
@@ -1181,6 +1625,91 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn refresh_reads_name_and_picture_from_userinfo() {
+        let server = Arc::new(MockAuthServer::start(vec![
+            MockResponse::token_success(),
+            MockResponse::userinfo_success(),
+        ]));
+        let endpoints = AuthEndpoints {
+            authorize: server.url("/authorize"),
+            token: server.url("/token"),
+            userinfo: server.url("/userinfo"),
+        };
+        let service = GoogleAuthService::with_endpoints(Client::new(), endpoints);
+
+        let token = service
+            .refresh_token(&test_settings(), "refresh")
+            .await
+            .unwrap();
+
+        let status = auth_status_for(Some(&token));
+        assert_eq!(status.email.as_deref(), Some("dev@example.com"));
+        assert_eq!(status.name.as_deref(), Some("Dev Example"));
+        assert_eq!(
+            status.picture.as_deref(),
+            Some("https://lh3.googleusercontent.com/a/dev-photo")
+        );
+    }
+
+    #[test]
+    fn status_change_is_emitted_to_event_sink() {
+        let service = GoogleAuthService::new(Client::new());
+        let received = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let captured = Arc::clone(&received);
+        service.set_event_sink(Arc::new(move |event, payload| {
+            captured.lock().unwrap().push((event.to_string(), payload));
+        }));
+
+        service.emit_status_changed(auth_status_for(Some(&example_token())));
+
+        let events = received.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].0, AUTH_STATUS_CHANGED);
+        assert_eq!(events[0].1["signedIn"], serde_json::json!(true));
+        assert_eq!(events[0].1["email"], serde_json::json!("dev@example.com"));
+    }
+
+    #[tokio::test]
+    async fn expiring_token_without_refresh_token_reports_signed_out() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let service = GoogleAuthService::with_token_cache_path(
+            Client::new(),
+            temp_dir.path().join("google-auth-token.json"),
+        );
+        service
+            .save_token(&GoogleTokenEnvelope {
+                refresh_token: None,
+                expires_at_utc: Utc::now() + chrono::Duration::minutes(1),
+                ..example_token()
+            })
+            .unwrap();
+        let received = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let captured = Arc::clone(&received);
+        service.set_event_sink(Arc::new(move |event, payload| {
+            captured.lock().unwrap().push((event.to_string(), payload));
+        }));
+
+        let err = service
+            .get_access_token_non_interactive(&test_settings())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            err.downcast_ref::<CoreError>(),
+            Some(CoreError::Auth {
+                code: AuthErrorCode::ReauthRequired,
+                ..
+            })
+        ));
+        let events = received.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].1["signedIn"], serde_json::json!(false));
+        assert_eq!(events[0].1["email"], serde_json::json!("dev@example.com"));
+        assert!(!service.status().unwrap().signed_in);
+    }
+
+    #[derive(Clone)]
     struct MockResponse {
         path: &'static str,
         status: u16,
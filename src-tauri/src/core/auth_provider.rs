@@ -0,0 +1,56 @@
+use futures::future::BoxFuture;
+
+use super::events::EventSink;
+use super::models::{
+    AuthStatus, GoogleSignInResult, ManualAuthChallenge, ManualAuthCompleteRequest, RuntimeSettings,
+};
+
+/// The account a source's files are read with. `CoreService` only talks to this trait, so a
+/// provider other than Google (e.g. OneDrive) can be added without touching job code.
+///
+/// Refreshing is the provider's business: `get_access_token_non_interactive` returns a
+/// usable token, refreshing a stale one when it can, and fails with `ReauthRequired` when
+/// the user has to sign in again.
+pub trait AuthProvider: Send + Sync {
+    /// Signs in through the browser, or asks for the manual flow when that is not possible.
+    fn sign_in<'a>(
+        &'a self,
+        settings: &'a RuntimeSettings,
+    ) -> BoxFuture<'a, anyhow::Result<GoogleSignInResult>>;
+
+    fn begin_manual_sign_in<'a>(
+        &'a self,
+        settings: &'a RuntimeSettings,
+    ) -> BoxFuture<'a, anyhow::Result<ManualAuthChallenge>>;
+
+    fn complete_manual_sign_in<'a>(
+        &'a self,
+        settings: &'a RuntimeSettings,
+        request: ManualAuthCompleteRequest,
+    ) -> BoxFuture<'a, anyhow::Result<AuthStatus>>;
+
+    /// Completes the pending manual sign-in whose `state` matches a deep-link callback URL.
+    fn complete_deep_link_sign_in<'a>(
+        &'a self,
+        settings: &'a RuntimeSettings,
+        url: &'a str,
+    ) -> BoxFuture<'a, anyhow::Result<AuthStatus>>;
+
+    /// Stops a browser sign-in that is still waiting. Returns whether one was waiting.
+    fn cancel_sign_in(&self) -> bool;
+
+    fn sign_out(&self) -> anyhow::Result<()>;
+
+    fn status(&self) -> anyhow::Result<AuthStatus>;
+
+    /// Whether jobs can get fresh access tokens without the user.
+    fn has_refresh_token(&self) -> anyhow::Result<bool>;
+
+    fn get_access_token_non_interactive<'a>(
+        &'a self,
+        settings: &'a RuntimeSettings,
+    ) -> BoxFuture<'a, anyhow::Result<String>>;
+
+    /// Where auth status changes (e.g. an expired session) are reported.
+    fn set_event_sink(&self, sink: EventSink);
+}
@@ -5,9 +5,12 @@ use base64::Engine;
 use tauri::State;
 
 use super::models::{
-    AuthStatus, BatchParseRequest, CommandOk, DriveBrowserFile, DriveFolderEntry, DrivePathEntry,
-    GoogleSignInResult, JobStatus, ManualAuthChallenge, ManualAuthCompleteRequest, ParsedCandidate,
-    RuntimeSettingsUpdate, RuntimeSettingsView, StartJobResponse,
+    AggregateStats, AppPaths, AuthStatus, BatchEstimate, BatchParseRequest, CommandOk,
+    DriveBrowserFile, DriveFolderEntry, DrivePathEntry, ExtractedText, FileTypeInfo, FolderAccess,
+    FolderPreviewResponse, FolderSample, GoogleSignInResult, JobDeletion, JobStatus,
+    ManualAuthChallenge, ManualAuthCompleteRequest, ParsedCandidate, PersistedSettings,
+    QueueStatus, ResumeExport, RuntimeSettingsUpdate, RuntimeSettingsView, SheetColumn,
+    StartJobResponse, WatchFolderRequest,
 };
 use super::service::CoreService;
 
@@ -32,6 +35,41 @@ pub async fn parse_single(
         .map_err(|err| err.to_string())
 }
 
+#[tauri::command]
+pub async fn debug_extract_text(
+    state: State<'_, AppState>,
+    file_name: String,
+    file_bytes_base64: String,
+) -> Result<ExtractedText, String> {
+    let bytes = STANDARD
+        .decode(file_bytes_base64.as_bytes())
+        .map_err(|err| format!("invalid base64 input: {err}"))?;
+
+    state
+        .core
+        .debug_extract_text(file_name, bytes)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+pub async fn parse_single_as_row(
+    state: State<'_, AppState>,
+    file_name: String,
+    file_bytes_base64: String,
+    columns: Option<Vec<SheetColumn>>,
+) -> Result<Vec<String>, String> {
+    let bytes = STANDARD
+        .decode(file_bytes_base64.as_bytes())
+        .map_err(|err| format!("invalid base64 input: {err}"))?;
+
+    state
+        .core
+        .parse_single_as_row(file_name, bytes, columns)
+        .await
+        .map_err(|err| err.to_string())
+}
+
 #[tauri::command]
 pub async fn start_batch_job(
     state: State<'_, AppState>,
@@ -58,6 +96,18 @@ pub async fn get_job_status(
         .map_err(|err| err.to_string())
 }
 
+#[tauri::command]
+pub async fn get_job_config(
+    state: State<'_, AppState>,
+    job_id: String,
+) -> Result<Option<PersistedSettings>, String> {
+    state
+        .core
+        .get_job_config(&job_id)
+        .await
+        .map_err(|err| err.to_string())
+}
+
 #[tauri::command]
 pub async fn get_job_results(
     state: State<'_, AppState>,
@@ -70,11 +120,93 @@ pub async fn get_job_results(
         .map_err(|err| err.to_string())
 }
 
+#[tauri::command]
+pub async fn get_job_candidates(
+    state: State<'_, AppState>,
+    job_id: String,
+    drive_file_id: String,
+) -> Result<Vec<ParsedCandidate>, String> {
+    state
+        .core
+        .get_job_candidates(&job_id, &drive_file_id)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+pub async fn rescore_job(
+    state: State<'_, AppState>,
+    job_id: String,
+) -> Result<Vec<ParsedCandidate>, String> {
+    state
+        .core
+        .rescore_job(&job_id)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+pub async fn export_job_archive(
+    state: State<'_, AppState>,
+    job_id: String,
+    dest_path: String,
+) -> Result<CommandOk, String> {
+    state
+        .core
+        .export_job_archive(&job_id, &dest_path)
+        .await
+        .map_err(|err| err.to_string())?;
+
+    Ok(CommandOk { ok: true })
+}
+
+#[tauri::command]
+pub async fn export_job_resumes(
+    state: State<'_, AppState>,
+    job_id: String,
+    dest_path: String,
+    only_drive_ids: Option<Vec<String>>,
+) -> Result<ResumeExport, String> {
+    state
+        .core
+        .export_job_resumes(&job_id, &dest_path, only_drive_ids)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+pub async fn import_job_archive(
+    state: State<'_, AppState>,
+    src_path: String,
+) -> Result<StartJobResponse, String> {
+    let job_id = state
+        .core
+        .import_job_archive(&src_path)
+        .await
+        .map_err(|err| err.to_string())?;
+
+    Ok(StartJobResponse { job_id })
+}
+
 #[tauri::command]
 pub async fn list_jobs(state: State<'_, AppState>) -> Result<Vec<String>, String> {
     state.core.list_jobs().await.map_err(|err| err.to_string())
 }
 
+#[tauri::command]
+pub async fn aggregate_stats(state: State<'_, AppState>) -> Result<AggregateStats, String> {
+    state
+        .core
+        .aggregate_stats()
+        .await
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+pub async fn get_queue_status(state: State<'_, AppState>) -> Result<QueueStatus, String> {
+    Ok(state.core.queue_status().await)
+}
+
 #[tauri::command]
 pub async fn cancel_job(state: State<'_, AppState>, job_id: String) -> Result<CommandOk, String> {
     let ok = state
@@ -86,6 +218,28 @@ pub async fn cancel_job(state: State<'_, AppState>, job_id: String) -> Result<Co
     Ok(CommandOk { ok })
 }
 
+#[tauri::command]
+pub async fn pause_job(state: State<'_, AppState>, job_id: String) -> Result<CommandOk, String> {
+    let ok = state
+        .core
+        .pause_job(&job_id)
+        .await
+        .map_err(|err| err.to_string())?;
+
+    Ok(CommandOk { ok })
+}
+
+#[tauri::command]
+pub async fn resume_job(state: State<'_, AppState>, job_id: String) -> Result<CommandOk, String> {
+    let ok = state
+        .core
+        .resume_job(&job_id)
+        .await
+        .map_err(|err| err.to_string())?;
+
+    Ok(CommandOk { ok })
+}
+
 #[tauri::command]
 pub async fn kill_job(state: State<'_, AppState>, job_id: String) -> Result<CommandOk, String> {
     let ok = state
@@ -97,6 +251,46 @@ pub async fn kill_job(state: State<'_, AppState>, job_id: String) -> Result<Comm
     Ok(CommandOk { ok })
 }
 
+#[tauri::command]
+pub async fn abort_and_delete_job(
+    state: State<'_, AppState>,
+    job_id: String,
+) -> Result<JobDeletion, String> {
+    state
+        .core
+        .abort_and_delete_job(&job_id)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+pub async fn start_watch(
+    state: State<'_, AppState>,
+    request: WatchFolderRequest,
+) -> Result<CommandOk, String> {
+    let ok = state
+        .core
+        .start_watch(request)
+        .await
+        .map_err(|err| err.to_string())?;
+
+    Ok(CommandOk { ok })
+}
+
+#[tauri::command]
+pub async fn stop_watch(
+    state: State<'_, AppState>,
+    folder_id: String,
+) -> Result<CommandOk, String> {
+    let ok = state
+        .core
+        .stop_watch(&folder_id)
+        .await
+        .map_err(|err| err.to_string())?;
+
+    Ok(CommandOk { ok })
+}
+
 #[tauri::command]
 pub async fn google_auth_sign_in(state: State<'_, AppState>) -> Result<GoogleSignInResult, String> {
     state
@@ -153,6 +347,48 @@ pub async fn list_drive_files(
         .map_err(|err| err.to_string())
 }
 
+#[tauri::command]
+pub async fn preview_folder(
+    state: State<'_, AppState>,
+    folder_id: String,
+) -> Result<FolderPreviewResponse, String> {
+    let files = state
+        .core
+        .preview_folder(folder_id)
+        .await
+        .map_err(|err| err.to_string())?;
+
+    Ok(FolderPreviewResponse {
+        file_count: files.len(),
+        files,
+    })
+}
+
+#[tauri::command]
+pub async fn estimate_batch(
+    state: State<'_, AppState>,
+    folder_id: String,
+) -> Result<BatchEstimate, String> {
+    state
+        .core
+        .estimate_batch(folder_id)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+pub async fn sample_folder(
+    state: State<'_, AppState>,
+    folder_id: String,
+    sample_size: usize,
+) -> Result<FolderSample, String> {
+    state
+        .core
+        .sample_folder(folder_id, sample_size)
+        .await
+        .map_err(|err| err.to_string())
+}
+
 #[tauri::command]
 pub async fn get_drive_folder_path(
     state: State<'_, AppState>,
@@ -165,6 +401,18 @@ pub async fn get_drive_folder_path(
         .map_err(|err| err.to_string())
 }
 
+#[tauri::command]
+pub async fn check_folder_access(
+    state: State<'_, AppState>,
+    folder_id: String,
+) -> Result<FolderAccess, String> {
+    state
+        .core
+        .check_folder_access(folder_id)
+        .await
+        .map_err(|err| err.to_string())
+}
+
 #[tauri::command]
 pub fn google_auth_sign_out(state: State<'_, AppState>) -> Result<CommandOk, String> {
     state
@@ -175,6 +423,13 @@ pub fn google_auth_sign_out(state: State<'_, AppState>) -> Result<CommandOk, Str
     Ok(CommandOk { ok: true })
 }
 
+#[tauri::command]
+pub fn cancel_sign_in(state: State<'_, AppState>) -> CommandOk {
+    CommandOk {
+        ok: state.core.cancel_sign_in(),
+    }
+}
+
 #[tauri::command]
 pub fn google_auth_status(state: State<'_, AppState>) -> Result<AuthStatus, String> {
     state
@@ -183,6 +438,24 @@ pub fn google_auth_status(state: State<'_, AppState>) -> Result<AuthStatus, Stri
         .map_err(|err| err.to_string())
 }
 
+#[tauri::command]
+pub fn can_run_unattended(state: State<'_, AppState>) -> Result<bool, String> {
+    state
+        .core
+        .can_run_unattended()
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+pub fn get_paths(state: State<'_, AppState>) -> AppPaths {
+    state.core.get_paths()
+}
+
+#[tauri::command]
+pub fn supported_file_types(state: State<'_, AppState>) -> Vec<FileTypeInfo> {
+    state.core.supported_file_types()
+}
+
 #[tauri::command]
 pub async fn get_settings(state: State<'_, AppState>) -> Result<RuntimeSettingsView, String> {
     Ok(state.core.get_settings().await)
@@ -0,0 +1,185 @@
+use std::sync::Mutex;
+
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+/// A concurrency limit that tunes itself from rate-limit feedback (additive increase,
+/// multiplicative decrease): a throttled request halves the limit, and as many successful
+/// requests in a row as the current limit raise it by one, up to `max`.
+pub struct AdaptiveConcurrency {
+    semaphore: Semaphore,
+    max: usize,
+    state: Mutex<LimitState>,
+}
+
+struct LimitState {
+    limit: usize,
+    /// Successful requests since the limit last rose or a request was throttled.
+    successes: usize,
+    /// Outcomes still to come from requests started before the last cut. They were sent at
+    /// the old limit, so their throttling does not cut the limit again.
+    grace: usize,
+    /// Permits to retire as they are released, because a cut found them in use.
+    debt: usize,
+}
+
+/// Held while a request runs; releases (or retires) its slot when dropped.
+pub struct AdaptivePermit<'a> {
+    limiter: &'a AdaptiveConcurrency,
+    permit: Option<SemaphorePermit<'a>>,
+}
+
+impl AdaptiveConcurrency {
+    pub fn new(initial: usize, max: usize) -> Self {
+        let initial = initial.max(1);
+        Self {
+            semaphore: Semaphore::new(initial),
+            max: max.max(initial),
+            state: Mutex::new(LimitState {
+                limit: initial,
+                successes: 0,
+                grace: 0,
+                debt: 0,
+            }),
+        }
+    }
+
+    pub fn limit(&self) -> usize {
+        self.state.lock().unwrap().limit
+    }
+
+    /// The most requests that can ever run at once.
+    pub fn max(&self) -> usize {
+        self.max
+    }
+
+    pub async fn acquire(&self) -> AdaptivePermit<'_> {
+        let permit = self
+            .semaphore
+            .acquire()
+            .await
+            .expect("adaptive concurrency semaphore is never closed");
+        AdaptivePermit {
+            limiter: self,
+            permit: Some(permit),
+        }
+    }
+
+    pub fn on_success(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.grace = state.grace.saturating_sub(1);
+        state.successes += 1;
+        if state.successes < state.limit || state.limit >= self.max {
+            return;
+        }
+
+        state.successes = 0;
+        state.limit += 1;
+        if state.debt > 0 {
+            state.debt -= 1;
+        } else {
+            self.semaphore.add_permits(1);
+        }
+        eprintln!(
+            "adaptive concurrency: no throttling, raising limit to {}",
+            state.limit
+        );
+    }
+
+    pub fn on_rate_limited(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.successes = 0;
+        if state.grace > 0 {
+            state.grace -= 1;
+            return;
+        }
+
+        let previous = state.limit;
+        state.limit = (previous / 2).max(1);
+        state.grace = previous;
+        let retired = previous - state.limit;
+        let forgotten = self.semaphore.forget_permits(retired);
+        state.debt += retired - forgotten;
+        if retired > 0 {
+            eprintln!(
+                "adaptive concurrency: rate limited, lowering limit from {previous} to {}",
+                state.limit
+            );
+        }
+    }
+}
+
+impl Drop for AdaptivePermit<'_> {
+    fn drop(&mut self) {
+        let mut state = self.limiter.state.lock().unwrap();
+        if state.debt > 0 {
+            state.debt -= 1;
+            if let Some(permit) = self.permit.take() {
+                permit.forget();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn limit_halves_on_throttling_and_climbs_back_while_clear() {
+        let limiter = AdaptiveConcurrency::new(8, 16);
+
+        limiter.on_rate_limited();
+        assert_eq!(limiter.limit(), 4);
+        // The other requests sent at the old limit are throttled too; that is one signal.
+        for _ in 0..7 {
+            limiter.on_rate_limited();
+        }
+        assert_eq!(limiter.limit(), 4);
+        limiter.on_success();
+        limiter.on_rate_limited();
+        assert_eq!(limiter.limit(), 2);
+        for _ in 0..4 {
+            limiter.on_rate_limited();
+        }
+        limiter.on_rate_limited();
+        limiter.on_rate_limited();
+        limiter.on_rate_limited();
+        assert_eq!(limiter.limit(), 1);
+
+        // A full round of successes at the current limit adds one slot.
+        limiter.on_success();
+        assert_eq!(limiter.limit(), 2);
+        limiter.on_success();
+        assert_eq!(limiter.limit(), 2);
+        limiter.on_success();
+        assert_eq!(limiter.limit(), 3);
+        for _ in 0..200 {
+            limiter.on_success();
+        }
+        assert_eq!(limiter.limit(), 16);
+        assert_eq!(limiter.semaphore.available_permits(), 16);
+    }
+
+    #[tokio::test]
+    async fn permits_in_use_during_a_cut_are_retired_when_released() {
+        let limiter = AdaptiveConcurrency::new(4, 4);
+        let permits = vec![
+            limiter.acquire().await,
+            limiter.acquire().await,
+            limiter.acquire().await,
+            limiter.acquire().await,
+        ];
+        assert_eq!(limiter.semaphore.available_permits(), 0);
+
+        limiter.on_rate_limited();
+        assert_eq!(limiter.limit(), 2);
+        drop(permits);
+        assert_eq!(limiter.semaphore.available_permits(), 2);
+
+        let _first = limiter.acquire().await;
+        let _second = limiter.acquire().await;
+        let third =
+            tokio::time::timeout(std::time::Duration::from_millis(20), limiter.acquire()).await;
+        assert!(third.is_err());
+    }
+}
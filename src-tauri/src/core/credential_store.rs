@@ -0,0 +1,293 @@
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Once;
+
+use anyhow::Context;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::settings_store::{app_data_root, profile_keyring_service};
+
+const KEYRING_USERNAME: &str = "default";
+const FALLBACK_DIR: &str = "credentials";
+const KEY_CONTEXT: &[u8] = b"sourcestack.credential-store.v1";
+
+static FALLBACK_WARNING: Once = Once::new();
+
+/// A single secret kept in the OS keychain. When the keychain has no usable backend
+/// (headless Linux, locked-down machines) the secret goes to an encrypted file under the
+/// app data directory instead.
+///
+/// The fallback key is derived from a per-file salt and the current user's identity, so it
+/// keeps the value out of plain sight in backups and support bundles; it is not a defence
+/// against someone who can already run code as this user.
+pub struct CredentialStore {
+    keyring_service: String,
+    fallback_path: PathBuf,
+    #[cfg(test)]
+    keyring_unavailable: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct EncryptedCredential {
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+impl CredentialStore {
+    /// `keyring_service` is scoped to the active profile; `fallback_file` names the
+    /// encrypted file used when the keychain is unavailable.
+    pub fn new(keyring_service: &str, fallback_file: &str) -> Self {
+        Self {
+            keyring_service: profile_keyring_service(keyring_service),
+            fallback_path: app_data_root().join(FALLBACK_DIR).join(fallback_file),
+            #[cfg(test)]
+            keyring_unavailable: false,
+        }
+    }
+
+    /// A store whose keychain always reports a platform failure, writing to `fallback_path`.
+    #[cfg(test)]
+    pub(crate) fn without_keyring(fallback_path: PathBuf) -> Self {
+        Self {
+            keyring_service: "com.sourcestack.desktop.test".to_string(),
+            fallback_path,
+            keyring_unavailable: true,
+        }
+    }
+
+    pub fn load(&self) -> anyhow::Result<Option<String>> {
+        match self.entry().and_then(|entry| entry.get_password()) {
+            Ok(value) => Ok(Some(value)),
+            // Values saved while the keychain was down are still only on disk.
+            Err(keyring::Error::NoEntry) => self.load_fallback(),
+            Err(err) if is_backend_unavailable(&err) => {
+                warn_fallback(&err, &self.fallback_path);
+                self.load_fallback()
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub fn save(&self, value: &str) -> anyhow::Result<()> {
+        match self.entry().and_then(|entry| entry.set_password(value)) {
+            Ok(()) => remove_file_if_exists(&self.fallback_path),
+            Err(err) if is_backend_unavailable(&err) => {
+                warn_fallback(&err, &self.fallback_path);
+                self.save_fallback(value)
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub fn clear(&self) -> anyhow::Result<()> {
+        match self.entry().and_then(|entry| entry.delete_credential()) {
+            Ok(()) | Err(keyring::Error::NoEntry) => {}
+            Err(err) if is_backend_unavailable(&err) => {}
+            Err(err) => return Err(err.into()),
+        }
+
+        remove_file_if_exists(&self.fallback_path)
+    }
+
+    fn entry(&self) -> keyring::Result<keyring::Entry> {
+        #[cfg(test)]
+        if self.keyring_unavailable {
+            return Err(keyring::Error::PlatformFailure(
+                "keychain disabled for this store".into(),
+            ));
+        }
+
+        keyring::Entry::new(&self.keyring_service, KEYRING_USERNAME)
+    }
+
+    fn load_fallback(&self) -> anyhow::Result<Option<String>> {
+        if !self.fallback_path.exists() {
+            return Ok(None);
+        }
+
+        let raw = fs::read_to_string(&self.fallback_path).with_context(|| {
+            format!(
+                "failed to read credential file {}",
+                self.fallback_path.display()
+            )
+        })?;
+        let stored = serde_json::from_str::<EncryptedCredential>(&raw)
+            .with_context(|| format!("invalid credential file {}", self.fallback_path.display()))?;
+
+        let salt = STANDARD.decode(stored.salt)?;
+        let nonce = STANDARD.decode(stored.nonce)?;
+        let ciphertext = STANDARD.decode(stored.ciphertext)?;
+        if nonce.len() != 24 {
+            anyhow::bail!("invalid credential file {}", self.fallback_path.display());
+        }
+
+        let plaintext = cipher(&salt)
+            .decrypt(XNonce::from_slice(&nonce), ciphertext.as_slice())
+            .map_err(|_| {
+                anyhow::anyhow!(
+                    "credential file {} could not be decrypted for this user",
+                    self.fallback_path.display()
+                )
+            })?;
+        Ok(Some(String::from_utf8(plaintext)?))
+    }
+
+    fn save_fallback(&self, value: &str) -> anyhow::Result<()> {
+        let mut salt = [0u8; 16];
+        let mut nonce = [0u8; 24];
+        rand::rng().fill(&mut salt);
+        rand::rng().fill(&mut nonce);
+
+        let ciphertext = cipher(&salt)
+            .encrypt(XNonce::from_slice(&nonce), value.as_bytes())
+            .map_err(|_| anyhow::anyhow!("failed to encrypt credential"))?;
+        let stored = EncryptedCredential {
+            salt: STANDARD.encode(salt),
+            nonce: STANDARD.encode(nonce),
+            ciphertext: STANDARD.encode(ciphertext),
+        };
+
+        if let Some(parent) = self.fallback_path.parent() {
+            fs::create_dir_all(parent).with_context(|| {
+                format!("failed to create credential directory {}", parent.display())
+            })?;
+        }
+        write_private_file(
+            &self.fallback_path,
+            serde_json::to_string(&stored)?.as_bytes(),
+        )
+        .with_context(|| {
+            format!(
+                "failed to write credential file {}",
+                self.fallback_path.display()
+            )
+        })
+    }
+}
+
+/// Writes `contents` to a file only the current user can read. On unix the file is created
+/// with mode 0600, and an existing file is narrowed to 0600 before it is truncated, so the
+/// secret is never readable by others, even briefly.
+pub(crate) fn write_private_file(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    }
+    file.set_len(0)?;
+    file.write_all(contents)
+}
+
+/// Errors meaning the keychain itself is unusable, as opposed to a bad or missing entry.
+fn is_backend_unavailable(err: &keyring::Error) -> bool {
+    matches!(
+        err,
+        keyring::Error::PlatformFailure(_) | keyring::Error::NoStorageAccess(_)
+    )
+}
+
+fn warn_fallback(err: &keyring::Error, fallback_path: &Path) {
+    FALLBACK_WARNING.call_once(|| {
+        eprintln!(
+            "credential store: OS keychain unavailable ({err}); using encrypted files in {}",
+            fallback_path.parent().unwrap_or(fallback_path).display()
+        );
+    });
+}
+
+fn cipher(salt: &[u8]) -> XChaCha20Poly1305 {
+    let mut hasher = Sha256::new();
+    hasher.update(KEY_CONTEXT);
+    hasher.update(salt);
+    if let Some(home) = dirs::home_dir() {
+        hasher.update(home.to_string_lossy().as_bytes());
+    }
+    for var in ["USER", "USERNAME"] {
+        if let Ok(value) = std::env::var(var) {
+            hasher.update(value.as_bytes());
+        }
+    }
+    XChaCha20Poly1305::new(Key::from_slice(&hasher.finalize()))
+}
+
+fn remove_file_if_exists(path: &Path) -> anyhow::Result<()> {
+    match fs::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(err) => {
+            Err(err).with_context(|| format!("failed to delete credential file {}", path.display()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unavailable_keyring_routes_to_encrypted_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir
+            .path()
+            .join("credentials")
+            .join("client-secret.json");
+        let store = CredentialStore::without_keyring(path.clone());
+
+        assert_eq!(store.load().unwrap(), None);
+        store.save("GOCSPX-secret-value").unwrap();
+
+        let on_disk = fs::read_to_string(&path).unwrap();
+        assert!(!on_disk.contains("GOCSPX-secret-value"));
+        assert_eq!(
+            store.load().unwrap().as_deref(),
+            Some("GOCSPX-secret-value")
+        );
+
+        store.clear().unwrap();
+        assert!(!path.exists());
+        assert_eq!(store.load().unwrap(), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn private_files_are_owner_only_even_when_they_already_existed() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let created = temp_dir.path().join("created.json");
+        write_private_file(&created, b"secret").unwrap();
+        assert_eq!(
+            fs::metadata(&created).unwrap().permissions().mode() & 0o777,
+            0o600
+        );
+
+        let existing = temp_dir.path().join("existing.json");
+        fs::write(&existing, b"an older and longer value").unwrap();
+        fs::set_permissions(&existing, fs::Permissions::from_mode(0o644)).unwrap();
+        write_private_file(&existing, b"secret").unwrap();
+        assert_eq!(
+            fs::metadata(&existing).unwrap().permissions().mode() & 0o777,
+            0o600
+        );
+        assert_eq!(fs::read(&existing).unwrap(), b"secret");
+    }
+}
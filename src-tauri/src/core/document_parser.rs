@@ -1,74 +1,395 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::io::{Cursor, Read};
 
 use quick_xml::events::Event;
 use quick_xml::Reader;
 
-use super::field_extractor;
-use super::models::ResumeExtractionResult;
+use super::field_extractor::{self, CustomFieldPattern, PhoneMode, SkillPattern};
+use super::models::{
+    ConfidenceWeights, EmailPreference, FileTypeInfo, ParseError, ParseErrorKind,
+    ResumeExtractionResult,
+};
 use super::pdf::PdfTextExtractor;
 
+/// PDFs whose text layer is at most this long may be partly scanned, so missing fields
+/// are worth an OCR pass when `merge_ocr_fields` is on.
+const PARTIAL_TEXT_MAX_CHARS: usize = 2000;
+
+/// Extracted text with fewer letters and digits than this holds nothing to parse.
+const MIN_USABLE_TEXT_CHARS: usize = 10;
+
+/// Every format the parser can read. `extract_text` matches on this exhaustively, so a new
+/// variant must be handled there and is then reported by [`supported_file_types`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DocumentFormat {
+    Pdf,
+    Docx,
+    Odt,
+}
+
+impl DocumentFormat {
+    const ALL: [DocumentFormat; 3] = [
+        DocumentFormat::Pdf,
+        DocumentFormat::Docx,
+        DocumentFormat::Odt,
+    ];
+
+    fn from_file_name(file_name: &str) -> Option<Self> {
+        let extension = std::path::Path::new(file_name)
+            .extension()
+            .and_then(|v| v.to_str())?
+            .to_ascii_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|format| format.extension() == extension)
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            DocumentFormat::Pdf => "pdf",
+            DocumentFormat::Docx => "docx",
+            DocumentFormat::Odt => "odt",
+        }
+    }
+
+    fn mime_type(self) -> &'static str {
+        match self {
+            DocumentFormat::Pdf => "application/pdf",
+            DocumentFormat::Docx => {
+                "application/vnd.openxmlformats-officedocument.wordprocessingml.document"
+            }
+            DocumentFormat::Odt => "application/vnd.oasis.opendocument.text",
+        }
+    }
+}
+
+/// Extensions and MIME types the parser handles, for keeping UI file pickers in sync.
+pub fn supported_file_types() -> Vec<FileTypeInfo> {
+    DocumentFormat::ALL
+        .into_iter()
+        .map(|format| FileTypeInfo {
+            extension: format.extension().to_string(),
+            mime_type: format.mime_type().to_string(),
+        })
+        .collect()
+}
+
+/// Whether the parser has a reader for this file, going by its MIME type or extension.
+pub fn is_supported_file(file_name: &str, mime_type: &str) -> bool {
+    DocumentFormat::ALL
+        .into_iter()
+        .any(|format| format.mime_type() == mime_type)
+        || DocumentFormat::from_file_name(file_name).is_some()
+}
+
 pub struct ResumeDocumentParser {
     pdf_text_extractor: PdfTextExtractor,
+    email_preference: EmailPreference,
+    phone_mode: PhoneMode,
+    skill_dictionary: Vec<SkillPattern>,
+    name_denylist: Vec<String>,
+    merge_ocr_fields: bool,
+    field_sources: bool,
+    name_candidates: bool,
+    plain_text_fallback: bool,
+    deobfuscate_emails: bool,
+    confidence_weights: ConfidenceWeights,
+    custom_fields: Vec<CustomFieldPattern>,
 }
 
 impl ResumeDocumentParser {
     pub fn new(pdf_text_extractor: PdfTextExtractor) -> Self {
-        Self { pdf_text_extractor }
+        Self {
+            pdf_text_extractor,
+            email_preference: EmailPreference::default(),
+            phone_mode: PhoneMode::default(),
+            skill_dictionary: Vec::new(),
+            name_denylist: Vec::new(),
+            merge_ocr_fields: false,
+            field_sources: false,
+            name_candidates: false,
+            plain_text_fallback: false,
+            deobfuscate_emails: false,
+            confidence_weights: ConfidenceWeights::default(),
+            custom_fields: Vec::new(),
+        }
+    }
+
+    pub fn with_email_preference(mut self, email_preference: EmailPreference) -> Self {
+        self.email_preference = email_preference;
+        self
+    }
+
+    pub fn with_phone_mode(mut self, phone_mode: PhoneMode) -> Self {
+        self.phone_mode = phone_mode;
+        self
+    }
+
+    pub fn with_name_denylist(mut self, name_denylist: Vec<String>) -> Self {
+        self.name_denylist = name_denylist;
+        self
+    }
+
+    /// Compiled here once, so every file the parser reads reuses the patterns.
+    pub fn with_skill_dictionary(mut self, skill_dictionary: Vec<String>) -> Self {
+        self.skill_dictionary = field_extractor::compile_skill_dictionary(&skill_dictionary);
+        self
+    }
+
+    /// Also OCRs short PDFs whose text pass missed fields, filling only the gaps.
+    pub fn with_merge_ocr_fields(mut self, merge_ocr_fields: bool) -> Self {
+        self.merge_ocr_fields = merge_ocr_fields;
+        self
+    }
+
+    /// Reads files of any other type as UTF-8 text instead of rejecting them.
+    pub fn with_plain_text_fallback(mut self, plain_text_fallback: bool) -> Self {
+        self.plain_text_fallback = plain_text_fallback;
+        self
+    }
+
+    /// Reads `jane (at) example (dot) com`-style addresses as real ones.
+    pub fn with_deobfuscate_emails(mut self, deobfuscate_emails: bool) -> Self {
+        self.deobfuscate_emails = deobfuscate_emails;
+        self
+    }
+
+    pub fn with_confidence_weights(mut self, confidence_weights: ConfidenceWeights) -> Self {
+        self.confidence_weights = confidence_weights;
+        self
+    }
+
+    /// Extra fields to pull out of every parsed text, from `compile_custom_fields`.
+    pub fn with_custom_fields(mut self, custom_fields: Vec<CustomFieldPattern>) -> Self {
+        self.custom_fields = custom_fields;
+        self
+    }
+
+    /// Records which extractor tier matched each field. Costs a second extraction pass, so
+    /// it is meant for debugging single files rather than batches.
+    pub fn with_field_sources(mut self, field_sources: bool) -> Self {
+        self.field_sources = field_sources;
+        self
+    }
+
+    /// Lists the lines that could be the name, so a user can pick another one.
+    pub fn with_name_candidates(mut self, name_candidates: bool) -> Self {
+        self.name_candidates = name_candidates;
+        self
     }
 
     pub async fn parse_resume_bytes(&self, file_name: &str, data: &[u8]) -> ResumeExtractionResult {
+        let (text, ocr_used, errors) = self.extract_text(file_name, data).await;
+        let result = self.result_from_text(&text, ocr_used, errors);
+        let result = self
+            .fill_gaps_from_ocr(file_name, data, &text, result)
+            .await;
+        name_from_filename_fallback(file_name, result)
+    }
+
+    /// Like `parse_resume_bytes`, but when the text looks like several concatenated resumes
+    /// returns one result per detected resume. Splitting is heuristic, so every split result
+    /// carries a warning naming its part.
+    pub async fn parse_resume_bytes_split(
+        &self,
+        file_name: &str,
+        data: &[u8],
+    ) -> Vec<ResumeExtractionResult> {
+        let (text, ocr_used, errors) = self.extract_text(file_name, data).await;
+        let segments = split_resume_text(&text);
+        if segments.len() < 2 {
+            let result = self.result_from_text(&text, ocr_used, errors);
+            let result = self
+                .fill_gaps_from_ocr(file_name, data, &text, result)
+                .await;
+            return vec![name_from_filename_fallback(file_name, result)];
+        }
+
+        let total = segments.len();
+        segments
+            .iter()
+            .enumerate()
+            .map(|(index, segment)| {
+                let mut segment_errors = errors.clone();
+                segment_errors.push(ParseError::new(
+                    ParseErrorKind::SplitDocument,
+                    format!(
+                        "Warning: split from multi-resume document (part {} of {total})",
+                        index + 1
+                    ),
+                ));
+                self.result_from_text(segment, ocr_used, segment_errors)
+            })
+            .collect()
+    }
+
+    /// The raw text fields are extracted from (after PDF text extraction, OCR, or DOCX/ODT
+    /// unzipping), whether OCR produced it, and any extraction errors.
+    pub async fn extract_text(
+        &self,
+        file_name: &str,
+        data: &[u8],
+    ) -> (String, bool, Vec<ParseError>) {
         let mut errors = Vec::new();
         let mut ocr_used = false;
 
-        let extension = std::path::Path::new(file_name)
-            .extension()
-            .and_then(|v| v.to_str())
-            .map(|v| v.to_ascii_lowercase())
-            .unwrap_or_default();
-
-        let text = match extension.as_str() {
-            "pdf" => match self
+        let text = match DocumentFormat::from_file_name(file_name) {
+            Some(DocumentFormat::Pdf) => match self
                 .pdf_text_extractor
                 .extract_text_with_ocr_fallback(data)
                 .await
             {
-                Ok((text, used_ocr)) => {
-                    ocr_used = used_ocr;
-                    text
+                Ok(extraction) => {
+                    ocr_used = extraction.ocr_used;
+                    errors.extend(extraction.warnings);
+                    extraction.text
                 }
                 Err(err) => {
-                    errors.push(format!("Parse error: {err}"));
+                    errors.push(extraction_failed(err));
                     String::new()
                 }
             },
-            "docx" => match extract_docx_text(data) {
+            Some(DocumentFormat::Docx) => match extract_docx_text(data) {
                 Ok(text) => text,
                 Err(err) => {
-                    errors.push(format!("Parse error: {err}"));
+                    errors.push(extraction_failed(err));
                     String::new()
                 }
             },
-            _ => {
-                errors.push(format!("Unsupported file type: {file_name}"));
-                String::new()
+            Some(DocumentFormat::Odt) => match extract_odt_text(data) {
+                Ok(text) => text,
+                Err(err) => {
+                    errors.push(extraction_failed(err));
+                    String::new()
+                }
+            },
+            None => {
+                match std::str::from_utf8(data) {
+                    Ok(text) if self.plain_text_fallback && !text.trim().is_empty() => {
+                        errors.push(ParseError::new(
+                        ParseErrorKind::PlainTextFallback,
+                        format!("Warning: {file_name} is not a PDF, DOCX, or ODT; read as plain text"),
+                    ));
+                        text.to_string()
+                    }
+                    _ => {
+                        errors.push(ParseError::new(
+                            ParseErrorKind::UnsupportedFileType,
+                            format!("Unsupported file type: {file_name}"),
+                        ));
+                        String::new()
+                    }
+                }
             }
         };
 
+        // A failed extraction already says why the text is missing.
+        let usable_chars = text.chars().filter(|c| c.is_alphanumeric()).count();
+        if usable_chars < MIN_USABLE_TEXT_CHARS && errors.iter().all(ParseError::is_warning) {
+            errors.push(ParseError::new(
+                ParseErrorKind::NoTextExtracted,
+                "No text could be extracted; the file may be an image or empty",
+            ));
+        }
+
+        (text, ocr_used, errors)
+    }
+
+    async fn fill_gaps_from_ocr(
+        &self,
+        file_name: &str,
+        data: &[u8],
+        text: &str,
+        mut result: ResumeExtractionResult,
+    ) -> ResumeExtractionResult {
+        if !self.merge_ocr_fields
+            || result.ocr_used
+            || !missing_contact_fields(&result)
+            || text.trim().is_empty()
+            || text.trim().len() > PARTIAL_TEXT_MAX_CHARS
+            || DocumentFormat::from_file_name(file_name) != Some(DocumentFormat::Pdf)
+        {
+            return result;
+        }
+
+        match self.pdf_text_extractor.extract_ocr_text(data).await {
+            Ok(Some(ocr_text)) if !ocr_text.trim().is_empty() => {
+                let ocr_result = self.result_from_text(&ocr_text, true, Vec::new());
+                merge_ocr_result(result, ocr_result, &self.confidence_weights)
+            }
+            Ok(_) => result,
+            Err(err) => {
+                result.errors.push(ParseError::new(
+                    ParseErrorKind::OcrMergeFailed,
+                    format!("Warning: OCR merge failed: {err}"),
+                ));
+                result
+            }
+        }
+    }
+
+    fn result_from_text(
+        &self,
+        text: &str,
+        ocr_used: bool,
+        errors: Vec<ParseError>,
+    ) -> ResumeExtractionResult {
         if text.is_empty() && !errors.is_empty() {
             return ResumeExtractionResult {
                 name: None,
                 email: None,
                 phone: None,
+                phone_extension: None,
                 linked_in: None,
                 git_hub: None,
+                skills: Vec::new(),
+                headline: None,
+                notice_period: None,
+                field_sources: None,
+                name_candidates: None,
+                custom: HashMap::new(),
+                education: None,
                 confidence: 0.0,
                 ocr_used,
                 errors,
             };
         }
 
-        let (email, phone, linked_in, git_hub) = field_extractor::extract_fields(&text);
-        let name = field_extractor::guess_name(&text);
+        let contact_text = if self.deobfuscate_emails {
+            field_extractor::deobfuscate_emails(text)
+        } else {
+            Cow::Borrowed(text)
+        };
+        let (email, phone, linked_in, git_hub) =
+            field_extractor::extract_fields(&contact_text, self.email_preference, self.phone_mode);
+        let phone_extension = phone.as_deref().and_then(|phone| {
+            field_extractor::extract_phone_extension(text, phone, self.phone_mode)
+        });
+        let name = field_extractor::guess_name(text, &self.name_denylist);
+        let headline = field_extractor::extract_headline(text, name.as_deref());
+        let skills = field_extractor::extract_skills(text, &self.skill_dictionary);
+        let notice_period = field_extractor::extract_notice_period(text);
+        let education = field_extractor::extract_education_with_institution(text);
+        let custom = field_extractor::extract_custom_fields(text, &self.custom_fields);
+        let mut errors = errors;
+        if let Some(notice_period) = &notice_period {
+            errors.push(notice_period_warning(notice_period));
+        }
+        if let Some((education, false)) = &education {
+            errors.push(education_warning(education));
+        }
+        let education = education.map(|(education, _)| education);
+        let field_sources = self.field_sources.then(|| {
+            field_extractor::extract_field_sources(
+                &contact_text,
+                self.email_preference,
+                self.phone_mode,
+            )
+        });
+        let name_candidates = self
+            .name_candidates
+            .then(|| field_extractor::name_candidates(text, &self.name_denylist));
         let confidence = field_extractor::score_confidence(
             name.as_deref(),
             email.as_deref(),
@@ -76,14 +397,23 @@ impl ResumeDocumentParser {
             linked_in.as_deref(),
             git_hub.as_deref(),
             ocr_used,
+            &self.confidence_weights,
         );
 
         ResumeExtractionResult {
             name,
             email,
             phone,
+            phone_extension,
             linked_in,
             git_hub,
+            skills,
+            headline,
+            notice_period,
+            field_sources,
+            name_candidates,
+            custom,
+            education,
             confidence,
             ocr_used,
             errors,
@@ -91,11 +421,211 @@ impl ResumeDocumentParser {
     }
 }
 
+/// Takes the name from the filename when the text had none. Confidence is left as the text
+/// scored it, and the warning sends the row for review. Failed parses are left alone.
+fn name_from_filename_fallback(
+    file_name: &str,
+    mut result: ResumeExtractionResult,
+) -> ResumeExtractionResult {
+    if result.name.is_some() || result.errors.iter().any(|error| !error.is_warning()) {
+        return result;
+    }
+
+    if let Some(name) = field_extractor::name_from_filename(file_name) {
+        result.errors.push(ParseError::new(
+            ParseErrorKind::NameFromFilename,
+            format!("Warning: name \"{name}\" was taken from the filename; verify it."),
+        ));
+        result.name = Some(name);
+    }
+    result
+}
+
+/// A degree without a nearby institution may be a course or certificate line instead.
+fn education_warning(education: &str) -> ParseError {
+    ParseError::new(
+        ParseErrorKind::EducationUncertain,
+        format!("Warning: education \"{education}\" names no institution; verify it."),
+    )
+}
+
+/// Notice periods come from loose phrasing, so each one is flagged for a recruiter to check.
+fn notice_period_warning(notice_period: &str) -> ParseError {
+    ParseError::new(
+        ParseErrorKind::NoticePeriod,
+        format!("Warning: notice period \"{notice_period}\" is a low-confidence match; verify it."),
+    )
+}
+
+fn extraction_failed(err: anyhow::Error) -> ParseError {
+    ParseError::new(
+        ParseErrorKind::ExtractionFailed,
+        format!("Parse error: {err}"),
+    )
+}
+
+/// Only a missing name, email or phone is worth an OCR pass; plenty of resumes simply have
+/// no LinkedIn or GitHub link.
+fn missing_contact_fields(result: &ResumeExtractionResult) -> bool {
+    result.name.is_none() || result.email.is_none() || result.phone.is_none()
+}
+
+/// Keeps every field the text pass found and fills the rest from the OCR pass. The result
+/// only counts as OCR-assisted when OCR actually contributed something.
+fn merge_ocr_result(
+    mut base: ResumeExtractionResult,
+    ocr: ResumeExtractionResult,
+    weights: &ConfidenceWeights,
+) -> ResumeExtractionResult {
+    let mut contributed = false;
+    if base.phone.is_none() && ocr.phone.is_some() {
+        base.phone_extension = ocr.phone_extension;
+    }
+    if base.name.is_none() && ocr.name.is_some() {
+        base.name_candidates = ocr.name_candidates;
+    }
+    for (key, field, ocr_value) in [
+        ("name", &mut base.name, ocr.name),
+        ("email", &mut base.email, ocr.email),
+        ("phone", &mut base.phone, ocr.phone),
+        ("linkedIn", &mut base.linked_in, ocr.linked_in),
+        ("gitHub", &mut base.git_hub, ocr.git_hub),
+    ] {
+        if field.is_none() && ocr_value.is_some() {
+            *field = ocr_value;
+            contributed = true;
+            if let Some(sources) = base.field_sources.as_mut() {
+                sources.insert(key.to_string(), "ocr".to_string());
+            }
+        }
+    }
+    for skill in ocr.skills {
+        if !base
+            .skills
+            .iter()
+            .any(|existing| existing.eq_ignore_ascii_case(&skill))
+        {
+            base.skills.push(skill);
+            contributed = true;
+        }
+    }
+    if base.headline.is_none() {
+        base.headline = ocr.headline;
+    }
+    if base.education.is_none() && ocr.education.is_some() {
+        base.errors.extend(
+            ocr.errors
+                .iter()
+                .filter(|error| error.code == ParseErrorKind::EducationUncertain)
+                .cloned(),
+        );
+        base.education = ocr.education;
+    }
+    for (name, value) in ocr.custom {
+        base.custom.entry(name).or_insert(value);
+    }
+    if base.notice_period.is_none() && ocr.notice_period.is_some() {
+        base.errors.extend(
+            ocr.errors
+                .into_iter()
+                .filter(|error| error.code == ParseErrorKind::NoticePeriod),
+        );
+        base.notice_period = ocr.notice_period;
+    }
+
+    if contributed {
+        base.ocr_used = true;
+        base.confidence = field_extractor::score_confidence(
+            base.name.as_deref(),
+            base.email.as_deref(),
+            base.phone.as_deref(),
+            base.linked_in.as_deref(),
+            base.git_hub.as_deref(),
+            true,
+            weights,
+        );
+    }
+    base
+}
+
+/// Splits text that looks like several concatenated resumes. A new resume starts where a
+/// different email address shows up: at a page break (form feed) when the text has pages,
+/// otherwise at a name-like line just above the new email. Text without such a boundary is
+/// returned as a single segment.
+fn split_resume_text(text: &str) -> Vec<String> {
+    let pages: Vec<&str> = text
+        .split('\u{c}')
+        .filter(|page| !page.trim().is_empty())
+        .collect();
+    let paged = pages.len() > 1;
+    let units = if paged { pages } else { text.lines().collect() };
+
+    let mut segments: Vec<Vec<&str>> = vec![Vec::new()];
+    let mut segment_email: Option<String> = None;
+    for unit in units {
+        let unit_email = field_extractor::extract_emails(unit).into_iter().next();
+        if let (Some(email), Some(current)) = (&unit_email, &segment_email) {
+            let segment = segments.last_mut().expect("at least one segment");
+            let boundary = if paged {
+                Some(segment.len())
+            } else {
+                name_line_before(segment)
+            };
+
+            if let Some(boundary) = boundary.filter(|index| email != current && *index > 0) {
+                let carried = segment.split_off(boundary);
+                segments.push(carried);
+                segment_email = None;
+            }
+        }
+
+        segments
+            .last_mut()
+            .expect("at least one segment")
+            .push(unit);
+        if segment_email.is_none() {
+            segment_email = unit_email;
+        }
+    }
+
+    segments
+        .into_iter()
+        .map(|segment| segment.join("\n").trim().to_string())
+        .filter(|segment| !segment.is_empty())
+        .collect()
+}
+
+/// Index of a name-like line among the last few lines, where a following resume would start.
+fn name_line_before(lines: &[&str]) -> Option<usize> {
+    let window_start = lines.len().saturating_sub(3);
+    (window_start..lines.len())
+        .find(|index| field_extractor::guess_name(lines[*index], &[]).is_some())
+}
+
 fn extract_docx_text(data: &[u8]) -> anyhow::Result<String> {
+    extract_zipped_xml_paragraphs(data, "word/document.xml", &[b"w:p"], &[])
+}
+
+/// OpenDocument text is a zip like DOCX; paragraphs and headings live in `content.xml`.
+fn extract_odt_text(data: &[u8]) -> anyhow::Result<String> {
+    extract_zipped_xml_paragraphs(
+        data,
+        "content.xml",
+        &[b"text:p", b"text:h"],
+        &[b"text:s", b"text:tab", b"text:line-break"],
+    )
+}
+
+fn extract_zipped_xml_paragraphs(
+    data: &[u8],
+    entry_name: &str,
+    paragraph_tags: &[&[u8]],
+    spacing_tags: &[&[u8]],
+) -> anyhow::Result<String> {
     let cursor = Cursor::new(data);
     let mut archive = zip::ZipArchive::new(cursor)?;
 
-    let mut document_file = archive.by_name("word/document.xml")?;
+    let mut document_file = archive.by_name(entry_name)?;
     let mut xml = String::new();
     document_file.read_to_string(&mut xml)?;
 
@@ -109,26 +639,23 @@ fn extract_docx_text(data: &[u8]) -> anyhow::Result<String> {
 
     loop {
         match reader.read_event_into(&mut buf) {
-            Ok(Event::Start(e)) => {
-                if e.name().as_ref() == b"w:p" {
-                    in_paragraph = true;
-                    current.clear();
-                }
+            Ok(Event::Start(e)) if paragraph_tags.contains(&e.name().as_ref()) => {
+                in_paragraph = true;
+                current.clear();
             }
-            Ok(Event::End(e)) => {
-                if e.name().as_ref() == b"w:p" {
-                    if !current.trim().is_empty() {
-                        lines.push(current.trim().to_string());
-                    }
-                    current.clear();
-                    in_paragraph = false;
+            Ok(Event::End(e)) if paragraph_tags.contains(&e.name().as_ref()) => {
+                if !current.trim().is_empty() {
+                    lines.push(current.trim().to_string());
                 }
+                current.clear();
+                in_paragraph = false;
             }
-            Ok(Event::Text(e)) => {
-                if in_paragraph {
-                    let value = e.xml_content()?.into_owned();
-                    current.push_str(&value);
-                }
+            Ok(Event::Empty(e)) if in_paragraph && spacing_tags.contains(&e.name().as_ref()) => {
+                current.push(' ');
+            }
+            Ok(Event::Text(e)) if in_paragraph => {
+                let value = e.xml_content()?.into_owned();
+                current.push_str(&value);
             }
             Ok(Event::Eof) => break,
             Err(err) => return Err(err.into()),
@@ -140,3 +667,264 @@ fn extract_docx_text(data: &[u8]) -> anyhow::Result<String> {
 
     Ok(lines.join("\n"))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_support::zip_with_entry;
+
+    #[test]
+    fn extract_odt_text_reads_paragraphs_and_spans() {
+        let odt = zip_with_entry(
+            "content.xml",
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<office:document-content xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0">
+  <office:body>
+    <office:text>
+      <text:h>Jane Doe</text:h>
+      <text:p>Email:<text:s/><text:span>jane.doe@example.com</text:span></text:p>
+      <text:p><text:span>Phone</text:span>:<text:s/>9876543210</text:p>
+    </office:text>
+  </office:body>
+</office:document-content>"#,
+        );
+
+        let text = extract_odt_text(&odt).unwrap();
+        assert_eq!(
+            text,
+            "Jane Doe\nEmail: jane.doe@example.com\nPhone: 9876543210"
+        );
+    }
+
+    #[tokio::test]
+    async fn files_without_usable_text_get_a_no_text_error() {
+        let parser = ResumeDocumentParser::new(
+            PdfTextExtractor::new(super::super::ocr::TesseractCliOcrService::new(
+                "/nonexistent/tesseract".to_string(),
+                std::time::Duration::from_secs(1),
+            ))
+            .with_ocr_disabled(true),
+        );
+
+        let garbage = parser
+            .parse_resume_bytes("scan.pdf", b"%PDF-1.4 image only")
+            .await;
+        assert_eq!(garbage.confidence, 0.0);
+        let codes = garbage
+            .errors
+            .iter()
+            .map(|error| error.code)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            codes,
+            vec![ParseErrorKind::OcrDisabled, ParseErrorKind::NoTextExtracted]
+        );
+        assert!(!garbage.errors[1].is_warning());
+
+        let blank = zip_with_entry(
+            "word/document.xml",
+            r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body><w:p><w:r><w:t> - . </w:t></w:r></w:p></w:body></w:document>"#,
+        );
+        let blank = parser.parse_resume_bytes("blank.docx", &blank).await;
+        assert_eq!(blank.errors[0].code, ParseErrorKind::NoTextExtracted);
+
+        // A broken file reports the extraction failure alone.
+        let broken = parser.parse_resume_bytes("broken.docx", b"not a zip").await;
+        assert_eq!(broken.errors.len(), 1);
+        assert_eq!(broken.errors[0].code, ParseErrorKind::ExtractionFailed);
+    }
+
+    #[tokio::test]
+    async fn missing_name_falls_back_to_the_filename_with_a_warning() {
+        let docx = zip_with_entry(
+            "word/document.xml",
+            r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+<w:p><w:r><w:t>jane.doe@example.com</w:t></w:r></w:p>
+<w:p><w:r><w:t>Built payment systems in Rust.</w:t></w:r></w:p>
+</w:body></w:document>"#,
+        );
+        let parser = ResumeDocumentParser::new(PdfTextExtractor::new(
+            super::super::ocr::TesseractCliOcrService::new(
+                "tesseract".to_string(),
+                std::time::Duration::from_secs(1),
+            ),
+        ));
+
+        let result = parser
+            .parse_resume_bytes("Jane_Doe_Resume_2024.docx", &docx)
+            .await;
+        assert_eq!(result.name.as_deref(), Some("Jane Doe"));
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].code, ParseErrorKind::NameFromFilename);
+        assert!(result.errors[0].is_warning());
+
+        let unnamed = parser.parse_resume_bytes("resume.docx", &docx).await;
+        assert_eq!(unnamed.name, None);
+        assert!(unnamed.errors.is_empty());
+    }
+
+    #[tokio::test]
+    async fn parse_resume_bytes_split_returns_one_result_per_concatenated_resume() {
+        let docx = zip_with_entry(
+            "word/document.xml",
+            r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+<w:p><w:r><w:t>Jane Doe</w:t></w:r></w:p>
+<w:p><w:r><w:t>jane.doe@example.com</w:t></w:r></w:p>
+<w:p><w:r><w:t>Built payment systems in Rust.</w:t></w:r></w:p>
+<w:p><w:r><w:t>John Smith</w:t></w:r></w:p>
+<w:p><w:r><w:t>Email: john.smith@example.org</w:t></w:r></w:p>
+<w:p><w:r><w:t>github.com/johnsmith</w:t></w:r></w:p>
+</w:body></w:document>"#,
+        );
+        let parser = ResumeDocumentParser::new(PdfTextExtractor::new(
+            super::super::ocr::TesseractCliOcrService::new(
+                "tesseract".to_string(),
+                std::time::Duration::from_secs(1),
+            ),
+        ));
+
+        let results = parser.parse_resume_bytes_split("bundle.docx", &docx).await;
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].name.as_deref(), Some("Jane Doe"));
+        assert_eq!(results[0].email.as_deref(), Some("jane.doe@example.com"));
+        assert_eq!(results[0].git_hub, None);
+        assert_eq!(results[1].name.as_deref(), Some("John Smith"));
+        assert_eq!(results[1].email.as_deref(), Some("john.smith@example.org"));
+        assert_eq!(results[1].errors[0].code, ParseErrorKind::SplitDocument);
+        assert!(results[1].errors[0].message.contains("part 2 of 2"));
+    }
+
+    #[tokio::test]
+    async fn unreadable_files_report_coded_errors() {
+        let parser = ResumeDocumentParser::new(PdfTextExtractor::new(
+            super::super::ocr::TesseractCliOcrService::new(
+                "tesseract".to_string(),
+                std::time::Duration::from_secs(1),
+            ),
+        ));
+
+        let unsupported = parser.parse_resume_bytes("resume.txt", b"Jane Doe").await;
+        assert_eq!(unsupported.errors.len(), 1);
+        assert_eq!(
+            unsupported.errors[0].code,
+            ParseErrorKind::UnsupportedFileType
+        );
+        assert_eq!(
+            unsupported.errors[0].message,
+            "Unsupported file type: resume.txt"
+        );
+
+        let corrupt = parser.parse_resume_bytes("resume.docx", b"not a zip").await;
+        assert_eq!(corrupt.errors[0].code, ParseErrorKind::ExtractionFailed);
+        assert!(!corrupt.errors[0].is_warning());
+        assert!(corrupt.errors[0].message.starts_with("Parse error:"));
+    }
+
+    #[test]
+    fn merge_ocr_result_fills_missing_email_without_overwriting_text_fields() {
+        let parser = ResumeDocumentParser::new(PdfTextExtractor::new(
+            super::super::ocr::TesseractCliOcrService::new(
+                "tesseract".to_string(),
+                std::time::Duration::from_secs(1),
+            ),
+        ));
+        let text_pass = parser.result_from_text(
+            "Jane Doe\nPhone: +91 9876543210\n[scanned contact block]",
+            false,
+            Vec::new(),
+        );
+        let ocr_pass = parser.result_from_text(
+            "Jane D0e\nPhone: +91 9876543210\njane.doe@example.com",
+            true,
+            Vec::new(),
+        );
+        assert_eq!(text_pass.email, None);
+
+        let weights = ConfidenceWeights::default();
+        let merged = merge_ocr_result(text_pass.clone(), ocr_pass, &weights);
+        assert_eq!(merged.name.as_deref(), Some("Jane Doe"));
+        assert_eq!(merged.email.as_deref(), Some("jane.doe@example.com"));
+        assert!(merged.ocr_used);
+        assert!(merged.confidence > text_pass.confidence);
+
+        let unchanged = merge_ocr_result(text_pass.clone(), text_pass.clone(), &weights);
+        assert!(!unchanged.ocr_used);
+    }
+
+    #[test]
+    fn ocr_merge_is_triggered_only_by_missing_contact_fields() {
+        let parser = ResumeDocumentParser::new(PdfTextExtractor::new(
+            super::super::ocr::TesseractCliOcrService::new(
+                "tesseract".to_string(),
+                std::time::Duration::from_secs(1),
+            ),
+        ));
+        let complete = parser.result_from_text(
+            "Jane Doe\njane.doe@example.com\nPhone: +91 9876543210",
+            false,
+            Vec::new(),
+        );
+        assert_eq!(complete.linked_in, None);
+        assert_eq!(complete.git_hub, None);
+        assert!(!missing_contact_fields(&complete));
+
+        let no_email =
+            parser.result_from_text("Jane Doe\nPhone: +91 9876543210", false, Vec::new());
+        assert!(missing_contact_fields(&no_email));
+    }
+
+    #[test]
+    fn notice_periods_carry_a_low_confidence_warning() {
+        let parser = ResumeDocumentParser::new(PdfTextExtractor::new(
+            super::super::ocr::TesseractCliOcrService::new(
+                "tesseract".to_string(),
+                std::time::Duration::from_secs(1),
+            ),
+        ));
+
+        let result = parser.result_from_text(
+            "Jane Doe\njane.doe@example.com\nNotice period: 30 days",
+            false,
+            Vec::new(),
+        );
+        assert_eq!(result.notice_period.as_deref(), Some("30 days"));
+        assert_eq!(result.errors, vec![notice_period_warning("30 days")]);
+
+        let result = parser.result_from_text("Jane Doe\njane.doe@example.com", false, Vec::new());
+        assert_eq!(result.notice_period, None);
+        assert!(result.errors.is_empty());
+    }
+
+    #[test]
+    fn supported_file_types_match_the_parser_dispatch() {
+        let types = supported_file_types();
+        let extensions = types
+            .iter()
+            .map(|info| info.extension.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(extensions, vec!["pdf", "docx", "odt"]);
+        assert_eq!(types[0].mime_type, "application/pdf");
+
+        assert_eq!(
+            DocumentFormat::from_file_name("Jane.DOCX"),
+            Some(DocumentFormat::Docx)
+        );
+        assert_eq!(DocumentFormat::from_file_name("notes.txt"), None);
+        assert_eq!(DocumentFormat::from_file_name("resume"), None);
+    }
+
+    #[test]
+    fn split_resume_text_prefers_page_breaks_and_keeps_single_resumes_whole() {
+        let paged = "Jane Doe\njane@example.com\nReferences\u{c}Page two of Jane\u{c}John Smith\njohn@example.org";
+        assert_eq!(
+            split_resume_text(paged),
+            vec![
+                "Jane Doe\njane@example.com\nReferences\nPage two of Jane",
+                "John Smith\njohn@example.org"
+            ]
+        );
+
+        let single = "Jane Doe\nwork: jane@corp.example\npersonal: jane@example.com";
+        assert_eq!(split_resume_text(single), vec![single]);
+    }
+}
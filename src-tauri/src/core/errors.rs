@@ -14,12 +14,97 @@ pub enum AuthErrorCode {
     StateMismatch,
     ChallengeExpired,
     SessionNotFound,
+    SignInCancelled,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GoogleApiService {
+    Drive,
+    Sheets,
+}
+
+impl std::fmt::Display for GoogleApiService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            GoogleApiService::Drive => "Google Drive",
+            GoogleApiService::Sheets => "Google Sheets",
+        })
+    }
+}
+
+/// Google's standard error envelope: `{ "error": { "code", "message", "status", "errors" } }`.
+#[derive(Debug, Deserialize)]
+struct GoogleErrorEnvelope {
+    error: GoogleErrorDetail,
+}
+
+#[derive(Debug, Deserialize)]
+struct GoogleErrorDetail {
+    #[serde(default)]
+    message: Option<String>,
+    #[serde(default)]
+    status: Option<String>,
+    #[serde(default)]
+    errors: Vec<GoogleErrorItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GoogleErrorItem {
+    #[serde(default)]
+    reason: Option<String>,
+}
+
+/// Transient failure kinds that `RetryPolicy` can retry independently.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryClass {
+    RateLimit,
+    ServerError,
+    Network,
+}
+
+impl RetryClass {
+    pub fn from_status(status: u16) -> Option<Self> {
+        match status {
+            429 => Some(RetryClass::RateLimit),
+            500.. => Some(RetryClass::ServerError),
+            _ => None,
+        }
+    }
+}
+
+/// 403 reasons Google uses for throttling rather than missing permissions.
+const RATE_LIMIT_REASONS: [&str; 2] = ["userRateLimitExceeded", "rateLimitExceeded"];
+
+fn parse_google_error(body: &str) -> Option<GoogleErrorDetail> {
+    serde_json::from_str::<GoogleErrorEnvelope>(body)
+        .ok()
+        .map(|envelope| envelope.error)
+}
+
+/// User-facing summary of a failed Google call; the raw body stays on the error for logs.
+fn google_api_message(service: &GoogleApiService, status: &u16, body: &str) -> String {
+    let detail = parse_google_error(body).and_then(|detail| {
+        detail
+            .message
+            .map(|message| message.trim().trim_end_matches('.').to_string())
+            .filter(|message| !message.is_empty())
+            .or(detail.status)
+    });
+
+    match detail {
+        Some(detail) => format!("{service}: {detail} ({status})"),
+        None => format!("{service} request failed ({status})"),
+    }
 }
 
 #[derive(Debug, Error)]
 pub enum CoreError {
-    #[error("Google API request failed with status {status}: {body}")]
-    GoogleApi { status: u16, body: String },
+    #[error("{}", google_api_message(.service, .status, .body))]
+    GoogleApi {
+        service: GoogleApiService,
+        status: u16,
+        body: String,
+    },
     #[error("Google OAuth is not configured in this app build. Contact Dipesh from engineering team.")]
     MissingGoogleClientId,
     #[error("{message}")]
@@ -33,13 +118,39 @@ pub enum CoreError {
     JobNotCompleted(String),
     #[error("Invalid request: {0}")]
     InvalidRequest(String),
+    #[error("Job queue is full ({0} jobs waiting). Try again once some have started.")]
+    QueueFull(usize),
+    #[error("Google Drive returned an empty or truncated download ({0} bytes)")]
+    EmptyDownload(usize),
+    #[error("Google Drive file {0} is no longer available; it may have been deleted or moved to the trash")]
+    FileUnavailable(String),
 }
 
 impl CoreError {
     pub fn is_retryable(&self) -> bool {
+        self.retry_class().is_some()
+    }
+
+    /// Why a retry could help, or `None` when retrying would fail the same way.
+    pub fn retry_class(&self) -> Option<RetryClass> {
         match self {
-            CoreError::GoogleApi { status, .. } => *status == 429 || *status >= 500,
-            _ => false,
+            CoreError::GoogleApi {
+                status: 403, body, ..
+            } => parse_google_error(body)
+                .is_some_and(|detail| {
+                    detail.errors.iter().any(|item| {
+                        item.reason
+                            .as_deref()
+                            .is_some_and(|reason| RATE_LIMIT_REASONS.contains(&reason))
+                    })
+                })
+                .then_some(RetryClass::RateLimit),
+            CoreError::GoogleApi { status, .. } => RetryClass::from_status(*status),
+            // A cut-off transfer usually downloads fine on the next attempt.
+            CoreError::EmptyDownload(_) => Some(RetryClass::Network),
+            // Unlike a 5xx, a missing file stays missing however often it is fetched.
+            CoreError::FileUnavailable(_) => None,
+            _ => None,
         }
     }
 
@@ -50,3 +161,69 @@ impl CoreError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn google_api_error_shows_envelope_message_without_raw_body() {
+        let drive = CoreError::GoogleApi {
+            service: GoogleApiService::Drive,
+            status: 403,
+            body: r#"{
+              "error": {
+                "code": 403,
+                "message": "The user does not have sufficient permissions for file 1AbC.",
+                "errors": [{ "domain": "global", "reason": "insufficientFilePermissions" }],
+                "status": "PERMISSION_DENIED"
+              }
+            }"#
+            .to_string(),
+        };
+        assert_eq!(
+            drive.to_string(),
+            "Google Drive: The user does not have sufficient permissions for file 1AbC (403)"
+        );
+
+        let sheets = CoreError::GoogleApi {
+            service: GoogleApiService::Sheets,
+            status: 400,
+            body: r#"{"error":{"code":400,"message":"Unable to parse range: Resume Data!A1:Z","status":"INVALID_ARGUMENT"}}"#
+                .to_string(),
+        };
+        assert_eq!(
+            sheets.to_string(),
+            "Google Sheets: Unable to parse range: Resume Data!A1:Z (400)"
+        );
+        assert!(format!("{sheets:?}").contains("INVALID_ARGUMENT"));
+
+        let html = CoreError::GoogleApi {
+            service: GoogleApiService::Sheets,
+            status: 502,
+            body: "<html>Bad Gateway</html>".to_string(),
+        };
+        assert_eq!(html.to_string(), "Google Sheets request failed (502)");
+    }
+
+    #[test]
+    fn drive_403_is_retryable_only_for_rate_limits() {
+        let forbidden = |reason: &str| CoreError::GoogleApi {
+            service: GoogleApiService::Drive,
+            status: 403,
+            body: format!(
+                r#"{{"error":{{"code":403,"message":"denied","errors":[{{"domain":"usageLimits","reason":"{reason}"}}]}}}}"#
+            ),
+        };
+
+        assert!(forbidden("userRateLimitExceeded").is_retryable());
+        assert!(forbidden("rateLimitExceeded").is_retryable());
+        assert!(!forbidden("insufficientFilePermissions").is_retryable());
+        assert!(!CoreError::GoogleApi {
+            service: GoogleApiService::Drive,
+            status: 403,
+            body: "Forbidden".to_string(),
+        }
+        .is_retryable());
+    }
+}
@@ -0,0 +1,11 @@
+use std::sync::Arc;
+
+pub const AUTH_STATUS_CHANGED: &str = "auth-status-changed";
+pub const AUTH_DEEP_LINK_FAILED: &str = "auth-deep-link-failed";
+pub const PROFILE_LAUNCH_REJECTED: &str = "profile-launch-rejected";
+pub const JOB_COMPLETE: &str = "job-complete";
+pub const JOB_FAILED: &str = "job-failed";
+pub const JOB_CANCELLED: &str = "job-cancelled";
+
+/// Callback used by the core to push events to the UI without depending on Tauri directly.
+pub type EventSink = Arc<dyn Fn(&str, serde_json::Value) + Send + Sync>;
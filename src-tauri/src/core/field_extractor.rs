@@ -1,6 +1,34 @@
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+
 use once_cell::sync::Lazy;
 use regex::Regex;
 
+use super::models::{ConfidenceWeights, CustomFieldRule, EmailPreference, NationalPhonePolicy};
+
+/// Free-mail providers treated as personal addresses by `EmailPreference`.
+const PERSONAL_EMAIL_DOMAINS: &[&str] = &[
+    "aol.com",
+    "gmail.com",
+    "gmx.com",
+    "googlemail.com",
+    "hotmail.com",
+    "icloud.com",
+    "live.com",
+    "mail.com",
+    "me.com",
+    "msn.com",
+    "outlook.com",
+    "proton.me",
+    "protonmail.com",
+    "rediffmail.com",
+    "yahoo.co.in",
+    "yahoo.com",
+    "yandex.com",
+    "ymail.com",
+    "zoho.com",
+];
+
 static MAILTO_REGEXES: Lazy<Vec<Regex>> = Lazy::new(|| {
     vec![
         Regex::new(r"mailto:\s*([A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,})").unwrap(),
@@ -16,33 +44,80 @@ static KEYWORD_EMAIL_RE: Lazy<Regex> = Lazy::new(|| {
 
 static EMAIL_RE: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"\b[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}\b").unwrap());
+/// An address written as `jane (at) example [dot] com` to dodge scrapers. Only bracketed
+/// `at`/`dot` count, so prose like "worked at Acme" is left alone. Spaces are allowed only
+/// around a bracketed `dot`; a literal `.` must touch both labels, so a sentence-ending
+/// period stays out of the domain.
+static OBFUSCATED_EMAIL_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)\b([a-z0-9._%+-]+(?:\s*[(\[{<]\s*dot\s*[)\]}>]\s*[a-z0-9._%+-]+)*)\s*(?:[(\[{<]\s*at\s*[)\]}>]|@)\s*([a-z0-9-]+(?:(?:\s*[(\[{<]\s*dot\s*[)\]}>]\s*|\.)[a-z0-9-]+)+)",
+    )
+    .unwrap()
+});
+static OBFUSCATED_DOT_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\s*[(\[{<]\s*dot\s*[)\]}>]\s*|\.").unwrap());
 static PHONE_CLEAN_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"[\s\-\(\)\.]").unwrap());
 static DIGIT_SEQ_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\d{7,15}").unwrap());
+/// A number followed by an extension marker (`ext`, `ext.`, `extension`, `x`, or `#`) and
+/// the extension digits. Stays on one line so numbers on separate lines are not joined.
+static PHONE_EXTENSION_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)(\+?\d[\d \-().]{5,}\d)[ ,]*(?:ext(?:ension)?\.?|x|#)[ :]*(\d{1,6})\b")
+        .unwrap()
+});
+/// "Notice period: 30 days", "notice period of 2 months".
+static NOTICE_PERIOD_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\bnotice\s+period\s*(?:of|is|[:\-])?\s*(\d{1,3})\s*(days?|weeks?|months?)\b")
+        .unwrap()
+});
+/// "2 months notice", "30 days' notice".
+static DURATION_NOTICE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(\d{1,3})\s*(days?|weeks?|months?)(?:['\u{2019}]s?)?\s+notice\b").unwrap()
+});
+/// "Available immediately", "Notice period: immediate", "immediate joiner".
+static IMMEDIATE_NOTICE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)\b(?:available|availability|can\s+join|notice\s+period)\s*[:\-]?\s*immediate(?:ly)?\b|\bimmediate\s+joiner\b",
+    )
+    .unwrap()
+});
+/// Degree names. Abbreviations are matched case-sensitively so words like "be" or "ms" in
+/// running text do not count.
+static DEGREE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"\b(?:[BM]\.?\s?Tech|[BM]\.?\s?Sc|MBA|Ph\.?\s?D|BS|MS|BCA|MCA)\b|\b[BM]\.\s?[SEA]\.|(?i:\b(?:bachelor|master|doctor(?:ate)?)(?:'s)?\b)",
+    )
+    .unwrap()
+});
+static INSTITUTION_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(?:university|college|institute|school|academy|polytechnic)\b|\b(?:IIT|NIT|IIM|BITS|MIT)\b")
+        .unwrap()
+});
 static NAME_STARTS_WITH_PHONE_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\+?\d").unwrap());
 
 static LINKEDIN_HREF_RES: Lazy<Vec<Regex>> = Lazy::new(|| {
     vec![
-        Regex::new(r#"href=["'](https?://(?:www\.)?linkedin\.com/in/[a-zA-Z0-9\-]+)["']"#).unwrap(),
-        Regex::new(r#"href=["'](linkedin\.com/in/[a-zA-Z0-9\-]+)["']"#).unwrap(),
+        Regex::new(r#"href=["'](https?://(?:www\.)?linkedin\.com/in/[a-zA-Z0-9_.%\-]+)["']"#)
+            .unwrap(),
+        Regex::new(r#"href=["'](linkedin\.com/in/[a-zA-Z0-9_.%\-]+)["']"#).unwrap(),
     ]
 });
 
 static LINKEDIN_KEYWORD_RE: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r#"(?:linkedin|linked\s*in)[\s:]*.*?(?:href=["'])?(https?://(?:www\.)?linkedin\.com/in/[a-zA-Z0-9\-]+)"#)
+    Regex::new(r#"(?:linkedin|linked\s*in)[\s:]*.*?(?:href=["'])?(https?://(?:www\.)?linkedin\.com/in/[a-zA-Z0-9_.%\-]+)"#)
         .unwrap()
 });
 
 static LINKEDIN_PATTERNS: Lazy<Vec<Regex>> = Lazy::new(|| {
     vec![
-        Regex::new(r"https?://(?:www\.)?linkedin\.com/in/([a-zA-Z0-9\-]+)").unwrap(),
-        Regex::new(r"linkedin\.com/in/([a-zA-Z0-9\-]+)").unwrap(),
-        Regex::new(r"www\.linkedin\.com/in/([a-zA-Z0-9\-]+)").unwrap(),
+        Regex::new(r"https?://(?:www\.)?linkedin\.com/in/([a-zA-Z0-9_.%\-]+)").unwrap(),
+        Regex::new(r"linkedin\.com/in/([a-zA-Z0-9_.%\-]+)").unwrap(),
+        Regex::new(r"www\.linkedin\.com/in/([a-zA-Z0-9_.%\-]+)").unwrap(),
         Regex::new(r"linkedin\.com/profile/view\?id=([a-zA-Z0-9\-]+)").unwrap(),
     ]
 });
 
 static LINKEDIN_FALLBACK_RE: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"https?://(?:www\.)?linkedin\.com/in/[a-zA-Z0-9\-]+").unwrap());
+    Lazy::new(|| Regex::new(r"https?://(?:www\.)?linkedin\.com/in/[a-zA-Z0-9_.%\-]+").unwrap());
 
 static GITHUB_HREF_RES: Lazy<Vec<Regex>> = Lazy::new(|| {
     vec![
@@ -68,29 +143,144 @@ static GITHUB_FALLBACK_RE: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"https?://(?:www\.)?github\.com/[A-Za-z0-9-]{1,39}").unwrap());
 
 pub fn extract_email(text: &str) -> Option<String> {
+    extract_email_with_source(text).map(|(email, _)| email)
+}
+
+/// Like `extract_email`, also naming the regex tier that matched: `mailto`, `keyword`,
+/// or `pattern`.
+pub fn extract_email_with_source(text: &str) -> Option<(String, &'static str)> {
     for regex in &*MAILTO_REGEXES {
         if let Some(captures) = regex.captures(text) {
             if let Some(email) = captures.get(1) {
-                return Some(email.as_str().to_lowercase());
+                return Some((email.as_str().to_lowercase(), "mailto"));
             }
         }
     }
 
     if let Some(captures) = KEYWORD_EMAIL_RE.captures(text) {
         if let Some(email) = captures.get(1) {
-            return Some(email.as_str().to_lowercase());
+            return Some((email.as_str().to_lowercase(), "keyword"));
         }
     }
 
-    EMAIL_RE.find(text).map(|m| m.as_str().to_lowercase())
+    EMAIL_RE
+        .find(text)
+        .map(|m| (m.as_str().to_lowercase(), "pattern"))
+}
+
+/// Picks an email according to `preference`, falling back to `extract_email` when no
+/// address matches the preferred kind.
+pub fn extract_email_with_preference(text: &str, preference: EmailPreference) -> Option<String> {
+    extract_email_with_preference_and_source(text, preference).map(|(email, _)| email)
+}
+
+/// Like `extract_email_with_preference`; an address chosen by preference reports `preference`.
+fn extract_email_with_preference_and_source(
+    text: &str,
+    preference: EmailPreference,
+) -> Option<(String, &'static str)> {
+    let first = extract_email_with_source(text);
+    let want_personal = match preference {
+        EmailPreference::First => return first,
+        EmailPreference::PreferPersonal => true,
+        EmailPreference::PreferCorporate => false,
+    };
+
+    extract_emails(text)
+        .into_iter()
+        .find(|email| is_personal_email(email) == want_personal)
+        .map(|email| {
+            let source = match &first {
+                Some((first_email, source)) if *first_email == email => *source,
+                _ => "preference",
+            };
+            (email, source)
+        })
+        .or(first)
+}
+
+/// Rewrites `(at)`/`[dot]`-style addresses as real ones so the email extractors find them.
+/// Plain addresses and the rest of the text are kept as written.
+pub fn deobfuscate_emails(text: &str) -> Cow<'_, str> {
+    OBFUSCATED_EMAIL_RE.replace_all(text, |captures: &regex::Captures| {
+        let whole = &captures[0];
+        let obfuscated = whole.contains(['(', '[', '{', '<']);
+        if !obfuscated {
+            return whole.to_string();
+        }
+        format!(
+            "{}@{}",
+            OBFUSCATED_DOT_RE.replace_all(&captures[1], "."),
+            OBFUSCATED_DOT_RE.replace_all(&captures[2], ".")
+        )
+    })
+}
+
+pub fn is_personal_email(email: &str) -> bool {
+    email_domain(email).is_some_and(|domain| PERSONAL_EMAIL_DOMAINS.contains(&domain.as_str()))
+}
+
+/// Every distinct email address in the text, lowercased, in order of appearance.
+pub fn extract_emails(text: &str) -> Vec<String> {
+    let mut emails: Vec<String> = Vec::new();
+    for found in EMAIL_RE.find_iter(text) {
+        let email = found.as_str().to_lowercase();
+        if !emails.contains(&email) {
+            emails.push(email);
+        }
+    }
+    emails
+}
+
+/// Domain part of an email address, lowercased, e.g. to tell personal from corporate mail.
+pub fn email_domain(email: &str) -> Option<String> {
+    let (_, domain) = email.trim().rsplit_once('@')?;
+    let domain = domain.trim();
+    if domain.is_empty() {
+        return None;
+    }
+
+    Some(domain.to_lowercase())
+}
+
+/// How phone numbers written without a `+` country code are read.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PhoneMode {
+    /// Ten-digit runs are Indian numbers; longer runs already start with a country code.
+    #[default]
+    DefaultToIndia,
+    /// Only numbers written with a leading `+` are normalized, so no region is assumed.
+    /// A bare ten-digit run is kept as written or dropped, per the policy.
+    InternationalOnly(NationalPhonePolicy),
+}
+
+impl PhoneMode {
+    pub fn from_settings(require_international: bool, national: NationalPhonePolicy) -> Self {
+        if require_international {
+            PhoneMode::InternationalOnly(national)
+        } else {
+            PhoneMode::DefaultToIndia
+        }
+    }
 }
 
 pub fn normalize_phone(text: &str) -> Option<String> {
+    normalize_phone_with_source(text, PhoneMode::default()).map(|(phone, _)| phone)
+}
+
+/// Like `normalize_phone`, naming how the number was found: `whole_text` when the text
+/// itself parses as a number, `digits` when a digit run was picked out of it, `national`
+/// when a bare number was kept as written under `PhoneMode::InternationalOnly`.
+pub fn normalize_phone_with_source(text: &str, mode: PhoneMode) -> Option<(String, &'static str)> {
     if let Some(normalized) = format_if_valid_phone(text) {
-        return Some(normalized);
+        return Some((normalized, "whole_text"));
     }
 
     let cleaned = PHONE_CLEAN_RE.replace_all(text, "");
+    if let PhoneMode::InternationalOnly(policy) = mode {
+        return international_phone(&cleaned, policy);
+    }
+
     for m in DIGIT_SEQ_RE.find_iter(&cleaned) {
         let digits = m.as_str();
         let candidate = if digits.len() == 10 {
@@ -102,71 +292,132 @@ pub fn normalize_phone(text: &str) -> Option<String> {
         };
 
         if let Some(normalized) = format_if_valid_phone(&candidate) {
-            return Some(normalized);
+            return Some((normalized, "digits"));
         }
     }
 
     None
 }
 
+/// First valid `+`-prefixed number in `cleaned`. Without one, the first bare ten-digit run
+/// is returned untouched under `NationalPhonePolicy::Keep`, since its country is unknown.
+fn international_phone(
+    cleaned: &str,
+    policy: NationalPhonePolicy,
+) -> Option<(String, &'static str)> {
+    let mut national = None;
+    for m in DIGIT_SEQ_RE.find_iter(cleaned) {
+        let digits = m.as_str();
+        if cleaned[..m.start()].ends_with('+') {
+            if let Some(normalized) = format_if_valid_phone(&format!("+{digits}")) {
+                return Some((normalized, "digits"));
+            }
+        } else if digits.len() == 10 && national.is_none() {
+            national = Some(digits);
+        }
+    }
+
+    match policy {
+        NationalPhonePolicy::Keep => national.map(|digits| (digits.to_string(), "national")),
+        NationalPhonePolicy::Drop => None,
+    }
+}
+
+/// Extension written after `phone` in the text, e.g. `89` in "+1 650 253 0000 x89". Only
+/// numbers that normalize to `phone` count, so another contact's extension is never used.
+pub fn extract_phone_extension(text: &str, phone: &str, mode: PhoneMode) -> Option<String> {
+    PHONE_EXTENSION_RE
+        .captures_iter(text)
+        .find(|captures| {
+            normalize_phone_with_source(&captures[1], mode).is_some_and(|(found, _)| found == phone)
+        })
+        .map(|captures| captures[2].to_string())
+}
+
+/// Profile handles may contain dots, so a sentence ending right after a URL leaks its
+/// period into the match; drop it.
+fn trim_trailing_periods(value: &str) -> &str {
+    value.trim_end_matches('.')
+}
+
 pub fn extract_linkedin(text: &str) -> Option<String> {
+    extract_linkedin_with_source(text).map(|(url, _)| url)
+}
+
+/// Like `extract_linkedin`, naming the tier that matched: `href`, `keyword`, `pattern`,
+/// or `fallback`.
+pub fn extract_linkedin_with_source(text: &str) -> Option<(String, &'static str)> {
     for regex in &*LINKEDIN_HREF_RES {
         if let Some(captures) = regex.captures(text) {
-            let mut url = captures.get(1)?.as_str().to_string();
+            let mut url = trim_trailing_periods(captures.get(1)?.as_str()).to_string();
             if !url.to_ascii_lowercase().starts_with("http") {
                 url = format!("https://www.{url}");
             }
-            return Some(url);
+            return Some((url, "href"));
         }
     }
 
     if let Some(captures) = LINKEDIN_KEYWORD_RE.captures(text) {
-        return captures.get(1).map(|m| m.as_str().to_string());
+        return captures
+            .get(1)
+            .map(|m| (trim_trailing_periods(m.as_str()).to_string(), "keyword"));
     }
 
     for regex in &*LINKEDIN_PATTERNS {
         if let Some(captures) = regex.captures(text) {
-            if let Some(username) = captures.get(1) {
-                return Some(format!("https://www.linkedin.com/in/{}", username.as_str()));
+            let username = captures.get(1).map(|m| trim_trailing_periods(m.as_str()));
+            if let Some(username) = username.filter(|u| !u.is_empty()) {
+                return Some((format!("https://www.linkedin.com/in/{username}"), "pattern"));
             }
         }
     }
 
     LINKEDIN_FALLBACK_RE
         .find(text)
-        .map(|m| m.as_str().to_string())
+        .map(|m| (trim_trailing_periods(m.as_str()).to_string(), "fallback"))
 }
 
 pub fn extract_github(text: &str) -> Option<String> {
+    extract_github_with_source(text).map(|(url, _)| url)
+}
+
+/// Like `extract_github`, naming the tier that matched: `href`, `keyword`, `pattern`,
+/// or `fallback`.
+pub fn extract_github_with_source(text: &str) -> Option<(String, &'static str)> {
     for regex in &*GITHUB_HREF_RES {
         if let Some(captures) = regex.captures(text) {
-            let mut url = captures.get(1)?.as_str().to_string();
+            let mut url = trim_trailing_periods(captures.get(1)?.as_str()).to_string();
             if !url.to_ascii_lowercase().starts_with("http") {
                 url = format!("https://{url}");
             }
-            return Some(url);
+            return Some((url, "href"));
         }
     }
 
     if let Some(captures) = GITHUB_KEYWORD_RE.captures(text) {
-        return captures.get(1).map(|m| m.as_str().to_string());
+        return captures
+            .get(1)
+            .map(|m| (trim_trailing_periods(m.as_str()).to_string(), "keyword"));
     }
 
     for regex in &*GITHUB_PATTERNS {
         if let Some(captures) = regex.captures(text) {
-            if let Some(username) = captures.get(1) {
-                return Some(format!("https://github.com/{}", username.as_str()));
+            let username = captures.get(1).map(|m| trim_trailing_periods(m.as_str()));
+            if let Some(username) = username.filter(|u| !u.is_empty()) {
+                return Some((format!("https://github.com/{username}"), "pattern"));
             }
         }
     }
 
     GITHUB_FALLBACK_RE
         .find(text)
-        .map(|m| m.as_str().to_string())
+        .map(|m| (trim_trailing_periods(m.as_str()).to_string(), "fallback"))
 }
 
 pub fn extract_fields(
     text: &str,
+    email_preference: EmailPreference,
+    phone_mode: PhoneMode,
 ) -> (
     Option<String>,
     Option<String>,
@@ -174,14 +425,178 @@ pub fn extract_fields(
     Option<String>,
 ) {
     (
-        extract_email(text),
-        normalize_phone(text),
+        extract_email_with_preference(text, email_preference),
+        normalize_phone_with_source(text, phone_mode).map(|(phone, _)| phone),
         extract_linkedin(text),
         extract_github(text),
     )
 }
 
-pub fn guess_name(text: &str) -> Option<String> {
+/// Which extractor tier matched each contact field, keyed by the field's JSON name
+/// (`email`, `phone`, `linkedIn`, `gitHub`). Runs the extractors again, so it is only meant
+/// for debugging single parses.
+pub fn extract_field_sources(
+    text: &str,
+    email_preference: EmailPreference,
+    phone_mode: PhoneMode,
+) -> HashMap<String, String> {
+    let sources = [
+        (
+            "email",
+            extract_email_with_preference_and_source(text, email_preference).map(|(_, s)| s),
+        ),
+        (
+            "phone",
+            normalize_phone_with_source(text, phone_mode).map(|(_, s)| s),
+        ),
+        (
+            "linkedIn",
+            extract_linkedin_with_source(text).map(|(_, s)| s),
+        ),
+        ("gitHub", extract_github_with_source(text).map(|(_, s)| s)),
+    ];
+
+    sources
+        .into_iter()
+        .filter_map(|(field, source)| Some((field.to_string(), source?.to_string())))
+        .collect()
+}
+
+/// A skill dictionary entry with its pattern compiled, ready to run against many texts.
+#[derive(Debug, Clone)]
+pub struct SkillPattern {
+    skill: String,
+    regex: Regex,
+}
+
+/// Compiles a skill dictionary once, so a batch does not rebuild the patterns for every
+/// file. Blank entries and case-insensitive repeats are dropped; the first spelling wins.
+pub fn compile_skill_dictionary(dictionary: &[String]) -> Vec<SkillPattern> {
+    let mut compiled: Vec<SkillPattern> = Vec::new();
+    for skill in dictionary {
+        let skill = skill.trim();
+        if skill.is_empty()
+            || compiled
+                .iter()
+                .any(|found| found.skill.eq_ignore_ascii_case(skill))
+        {
+            continue;
+        }
+
+        // `\b` would not match around skills such as "C++" or ".NET", so the boundary is any
+        // non-word character instead.
+        let pattern = format!(
+            r"(?i)(?:^|[^A-Za-z0-9_]){}(?:$|[^A-Za-z0-9_])",
+            regex::escape(skill)
+        );
+        if let Ok(regex) = Regex::new(&pattern) {
+            compiled.push(SkillPattern {
+                skill: skill.to_string(),
+                regex,
+            });
+        }
+    }
+    compiled
+}
+
+/// Skills from the compiled dictionary mentioned in the text, matched case-insensitively on
+/// word boundaries and returned in dictionary order using the dictionary's spelling.
+pub fn extract_skills(text: &str, dictionary: &[SkillPattern]) -> Vec<String> {
+    dictionary
+        .iter()
+        .filter(|pattern| pattern.regex.is_match(text))
+        .map(|pattern| pattern.skill.clone())
+        .collect()
+}
+
+/// A custom field rule with its pattern compiled, ready to run against many texts.
+#[derive(Debug, Clone)]
+pub struct CustomFieldPattern {
+    name: String,
+    regex: Regex,
+}
+
+/// Compiles custom field rules. Names must be unique and non-empty, and each pattern must
+/// parse and have exactly one capture group; every rule that breaks this is reported.
+pub fn compile_custom_fields(
+    rules: &[CustomFieldRule],
+) -> Result<Vec<CustomFieldPattern>, Vec<String>> {
+    let mut compiled = Vec::new();
+    let mut problems = Vec::new();
+    let mut names = HashSet::new();
+    for rule in rules {
+        let name = rule.name.trim();
+        if name.is_empty() {
+            problems.push("Custom field names cannot be empty".to_string());
+            continue;
+        }
+        if !names.insert(name.to_lowercase()) {
+            problems.push(format!("Custom field {name} is defined more than once"));
+            continue;
+        }
+        match Regex::new(&rule.pattern) {
+            // Group 0 is the whole match.
+            Ok(regex) if regex.captures_len() == 2 => compiled.push(CustomFieldPattern {
+                name: name.to_string(),
+                regex,
+            }),
+            Ok(_) => problems.push(format!(
+                "Custom field {name} needs a pattern with exactly one capture group"
+            )),
+            Err(err) => problems.push(format!("Custom field {name} has an invalid pattern: {err}")),
+        }
+    }
+
+    if problems.is_empty() {
+        Ok(compiled)
+    } else {
+        Err(problems)
+    }
+}
+
+/// The trimmed capture of each custom field's first match, by field name. Fields that do
+/// not match, or capture only whitespace, are left out.
+pub fn extract_custom_fields(text: &str, fields: &[CustomFieldPattern]) -> HashMap<String, String> {
+    fields
+        .iter()
+        .filter_map(|field| {
+            let value = field.regex.captures(text)?.get(1)?.as_str().trim();
+            (!value.is_empty()).then(|| (field.name.clone(), value.to_string()))
+        })
+        .collect()
+}
+
+/// How many lines `name_candidates` offers.
+const NAME_CANDIDATE_LIMIT: usize = 5;
+
+/// First capitalized two-to-four word line near the top or just above a contact line.
+/// Section titles and `denylist` entries are skipped, compared case-insensitively against
+/// the whole line.
+pub fn guess_name(text: &str, denylist: &[String]) -> Option<String> {
+    name_candidate_lines(text, denylist)
+        .next()
+        .map(str::to_string)
+}
+
+/// Up to five distinct lines that could be the name, in the order `guess_name` weighs
+/// them, so the first is its pick. Lets a user correct a wrong guess.
+pub fn name_candidates(text: &str, denylist: &[String]) -> Vec<String> {
+    let mut candidates: Vec<String> = Vec::new();
+    for line in name_candidate_lines(text, denylist) {
+        if !candidates.iter().any(|candidate| candidate == line) {
+            candidates.push(line.to_string());
+        }
+        if candidates.len() == NAME_CANDIDATE_LIMIT {
+            break;
+        }
+    }
+    candidates
+}
+
+fn name_candidate_lines<'a>(
+    text: &'a str,
+    denylist: &'a [String],
+) -> impl Iterator<Item = &'a str> + 'a {
     let lines: Vec<&str> = text.lines().collect();
     let mut candidate_lines: Vec<&str> = lines.iter().take(30).copied().collect();
 
@@ -193,32 +608,279 @@ pub fn guess_name(text: &str) -> Option<String> {
         }
     }
 
-    for raw in candidate_lines {
-        let line = raw.trim();
-        if line.is_empty() {
-            continue;
-        }
+    candidate_lines
+        .into_iter()
+        .map(str::trim)
+        .filter(move |line| is_name_like(line, denylist))
+}
 
-        if line.contains('@') || line.len() > 50 || NAME_STARTS_WITH_PHONE_RE.is_match(line) {
-            continue;
-        }
+fn is_name_like(line: &str, denylist: &[String]) -> bool {
+    if line.is_empty() {
+        return false;
+    }
+
+    if line.contains('@') || line.len() > 50 || NAME_STARTS_WITH_PHONE_RE.is_match(line) {
+        return false;
+    }
+
+    let title = line.trim_end_matches(':').trim();
+    if SECTION_TITLES
+        .iter()
+        .any(|section| section.eq_ignore_ascii_case(title))
+        || denylist
+            .iter()
+            .any(|entry| entry.trim().eq_ignore_ascii_case(title))
+    {
+        return false;
+    }
+
+    let words: Vec<&str> = line.split_whitespace().collect();
+    if words.len() < 2 || words.len() > 4 {
+        return false;
+    }
+
+    words
+        .iter()
+        .all(|w| w.chars().next().map(|c| c.is_uppercase()).unwrap_or(false))
+}
+
+/// Filename words that describe the document rather than name its owner.
+const FILENAME_NOISE_WORDS: &[&str] = &[
+    "copy",
+    "curriculum",
+    "cv",
+    "draft",
+    "final",
+    "latest",
+    "new",
+    "profile",
+    "resume",
+    "résumé",
+    "updated",
+    "vitae",
+];
+
+const MONTHS: &[&str] = &[
+    "jan",
+    "january",
+    "feb",
+    "february",
+    "mar",
+    "march",
+    "apr",
+    "april",
+    "may",
+    "jun",
+    "june",
+    "jul",
+    "july",
+    "aug",
+    "august",
+    "sep",
+    "sept",
+    "september",
+    "oct",
+    "october",
+    "nov",
+    "november",
+    "dec",
+    "december",
+];
+
+/// Name encoded in a filename such as `Jane_Smith_Resume.pdf` or `john-doe-cv-2024 (1).docx`.
+/// Document words, numbers and month names next to a number are dropped; like `guess_name`,
+/// two to four words must remain. All-lowercase and all-uppercase words are title-cased;
+/// mixed-case ones such as `O'Brien` or `McDonald` are kept as written.
+pub fn name_from_filename(file_name: &str) -> Option<String> {
+    let stem = std::path::Path::new(file_name).file_stem()?.to_str()?;
+    let tokens = stem
+        .split(|c: char| !(c.is_alphanumeric() || c == '\''))
+        .filter(|token| !token.is_empty())
+        .collect::<Vec<_>>();
+    let is_number = |index: usize| {
+        tokens
+            .get(index)
+            .is_some_and(|token| token.chars().all(|c| c.is_ascii_digit()))
+    };
+
+    let words = tokens
+        .iter()
+        .enumerate()
+        .filter(|(index, token)| {
+            let lower = token.to_lowercase();
+            let dated_month = MONTHS.contains(&lower.as_str())
+                && (is_number(index + 1) || index.checked_sub(1).is_some_and(is_number));
+            !token.chars().any(|c| c.is_ascii_digit())
+                && !FILENAME_NOISE_WORDS.contains(&lower.as_str())
+                && !dated_month
+        })
+        .map(|(_, token)| {
+            let mixed_case =
+                token.chars().any(char::is_lowercase) && token.chars().any(char::is_uppercase);
+            if mixed_case {
+                return token.to_string();
+            }
+            let mut chars = token.chars();
+            chars
+                .next()
+                .map(|first| {
+                    first
+                        .to_uppercase()
+                        .chain(chars.flat_map(char::to_lowercase))
+                })
+                .into_iter()
+                .flatten()
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>();
+
+    (2..=4).contains(&words.len()).then(|| words.join(" "))
+}
+
+/// Common resume section titles, which are neither names nor headlines.
+const SECTION_TITLES: &[&str] = &[
+    "about",
+    "about me",
+    "career objective",
+    "contact",
+    "contact details",
+    "contact information",
+    "core competencies",
+    "curriculum vitae",
+    "education",
+    "employment history",
+    "experience",
+    "key skills",
+    "objective",
+    "personal details",
+    "personal information",
+    "professional experience",
+    "professional summary",
+    "profile",
+    "projects",
+    "resume",
+    "skills",
+    "summary",
+    "technical skills",
+    "work experience",
+    "work history",
+];
+
+/// First substantive line right after the name line, e.g. "Senior Backend Engineer | Go, Rust".
+/// Contact lines are skipped; section titles and long sentences end the search.
+pub fn extract_headline(text: &str, name: Option<&str>) -> Option<String> {
+    let name = name?.trim();
+    let mut lines = text.lines().map(str::trim);
+    lines.find(|line| *line == name)?;
 
-        let words: Vec<&str> = line.split_whitespace().collect();
-        if words.len() < 2 || words.len() > 4 {
+    for line in lines.filter(|line| !line.is_empty()).take(3) {
+        let lower = line.to_lowercase();
+        let is_contact = line.contains('@')
+            || DIGIT_SEQ_RE.is_match(&PHONE_CLEAN_RE.replace_all(line, ""))
+            || [
+                "linkedin", "github", "http", "www.", "email", "phone", "mobile",
+            ]
+            .iter()
+            .any(|marker| lower.contains(marker));
+        if is_contact {
             continue;
         }
 
-        if words
-            .iter()
-            .all(|w| w.chars().next().map(|c| c.is_uppercase()).unwrap_or(false))
-        {
-            return Some(line.to_string());
+        let title = lower.trim_end_matches(':').trim();
+        let words = line.split_whitespace().count();
+        if SECTION_TITLES.contains(&title) || words < 2 || line.len() > 100 || line.ends_with('.') {
+            return None;
         }
+
+        return Some(line.to_string());
     }
 
     None
 }
 
+/// Headings that start an education section.
+const EDUCATION_TITLES: &[&str] = &[
+    "academic background",
+    "academic qualifications",
+    "academics",
+    "education",
+    "education & training",
+    "education and training",
+    "educational qualifications",
+    "qualifications",
+];
+
+/// Lines read under an education heading before giving up.
+const EDUCATION_SECTION_LINES: usize = 10;
+const EDUCATION_MAX_CHARS: usize = 160;
+
+/// First degree line under an Education heading, e.g. "B.Tech in Computer Science, IIT
+/// Delhi". An institution on the line just before or after is appended.
+pub fn extract_education(text: &str) -> Option<String> {
+    extract_education_with_institution(text).map(|(education, _)| education)
+}
+
+/// Like `extract_education`, also telling whether an institution was found next to the
+/// degree. Without one the line may be a course or certificate rather than a degree.
+pub fn extract_education_with_institution(text: &str) -> Option<(String, bool)> {
+    let lines = text
+        .lines()
+        .map(|line| line.trim().trim_start_matches(['•', '-', '*', '·']).trim())
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>();
+    let start = lines.iter().position(|line| {
+        let title = line.trim_end_matches(':').trim().to_lowercase();
+        EDUCATION_TITLES.contains(&title.as_str())
+    })? + 1;
+
+    let mut section = Vec::new();
+    for line in lines.iter().skip(start).take(EDUCATION_SECTION_LINES) {
+        let title = line.trim_end_matches(':').trim().to_lowercase();
+        if SECTION_TITLES.contains(&title.as_str()) {
+            break;
+        }
+        section.push(*line);
+    }
+
+    let index = section
+        .iter()
+        .position(|line| line.len() <= EDUCATION_MAX_CHARS && DEGREE_RE.is_match(line))?;
+    let degree = section[index];
+    if INSTITUTION_RE.is_match(degree) {
+        return Some((degree.to_string(), true));
+    }
+
+    let neighbour = [index.checked_sub(1), Some(index + 1)]
+        .into_iter()
+        .flatten()
+        .filter_map(|neighbour| section.get(neighbour))
+        .find(|line| line.len() <= EDUCATION_MAX_CHARS && INSTITUTION_RE.is_match(line));
+    match neighbour {
+        Some(institution) => Some((format!("{degree}, {institution}"), true)),
+        None => Some((degree.to_string(), false)),
+    }
+}
+
+/// Notice period or availability, normalized to "30 days", "2 months", or "Immediate".
+/// Only explicit phrasings match, so a bare "30 days" elsewhere in the text is ignored.
+pub fn extract_notice_period(text: &str) -> Option<String> {
+    if IMMEDIATE_NOTICE_RE.is_match(text) {
+        return Some("Immediate".to_string());
+    }
+
+    let captures = NOTICE_PERIOD_RE
+        .captures(text)
+        .or_else(|| DURATION_NOTICE_RE.captures(text))?;
+    let amount = &captures[1];
+    let unit = captures[2].to_lowercase();
+    let unit = unit.trim_end_matches('s');
+    if amount == "1" {
+        Some(format!("1 {unit}"))
+    } else {
+        Some(format!("{amount} {unit}s"))
+    }
+}
+
 pub fn score_confidence(
     name: Option<&str>,
     email: Option<&str>,
@@ -226,26 +888,27 @@ pub fn score_confidence(
     linked_in: Option<&str>,
     git_hub: Option<&str>,
     ocr_used: bool,
+    weights: &ConfidenceWeights,
 ) -> f64 {
     let mut score: f64 = 0.0;
 
     if email.is_some_and(|v| !v.trim().is_empty()) {
-        score += 0.4;
+        score += weights.email;
     }
     if phone.is_some_and(|v| !v.trim().is_empty()) {
-        score += 0.25;
+        score += weights.phone;
     }
     if name.is_some_and(|v| !v.trim().is_empty()) {
-        score += 0.15;
+        score += weights.name;
     }
     if linked_in.is_some_and(|v| !v.trim().is_empty()) {
-        score += 0.1;
+        score += weights.linked_in;
     }
     if git_hub.is_some_and(|v| !v.trim().is_empty()) {
-        score += 0.05;
+        score += weights.git_hub;
     }
     if !ocr_used {
-        score += 0.05;
+        score += weights.text_layer;
     }
 
     score.min(1.0)
@@ -264,6 +927,42 @@ fn format_if_valid_phone(input: &str) -> Option<String> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn custom_field_rules_capture_their_group_and_are_validated() {
+        let rule = |name: &str, pattern: &str| CustomFieldRule {
+            name: name.to_string(),
+            pattern: pattern.to_string(),
+        };
+        let fields = compile_custom_fields(&[
+            rule(" Visa Status ", r"(?i)visa(?: status)?\s*[:\-]\s*([^\n]+)"),
+            rule("Candidate Ref", r"\b(CR-\d{5})\b"),
+            rule("Clearance", r"(?i)clearance:\s*(\w+)"),
+        ])
+        .unwrap();
+
+        let values = extract_custom_fields(
+            "Jane Doe\nVisa status: H-1B (transferable) \nRef CR-00421",
+            &fields,
+        );
+        assert_eq!(values.len(), 2);
+        assert_eq!(values["Visa Status"], "H-1B (transferable)");
+        assert_eq!(values["Candidate Ref"], "CR-00421");
+
+        let problems = compile_custom_fields(&[
+            rule("", r"(\d+)"),
+            rule("Ref", r"(\d+)"),
+            rule("ref", r"(\w+)"),
+            rule("Visa", r"visa: \w+"),
+            rule("Notice", r"(a)(b)"),
+            rule("Broken", r"(unclosed"),
+        ])
+        .unwrap_err();
+        assert_eq!(problems.len(), 5);
+        assert!(problems[1].contains("ref is defined more than once"));
+        assert!(problems[2].contains("Visa needs a pattern with exactly one capture group"));
+        assert!(problems[4].contains("Broken has an invalid pattern"));
+    }
+
     #[test]
     fn extract_email_finds_standard_addresses() {
         assert_eq!(
@@ -277,6 +976,114 @@ mod tests {
         assert_eq!(extract_email("No email here"), None);
     }
 
+    #[test]
+    fn deobfuscate_emails_rewrites_bracketed_at_and_dot() {
+        let cases = [
+            ("Email: jane (at) example (dot) com", "jane@example.com"),
+            ("JANE.DOE [AT] GMAIL [DOT] COM", "jane.doe@gmail.com"),
+            (
+                "reach me: jane{at}mail.example.org",
+                "jane@mail.example.org",
+            ),
+            (
+                "jane (dot) doe (at) acme (dot) co (dot) uk",
+                "jane.doe@acme.co.uk",
+            ),
+            ("j.smith <at> uni <dot> edu", "j.smith@uni.edu"),
+            ("jane@example (dot) com", "jane@example.com"),
+            (
+                "Email: jane (at) example (dot) com. Phone: 555",
+                "jane@example.com",
+            ),
+        ];
+        for (text, expected) in cases {
+            let text = deobfuscate_emails(text);
+            assert_eq!(extract_email(&text).as_deref(), Some(expected), "{text}");
+        }
+
+        assert_eq!(
+            deobfuscate_emails("Email: jane (at) example (dot) com. Phone: 555"),
+            "Email: jane@example.com. Phone: 555"
+        );
+        for text in [
+            "Contact me at john.doe@example.com",
+            "Worked at Acme dot com startup (2019 - 2021)",
+        ] {
+            assert_eq!(deobfuscate_emails(text), text);
+        }
+    }
+
+    #[test]
+    fn field_sources_name_the_matching_tier() {
+        let text = "Jane Doe\nmailto:jane@corp.example\nPhone: 98765 43210\nlinkedin.com/in/janedoe\nGitHub: https://github.com/janedoe";
+        let sources = extract_field_sources(text, EmailPreference::First, PhoneMode::default());
+
+        assert_eq!(sources["email"], "mailto");
+        assert_eq!(sources["phone"], "digits");
+        assert_eq!(sources["linkedIn"], "pattern");
+        assert_eq!(sources["gitHub"], "pattern");
+
+        let preferred = extract_field_sources(
+            "work: jane@corp.example\npersonal: jane@gmail.com",
+            EmailPreference::PreferPersonal,
+            PhoneMode::default(),
+        );
+        assert_eq!(preferred["email"], "preference");
+        assert!(!preferred.contains_key("phone"));
+    }
+
+    #[test]
+    fn email_preference_orders_personal_and_corporate_addresses() {
+        let text = "Jane Doe\nEmail: jane.doe@acme-corp.com\nPersonal: JaneDoe87@Gmail.com";
+
+        assert_eq!(
+            extract_email_with_preference(text, EmailPreference::First),
+            Some("jane.doe@acme-corp.com".to_string())
+        );
+        assert_eq!(
+            extract_email_with_preference(text, EmailPreference::PreferPersonal),
+            Some("janedoe87@gmail.com".to_string())
+        );
+        assert_eq!(
+            extract_email_with_preference(text, EmailPreference::PreferCorporate),
+            Some("jane.doe@acme-corp.com".to_string())
+        );
+        assert_eq!(
+            extract_email_with_preference(
+                "only.personal@outlook.com",
+                EmailPreference::PreferCorporate
+            ),
+            Some("only.personal@outlook.com".to_string())
+        );
+    }
+
+    #[test]
+    fn extract_skills_matches_dictionary_on_word_boundaries() {
+        let dictionary = ["Rust", "Go", "C++", "Node.js", "SQL", "rust"]
+            .iter()
+            .map(|skill| skill.to_string())
+            .collect::<Vec<_>>();
+        let text =
+            "Built services in RUST and c++; some node.js tooling.\nGoogle Cloud, PostgreSQL";
+
+        assert_eq!(
+            extract_skills(text, &compile_skill_dictionary(&dictionary)),
+            vec!["Rust", "C++", "Node.js"]
+        );
+        assert!(extract_skills(text, &[]).is_empty());
+    }
+
+    #[test]
+    fn email_domain_is_lowercased_and_trimmed() {
+        assert_eq!(email_domain("a@b.com"), Some("b.com".to_string()));
+        assert_eq!(
+            email_domain(" Jane.Doe@Example.CO.uk "),
+            Some("example.co.uk".to_string())
+        );
+        assert_eq!(email_domain("no-at-sign"), None);
+        assert_eq!(email_domain("dangling@"), None);
+    }
+
     #[test]
     fn normalize_phone_handles_indian_defaults_and_formatted_numbers() {
         assert_eq!(
@@ -303,6 +1110,124 @@ mod tests {
         assert_eq!(normalize_phone("not a phone"), None);
     }
 
+    #[test]
+    fn phone_extensions_are_split_from_the_number() {
+        for text in [
+            "Office: +1 650 253 0000 x89",
+            "Office: +1 (650) 253-0000 ext. 89",
+            "Office: +1 650 253 0000 Ext 89",
+            "Office: +1 650-253-0000, extension: 89",
+            "Office: +1 650 253 0000 #89",
+        ] {
+            assert_eq!(
+                normalize_phone(text),
+                Some("+16502530000".to_string()),
+                "{text}"
+            );
+            assert_eq!(
+                extract_phone_extension(text, "+16502530000", PhoneMode::default()),
+                Some("89".to_string()),
+                "{text}"
+            );
+        }
+
+        assert_eq!(
+            extract_phone_extension(
+                "Office: +1 650 253 0000",
+                "+16502530000",
+                PhoneMode::default()
+            ),
+            None
+        );
+        assert_eq!(
+            extract_phone_extension(
+                "Reception: +1 650 253 0001 x12",
+                "+16502530000",
+                PhoneMode::default()
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn international_only_mode_never_assumes_a_region() {
+        let keep = PhoneMode::InternationalOnly(NationalPhonePolicy::Keep);
+        let drop = PhoneMode::InternationalOnly(NationalPhonePolicy::Drop);
+
+        for mode in [keep, drop] {
+            assert_eq!(
+                normalize_phone_with_source("+1 (650) 253-0000", mode),
+                Some(("+16502530000".to_string(), "whole_text"))
+            );
+            assert_eq!(
+                normalize_phone_with_source("Mobile: +44 20 7946 0958 | London", mode),
+                Some(("+442079460958".to_string(), "digits"))
+            );
+            assert_eq!(
+                normalize_phone_with_source("Phone: 98765 43210\nAlt: +91 98765 43211", mode),
+                Some(("+919876543211".to_string(), "digits"))
+            );
+            // Twelve digits without a `+` are not read as a country code either.
+            assert_eq!(
+                normalize_phone_with_source("Phone: 919876543210", mode),
+                None
+            );
+        }
+
+        assert_eq!(
+            normalize_phone_with_source("Phone: (987) 654-3210", keep),
+            Some(("9876543210".to_string(), "national"))
+        );
+        assert_eq!(
+            normalize_phone_with_source("Phone: (987) 654-3210", drop),
+            None
+        );
+        assert_eq!(
+            normalize_phone("Phone: (987) 654-3210"),
+            Some("+919876543210".to_string())
+        );
+
+        assert_eq!(
+            extract_phone_extension("Office: 650 253 0000 x89", "6502530000", keep),
+            Some("89".to_string())
+        );
+        assert_eq!(
+            PhoneMode::from_settings(false, NationalPhonePolicy::Drop),
+            PhoneMode::DefaultToIndia
+        );
+        assert_eq!(
+            PhoneMode::from_settings(true, NationalPhonePolicy::Drop),
+            drop
+        );
+    }
+
+    #[test]
+    fn notice_period_phrasings_are_normalized() {
+        for (text, expected) in [
+            ("Notice period: 30 days", "30 days"),
+            ("NOTICE PERIOD - 60 Days", "60 days"),
+            ("Current notice period of 2 months", "2 months"),
+            ("Can join with 2 months notice.", "2 months"),
+            ("Requires 1 month's notice", "1 month"),
+            ("4 weeks' notice", "4 weeks"),
+            ("Available immediately", "Immediate"),
+            ("Notice period: Immediate", "Immediate"),
+            ("Immediate joiner", "Immediate"),
+        ] {
+            assert_eq!(
+                extract_notice_period(text),
+                Some(expected.to_string()),
+                "{text}"
+            );
+        }
+
+        assert_eq!(
+            extract_notice_period("Shipped the release in 30 days"),
+            None
+        );
+        assert_eq!(extract_notice_period("Gave notice to vendors"), None);
+    }
+
     #[test]
     fn extract_linkedin_formats_supported_values() {
         assert_eq!(
@@ -329,6 +1254,140 @@ mod tests {
         assert_eq!(extract_github("No GitHub here"), None);
     }
 
+    #[test]
+    fn linkedin_handles_keep_dots_and_underscores_but_not_prose_punctuation() {
+        assert_eq!(
+            extract_github("Code lives at github.com/jane-doe."),
+            Some("https://github.com/jane-doe".to_string())
+        );
+        assert_eq!(
+            extract_github("GitHub: https://github.com/jane-doe, and more"),
+            Some("https://github.com/jane-doe".to_string())
+        );
+        assert_eq!(
+            extract_linkedin("Find me on linkedin.com/in/jane.doe_42."),
+            Some("https://www.linkedin.com/in/jane.doe_42".to_string())
+        );
+        assert_eq!(
+            extract_linkedin("LinkedIn: https://www.linkedin.com/in/jos%C3%A9-garc%C3%ADa, Madrid"),
+            Some("https://www.linkedin.com/in/jos%C3%A9-garc%C3%ADa".to_string())
+        );
+    }
+
+    #[test]
+    fn guess_name_skips_section_titles_and_denylisted_lines() {
+        let text = "Curriculum Vitae\nJane Doe\njane@example.com";
+        assert_eq!(guess_name(text, &[]), Some("Jane Doe".to_string()));
+
+        let text = "CONTACT INFORMATION:\nProfessional Summary\nJane Doe";
+        assert_eq!(guess_name(text, &[]), Some("Jane Doe".to_string()));
+
+        let text = "Acme Talent Partners\nJane Doe\nPhone: 98765 43210";
+        assert_eq!(
+            guess_name(text, &["acme talent partners".to_string()]),
+            Some("Jane Doe".to_string())
+        );
+        assert_eq!(guess_name("Curriculum Vitae", &[]), None);
+    }
+
+    #[test]
+    fn name_candidates_rank_every_plausible_header_line() {
+        let text = "Senior Backend Engineer\nJane Doe\nBangalore, India\nJane Doe\nEmail: jane@example.com";
+        let candidates = name_candidates(text, &[]);
+        assert_eq!(
+            candidates,
+            vec!["Senior Backend Engineer", "Jane Doe", "Bangalore, India"]
+        );
+        assert_eq!(
+            guess_name(text, &[]).as_deref(),
+            Some(candidates[0].as_str())
+        );
+
+        let many = ('A'..='H')
+            .map(|initial| format!("Jane {initial} Doe"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(name_candidates(&many, &[]).len(), 5);
+        assert!(name_candidates("jane@example.com", &[]).is_empty());
+    }
+
+    #[test]
+    fn name_from_filename_handles_common_conventions() {
+        let cases = [
+            ("Jane_Smith_Resume.pdf", Some("Jane Smith")),
+            ("john-doe-cv-2024 (1).docx", Some("John Doe")),
+            ("RESUME - ANA MARIA LIMA.pdf", Some("Ana Maria Lima")),
+            ("priya.sharma.final.updated.docx", Some("Priya Sharma")),
+            ("Resume_Sean_O'Brien_March_2024.pdf", Some("Sean O'Brien")),
+            ("ronald_McDonald_CV.pdf", Some("Ronald McDonald")),
+            ("June Carter CV.odt", Some("June Carter")),
+            ("resume.pdf", None),
+            ("Jane_Resume_v2.pdf", None),
+            ("scan_0042.pdf", None),
+        ];
+        for (file_name, expected) in cases {
+            assert_eq!(
+                name_from_filename(file_name).as_deref(),
+                expected,
+                "{file_name}"
+            );
+        }
+    }
+
+    #[test]
+    fn extract_education_reads_the_first_degree_under_the_heading() {
+        let single_line = "Jane Doe\nEDUCATION\nB.Tech in Computer Science, IIT Delhi, 2018\nM.S. Data Science, Stanford University";
+        assert_eq!(
+            extract_education_with_institution(single_line),
+            Some((
+                "B.Tech in Computer Science, IIT Delhi, 2018".to_string(),
+                true
+            ))
+        );
+
+        let stacked = "Experience\nBackend engineer at Acme.\n\nEducation:\n• University of Toronto\n• Bachelor of Applied Science, Computer Engineering\n• 2014 - 2018\n\nSkills\nRust";
+        assert_eq!(
+            extract_education(stacked).as_deref(),
+            Some("Bachelor of Applied Science, Computer Engineering, University of Toronto")
+        );
+
+        let no_institution = "Academic Qualifications\nMBA, Finance (2020)";
+        assert_eq!(
+            extract_education_with_institution(no_institution),
+            Some(("MBA, Finance (2020)".to_string(), false))
+        );
+
+        // Degrees outside an education section, or a section without one, are ignored.
+        assert_eq!(
+            extract_education("Masters of the craft\nSkills\nRust"),
+            None
+        );
+        assert_eq!(
+            extract_education("Education\nSelf-taught\nExperience\nB.Tech intern mentor"),
+            None
+        );
+    }
+
+    #[test]
+    fn extract_headline_takes_the_line_under_the_name() {
+        let stacked = "Jane Doe\nSenior Backend Engineer | Go, Rust\njane@example.com";
+        assert_eq!(
+            extract_headline(stacked, Some("Jane Doe")),
+            Some("Senior Backend Engineer | Go, Rust".to_string())
+        );
+
+        let contact_first =
+            "John Smith\n+91 98765 43210 | john@example.org\nData Analyst\n\nSUMMARY\nAnalyst.";
+        assert_eq!(
+            extract_headline(contact_first, Some("John Smith")),
+            Some("Data Analyst".to_string())
+        );
+
+        let section_next = "Asha Rao\nasha@example.com\nProfessional Summary\nBuilt things.";
+        assert_eq!(extract_headline(section_next, Some("Asha Rao")), None);
+        assert_eq!(extract_headline(stacked, None), None);
+    }
+
     #[test]
     fn score_confidence_matches_weights() {
         let max = score_confidence(
@@ -338,6 +1397,7 @@ mod tests {
             Some("https://linkedin.com/in/johndoe"),
             Some("https://github.com/johndoe"),
             false,
+            &ConfidenceWeights::default(),
         );
         assert!((max - 1.0).abs() < 0.001);
 
@@ -348,10 +1408,19 @@ mod tests {
             None,
             None,
             false,
+            &ConfidenceWeights::default(),
         );
         assert!((email_phone - 0.7).abs() < 0.01);
 
-        let email_only = score_confidence(None, Some("john@example.com"), None, None, None, false);
+        let email_only = score_confidence(
+            None,
+            Some("john@example.com"),
+            None,
+            None,
+            None,
+            false,
+            &ConfidenceWeights::default(),
+        );
         assert!((email_only - 0.45).abs() < 0.01);
     }
 }
@@ -1,14 +1,23 @@
 use anyhow::Context;
+use chrono::{DateTime, SecondsFormat, Utc};
 use reqwest::Client;
 use serde::Deserialize;
 
-use super::errors::CoreError;
-use super::models::{DriveBrowserFile, DriveFileRef, DriveFolderEntry, DrivePathEntry};
+use super::errors::{AuthErrorCode, CoreError, GoogleApiService};
+use super::models::{
+    DriveBrowserFile, DriveFileRef, DriveFolderEntry, DrivePathEntry, FolderAccess,
+    FolderAccessStatus,
+};
 
 const DRIVE_FILES_ENDPOINT: &str = "https://www.googleapis.com/drive/v3/files";
 const FOLDER_MIME: &str = "application/vnd.google-apps.folder";
 const PDF_MIME: &str = "application/pdf";
 const DOCX_MIME: &str = "application/vnd.openxmlformats-officedocument.wordprocessingml.document";
+const ODT_MIME: &str = "application/vnd.oasis.opendocument.text";
+/// Types listed when a job does not narrow them down.
+const RESUME_MIMES: [&str; 3] = [PDF_MIME, DOCX_MIME, ODT_MIME];
+/// Stands in for a missing mimeType that the file extension does not explain either.
+const UNKNOWN_MIME: &str = "application/octet-stream";
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -28,13 +37,32 @@ struct DriveFileItem {
     modified_time: Option<String>,
 }
 
+/// One page of a Drive listing with the token that fetched it (`None` for the first page).
+#[derive(Debug, Clone)]
+pub struct DriveFilePage {
+    pub page_token: Option<String>,
+    pub files: Vec<DriveFileRef>,
+}
+
 pub struct GoogleDriveClient {
     client: Client,
+    files_endpoint: String,
 }
 
 impl GoogleDriveClient {
     pub fn new(client: Client) -> Self {
-        Self { client }
+        Self {
+            client,
+            files_endpoint: DRIVE_FILES_ENDPOINT.to_string(),
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn with_endpoint(client: Client, files_endpoint: String) -> Self {
+        Self {
+            client,
+            files_endpoint,
+        }
     }
 
     pub async fn list_folders(
@@ -71,24 +99,45 @@ impl GoogleDriveClient {
         access_token: &str,
         folder_id: &str,
     ) -> anyhow::Result<Vec<DriveFileRef>> {
-        let query = format!(
-            "'{folder_id}' in parents and trashed=false and (mimeType='{PDF_MIME}' or mimeType='{DOCX_MIME}')"
-        );
-
-        let items = self.query_files(access_token, &query).await?;
-        Ok(items
+        Ok(self
+            .list_resume_file_pages(access_token, folder_id, None, false, &[], None)
+            .await?
             .into_iter()
-            .filter_map(|item| {
-                let (Some(id), Some(name), Some(mime_type)) = (item.id, item.name, item.mime_type)
-                else {
-                    return None;
-                };
+            .flat_map(|page| page.files)
+            .collect())
+    }
 
-                Some(DriveFileRef {
-                    id,
-                    name,
-                    mime_type,
-                })
+    /// Lists resume files page by page, starting at `start_page_token` when resuming a
+    /// listing instead of at the first page. `mime_types` limits the listing to those types;
+    /// when it is empty, every supported type is listed, or with `include_unsupported` every
+    /// file that is not a folder. `modified_after` leaves out files Drive last saw modified
+    /// at or before that time.
+    pub async fn list_resume_file_pages(
+        &self,
+        access_token: &str,
+        folder_id: &str,
+        start_page_token: Option<&str>,
+        include_unsupported: bool,
+        mime_types: &[String],
+        modified_after: Option<DateTime<Utc>>,
+    ) -> anyhow::Result<Vec<DriveFilePage>> {
+        let include_unsupported = include_unsupported && mime_types.is_empty();
+        let query = resume_files_query(folder_id, include_unsupported, mime_types, modified_after);
+        let pages = self
+            .query_file_pages(access_token, &query, start_page_token)
+            .await?;
+        Ok(pages
+            .into_iter()
+            .map(|(page_token, items)| DriveFilePage {
+                page_token,
+                files: items
+                    .into_iter()
+                    .filter_map(if include_unsupported {
+                        any_file_from_item
+                    } else {
+                        resume_file_from_item
+                    })
+                    .collect(),
             })
             .collect())
     }
@@ -152,12 +201,84 @@ impl GoogleDriveClient {
         Ok(path)
     }
 
+    /// Looks up `folder_id` and reports whether it exists, is a folder, and is readable.
+    /// Drive answers 404 for items that exist but are not shared with the account.
+    pub async fn check_folder_access(
+        &self,
+        access_token: &str,
+        folder_id: &str,
+    ) -> anyhow::Result<FolderAccess> {
+        let url = format!(
+            "{}/{folder_id}?fields=id,name,mimeType",
+            self.files_endpoint
+        );
+        let response = self
+            .client
+            .get(url)
+            .bearer_auth(access_token)
+            .send()
+            .await?;
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+
+        let access = |status, name, message: String| FolderAccess {
+            folder_id: folder_id.to_string(),
+            status,
+            name,
+            message,
+        };
+        match status {
+            reqwest::StatusCode::NOT_FOUND => {
+                return Ok(access(
+                    FolderAccessStatus::NotFound,
+                    None,
+                    format!(
+                        "Folder {folder_id} does not exist or is not shared with this account."
+                    ),
+                ));
+            }
+            reqwest::StatusCode::FORBIDDEN => {
+                return Ok(access(
+                    FolderAccessStatus::PermissionDenied,
+                    None,
+                    format!("This account does not have permission to read folder {folder_id}."),
+                ));
+            }
+            _ if !status.is_success() => {
+                return Err(CoreError::GoogleApi {
+                    service: GoogleApiService::Drive,
+                    status: status.as_u16(),
+                    body,
+                }
+                .into());
+            }
+            _ => {}
+        }
+
+        let item = serde_json::from_str::<DriveFileItem>(&body)
+            .context("failed to parse Google Drive file response")?;
+        let name = item.name.unwrap_or_default();
+        if item.mime_type.as_deref() != Some(FOLDER_MIME) {
+            return Ok(access(
+                FolderAccessStatus::NotAFolder,
+                Some(name.clone()),
+                format!("\"{name}\" is a file, not a folder."),
+            ));
+        }
+
+        Ok(access(
+            FolderAccessStatus::Accessible,
+            Some(name.clone()),
+            format!("Folder \"{name}\" is accessible."),
+        ))
+    }
+
     pub async fn download_file(
         &self,
         access_token: &str,
         file_id: &str,
     ) -> anyhow::Result<Vec<u8>> {
-        let url = format!("{DRIVE_FILES_ENDPOINT}/{file_id}?alt=media");
+        let url = format!("{}/{file_id}?alt=media", self.files_endpoint);
         let response = self
             .client
             .get(url)
@@ -166,15 +287,37 @@ impl GoogleDriveClient {
             .await?;
         let status = response.status();
 
+        // The listing skips trashed files, but one deleted or trashed since (or reached
+        // through a shortcut) is a 404 here.
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Err(CoreError::FileUnavailable(file_id.to_string()).into());
+        }
         if !status.is_success() {
             let body = response.text().await.unwrap_or_default();
             return Err(CoreError::GoogleApi {
+                service: GoogleApiService::Drive,
                 status: status.as_u16(),
                 body,
             }
             .into());
         }
 
+        // A subtly invalid token can yield a 200 consent/quota page instead of file bytes.
+        let is_html = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.to_ascii_lowercase().starts_with("text/html"));
+        if is_html {
+            return Err(CoreError::auth(
+                AuthErrorCode::ProviderError,
+                format!(
+                    "Google Drive returned a web page instead of file {file_id}. The session may have expired or hit a quota limit; sign in again or retry later."
+                ),
+            )
+            .into());
+        }
+
         let bytes = response.bytes().await?;
         Ok(bytes.to_vec())
     }
@@ -184,7 +327,10 @@ impl GoogleDriveClient {
         access_token: &str,
         folder_id: &str,
     ) -> anyhow::Result<Option<DriveFileItem>> {
-        let url = format!("{DRIVE_FILES_ENDPOINT}/{folder_id}?fields=id,name,mimeType,parents");
+        let url = format!(
+            "{}/{folder_id}?fields=id,name,mimeType,parents",
+            self.files_endpoint
+        );
         let response = self
             .client
             .get(url)
@@ -200,6 +346,7 @@ impl GoogleDriveClient {
         let body = response.text().await.unwrap_or_default();
         if !status.is_success() {
             return Err(CoreError::GoogleApi {
+                service: GoogleApiService::Drive,
                 status: status.as_u16(),
                 body,
             }
@@ -221,13 +368,28 @@ impl GoogleDriveClient {
         access_token: &str,
         query: &str,
     ) -> anyhow::Result<Vec<DriveFileItem>> {
-        let mut items = Vec::new();
-        let mut page_token: Option<String> = None;
+        Ok(self
+            .query_file_pages(access_token, query, None)
+            .await?
+            .into_iter()
+            .flat_map(|(_, items)| items)
+            .collect())
+    }
+
+    /// Every page from `start_page_token` onwards, each paired with the token that fetched it.
+    async fn query_file_pages(
+        &self,
+        access_token: &str,
+        query: &str,
+        start_page_token: Option<&str>,
+    ) -> anyhow::Result<Vec<(Option<String>, Vec<DriveFileItem>)>> {
+        let mut pages = Vec::new();
+        let mut page_token = start_page_token.map(str::to_string);
 
         loop {
             let mut request = self
                 .client
-                .get(DRIVE_FILES_ENDPOINT)
+                .get(&self.files_endpoint)
                 .bearer_auth(access_token)
                 .query(&[
                     (
@@ -248,6 +410,7 @@ impl GoogleDriveClient {
             let body = response.text().await.unwrap_or_default();
             if !status.is_success() {
                 return Err(CoreError::GoogleApi {
+                    service: GoogleApiService::Drive,
                     status: status.as_u16(),
                     body,
                 }
@@ -257,9 +420,7 @@ impl GoogleDriveClient {
             let payload = serde_json::from_str::<DriveFilesResponse>(&body)
                 .context("failed to parse Google Drive list response")?;
 
-            if let Some(batch) = payload.files {
-                items.extend(batch);
-            }
+            pages.push((page_token, payload.files.unwrap_or_default()));
 
             page_token = payload.next_page_token;
             if page_token.is_none() {
@@ -267,6 +428,249 @@ impl GoogleDriveClient {
             }
         }
 
-        Ok(items)
+        Ok(pages)
+    }
+}
+
+fn resume_files_query(
+    folder_id: &str,
+    include_unsupported: bool,
+    mime_types: &[String],
+    modified_after: Option<DateTime<Utc>>,
+) -> String {
+    let mut query = format!("'{folder_id}' in parents and trashed=false and ");
+    if include_unsupported {
+        query.push_str(&format!("mimeType!='{FOLDER_MIME}'"));
+    } else {
+        let clause = if mime_types.is_empty() {
+            RESUME_MIMES
+                .iter()
+                .map(|mime_type| format!("mimeType='{mime_type}'"))
+                .collect::<Vec<_>>()
+        } else {
+            mime_types
+                .iter()
+                .map(|mime_type| format!("mimeType='{}'", mime_type.trim()))
+                .collect()
+        };
+        query.push_str(&format!("({})", clause.join(" or ")));
+    }
+    if let Some(modified_after) = modified_after {
+        query.push_str(&format!(
+            " and modifiedTime > '{}'",
+            modified_after.to_rfc3339_opts(SecondsFormat::Secs, true)
+        ));
+    }
+    query
+}
+
+fn resume_file_from_item(item: DriveFileItem) -> Option<DriveFileRef> {
+    let (Some(id), Some(name)) = (item.id, item.name) else {
+        return None;
+    };
+
+    // Drive occasionally omits mimeType for valid uploads; fall back to the file extension.
+    let mime_type = item
+        .mime_type
+        .filter(|value| !value.trim().is_empty())
+        .or_else(|| resume_mime_from_extension(&name).map(str::to_string))?;
+
+    Some(DriveFileRef {
+        id,
+        name,
+        mime_type,
+        size: item.size.and_then(|size| size.parse().ok()),
+    })
+}
+
+/// Like `resume_file_from_item`, but keeps files whose type is unknown.
+fn any_file_from_item(mut item: DriveFileItem) -> Option<DriveFileRef> {
+    if item
+        .mime_type
+        .as_deref()
+        .is_none_or(|value| value.trim().is_empty())
+        && item
+            .name
+            .as_deref()
+            .and_then(resume_mime_from_extension)
+            .is_none()
+    {
+        item.mime_type = Some(UNKNOWN_MIME.to_string());
+    }
+    resume_file_from_item(item)
+}
+
+fn resume_mime_from_extension(file_name: &str) -> Option<&'static str> {
+    let extension = std::path::Path::new(file_name)
+        .extension()
+        .and_then(|v| v.to_str())
+        .map(|v| v.to_ascii_lowercase())?;
+
+    match extension.as_str() {
+        "pdf" => Some(PDF_MIME),
+        "docx" => Some(DOCX_MIME),
+        "odt" => Some(ODT_MIME),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_support::{MockHttpServer, MockResponse};
+
+    #[tokio::test]
+    async fn check_folder_access_reports_each_outcome() {
+        let server = MockHttpServer::start(vec![
+            MockResponse::with_content_type(
+                200,
+                "application/json",
+                r#"{"id":"folder-1","name":"Resumes","mimeType":"application/vnd.google-apps.folder"}"#,
+            ),
+            MockResponse::with_content_type(
+                200,
+                "application/json",
+                r#"{"id":"file-1","name":"cv.pdf","mimeType":"application/pdf"}"#,
+            ),
+            MockResponse::with_content_type(
+                404,
+                "application/json",
+                r#"{"error":{"code":404,"message":"File not found: missing."}}"#,
+            ),
+            MockResponse::with_content_type(
+                403,
+                "application/json",
+                r#"{"error":{"code":403,"message":"The user does not have sufficient permissions."}}"#,
+            ),
+            MockResponse::with_content_type(500, "application/json", r#"{"error":{}}"#),
+        ]);
+        let drive = GoogleDriveClient::with_endpoint(Client::new(), server.url("/files"));
+
+        let access = drive
+            .check_folder_access("token", "folder-1")
+            .await
+            .unwrap();
+        assert_eq!(access.status, FolderAccessStatus::Accessible);
+        assert_eq!(access.name.as_deref(), Some("Resumes"));
+
+        let access = drive.check_folder_access("token", "file-1").await.unwrap();
+        assert_eq!(access.status, FolderAccessStatus::NotAFolder);
+        assert_eq!(access.name.as_deref(), Some("cv.pdf"));
+
+        let access = drive.check_folder_access("token", "missing").await.unwrap();
+        assert_eq!(access.status, FolderAccessStatus::NotFound);
+        assert!(access.message.contains("not shared"));
+
+        let access = drive.check_folder_access("token", "private").await.unwrap();
+        assert_eq!(access.status, FolderAccessStatus::PermissionDenied);
+        assert_eq!(access.name, None);
+
+        let err = drive
+            .check_folder_access("token", "flaky")
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<CoreError>(),
+            Some(CoreError::GoogleApi { status: 500, .. })
+        ));
+
+        assert_eq!(
+            server.requests()[0].path,
+            "/files/folder-1?fields=id,name,mimeType"
+        );
+    }
+
+    #[tokio::test]
+    async fn download_file_rejects_html_page() {
+        let server = MockHttpServer::start(vec![MockResponse::with_content_type(
+            200,
+            "text/html; charset=utf-8",
+            "<html><body>Sign in to continue</body></html>",
+        )]);
+        let drive = GoogleDriveClient::with_endpoint(Client::new(), server.url("/files"));
+
+        let err = drive.download_file("token", "file-1").await.unwrap_err();
+
+        let core = err.downcast_ref::<CoreError>().unwrap();
+        assert!(matches!(
+            core,
+            CoreError::Auth {
+                code: AuthErrorCode::ProviderError,
+                ..
+            }
+        ));
+        assert!(err.to_string().contains("sign in again"));
+        assert_eq!(server.requests()[0].path, "/files/file-1?alt=media");
+    }
+
+    #[tokio::test]
+    async fn download_file_returns_binary_body() {
+        let server = MockHttpServer::start(vec![MockResponse::with_content_type(
+            200,
+            "application/pdf",
+            "%PDF-1.4 fake",
+        )]);
+        let drive = GoogleDriveClient::with_endpoint(Client::new(), server.url("/files"));
+
+        let bytes = drive.download_file("token", "file-1").await.unwrap();
+        assert_eq!(bytes, b"%PDF-1.4 fake");
+    }
+
+    #[test]
+    fn resume_file_without_mime_type_uses_extension() {
+        let payload = serde_json::from_str::<DriveFilesResponse>(
+            r#"{
+              "files": [
+                { "id": "file-1", "name": "Jane Doe.PDF", "mimeType": null },
+                { "id": "file-2", "name": "cover.docx" },
+                { "id": "file-3", "name": "notes.txt", "mimeType": null },
+                { "id": "file-4", "name": "resume", "mimeType": "application/pdf" }
+              ]
+            }"#,
+        )
+        .unwrap();
+
+        let files: Vec<DriveFileRef> = payload
+            .files
+            .unwrap()
+            .into_iter()
+            .filter_map(resume_file_from_item)
+            .collect();
+
+        assert_eq!(files.len(), 3);
+        assert_eq!(files[0].id, "file-1");
+        assert_eq!(files[0].mime_type, PDF_MIME);
+        assert_eq!(files[1].mime_type, DOCX_MIME);
+        assert_eq!(files[2].id, "file-4");
+        assert_eq!(files[2].mime_type, PDF_MIME);
+    }
+
+    #[test]
+    fn resume_query_lists_only_the_allowed_mime_types() {
+        assert_eq!(
+            resume_files_query("folder-1", false, &[PDF_MIME.to_string()], None),
+            "'folder-1' in parents and trashed=false and (mimeType='application/pdf')"
+        );
+        assert_eq!(
+            resume_files_query(
+                "folder-1",
+                false,
+                &[PDF_MIME.to_string(), ODT_MIME.to_string()],
+                None
+            ),
+            format!(
+                "'folder-1' in parents and trashed=false and (mimeType='{PDF_MIME}' or mimeType='{ODT_MIME}')"
+            )
+        );
+        assert_eq!(
+            resume_files_query("folder-1", false, &[], None),
+            format!(
+                "'folder-1' in parents and trashed=false and (mimeType='{PDF_MIME}' or mimeType='{DOCX_MIME}' or mimeType='{ODT_MIME}')"
+            )
+        );
+        assert_eq!(
+            resume_files_query("folder-1", true, &[], None),
+            format!("'folder-1' in parents and trashed=false and mimeType!='{FOLDER_MIME}'")
+        );
     }
 }
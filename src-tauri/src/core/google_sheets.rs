@@ -3,9 +3,15 @@ use reqwest::Client;
 use serde::Deserialize;
 use serde_json::json;
 
-use super::errors::CoreError;
+use super::errors::{CoreError, GoogleApiService};
 
 const SHEETS_ENDPOINT: &str = "https://sheets.googleapis.com/v4/spreadsheets";
+/// Created spreadsheets pin their only tab to this id so later `batchUpdate` calls can
+/// target it without a metadata lookup.
+const RESULTS_SHEET_ID: i64 = 0;
+/// Lower bounds for the green and yellow confidence bands; anything below is red.
+pub(crate) const HIGH_CONFIDENCE: f64 = 0.8;
+pub(crate) const MEDIUM_CONFIDENCE: f64 = 0.5;
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -14,17 +20,60 @@ struct CreateSpreadsheetResponse {
 }
 
 #[derive(Debug, Deserialize)]
-struct ValuesCheckResponse {
+struct ValuesResponse {
     values: Option<Vec<Vec<String>>>,
 }
 
+/// How Sheets interprets written cells. `Raw` stores text as-is, so values such as
+/// `+919876543210` keep their leading `+` instead of becoming numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueInputOption {
+    UserEntered,
+    Raw,
+}
+
+impl ValueInputOption {
+    fn as_str(self) -> &'static str {
+        match self {
+            ValueInputOption::UserEntered => "USER_ENTERED",
+            ValueInputOption::Raw => "RAW",
+        }
+    }
+}
+
+/// What [`GoogleSheetsClient::append_rows`] does with the sheet's first row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AppendMode {
+    /// Every row is data and goes below the existing content; the first row is not read.
+    /// For sheets whose header is already in place, such as ones the app created.
+    AppendAlways,
+    /// An empty sheet gets the header and data from A1. Otherwise the header is dropped and
+    /// only the data is appended; a first row that differs from the header is left alone
+    /// rather than getting a second header below it.
+    SkipIfHeaderMatches,
+    /// The header replaces whatever the first row holds, and the data is appended below.
+    OverwriteHeaders,
+}
+
 pub struct GoogleSheetsClient {
     client: Client,
+    spreadsheets_endpoint: String,
 }
 
 impl GoogleSheetsClient {
     pub fn new(client: Client) -> Self {
-        Self { client }
+        Self {
+            client,
+            spreadsheets_endpoint: SHEETS_ENDPOINT.to_string(),
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn with_endpoint(client: Client, spreadsheets_endpoint: String) -> Self {
+        Self {
+            client,
+            spreadsheets_endpoint,
+        }
     }
 
     pub async fn create_spreadsheet(
@@ -35,13 +84,13 @@ impl GoogleSheetsClient {
         let payload = json!({
             "properties": { "title": title },
             "sheets": [
-                { "properties": { "title": "Resume Data" } }
+                { "properties": { "title": "Resume Data", "sheetId": RESULTS_SHEET_ID } }
             ]
         });
 
         let response = self
             .client
-            .post(SHEETS_ENDPOINT)
+            .post(&self.spreadsheets_endpoint)
             .bearer_auth(access_token)
             .json(&payload)
             .send()
@@ -51,6 +100,7 @@ impl GoogleSheetsClient {
         let body = response.text().await.unwrap_or_default();
         if !status.is_success() {
             return Err(CoreError::GoogleApi {
+                service: GoogleApiService::Sheets,
                 status: status.as_u16(),
                 body,
             }
@@ -65,78 +115,169 @@ impl GoogleSheetsClient {
             .ok_or_else(|| anyhow::anyhow!("Google response missing spreadsheetId"))
     }
 
-    pub async fn append_rows(
+    /// Fetches only the spreadsheet's id, to confirm it exists and is readable with
+    /// `access_token`.
+    pub async fn check_access(
+        &self,
+        access_token: &str,
+        spreadsheet_id: &str,
+    ) -> anyhow::Result<()> {
+        let url = format!("{}/{spreadsheet_id}", self.spreadsheets_endpoint);
+        let response = self
+            .client
+            .get(&url)
+            .query(&[("fields", "spreadsheetId")])
+            .bearer_auth(access_token)
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(CoreError::GoogleApi {
+                service: GoogleApiService::Sheets,
+                status: status.as_u16(),
+                body,
+            }
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Reads every value in a single column (e.g. `"D"`), header row included.
+    pub async fn read_column(
+        &self,
+        access_token: &str,
+        spreadsheet_id: &str,
+        column: &str,
+    ) -> anyhow::Result<Vec<String>> {
+        let url = format!(
+            "{}/{spreadsheet_id}/values/{column}:{column}",
+            self.spreadsheets_endpoint
+        );
+        let response = self
+            .client
+            .get(&url)
+            .bearer_auth(access_token)
+            .send()
+            .await?;
+
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        if !status.is_success() {
+            return Err(CoreError::GoogleApi {
+                service: GoogleApiService::Sheets,
+                status: status.as_u16(),
+                body,
+            }
+            .into());
+        }
+
+        let payload = serde_json::from_str::<ValuesResponse>(&body)
+            .context("failed to parse spreadsheet values response")?;
+        Ok(payload
+            .values
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|row| row.into_iter().next())
+            .collect())
+    }
+
+    /// Writes `rows` (header included) starting at A1 in a single `values.update` call.
+    /// Meant for freshly created sheets, where nothing needs to be preserved.
+    pub async fn write_all_values(
         &self,
         access_token: &str,
         spreadsheet_id: &str,
         rows: &[Vec<String>],
-        skip_headers: bool,
+        value_input: ValueInputOption,
     ) -> anyhow::Result<()> {
         if rows.is_empty() {
             return Ok(());
         }
 
-        let check_url = format!("{SHEETS_ENDPOINT}/{spreadsheet_id}/values/A1:Z1");
-        let check_response = self
+        let put_url = format!(
+            "{}/{spreadsheet_id}/values/A1?valueInputOption={}",
+            self.spreadsheets_endpoint,
+            value_input.as_str()
+        );
+        let payload = json!({ "values": rows });
+        let response = self
             .client
-            .get(&check_url)
+            .put(&put_url)
             .bearer_auth(access_token)
+            .json(&payload)
             .send()
             .await?;
 
-        let has_data = if check_response.status().is_success() {
-            let body = check_response.text().await.unwrap_or_default();
-            let payload = serde_json::from_str::<ValuesCheckResponse>(&body)
-                .unwrap_or(ValuesCheckResponse { values: None });
-            payload
-                .values
-                .map(|v| !v.is_empty() && !v[0].is_empty())
-                .unwrap_or(false)
-        } else {
-            false
-        };
-
-        if !has_data {
-            let put_url = format!(
-                "{SHEETS_ENDPOINT}/{spreadsheet_id}/values/A1?valueInputOption=USER_ENTERED"
-            );
-            let payload = json!({ "values": rows });
-            let put_response = self
-                .client
-                .put(&put_url)
-                .bearer_auth(access_token)
-                .json(&payload)
-                .send()
-                .await?;
-
-            let status = put_response.status();
-            let body = put_response.text().await.unwrap_or_default();
-            if !status.is_success() {
-                return Err(CoreError::GoogleApi {
-                    status: status.as_u16(),
-                    body,
-                }
-                .into());
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        if !status.is_success() {
+            return Err(CoreError::GoogleApi {
+                service: GoogleApiService::Sheets,
+                status: status.as_u16(),
+                body,
             }
+            .into());
+        }
 
+        Ok(())
+    }
+
+    /// Appends `rows` below the sheet's existing content, handling the sheet's first row as
+    /// `mode` says. Except for [`AppendMode::AppendAlways`], `rows[0]` is the header row.
+    pub async fn append_rows(
+        &self,
+        access_token: &str,
+        spreadsheet_id: &str,
+        rows: &[Vec<String>],
+        mode: AppendMode,
+        value_input: ValueInputOption,
+    ) -> anyhow::Result<()> {
+        if rows.is_empty() {
             return Ok(());
         }
 
-        let rows_to_append: Vec<Vec<String>> = if skip_headers {
-            rows.to_vec()
-        } else {
-            rows.iter().skip(1).cloned().collect()
-        }
-        .into_iter()
-        .filter(|row| row.iter().any(|cell| !cell.trim().is_empty()))
-        .collect();
+        let width = rows.iter().map(Vec::len).max().unwrap_or(1);
+        let data_rows = match mode {
+            AppendMode::AppendAlways => rows,
+            AppendMode::OverwriteHeaders => {
+                self.write_all_values(access_token, spreadsheet_id, &rows[..1], value_input)
+                    .await?;
+                &rows[1..]
+            }
+            AppendMode::SkipIfHeaderMatches => {
+                let first_row = self
+                    .read_first_row(access_token, spreadsheet_id, width)
+                    .await?;
+                if first_row.iter().all(|cell| cell.trim().is_empty()) {
+                    return self
+                        .write_all_values(access_token, spreadsheet_id, rows, value_input)
+                        .await;
+                }
+                if !header_matches(&first_row, &rows[0]) {
+                    eprintln!(
+                        "sheets: first row of {spreadsheet_id} is not the expected header; leaving it as is"
+                    );
+                }
+                &rows[1..]
+            }
+        };
+
+        let rows_to_append: Vec<&Vec<String>> = data_rows
+            .iter()
+            .filter(|row| row.iter().any(|cell| !cell.trim().is_empty()))
+            .collect();
 
         if rows_to_append.is_empty() {
             return Ok(());
         }
 
         let append_url = format!(
-            "{SHEETS_ENDPOINT}/{spreadsheet_id}/values/A1:append?valueInputOption=USER_ENTERED&insertDataOption=INSERT_ROWS"
+            "{}/{spreadsheet_id}/values/{}:append?valueInputOption={}&insertDataOption=INSERT_ROWS",
+            self.spreadsheets_endpoint,
+            first_row_range(width),
+            value_input.as_str()
         );
 
         let payload = json!({ "values": rows_to_append });
@@ -152,6 +293,7 @@ impl GoogleSheetsClient {
         let body = append_response.text().await.unwrap_or_default();
         if !status.is_success() {
             return Err(CoreError::GoogleApi {
+                service: GoogleApiService::Sheets,
                 status: status.as_u16(),
                 body,
             }
@@ -160,4 +302,333 @@ impl GoogleSheetsClient {
 
         Ok(())
     }
+
+    /// The first `width` cells of the sheet's first row; empty when the row is blank.
+    async fn read_first_row(
+        &self,
+        access_token: &str,
+        spreadsheet_id: &str,
+        width: usize,
+    ) -> anyhow::Result<Vec<String>> {
+        let url = format!(
+            "{}/{spreadsheet_id}/values/{}",
+            self.spreadsheets_endpoint,
+            first_row_range(width)
+        );
+        let response = self
+            .client
+            .get(&url)
+            .bearer_auth(access_token)
+            .send()
+            .await?;
+
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        // Treating a failed read as an empty sheet would write over the real first row.
+        if !status.is_success() {
+            return Err(CoreError::GoogleApi {
+                service: GoogleApiService::Sheets,
+                status: status.as_u16(),
+                body,
+            }
+            .into());
+        }
+
+        let payload = serde_json::from_str::<ValuesResponse>(&body)
+            .context("Google Sheets returned an unreadable first row")?;
+        Ok(payload
+            .values
+            .and_then(|rows| rows.into_iter().next())
+            .unwrap_or_default())
+    }
+
+    /// Adds conditional-format rules to a sheet created by [`Self::create_spreadsheet`]
+    /// that shade each data row green, yellow or red by the value in `confidence_column`.
+    pub async fn add_confidence_colors(
+        &self,
+        access_token: &str,
+        spreadsheet_id: &str,
+        confidence_column: usize,
+        column_count: usize,
+    ) -> anyhow::Result<()> {
+        let column = column_letter(confidence_column);
+        let range = json!({
+            "sheetId": RESULTS_SHEET_ID,
+            "startRowIndex": 1,
+            "startColumnIndex": 0,
+            "endColumnIndex": column_count.max(confidence_column + 1),
+        });
+        // VALUE() keeps the rules working when cells were written as RAW text.
+        let bands = [
+            (
+                format!("=AND(${column}2<>\"\",VALUE(${column}2)>={HIGH_CONFIDENCE})"),
+                (0.85, 0.94, 0.85),
+            ),
+            (
+                format!("=AND(${column}2<>\"\",VALUE(${column}2)>={MEDIUM_CONFIDENCE})"),
+                (1.0, 0.95, 0.8),
+            ),
+            (
+                format!("=AND(${column}2<>\"\",VALUE(${column}2)<{MEDIUM_CONFIDENCE})"),
+                (0.96, 0.8, 0.8),
+            ),
+        ];
+        let requests = bands
+            .iter()
+            .enumerate()
+            .map(|(index, (formula, (red, green, blue)))| {
+                json!({
+                    "addConditionalFormatRule": {
+                        "index": index,
+                        "rule": {
+                            "ranges": [range],
+                            "booleanRule": {
+                                "condition": {
+                                    "type": "CUSTOM_FORMULA",
+                                    "values": [{ "userEnteredValue": formula }]
+                                },
+                                "format": {
+                                    "backgroundColor": { "red": red, "green": green, "blue": blue }
+                                }
+                            }
+                        }
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let url = format!(
+            "{}/{spreadsheet_id}:batchUpdate",
+            self.spreadsheets_endpoint
+        );
+        let response = self
+            .client
+            .post(&url)
+            .bearer_auth(access_token)
+            .json(&json!({ "requests": requests }))
+            .send()
+            .await?;
+
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        if !status.is_success() {
+            return Err(CoreError::GoogleApi {
+                service: GoogleApiService::Sheets,
+                status: status.as_u16(),
+                body,
+            }
+            .into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Whether a sheet's first row already is `header`, ignoring case and padding.
+fn header_matches(first_row: &[String], header: &[String]) -> bool {
+    let cells = |row: &[String]| {
+        let mut cells = row
+            .iter()
+            .map(|cell| cell.trim().to_lowercase())
+            .collect::<Vec<_>>();
+        while cells.last().is_some_and(String::is_empty) {
+            cells.pop();
+        }
+        cells
+    };
+    cells(first_row) == cells(header)
+}
+
+/// Zero-based column index to an A1 column letter (0 -> "A", 26 -> "AA").
+pub fn column_letter(index: usize) -> String {
+    let mut letters = Vec::new();
+    let mut remaining = index + 1;
+    while remaining > 0 {
+        let offset = (remaining - 1) % 26;
+        letters.push(char::from(b'A' + offset as u8));
+        remaining = (remaining - 1) / 26;
+    }
+    letters.iter().rev().collect()
+}
+
+/// A1 range covering the first row of `column_count` columns, e.g. `A1:G1` for seven.
+pub fn first_row_range(column_count: usize) -> String {
+    format!("A1:{}1", column_letter(column_count.max(1) - 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_support::{MockHttpServer, MockResponse};
+
+    #[test]
+    fn column_helpers_handle_multi_letter_columns() {
+        assert_eq!(column_letter(0), "A");
+        assert_eq!(column_letter(3), "D");
+        assert_eq!(column_letter(25), "Z");
+        assert_eq!(column_letter(26), "AA");
+        assert_eq!(first_row_range(7), "A1:G1");
+        assert_eq!(first_row_range(28), "A1:AB1");
+        assert_eq!(first_row_range(0), "A1:A1");
+    }
+
+    #[tokio::test]
+    async fn raw_input_keeps_phone_numbers_as_text() {
+        let server = MockHttpServer::start(vec![MockResponse::with_content_type(
+            200,
+            "application/json",
+            "{}",
+        )]);
+        let sheets = GoogleSheetsClient::with_endpoint(Client::new(), server.url("/spreadsheets"));
+
+        sheets
+            .append_rows(
+                "token",
+                "sheet-1",
+                &[vec![
+                    "Jane Doe".to_string(),
+                    String::new(),
+                    "+919876543210".to_string(),
+                ]],
+                AppendMode::AppendAlways,
+                ValueInputOption::Raw,
+            )
+            .await
+            .unwrap();
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(
+            requests[0].path,
+            "/spreadsheets/sheet-1/values/A1:C1:append?valueInputOption=RAW&insertDataOption=INSERT_ROWS"
+        );
+        let payload: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(payload["values"][0][2], "+919876543210");
+    }
+
+    fn header_and_row() -> Vec<Vec<String>> {
+        vec![
+            vec!["Name".to_string(), "Email ID".to_string()],
+            vec!["Jane Doe".to_string(), "jane@example.com".to_string()],
+        ]
+    }
+
+    /// Runs `append_rows` against a sheet whose first row is `first_row` (`None` for an empty
+    /// sheet) and returns each request's method and the values it sent.
+    async fn append_with_mode(
+        mode: AppendMode,
+        first_row: Option<&str>,
+    ) -> Vec<(String, serde_json::Value)> {
+        let first_row = match first_row {
+            Some(row) => format!(r#"{{"values":[{row}]}}"#),
+            None => "{}".to_string(),
+        };
+        let server = MockHttpServer::start(vec![
+            MockResponse::with_content_type(200, "application/json", first_row),
+            MockResponse::with_content_type(200, "application/json", "{}"),
+            MockResponse::with_content_type(200, "application/json", "{}"),
+        ]);
+        let sheets = GoogleSheetsClient::with_endpoint(Client::new(), server.url("/spreadsheets"));
+        let rows = match mode {
+            AppendMode::AppendAlways => header_and_row()[1..].to_vec(),
+            _ => header_and_row(),
+        };
+
+        sheets
+            .append_rows(
+                "token",
+                "sheet-1",
+                &rows,
+                mode,
+                ValueInputOption::UserEntered,
+            )
+            .await
+            .unwrap();
+
+        server
+            .requests()
+            .into_iter()
+            .map(|request| {
+                let values = serde_json::from_slice::<serde_json::Value>(&request.body)
+                    .map(|payload| payload["values"].clone())
+                    .unwrap_or_default();
+                (request.method, values)
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn append_always_never_reads_or_writes_a_header() {
+        for first_row in [None, Some(r#"["Name","Email ID"]"#)] {
+            let requests = append_with_mode(AppendMode::AppendAlways, first_row).await;
+            assert_eq!(requests.len(), 1);
+            assert_eq!(requests[0].0, "POST");
+            assert_eq!(requests[0].1, json!([["Jane Doe", "jane@example.com"]]));
+        }
+    }
+
+    #[tokio::test]
+    async fn skip_if_header_matches_writes_the_header_only_into_an_empty_sheet() {
+        let empty = append_with_mode(AppendMode::SkipIfHeaderMatches, None).await;
+        let methods = empty
+            .iter()
+            .map(|(method, _)| method.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(methods, vec!["GET", "PUT"]);
+        assert_eq!(
+            empty[1].1,
+            json!([["Name", "Email ID"], ["Jane Doe", "jane@example.com"]])
+        );
+
+        for first_row in [r#"["name ","EMAIL ID"]"#, r#"["Candidate","Contact"]"#] {
+            let populated =
+                append_with_mode(AppendMode::SkipIfHeaderMatches, Some(first_row)).await;
+            let methods = populated
+                .iter()
+                .map(|(method, _)| method.as_str())
+                .collect::<Vec<_>>();
+            assert_eq!(methods, vec!["GET", "POST"]);
+            assert_eq!(populated[1].1, json!([["Jane Doe", "jane@example.com"]]));
+        }
+    }
+
+    #[tokio::test]
+    async fn overwrite_headers_replaces_the_first_row_then_appends() {
+        for first_row in [None, Some(r#"["Candidate","Contact"]"#)] {
+            let requests = append_with_mode(AppendMode::OverwriteHeaders, first_row).await;
+            let methods = requests
+                .iter()
+                .map(|(method, _)| method.as_str())
+                .collect::<Vec<_>>();
+            assert_eq!(methods, vec!["PUT", "POST"]);
+            assert_eq!(requests[0].1, json!([["Name", "Email ID"]]));
+            assert_eq!(requests[1].1, json!([["Jane Doe", "jane@example.com"]]));
+        }
+    }
+
+    #[tokio::test]
+    async fn failed_first_row_read_does_not_overwrite_the_sheet() {
+        let server = MockHttpServer::start(vec![MockResponse::with_content_type(
+            429,
+            "application/json",
+            "{}",
+        )]);
+        let sheets = GoogleSheetsClient::with_endpoint(Client::new(), server.url("/spreadsheets"));
+
+        let err = sheets
+            .append_rows(
+                "token",
+                "sheet-1",
+                &header_and_row(),
+                AppendMode::SkipIfHeaderMatches,
+                ValueInputOption::UserEntered,
+            )
+            .await
+            .unwrap_err();
+
+        assert!(err
+            .downcast_ref::<CoreError>()
+            .is_some_and(CoreError::is_retryable));
+        assert_eq!(server.requests().len(), 1);
+    }
 }
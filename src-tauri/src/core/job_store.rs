@@ -1,16 +1,40 @@
+use std::collections::HashMap;
+use std::io::{Cursor, Read, Write};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Instant;
 
 use anyhow::Context;
 use chrono::{Duration, Utc};
 use tokio::sync::Mutex;
+use uuid::Uuid;
 
-use super::models::{JobStatus, ParsedCandidate};
+use super::errors::CoreError;
+use super::models::{
+    JobCheckpoint, JobProcessingState, JobStatus, ParsedCandidate, PersistedSettings, WatchState,
+};
 use super::settings_store::app_data_root;
 
+/// Stands in for extra header values in job config snapshots.
+const REDACTED_HEADER_VALUE: &str = "[redacted]";
+
 pub struct JsonJobStore {
     jobs_root: PathBuf,
     retention_hours: i64,
-    mutex: Mutex<()>,
+    /// Milliseconds between non-terminal status writes of one job.
+    status_write_interval_ms: AtomicU64,
+    /// Compact `results.json` instead of pretty-printed; status files always stay pretty.
+    compact_results: AtomicBool,
+    mutex: Mutex<StatusWrites>,
+}
+
+/// Coalesces progress status writes: a pending or processing status saved within the write
+/// interval is kept in memory (and served by `load_status`) until the next write that is
+/// due. Paused and terminal statuses are always written at once.
+#[derive(Default)]
+struct StatusWrites {
+    last_written: HashMap<String, Instant>,
+    pending: HashMap<String, JobStatus>,
 }
 
 impl JsonJobStore {
@@ -23,16 +47,49 @@ impl JsonJobStore {
         Self {
             jobs_root,
             retention_hours: retention_hours.max(1),
-            mutex: Mutex::new(()),
+            status_write_interval_ms: AtomicU64::new(0),
+            compact_results: AtomicBool::new(false),
+            mutex: Mutex::new(StatusWrites::default()),
         }
     }
 
+    /// Writes progress statuses at most once per `interval` for each job, starting with the
+    /// next status saved.
+    pub fn set_status_write_interval(&self, interval: std::time::Duration) {
+        self.status_write_interval_ms
+            .store(interval.as_millis() as u64, Ordering::Relaxed);
+    }
+
+    /// Applies to results written from now on; existing files load either way.
+    pub fn set_compact_results(&self, compact: bool) {
+        self.compact_results.store(compact, Ordering::Relaxed);
+    }
+
     pub fn jobs_root(&self) -> &Path {
         &self.jobs_root
     }
 
     pub async fn save_status(&self, status: &JobStatus) -> anyhow::Result<()> {
-        let _lock = self.mutex.lock().await;
+        let mut writes = self.mutex.lock().await;
+        // A paused job can sit for hours, so its status must not wait for a later write.
+        let write_now = matches!(
+            status.status,
+            JobProcessingState::Paused
+                | JobProcessingState::Completed
+                | JobProcessingState::Failed
+                | JobProcessingState::Revoked
+        );
+        let interval =
+            std::time::Duration::from_millis(self.status_write_interval_ms.load(Ordering::Relaxed));
+        let write_due = writes
+            .last_written
+            .get(&status.job_id)
+            .is_none_or(|written| written.elapsed() >= interval);
+        if !write_now && !write_due {
+            writes.pending.insert(status.job_id.clone(), status.clone());
+            return Ok(());
+        }
+
         let path = self.status_path(&status.job_id);
         if let Some(parent) = path.parent() {
             tokio::fs::create_dir_all(parent).await?;
@@ -40,11 +97,30 @@ impl JsonJobStore {
 
         let json = serde_json::to_string_pretty(status)?;
         tokio::fs::write(path, json).await?;
+        writes.pending.remove(&status.job_id);
+        writes
+            .last_written
+            .insert(status.job_id.clone(), Instant::now());
+        Ok(())
+    }
+
+    /// Writes every coalesced status to disk, e.g. before the app exits.
+    pub async fn flush_pending_statuses(&self) -> anyhow::Result<()> {
+        let mut writes = self.mutex.lock().await;
+        for (job_id, status) in std::mem::take(&mut writes.pending) {
+            let json = serde_json::to_string_pretty(&status)?;
+            tokio::fs::write(self.status_path(&job_id), json).await?;
+            writes.last_written.insert(job_id, Instant::now());
+        }
         Ok(())
     }
 
     pub async fn load_status(&self, job_id: &str) -> anyhow::Result<Option<JobStatus>> {
-        let _lock = self.mutex.lock().await;
+        let writes = self.mutex.lock().await;
+        if let Some(pending) = writes.pending.get(job_id) {
+            return Ok(Some(pending.clone()));
+        }
+
         let path = self.status_path(job_id);
         if !tokio::fs::try_exists(&path).await.unwrap_or(false) {
             return Ok(None);
@@ -66,11 +142,42 @@ impl JsonJobStore {
             tokio::fs::create_dir_all(parent).await?;
         }
 
-        let json = serde_json::to_string_pretty(results)?;
+        let json = if self.compact_results.load(Ordering::Relaxed) {
+            serde_json::to_string(results)?
+        } else {
+            serde_json::to_string_pretty(results)?
+        };
         tokio::fs::write(path, json).await?;
         Ok(())
     }
 
+    /// Writes rows that could not reach Google Sheets to `unwritten-rows.csv`, replacing any
+    /// earlier copy. `rows` includes the header row.
+    pub async fn save_unwritten_rows(
+        &self,
+        job_id: &str,
+        rows: &[Vec<String>],
+    ) -> anyhow::Result<PathBuf> {
+        let _lock = self.mutex.lock().await;
+        let path = self.unwritten_rows_path(job_id);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        let csv = rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| csv_cell(cell))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            })
+            .collect::<Vec<_>>()
+            .join("\r\n");
+        tokio::fs::write(&path, csv).await?;
+        Ok(path)
+    }
+
     pub async fn load_results(&self, job_id: &str) -> anyhow::Result<Option<Vec<ParsedCandidate>>> {
         let _lock = self.mutex.lock().await;
         let path = self.results_path(job_id);
@@ -83,6 +190,99 @@ impl JsonJobStore {
         Ok(Some(results))
     }
 
+    pub async fn save_checkpoint(
+        &self,
+        job_id: &str,
+        checkpoint: &JobCheckpoint,
+    ) -> anyhow::Result<()> {
+        let _lock = self.mutex.lock().await;
+        let path = self.checkpoint_path(job_id);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        let json = serde_json::to_string_pretty(checkpoint)?;
+        tokio::fs::write(path, json).await?;
+        Ok(())
+    }
+
+    /// Records the settings a job runs with as `config.json`. The client secret is not part
+    /// of `PersistedSettings`, so it never lands in the job directory or its archive. Extra
+    /// header values often carry API keys, so only the header names are kept.
+    pub async fn save_config(
+        &self,
+        job_id: &str,
+        settings: &PersistedSettings,
+    ) -> anyhow::Result<()> {
+        let path = self.config_path(job_id);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        let mut settings = settings.clone();
+        for value in settings.extra_headers.values_mut() {
+            *value = REDACTED_HEADER_VALUE.to_string();
+        }
+        let json = serde_json::to_string_pretty(&settings)?;
+        tokio::fs::write(path, json).await?;
+        Ok(())
+    }
+
+    pub async fn load_config(&self, job_id: &str) -> anyhow::Result<Option<PersistedSettings>> {
+        let path = self.config_path(job_id);
+        if !tokio::fs::try_exists(&path).await.unwrap_or(false) {
+            return Ok(None);
+        }
+
+        let json = tokio::fs::read_to_string(path).await?;
+        let settings = serde_json::from_str::<PersistedSettings>(&json)?;
+        Ok(Some(settings))
+    }
+
+    pub async fn load_checkpoint(&self, job_id: &str) -> anyhow::Result<Option<JobCheckpoint>> {
+        let _lock = self.mutex.lock().await;
+        let path = self.checkpoint_path(job_id);
+        if !tokio::fs::try_exists(&path).await.unwrap_or(false) {
+            return Ok(None);
+        }
+
+        let json = tokio::fs::read_to_string(path).await?;
+        let checkpoint = serde_json::from_str::<JobCheckpoint>(&json)?;
+        Ok(Some(checkpoint))
+    }
+
+    pub async fn clear_checkpoint(&self, job_id: &str) -> anyhow::Result<()> {
+        let _lock = self.mutex.lock().await;
+        match tokio::fs::remove_file(self.checkpoint_path(job_id)).await {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Watch state lives next to the jobs directory, so job cleanup never removes it.
+    pub async fn save_watch_state(&self, state: &WatchState) -> anyhow::Result<()> {
+        let path = self.watch_state_path(&state.folder_id);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        let json = serde_json::to_string_pretty(state)?;
+        tokio::fs::write(path, json).await?;
+        Ok(())
+    }
+
+    pub async fn load_watch_state(&self, folder_id: &str) -> anyhow::Result<Option<WatchState>> {
+        let path = self.watch_state_path(folder_id);
+        if !tokio::fs::try_exists(&path).await.unwrap_or(false) {
+            return Ok(None);
+        }
+
+        let json = tokio::fs::read_to_string(path).await?;
+        let state = serde_json::from_str::<WatchState>(&json)?;
+        Ok(Some(state))
+    }
+
     pub async fn list_jobs(&self) -> anyhow::Result<Vec<String>> {
         self.cleanup_expired_jobs().await?;
 
@@ -111,6 +311,22 @@ impl JsonJobStore {
         Ok(ids)
     }
 
+    /// Removes a job's directory with everything in it. Returns `Ok(false)` when the job
+    /// does not exist.
+    pub async fn delete_job(&self, job_id: &str) -> anyhow::Result<bool> {
+        ensure_plain_job_id(job_id)?;
+        let mut writes = self.mutex.lock().await;
+        writes.pending.remove(job_id);
+        writes.last_written.remove(job_id);
+        match tokio::fs::remove_dir_all(self.jobs_root.join(job_id)).await {
+            Ok(()) => Ok(true),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Deletes jobs past retention. Jobs still pending, processing, or paused are kept
+    /// however old they are.
     pub async fn cleanup_expired_jobs(&self) -> anyhow::Result<()> {
         if !tokio::fs::try_exists(&self.jobs_root)
             .await
@@ -119,7 +335,7 @@ impl JsonJobStore {
             return Ok(());
         }
 
-        let _lock = self.mutex.lock().await;
+        let mut writes = self.mutex.lock().await;
         let now = Utc::now();
         let mut dir = tokio::fs::read_dir(&self.jobs_root).await?;
 
@@ -140,6 +356,14 @@ impl JsonJobStore {
                     .await
                     .with_context(|| format!("failed reading {}", status_path.display()))?;
                 if let Ok(status) = serde_json::from_str::<JobStatus>(&json) {
+                    if matches!(
+                        status.status,
+                        JobProcessingState::Pending
+                            | JobProcessingState::Processing
+                            | JobProcessingState::Paused
+                    ) {
+                        continue;
+                    }
                     status.completed_at.or(status.created_at).unwrap_or(now)
                 } else {
                     now
@@ -150,12 +374,108 @@ impl JsonJobStore {
 
             if now.signed_duration_since(reference_time) > Duration::hours(self.retention_hours) {
                 tokio::fs::remove_dir_all(entry.path()).await?;
+                writes.pending.remove(&job_id);
+                writes.last_written.remove(&job_id);
             }
         }
 
         Ok(())
     }
 
+    /// Zips the files in a job's directory (status, results, logs) into `dest_path`.
+    /// Returns `Ok(false)` when the job directory does not exist.
+    pub async fn export_archive(&self, job_id: &str, dest_path: &Path) -> anyhow::Result<bool> {
+        ensure_plain_job_id(job_id)?;
+        let mut writes = self.mutex.lock().await;
+        let job_dir = self.jobs_root.join(job_id);
+        if !tokio::fs::try_exists(&job_dir).await.unwrap_or(false) {
+            return Ok(false);
+        }
+
+        // Coalesced progress should be part of the archive.
+        if let Some(pending) = writes.pending.remove(job_id) {
+            let json = serde_json::to_string_pretty(&pending)?;
+            tokio::fs::write(self.status_path(job_id), json).await?;
+            writes
+                .last_written
+                .insert(job_id.to_string(), Instant::now());
+        }
+
+        let mut entries = Vec::new();
+        let mut dir = tokio::fs::read_dir(&job_dir).await?;
+        while let Some(entry) = dir.next_entry().await? {
+            if !entry.metadata().await?.is_file() {
+                continue;
+            }
+
+            let name = entry.file_name().to_string_lossy().to_string();
+            let bytes = tokio::fs::read(entry.path())
+                .await
+                .with_context(|| format!("failed reading {}", entry.path().display()))?;
+            entries.push((name, bytes));
+        }
+        drop(writes);
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, bytes) in entries {
+            writer.start_file(
+                format!("{job_id}/{name}"),
+                zip::write::SimpleFileOptions::default(),
+            )?;
+            writer.write_all(&bytes)?;
+        }
+        let archive = writer.finish()?.into_inner();
+
+        if let Some(parent) = dest_path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(dest_path, archive)
+            .await
+            .with_context(|| format!("failed writing {}", dest_path.display()))?;
+        Ok(true)
+    }
+
+    /// Restores an archive written by `export_archive` and returns the imported job's id.
+    /// A job id that already exists locally is replaced with a fresh one.
+    pub async fn import_archive(&self, src_path: &Path) -> anyhow::Result<String> {
+        let data = tokio::fs::read(src_path)
+            .await
+            .with_context(|| format!("failed reading {}", src_path.display()))?;
+        let (mut status, files) = read_job_archive(&data)?;
+
+        let _lock = self.mutex.lock().await;
+        if tokio::fs::try_exists(self.jobs_root.join(&status.job_id))
+            .await
+            .unwrap_or(false)
+        {
+            status.job_id = Uuid::new_v4().to_string();
+        }
+        // Nothing is running an imported job, so an in-flight snapshot cannot finish.
+        if !matches!(
+            status.status,
+            JobProcessingState::Completed
+                | JobProcessingState::Failed
+                | JobProcessingState::Revoked
+        ) {
+            status.status = JobProcessingState::Failed;
+            status.error = Some("Job was exported before it finished.".to_string());
+        }
+
+        let job_dir = self.jobs_root.join(&status.job_id);
+        tokio::fs::create_dir_all(&job_dir).await?;
+        for (name, bytes) in files {
+            tokio::fs::write(job_dir.join(name), bytes).await?;
+        }
+        let json = serde_json::to_string_pretty(&status)?;
+        tokio::fs::write(self.status_path(&status.job_id), json).await?;
+
+        Ok(status.job_id)
+    }
+
     fn status_path(&self, job_id: &str) -> PathBuf {
         self.jobs_root.join(job_id).join("status.json")
     }
@@ -163,6 +483,115 @@ impl JsonJobStore {
     fn results_path(&self, job_id: &str) -> PathBuf {
         self.jobs_root.join(job_id).join("results.json")
     }
+
+    fn checkpoint_path(&self, job_id: &str) -> PathBuf {
+        self.jobs_root.join(job_id).join("checkpoint.json")
+    }
+
+    fn config_path(&self, job_id: &str) -> PathBuf {
+        self.jobs_root.join(job_id).join("config.json")
+    }
+
+    fn unwritten_rows_path(&self, job_id: &str) -> PathBuf {
+        self.jobs_root.join(job_id).join("unwritten-rows.csv")
+    }
+
+    fn watch_state_path(&self, folder_id: &str) -> PathBuf {
+        let file_name: String = folder_id
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        self.jobs_root
+            .with_file_name("watches")
+            .join(format!("{file_name}.json"))
+    }
+}
+
+/// Quotes a CSV cell when it holds a separator, quote, or line break.
+fn csv_cell(cell: &str) -> String {
+    if cell.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+/// File name and contents of one archived job file.
+type ArchiveFile = (String, Vec<u8>);
+
+/// Reads a job archive: files must sit directly at the root or in a single top-level folder,
+/// and `status.json` must parse. Returns the status plus every other file by name.
+fn read_job_archive(data: &[u8]) -> anyhow::Result<(JobStatus, Vec<ArchiveFile>)> {
+    let invalid = |reason: &str| CoreError::InvalidRequest(format!("not a job archive: {reason}"));
+    let mut archive =
+        zip::ZipArchive::new(Cursor::new(data)).map_err(|_| invalid("not a zip file"))?;
+
+    let mut status = None;
+    let mut files = Vec::new();
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index)?;
+        if entry.is_dir() {
+            continue;
+        }
+
+        let Some(path) = entry.enclosed_name() else {
+            return Err(invalid("entry escapes the archive").into());
+        };
+        if path.components().count() > 2 {
+            return Err(invalid("unexpected nested folders").into());
+        }
+        let Some(name) = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .map(str::to_string)
+        else {
+            continue;
+        };
+
+        let mut bytes = Vec::new();
+        entry.read_to_end(&mut bytes)?;
+        if name == "status.json" {
+            let parsed = serde_json::from_slice::<JobStatus>(&bytes)
+                .map_err(|_| invalid("status.json is not a job status"))?;
+            status = Some(parsed);
+        } else {
+            files.push((name, bytes));
+        }
+    }
+
+    let status = status.ok_or_else(|| invalid("missing status.json"))?;
+    if status.job_id.trim().is_empty() {
+        return Err(invalid("status.json has no job id").into());
+    }
+    if !is_plain_job_id(&status.job_id) {
+        return Err(invalid("status.json has an invalid job id").into());
+    }
+    Ok((status, files))
+}
+
+/// A job id names the job's directory, so it must be a single plain path component:
+/// ASCII letters, digits, `-` and `_` only.
+pub(crate) fn is_plain_job_id(job_id: &str) -> bool {
+    !job_id.is_empty()
+        && job_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+pub(crate) fn ensure_plain_job_id(job_id: &str) -> Result<(), CoreError> {
+    if is_plain_job_id(job_id) {
+        Ok(())
+    } else {
+        Err(CoreError::InvalidRequest(format!(
+            "invalid job id: {job_id}"
+        )))
+    }
 }
 
 #[cfg(test)]
@@ -170,7 +599,7 @@ mod tests {
     use chrono::Utc;
 
     use super::*;
-    use crate::core::models::{JobProcessingState, ParsedCandidate};
+    use crate::core::models::{JobProcessingState, ParseErrorKind, ParsedCandidate};
 
     #[tokio::test]
     async fn save_and_load_status_and_results_round_trip() {
@@ -196,11 +625,23 @@ mod tests {
         let results = vec![ParsedCandidate {
             drive_file_id: None,
             source_file: Some("resume.pdf".to_string()),
+            mime_type: None,
             name: Some("John Doe".to_string()),
             email: Some("john@example.com".to_string()),
+            email_domain: Some("example.com".to_string()),
             phone: None,
+            phone_extension: None,
             linked_in: None,
             git_hub: None,
+            skills: vec!["Rust".to_string()],
+            headline: None,
+            notice_period: None,
+            field_sources: None,
+            name_candidates: None,
+            ocr_used: false,
+            parse_duration_ms: None,
+            custom: HashMap::new(),
+            education: None,
             confidence: 0.95,
             errors: Vec::new(),
         }];
@@ -217,4 +658,345 @@ mod tests {
         assert!(loaded_results.is_some());
         assert_eq!(loaded_results.unwrap()[0].name.as_deref(), Some("John Doe"));
     }
+
+    #[tokio::test]
+    async fn compact_results_round_trip_alongside_pretty_ones() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = JsonJobStore::new_with_root(temp_dir.path().to_path_buf(), 24);
+        let mut candidate =
+            ParsedCandidate::empty(Some("resume.pdf".to_string()), None, Vec::new());
+        candidate.name = Some("John Doe".to_string());
+        let results = vec![candidate];
+
+        store.save_results("pretty-job", &results).await.unwrap();
+        store.set_compact_results(true);
+        store.save_results("compact-job", &results).await.unwrap();
+
+        let pretty = std::fs::read_to_string(store.results_path("pretty-job")).unwrap();
+        let compact = std::fs::read_to_string(store.results_path("compact-job")).unwrap();
+        assert!(pretty.contains('\n'));
+        assert!(!compact.contains('\n'));
+        assert!(compact.len() < pretty.len());
+
+        for job_id in ["pretty-job", "compact-job"] {
+            let loaded = store.load_results(job_id).await.unwrap().unwrap();
+            assert_eq!(loaded[0].name.as_deref(), Some("John Doe"));
+        }
+    }
+
+    #[tokio::test]
+    async fn results_with_plain_string_errors_still_load() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = JsonJobStore::new_with_root(temp_dir.path().to_path_buf(), 24);
+        let path = store.results_path("old-job");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(
+            &path,
+            r#"[{"driveFileId":"file-1","sourceFile":"cv.pdf","name":null,"email":null,
+                "phone":null,"linkedIn":null,"gitHub":null,"confidence":0.0,
+                "errors":["Warning: possible duplicate of cv (1).pdf","Missing file ID",
+                          {"code":"timeout","message":"Error processing file: deadline has elapsed"}]}]"#,
+        )
+        .unwrap();
+
+        let loaded = store.load_results("old-job").await.unwrap().unwrap();
+        let codes: Vec<ParseErrorKind> = loaded[0].errors.iter().map(|error| error.code).collect();
+        assert_eq!(
+            codes,
+            vec![
+                ParseErrorKind::Warning,
+                ParseErrorKind::Other,
+                ParseErrorKind::Timeout
+            ]
+        );
+        assert_eq!(loaded[0].errors[1].message, "Missing file ID");
+
+        store.save_results("old-job", &loaded).await.unwrap();
+        let saved = std::fs::read_to_string(&path).unwrap();
+        assert!(saved.contains(r#""code": "warning""#));
+    }
+
+    #[test]
+    fn csv_cells_are_quoted_only_when_needed() {
+        assert_eq!(csv_cell("Rust"), "Rust");
+        assert_eq!(csv_cell("Rust, Go"), "\"Rust, Go\"");
+        assert_eq!(csv_cell("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[tokio::test]
+    async fn cleanup_removes_expired_jobs_but_not_running_ones() {
+        let temp = tempfile::tempdir().unwrap();
+        let store = JsonJobStore::new_with_root(temp.path().join("jobs"), 1);
+        let started = Utc::now() - chrono::Duration::hours(3);
+        let status = |job_id: &str, state: JobProcessingState| JobStatus {
+            job_id: job_id.to_string(),
+            status: state,
+            progress: 0,
+            total_files: 0,
+            processed_files: 0,
+            spreadsheet_id: None,
+            results_count: None,
+            error: None,
+            created_at: Some(started),
+            started_at: Some(started),
+            completed_at: None,
+            duration_seconds: None,
+        };
+
+        store
+            .save_status(&status("done", JobProcessingState::Completed))
+            .await
+            .unwrap();
+        store
+            .save_status(&status("running", JobProcessingState::Processing))
+            .await
+            .unwrap();
+        store.cleanup_expired_jobs().await.unwrap();
+
+        assert!(store.load_status("done").await.unwrap().is_none());
+        assert!(store.load_status("running").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn export_archive_zips_job_files() {
+        let temp = tempfile::tempdir().unwrap();
+        let store = JsonJobStore::new_with_root(temp.path().join("jobs"), 24);
+        store.save_results("job-1", &[]).await.unwrap();
+        tokio::fs::write(temp.path().join("jobs/job-1/job.log"), "started")
+            .await
+            .unwrap();
+
+        let dest = temp.path().join("exports/job-1.zip");
+        assert!(store.export_archive("job-1", &dest).await.unwrap());
+        assert!(!store
+            .export_archive("missing", &temp.path().join("missing.zip"))
+            .await
+            .unwrap());
+
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(&dest).unwrap()).unwrap();
+        let names: Vec<String> = archive.file_names().map(str::to_string).collect();
+        assert_eq!(names.len(), 2);
+        let mut log = String::new();
+        std::io::Read::read_to_string(&mut archive.by_name("job-1/job.log").unwrap(), &mut log)
+            .unwrap();
+        assert_eq!(log, "started");
+        assert!(archive.by_name("job-1/results.json").is_ok());
+
+        std::fs::create_dir_all(temp.path().join("outside")).unwrap();
+        let escaped = temp.path().join("escaped.zip");
+        let err = store
+            .export_archive("../outside", &escaped)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("invalid job id"));
+        assert!(!escaped.exists());
+    }
+
+    #[tokio::test]
+    async fn import_archive_restores_job_under_a_fresh_id_on_collision() {
+        let temp = tempfile::tempdir().unwrap();
+        let store = JsonJobStore::new_with_root(temp.path().join("jobs"), 24);
+        let status = JobStatus {
+            job_id: "job-1".to_string(),
+            status: JobProcessingState::Completed,
+            progress: 100,
+            total_files: 1,
+            processed_files: 1,
+            spreadsheet_id: None,
+            results_count: Some(0),
+            error: None,
+            created_at: Some(Utc::now()),
+            started_at: Some(Utc::now()),
+            completed_at: Some(Utc::now()),
+            duration_seconds: Some(1.0),
+        };
+        store.save_status(&status).await.unwrap();
+        store.save_results("job-1", &[]).await.unwrap();
+        let archive = temp.path().join("job-1.zip");
+        store.export_archive("job-1", &archive).await.unwrap();
+
+        let imported = store.import_archive(&archive).await.unwrap();
+        assert_ne!(imported, "job-1");
+        let loaded = store.load_status(&imported).await.unwrap().unwrap();
+        assert_eq!(loaded.job_id, imported);
+        assert_eq!(loaded.status, JobProcessingState::Completed);
+        assert!(store.load_results(&imported).await.unwrap().is_some());
+
+        let bogus = temp.path().join("bogus.zip");
+        std::fs::write(
+            &bogus,
+            crate::core::test_support::zip_with_entry("notes.txt", "hello"),
+        )
+        .unwrap();
+        let err = store.import_archive(&bogus).await.unwrap_err();
+        assert!(err.to_string().contains("missing status.json"));
+    }
+
+    #[tokio::test]
+    async fn import_archive_rejects_job_ids_that_leave_the_jobs_folder() {
+        let temp = tempfile::tempdir().unwrap();
+        let jobs_root = temp.path().join("jobs");
+        let store = JsonJobStore::new_with_root(jobs_root.clone(), 24);
+        let outside = temp.path().join("outside");
+
+        for job_id in [
+            "../outside".to_string(),
+            "../../x".to_string(),
+            outside.display().to_string(),
+        ] {
+            let status = serde_json::json!({
+                "jobId": job_id,
+                "status": "completed",
+                "progress": 100,
+                "totalFiles": 0,
+                "processedFiles": 0,
+            });
+            let archive = temp.path().join("crafted.zip");
+            std::fs::write(
+                &archive,
+                crate::core::test_support::zip_with_entry("status.json", &status.to_string()),
+            )
+            .unwrap();
+
+            let err = store.import_archive(&archive).await.unwrap_err();
+            assert!(
+                err.to_string().contains("invalid job id"),
+                "{job_id}: {err}"
+            );
+        }
+        assert!(!outside.exists());
+        assert!(!temp.path().parent().unwrap().join("x").exists());
+    }
+
+    #[tokio::test]
+    async fn config_snapshots_keep_extra_header_names_but_not_their_values() {
+        let temp = tempfile::tempdir().unwrap();
+        let store = JsonJobStore::new_with_root(temp.path().join("jobs"), 24);
+        let mut settings = crate::core::models::RuntimeSettings::default().to_persisted();
+        settings
+            .extra_headers
+            .insert("X-Api-Key".to_string(), "header-secret".to_string());
+
+        store.save_config("job-1", &settings).await.unwrap();
+
+        let json = std::fs::read_to_string(temp.path().join("jobs/job-1/config.json")).unwrap();
+        assert!(!json.contains("header-secret"));
+        let config = store.load_config("job-1").await.unwrap().unwrap();
+        assert_eq!(config.extra_headers["X-Api-Key"], REDACTED_HEADER_VALUE);
+    }
+
+    #[tokio::test]
+    async fn delete_job_rejects_ids_that_leave_the_jobs_folder() {
+        let temp = tempfile::tempdir().unwrap();
+        let store = JsonJobStore::new_with_root(temp.path().join("jobs"), 24);
+        store.save_results("job-1", &[]).await.unwrap();
+        let outside = temp.path().join("outside");
+        std::fs::create_dir_all(&outside).unwrap();
+
+        for job_id in ["../outside", "job-1/..", "", "."] {
+            let err = store.delete_job(job_id).await.unwrap_err();
+            assert!(
+                err.to_string().contains("invalid job id"),
+                "{job_id}: {err}"
+            );
+        }
+        assert!(outside.exists());
+        assert!(store.delete_job("job-1").await.unwrap());
+        assert!(!store.delete_job("job-1").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn throttled_status_writes_always_flush_terminal_states() {
+        let temp = tempfile::tempdir().unwrap();
+        let store = JsonJobStore::new_with_root(temp.path().join("jobs"), 24);
+        store.set_status_write_interval(std::time::Duration::from_secs(3600));
+        let status = |state: JobProcessingState, progress: i32| JobStatus {
+            job_id: "job-1".to_string(),
+            status: state,
+            progress,
+            total_files: 10,
+            processed_files: progress / 10,
+            spreadsheet_id: None,
+            results_count: None,
+            error: None,
+            created_at: Some(Utc::now()),
+            started_at: Some(Utc::now()),
+            completed_at: None,
+            duration_seconds: None,
+        };
+        let on_disk = || {
+            let json = std::fs::read_to_string(store.status_path("job-1")).unwrap();
+            serde_json::from_str::<JobStatus>(&json).unwrap()
+        };
+
+        store
+            .save_status(&status(JobProcessingState::Processing, 10))
+            .await
+            .unwrap();
+        store
+            .save_status(&status(JobProcessingState::Processing, 50))
+            .await
+            .unwrap();
+        assert_eq!(on_disk().progress, 10);
+        assert_eq!(
+            store.load_status("job-1").await.unwrap().unwrap().progress,
+            50
+        );
+
+        store
+            .save_status(&status(JobProcessingState::Completed, 100))
+            .await
+            .unwrap();
+        assert_eq!(on_disk().status, JobProcessingState::Completed);
+        assert_eq!(on_disk().progress, 100);
+    }
+
+    #[tokio::test]
+    async fn throttled_status_writes_flush_paused_jobs_and_follow_interval_changes() {
+        let temp = tempfile::tempdir().unwrap();
+        let store = JsonJobStore::new_with_root(temp.path().join("jobs"), 24);
+        store.set_status_write_interval(std::time::Duration::from_secs(3600));
+        let status = |state: JobProcessingState, progress: i32| JobStatus {
+            job_id: "job-1".to_string(),
+            status: state,
+            progress,
+            total_files: 10,
+            processed_files: progress / 10,
+            spreadsheet_id: None,
+            results_count: None,
+            error: None,
+            created_at: Some(Utc::now()),
+            started_at: Some(Utc::now()),
+            completed_at: None,
+            duration_seconds: None,
+        };
+        let on_disk = || {
+            let json = std::fs::read_to_string(store.status_path("job-1")).unwrap();
+            serde_json::from_str::<JobStatus>(&json).unwrap()
+        };
+
+        store
+            .save_status(&status(JobProcessingState::Processing, 10))
+            .await
+            .unwrap();
+        store
+            .save_status(&status(JobProcessingState::Paused, 30))
+            .await
+            .unwrap();
+        assert_eq!(on_disk().status, JobProcessingState::Paused);
+        assert_eq!(on_disk().progress, 30);
+
+        store
+            .save_status(&status(JobProcessingState::Processing, 40))
+            .await
+            .unwrap();
+        assert_eq!(on_disk().progress, 30);
+
+        store.set_status_write_interval(std::time::Duration::ZERO);
+        store
+            .save_status(&status(JobProcessingState::Processing, 50))
+            .await
+            .unwrap();
+        assert_eq!(on_disk().progress, 50);
+    }
 }
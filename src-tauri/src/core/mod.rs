@@ -1,7 +1,11 @@
 pub mod auth;
+pub mod auth_provider;
 pub mod commands;
+pub mod concurrency;
+pub mod credential_store;
 pub mod document_parser;
 pub mod errors;
+pub mod events;
 pub mod field_extractor;
 pub mod google_drive;
 pub mod google_sheets;
@@ -12,3 +16,5 @@ pub mod pdf;
 pub mod secret_store;
 pub mod service;
 pub mod settings_store;
+#[cfg(test)]
+mod test_support;
@@ -1,49 +1,248 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use super::errors::RetryClass;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ParsedCandidate {
     pub drive_file_id: Option<String>,
     pub source_file: Option<String>,
+    /// MIME type Drive listed the file with; used to name the file in resume exports.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
     pub name: Option<String>,
     pub email: Option<String>,
+    #[serde(default)]
+    pub email_domain: Option<String>,
     pub phone: Option<String>,
+    /// Extension written after the phone number, e.g. `89` for "+1 650 253 0000 x89".
+    #[serde(default)]
+    pub phone_extension: Option<String>,
     pub linked_in: Option<String>,
     pub git_hub: Option<String>,
+    #[serde(default)]
+    pub skills: Vec<String>,
+    #[serde(default)]
+    pub headline: Option<String>,
+    /// Notice period or availability, e.g. "30 days" or "Immediate".
+    #[serde(default)]
+    pub notice_period: Option<String>,
+    /// Which extractor tier matched each field; only filled for debug single-file parses.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field_sources: Option<HashMap<String, String>>,
+    /// Lines that could be the name, best first; only filled for single-file parses.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name_candidates: Option<Vec<String>>,
+    /// Whether OCR supplied any of the text the fields were read from.
+    #[serde(default)]
+    pub ocr_used: bool,
+    /// Time spent parsing; only filled for single-file parses.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parse_duration_ms: Option<u64>,
+    /// Values matched by the custom field rules, by rule name.
+    #[serde(default)]
+    pub custom: HashMap<String, String>,
+    /// Degree and institution from the education section.
+    #[serde(default)]
+    pub education: Option<String>,
     pub confidence: f64,
     #[serde(default)]
-    pub errors: Vec<String>,
+    pub errors: Vec<ParseError>,
 }
 
 impl ParsedCandidate {
     pub fn empty(
         source_file: Option<String>,
         drive_file_id: Option<String>,
-        errors: Vec<String>,
+        errors: Vec<ParseError>,
     ) -> Self {
         Self {
             drive_file_id,
             source_file,
+            mime_type: None,
             name: None,
             email: None,
+            email_domain: None,
             phone: None,
+            phone_extension: None,
             linked_in: None,
             git_hub: None,
+            skills: Vec::new(),
+            headline: None,
+            notice_period: None,
+            field_sources: None,
+            name_candidates: None,
+            ocr_used: false,
+            parse_duration_ms: None,
+            custom: HashMap::new(),
+            education: None,
             confidence: 0.0,
             errors,
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// What went wrong (or looks doubtful) with one parsed file, so the UI can group and
+/// localize errors without matching on message text.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ParseErrorKind {
+    MissingFileId,
+    DownloadFailed,
+    /// Drive no longer has the file, typically because it was deleted or trashed after the
+    /// folder was listed.
+    FileUnavailable,
+    Timeout,
+    UnsupportedFileType,
+    ExtractionFailed,
+    /// Extraction and OCR ran but produced next to no text, as with an image-only or
+    /// empty file.
+    NoTextExtracted,
+    /// A legacy error string stored before errors had codes.
+    Other,
+    OcrDisabled,
+    MetadataFallback,
+    OcrMergeFailed,
+    SplitDocument,
+    NoticePeriod,
+    PossibleDuplicate,
+    MissingRequiredFields,
+    SkippedFileType,
+    PlainTextFallback,
+    /// The text had no recognisable name, so it was read from the filename.
+    NameFromFilename,
+    /// An education line with no institution next to it.
+    EducationUncertain,
+    /// A legacy warning string stored before errors had codes.
+    Warning,
+}
+
+impl ParseErrorKind {
+    /// Warnings flag a result for review; everything else means the file failed to parse.
+    pub fn is_warning(self) -> bool {
+        matches!(
+            self,
+            ParseErrorKind::OcrDisabled
+                | ParseErrorKind::MetadataFallback
+                | ParseErrorKind::OcrMergeFailed
+                | ParseErrorKind::SplitDocument
+                | ParseErrorKind::NoticePeriod
+                | ParseErrorKind::PossibleDuplicate
+                | ParseErrorKind::MissingRequiredFields
+                | ParseErrorKind::SkippedFileType
+                | ParseErrorKind::PlainTextFallback
+                | ParseErrorKind::NameFromFilename
+                | ParseErrorKind::EducationUncertain
+                | ParseErrorKind::Warning
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(from = "StoredParseError")]
+pub struct ParseError {
+    pub code: ParseErrorKind,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(code: ParseErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    pub fn is_warning(&self) -> bool {
+        self.code.is_warning()
+    }
+}
+
+/// Results saved by older builds hold plain strings; those keep their text and get a
+/// generic code based on the "Warning:" prefix they were written with.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredParseError {
+    Coded {
+        code: ParseErrorKind,
+        message: String,
+    },
+    Legacy(String),
+}
+
+impl From<StoredParseError> for ParseError {
+    fn from(stored: StoredParseError) -> Self {
+        match stored {
+            StoredParseError::Coded { code, message } => ParseError { code, message },
+            StoredParseError::Legacy(message) => {
+                let code = if message.starts_with("Warning:") {
+                    ParseErrorKind::Warning
+                } else {
+                    ParseErrorKind::Other
+                };
+                ParseError { code, message }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BatchParseRequest {
     pub folder_id: String,
     pub spreadsheet_id: Option<String>,
+    #[serde(default)]
+    pub name_include_regex: Option<String>,
+    #[serde(default)]
+    pub name_exclude_regex: Option<String>,
+    /// Run the full pipeline and keep results locally without creating or appending to a
+    /// Google Sheet.
+    #[serde(default)]
+    pub skip_spreadsheet: bool,
+    /// Best-effort POST target notified with the final job status.
+    #[serde(default)]
+    pub completion_webhook_url: Option<String>,
+    /// Opt-in heuristic that splits a document holding several concatenated resumes into
+    /// one result per resume.
+    #[serde(default)]
+    pub split_multi_resume: bool,
+    /// Repeated submissions with the same key shortly after the first return the original
+    /// job instead of starting a duplicate.
+    #[serde(default)]
+    pub idempotency_key: Option<String>,
+    /// Skip appending candidates whose email is already in the target sheet's email column.
+    #[serde(default)]
+    pub skip_existing_in_sheet: bool,
+    /// Title for a newly created sheet, overriding the title template setting. Supports the
+    /// same `{date}`, `{time}`, and `{count}` placeholders.
+    #[serde(default)]
+    pub spreadsheet_title: Option<String>,
+    /// Warn on files whose names differ only by copy markers such as `cv.pdf` and
+    /// `cv (1).pdf`. Flagged files are still processed.
+    #[serde(default)]
+    pub flag_possible_duplicates: bool,
+    /// Existing sheet that receives candidates scoring below `review_confidence_threshold`
+    /// instead of the main sheet.
+    #[serde(default)]
+    pub review_spreadsheet_id: Option<String>,
+    /// Confidence (0.0 to 1.0) below which candidates go to the review sheet.
+    #[serde(default)]
+    pub review_confidence_threshold: Option<f64>,
+    /// Only list files Drive reports as modified after this time.
+    #[serde(default)]
+    pub modified_after: Option<DateTime<Utc>>,
+    /// Drive MIME types to list, e.g. only `application/pdf` for a PDF-only run. Empty means
+    /// every type the parser supports.
+    #[serde(default)]
+    pub allowed_mime_types: Vec<String>,
+    /// Only process the listed files with these Drive ids. Empty processes every listed
+    /// file. Watch mode sets it to the files no earlier cycle has processed.
+    #[serde(default)]
+    pub file_ids: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -60,6 +259,9 @@ pub struct DriveFileRef {
     pub id: String,
     pub name: String,
     pub mime_type: String,
+    /// Size in bytes as reported by Drive.
+    #[serde(default)]
+    pub size: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -79,11 +281,62 @@ pub struct DrivePathEntry {
     pub name: String,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum FolderAccessStatus {
+    Accessible,
+    NotFound,
+    NotAFolder,
+    PermissionDenied,
+}
+
+/// Pre-flight result for a Drive folder id, so a bad id is not mistaken for an empty folder.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderAccess {
+    pub folder_id: String,
+    pub status: FolderAccessStatus,
+    /// Folder name, or the file name when the id points at something else.
+    pub name: Option<String>,
+    pub message: String,
+}
+
+/// A resume that could not be added to a resume export.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResumeExportFailure {
+    pub drive_file_id: String,
+    pub source_file: Option<String>,
+    pub message: String,
+}
+
+/// Text extracted from one file before any fields are parsed from it, for debugging
+/// extraction problems.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtractedText {
+    pub text: String,
+    pub ocr_used: bool,
+    pub errors: Vec<ParseError>,
+    /// Set when `text` was cut short, saying how much of it is shown.
+    pub note: Option<String>,
+}
+
+/// Outcome of zipping a job's source resumes; failed files are left out of the zip.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResumeExport {
+    pub exported_count: usize,
+    pub total_bytes: u64,
+    pub failed: Vec<ResumeExportFailure>,
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum JobProcessingState {
     Pending,
     Processing,
+    Paused,
     Completed,
     Failed,
     Revoked,
@@ -106,6 +359,69 @@ pub struct JobStatus {
     pub duration_seconds: Option<f64>,
 }
 
+/// How far a batch job got, saved in its job directory so a failed run can be resumed
+/// without listing or processing the folder from the start.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobCheckpoint {
+    pub request: BatchParseRequest,
+    /// Sheet the processed rows were written to, if any.
+    pub spreadsheet_id: Option<String>,
+    /// Drive page token for the first listing page that still has unprocessed files;
+    /// `None` lists from the start of the folder.
+    pub page_token: Option<String>,
+    /// Files whose results are saved and whose rows are in the sheet.
+    pub processed_file_ids: Vec<String>,
+}
+
+/// Folder to keep processing as new files arrive. Each cycle lists the folder and, when it
+/// holds files no earlier cycle has processed, runs a regular batch job over just those.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchFolderRequest {
+    pub folder_id: String,
+    pub interval_seconds: u64,
+    /// Sheet every cycle appends to. When unset, the first cycle creates one and later
+    /// cycles keep using it.
+    #[serde(default)]
+    pub spreadsheet_id: Option<String>,
+}
+
+/// Which files of a watched folder have already been processed, kept across restarts.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchState {
+    pub folder_id: String,
+    pub spreadsheet_id: Option<String>,
+    /// Start of the last cycle whose job completed.
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub last_job_id: Option<String>,
+    /// Files processed by a completed cycle; later cycles skip them even when Drive reports
+    /// an older modified time.
+    #[serde(default)]
+    pub seen_file_ids: BTreeSet<String>,
+}
+
+/// Body POSTed to a batch request's completion webhook.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobCompletionWebhookPayload {
+    #[serde(flatten)]
+    pub status: JobStatus,
+    pub spreadsheet_url: Option<String>,
+}
+
+/// Payload of the `job-complete`, `job-failed` and `job-cancelled` events.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobFinishedEvent {
+    pub status: JobStatus,
+    pub spreadsheet_url: Option<String>,
+    pub parsed_count: usize,
+    pub failed_count: usize,
+    pub ocr_count: usize,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AuthStatus {
@@ -116,28 +432,350 @@ pub struct AuthStatus {
     pub expires_at: Option<DateTime<Utc>>,
 }
 
+/// Which address wins when a resume lists more than one email.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum EmailPreference {
+    /// First address found, matching the historical behavior.
+    #[default]
+    First,
+    /// Free-mail providers such as Gmail or Outlook.
+    PreferPersonal,
+    /// Anything that is not a known free-mail provider.
+    PreferCorporate,
+}
+
+/// What `require_international_phone` does with a number written without a country code.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum NationalPhonePolicy {
+    /// Keep the digits as written, without guessing a country.
+    #[default]
+    Keep,
+    /// Leave the phone empty.
+    Drop,
+}
+
+/// What a batch does with files in the folder that are not PDF, DOCX, or ODT.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum UnsupportedFilePolicy {
+    /// Leave them out of the listing, matching the historical behavior.
+    Skip,
+    /// List each one in the results with a warning, without downloading it.
+    #[default]
+    Warn,
+    /// Download them and read any plain text they hold.
+    ExtractText,
+}
+
+/// Order in which a batch writes candidates to the sheet and its results. Sorting holds
+/// rows back until the job finishes or stops, so progress is only checkpointed then: a job
+/// that is killed or crashes resumes from its last stop.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum SortOrder {
+    /// Drive listing order.
+    #[default]
+    None,
+    /// Highest confidence first.
+    ConfidenceDesc,
+    /// Alphabetical by name, ignoring case; candidates without a name go last.
+    NameAsc,
+}
+
+/// Which transient failures are retried. All are retried by default.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RetryPolicy {
+    /// HTTP 429 and Drive's rate-limit 403s.
+    pub rate_limit: bool,
+    /// HTTP 5xx responses.
+    pub server_error: bool,
+    /// Timeouts and failed connections.
+    pub network: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            rate_limit: true,
+            server_error: true,
+            network: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn allows(&self, class: RetryClass) -> bool {
+        match class {
+            RetryClass::RateLimit => self.rate_limit,
+            RetryClass::ServerError => self.server_error,
+            RetryClass::Network => self.network,
+        }
+    }
+}
+
+/// Points each found field adds to a candidate's confidence. The total is capped at 1.0.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ConfidenceWeights {
+    pub email: f64,
+    pub phone: f64,
+    pub name: f64,
+    pub linked_in: f64,
+    pub git_hub: f64,
+    /// Added when the text came from the document itself rather than OCR.
+    pub text_layer: f64,
+}
+
+impl Default for ConfidenceWeights {
+    fn default() -> Self {
+        Self {
+            email: 0.4,
+            phone: 0.25,
+            name: 0.15,
+            linked_in: 0.1,
+            git_hub: 0.05,
+            text_layer: 0.05,
+        }
+    }
+}
+
+impl ConfidenceWeights {
+    /// Replaces negative or non-finite weights with zero.
+    pub fn sanitized(self) -> Self {
+        let clean = |weight: f64| {
+            if weight.is_finite() {
+                weight.max(0.0)
+            } else {
+                0.0
+            }
+        };
+        Self {
+            email: clean(self.email),
+            phone: clean(self.phone),
+            name: clean(self.name),
+            linked_in: clean(self.linked_in),
+            git_hub: clean(self.git_hub),
+            text_layer: clean(self.text_layer),
+        }
+    }
+}
+
+/// One column of a candidate row, as written to batch sheets and by `parse_single_as_row`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum SheetColumn {
+    Name,
+    ResumeLink,
+    Phone,
+    Email,
+    /// Lowercased domain of the email, e.g. to filter personal from corporate addresses.
+    EmailDomain,
+    LinkedIn,
+    GitHub,
+    Skills,
+    NoticePeriod,
+    Education,
+    Confidence,
+}
+
+impl SheetColumn {
+    /// Columns every batch sheet starts with, in order.
+    pub const DEFAULT_LAYOUT: [SheetColumn; 6] = [
+        Self::Name,
+        Self::ResumeLink,
+        Self::Phone,
+        Self::Email,
+        Self::LinkedIn,
+        Self::GitHub,
+    ];
+
+    pub fn header(self) -> &'static str {
+        match self {
+            Self::Name => "Name",
+            Self::ResumeLink => "Resume Link",
+            Self::Phone => "Phone Number",
+            Self::Email => "Email ID",
+            Self::EmailDomain => "Email Domain",
+            Self::LinkedIn => "LinkedIn",
+            Self::GitHub => "GitHub",
+            Self::Skills => "Skills",
+            Self::NoticePeriod => "Notice Period",
+            Self::Education => "Education",
+            Self::Confidence => "Confidence",
+        }
+    }
+}
+
+/// A user-defined field: the first capture group of `pattern`'s first match is stored
+/// under `name` and written to a sheet column of the same name.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomFieldRule {
+    pub name: String,
+    pub pattern: String,
+}
+
+/// A candidate field that can be required before a row is written to the sheet.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum FieldKind {
+    Name,
+    Email,
+    Phone,
+    LinkedIn,
+    GitHub,
+    Skills,
+}
+
+impl FieldKind {
+    pub const ALL: [FieldKind; 6] = [
+        Self::Name,
+        Self::Email,
+        Self::Phone,
+        Self::LinkedIn,
+        Self::GitHub,
+        Self::Skills,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Name => "name",
+            Self::Email => "email",
+            Self::Phone => "phone",
+            Self::LinkedIn => "LinkedIn",
+            Self::GitHub => "GitHub",
+            Self::Skills => "skills",
+        }
+    }
+
+    pub fn is_present(self, candidate: &ParsedCandidate) -> bool {
+        let value = match self {
+            Self::Name => &candidate.name,
+            Self::Email => &candidate.email,
+            Self::Phone => &candidate.phone,
+            Self::LinkedIn => &candidate.linked_in,
+            Self::GitHub => &candidate.git_hub,
+            Self::Skills => return !candidate.skills.is_empty(),
+        };
+        value
+            .as_deref()
+            .is_some_and(|value| !value.trim().is_empty())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct RuntimeSettings {
     pub google_client_id: String,
     pub google_client_secret: Option<String>,
     pub tesseract_path: String,
     pub max_concurrent_requests: usize,
+    pub max_concurrent_downloads: Option<usize>,
+    pub max_concurrent_parses: Option<usize>,
     pub spreadsheet_batch_size: usize,
     pub max_retries: usize,
     pub retry_delay_seconds: f64,
     pub job_retention_hours: i64,
+    pub custom_ca_cert_path: Option<String>,
+    pub email_preference: EmailPreference,
+    pub user_agent_suffix: Option<String>,
+    pub skill_dictionary: Vec<String>,
+    pub disable_ocr: bool,
+    pub status_write_interval_ms: u64,
+    pub preserve_text_columns: bool,
+    pub spreadsheet_title_template: String,
+    pub color_by_confidence: bool,
+    pub merge_ocr_fields: bool,
+    pub max_queued_jobs: usize,
+    pub debug_field_sources: bool,
+    pub required_fields: Vec<FieldKind>,
+    pub confidence_as_percent: bool,
+    pub confidence_weights: ConfidenceWeights,
+    pub name_denylist: Vec<String>,
+    pub keep_ocr_temp: bool,
+    pub retry_on: RetryPolicy,
+    pub cleanup_interval_minutes: u64,
+    pub notice_period_column: bool,
+    pub compact_results: bool,
+    pub sheet_append_page_size: Option<usize>,
+    pub missing_value_placeholder: String,
+    pub unsupported_file_policy: UnsupportedFilePolicy,
+    pub adaptive_concurrency: bool,
+    pub custom_fields: Vec<CustomFieldRule>,
+    pub sort_order: SortOrder,
+    pub job_retry_budget: Option<usize>,
+    pub sheet_append_delay_ms: u64,
+    pub deobfuscate_emails: bool,
+    pub education_column: bool,
+    pub extra_headers: HashMap<String, String>,
+    pub require_international_phone: bool,
+    pub national_phone_policy: NationalPhonePolicy,
+    pub email_domain_column: bool,
 }
 
 impl RuntimeSettings {
+    /// Concurrent Drive downloads, falling back to `max_concurrent_requests`.
+    pub fn download_concurrency(&self) -> usize {
+        self.max_concurrent_downloads
+            .unwrap_or(self.max_concurrent_requests)
+            .max(1)
+    }
+
+    /// Concurrent parse/OCR tasks, falling back to `max_concurrent_requests`.
+    pub fn parse_concurrency(&self) -> usize {
+        self.max_concurrent_parses
+            .unwrap_or(self.max_concurrent_requests)
+            .max(1)
+    }
+
     pub fn to_persisted(&self) -> PersistedSettings {
         PersistedSettings {
             google_client_id: self.google_client_id.clone(),
             tesseract_path: self.tesseract_path.clone(),
             max_concurrent_requests: self.max_concurrent_requests,
+            max_concurrent_downloads: self.max_concurrent_downloads,
+            max_concurrent_parses: self.max_concurrent_parses,
             spreadsheet_batch_size: self.spreadsheet_batch_size,
             max_retries: self.max_retries,
             retry_delay_seconds: self.retry_delay_seconds,
             job_retention_hours: self.job_retention_hours,
+            custom_ca_cert_path: self.custom_ca_cert_path.clone(),
+            email_preference: self.email_preference,
+            user_agent_suffix: self.user_agent_suffix.clone(),
+            skill_dictionary: self.skill_dictionary.clone(),
+            disable_ocr: self.disable_ocr,
+            status_write_interval_ms: self.status_write_interval_ms,
+            preserve_text_columns: self.preserve_text_columns,
+            spreadsheet_title_template: self.spreadsheet_title_template.clone(),
+            color_by_confidence: self.color_by_confidence,
+            merge_ocr_fields: self.merge_ocr_fields,
+            max_queued_jobs: self.max_queued_jobs,
+            debug_field_sources: self.debug_field_sources,
+            required_fields: self.required_fields.clone(),
+            confidence_as_percent: self.confidence_as_percent,
+            confidence_weights: self.confidence_weights,
+            name_denylist: self.name_denylist.clone(),
+            keep_ocr_temp: self.keep_ocr_temp,
+            retry_on: self.retry_on,
+            cleanup_interval_minutes: self.cleanup_interval_minutes,
+            notice_period_column: self.notice_period_column,
+            compact_results: self.compact_results,
+            sheet_append_page_size: self.sheet_append_page_size,
+            missing_value_placeholder: self.missing_value_placeholder.clone(),
+            unsupported_file_policy: self.unsupported_file_policy,
+            adaptive_concurrency: self.adaptive_concurrency,
+            custom_fields: self.custom_fields.clone(),
+            sort_order: self.sort_order,
+            job_retry_budget: self.job_retry_budget,
+            sheet_append_delay_ms: self.sheet_append_delay_ms,
+            deobfuscate_emails: self.deobfuscate_emails,
+            education_column: self.education_column,
+            extra_headers: self.extra_headers.clone(),
+            require_international_phone: self.require_international_phone,
+            national_phone_policy: self.national_phone_policy,
+            email_domain_column: self.email_domain_column,
         }
     }
 
@@ -147,13 +785,60 @@ impl RuntimeSettings {
             google_client_secret: google_client_secret.filter(|v| !v.trim().is_empty()),
             tesseract_path: persisted.tesseract_path,
             max_concurrent_requests: persisted.max_concurrent_requests,
+            max_concurrent_downloads: persisted.max_concurrent_downloads,
+            max_concurrent_parses: persisted.max_concurrent_parses,
             spreadsheet_batch_size: persisted.spreadsheet_batch_size,
             max_retries: persisted.max_retries,
             retry_delay_seconds: persisted.retry_delay_seconds,
             job_retention_hours: persisted.job_retention_hours,
+            custom_ca_cert_path: persisted.custom_ca_cert_path,
+            email_preference: persisted.email_preference,
+            user_agent_suffix: persisted.user_agent_suffix,
+            skill_dictionary: persisted.skill_dictionary,
+            disable_ocr: persisted.disable_ocr,
+            status_write_interval_ms: persisted.status_write_interval_ms,
+            preserve_text_columns: persisted.preserve_text_columns,
+            spreadsheet_title_template: persisted.spreadsheet_title_template,
+            color_by_confidence: persisted.color_by_confidence,
+            merge_ocr_fields: persisted.merge_ocr_fields,
+            max_queued_jobs: persisted.max_queued_jobs,
+            debug_field_sources: persisted.debug_field_sources,
+            required_fields: persisted.required_fields,
+            confidence_as_percent: persisted.confidence_as_percent,
+            confidence_weights: persisted.confidence_weights,
+            name_denylist: persisted.name_denylist,
+            keep_ocr_temp: persisted.keep_ocr_temp,
+            retry_on: persisted.retry_on,
+            cleanup_interval_minutes: persisted.cleanup_interval_minutes,
+            notice_period_column: persisted.notice_period_column,
+            compact_results: persisted.compact_results,
+            sheet_append_page_size: persisted.sheet_append_page_size,
+            missing_value_placeholder: persisted.missing_value_placeholder,
+            unsupported_file_policy: persisted.unsupported_file_policy,
+            adaptive_concurrency: persisted.adaptive_concurrency,
+            custom_fields: persisted.custom_fields,
+            sort_order: persisted.sort_order,
+            job_retry_budget: persisted.job_retry_budget,
+            sheet_append_delay_ms: persisted.sheet_append_delay_ms,
+            deobfuscate_emails: persisted.deobfuscate_emails,
+            education_column: persisted.education_column,
+            extra_headers: persisted.extra_headers,
+            require_international_phone: persisted.require_international_phone,
+            national_phone_policy: persisted.national_phone_policy,
+            email_domain_column: persisted.email_domain_column,
         }
     }
 
+    /// Whether `self` and `other` differ in a setting that is only read when the app starts:
+    /// the HTTP clients are built from the CA certificate, user agent suffix, and extra
+    /// headers, and the job queue from its capacity.
+    pub fn differs_in_startup_settings(&self, other: &RuntimeSettings) -> bool {
+        self.custom_ca_cert_path != other.custom_ca_cert_path
+            || self.user_agent_suffix != other.user_agent_suffix
+            || self.extra_headers != other.extra_headers
+            || self.max_queued_jobs != other.max_queued_jobs
+    }
+
     pub fn to_view(&self, legacy_secret_scrubbed: bool) -> RuntimeSettingsView {
         RuntimeSettingsView {
             google_client_id: self.google_client_id.clone(),
@@ -163,12 +848,51 @@ impl RuntimeSettings {
                 .map(|v| !v.trim().is_empty())
                 .unwrap_or(false),
             legacy_secret_scrubbed,
+            restart_required: false,
             tesseract_path: self.tesseract_path.clone(),
+            tesseract_version: None,
             max_concurrent_requests: self.max_concurrent_requests,
+            max_concurrent_downloads: self.max_concurrent_downloads,
+            max_concurrent_parses: self.max_concurrent_parses,
             spreadsheet_batch_size: self.spreadsheet_batch_size,
             max_retries: self.max_retries,
             retry_delay_seconds: self.retry_delay_seconds,
             job_retention_hours: self.job_retention_hours,
+            custom_ca_cert_path: self.custom_ca_cert_path.clone(),
+            email_preference: self.email_preference,
+            user_agent_suffix: self.user_agent_suffix.clone(),
+            skill_dictionary: self.skill_dictionary.clone(),
+            disable_ocr: self.disable_ocr,
+            status_write_interval_ms: self.status_write_interval_ms,
+            preserve_text_columns: self.preserve_text_columns,
+            spreadsheet_title_template: self.spreadsheet_title_template.clone(),
+            color_by_confidence: self.color_by_confidence,
+            merge_ocr_fields: self.merge_ocr_fields,
+            max_queued_jobs: self.max_queued_jobs,
+            debug_field_sources: self.debug_field_sources,
+            required_fields: self.required_fields.clone(),
+            confidence_as_percent: self.confidence_as_percent,
+            confidence_weights: self.confidence_weights,
+            name_denylist: self.name_denylist.clone(),
+            keep_ocr_temp: self.keep_ocr_temp,
+            retry_on: self.retry_on,
+            cleanup_interval_minutes: self.cleanup_interval_minutes,
+            notice_period_column: self.notice_period_column,
+            compact_results: self.compact_results,
+            sheet_append_page_size: self.sheet_append_page_size,
+            missing_value_placeholder: self.missing_value_placeholder.clone(),
+            unsupported_file_policy: self.unsupported_file_policy,
+            adaptive_concurrency: self.adaptive_concurrency,
+            custom_fields: self.custom_fields.clone(),
+            sort_order: self.sort_order,
+            job_retry_budget: self.job_retry_budget,
+            sheet_append_delay_ms: self.sheet_append_delay_ms,
+            deobfuscate_emails: self.deobfuscate_emails,
+            education_column: self.education_column,
+            extra_headers: self.extra_headers.clone(),
+            require_international_phone: self.require_international_phone,
+            national_phone_policy: self.national_phone_policy,
+            email_domain_column: self.email_domain_column,
         }
     }
 }
@@ -188,6 +912,10 @@ pub struct PersistedSettings {
     pub tesseract_path: String,
     #[serde(default = "default_max_concurrent_requests")]
     pub max_concurrent_requests: usize,
+    #[serde(default)]
+    pub max_concurrent_downloads: Option<usize>,
+    #[serde(default)]
+    pub max_concurrent_parses: Option<usize>,
     #[serde(default = "default_spreadsheet_batch_size")]
     pub spreadsheet_batch_size: usize,
     #[serde(default = "default_max_retries")]
@@ -196,6 +924,76 @@ pub struct PersistedSettings {
     pub retry_delay_seconds: f64,
     #[serde(default = "default_job_retention_hours")]
     pub job_retention_hours: i64,
+    #[serde(default)]
+    pub custom_ca_cert_path: Option<String>,
+    #[serde(default)]
+    pub email_preference: EmailPreference,
+    #[serde(default)]
+    pub user_agent_suffix: Option<String>,
+    #[serde(default)]
+    pub skill_dictionary: Vec<String>,
+    #[serde(default)]
+    pub disable_ocr: bool,
+    #[serde(default = "default_status_write_interval_ms")]
+    pub status_write_interval_ms: u64,
+    #[serde(default)]
+    pub preserve_text_columns: bool,
+    #[serde(default = "default_spreadsheet_title_template")]
+    pub spreadsheet_title_template: String,
+    #[serde(default)]
+    pub color_by_confidence: bool,
+    #[serde(default)]
+    pub merge_ocr_fields: bool,
+    #[serde(default = "default_max_queued_jobs")]
+    pub max_queued_jobs: usize,
+    #[serde(default)]
+    pub debug_field_sources: bool,
+    #[serde(default)]
+    pub required_fields: Vec<FieldKind>,
+    #[serde(default)]
+    pub confidence_as_percent: bool,
+    #[serde(default)]
+    pub confidence_weights: ConfidenceWeights,
+    #[serde(default)]
+    pub name_denylist: Vec<String>,
+    #[serde(default)]
+    pub keep_ocr_temp: bool,
+    #[serde(default)]
+    pub retry_on: RetryPolicy,
+    #[serde(default = "default_cleanup_interval_minutes")]
+    pub cleanup_interval_minutes: u64,
+    #[serde(default)]
+    pub notice_period_column: bool,
+    #[serde(default)]
+    pub compact_results: bool,
+    #[serde(default)]
+    pub sheet_append_page_size: Option<usize>,
+    #[serde(default)]
+    pub missing_value_placeholder: String,
+    #[serde(default)]
+    pub unsupported_file_policy: UnsupportedFilePolicy,
+    #[serde(default)]
+    pub adaptive_concurrency: bool,
+    #[serde(default)]
+    pub custom_fields: Vec<CustomFieldRule>,
+    #[serde(default)]
+    pub sort_order: SortOrder,
+    #[serde(default)]
+    pub job_retry_budget: Option<usize>,
+    #[serde(default)]
+    pub sheet_append_delay_ms: u64,
+    #[serde(default = "default_deobfuscate_emails")]
+    pub deobfuscate_emails: bool,
+    #[serde(default)]
+    pub education_column: bool,
+    #[serde(default)]
+    pub extra_headers: HashMap<String, String>,
+    #[serde(default)]
+    pub require_international_phone: bool,
+    #[serde(default)]
+    pub national_phone_policy: NationalPhonePolicy,
+    #[serde(default)]
+    pub email_domain_column: bool,
 }
 
 impl PersistedSettings {
@@ -204,6 +1002,8 @@ impl PersistedSettings {
             self.google_client_id = default_google_client_id();
         }
         self.max_concurrent_requests = self.max_concurrent_requests.max(1);
+        self.max_concurrent_downloads = self.max_concurrent_downloads.map(|v| v.max(1));
+        self.max_concurrent_parses = self.max_concurrent_parses.map(|v| v.max(1));
         self.spreadsheet_batch_size = self.spreadsheet_batch_size.max(1);
         self.max_retries = self.max_retries.max(1);
         self.retry_delay_seconds = self.retry_delay_seconds.max(0.1);
@@ -211,6 +1011,28 @@ impl PersistedSettings {
         if self.tesseract_path.trim().is_empty() {
             self.tesseract_path = default_tesseract_path();
         }
+        self.custom_ca_cert_path = self
+            .custom_ca_cert_path
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty());
+        self.user_agent_suffix = self
+            .user_agent_suffix
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty());
+        self.skill_dictionary = sanitize_skill_dictionary(self.skill_dictionary);
+        if self.spreadsheet_title_template.trim().is_empty() {
+            self.spreadsheet_title_template = default_spreadsheet_title_template();
+        }
+        self.max_queued_jobs = self.max_queued_jobs.max(1);
+        self.confidence_weights = self.confidence_weights.sanitized();
+        self.name_denylist = sanitize_skill_dictionary(self.name_denylist);
+        self.sheet_append_page_size = self.sheet_append_page_size.map(|v| v.max(1));
+        self.extra_headers = self
+            .extra_headers
+            .into_iter()
+            .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+            .filter(|(name, _)| !name.is_empty())
+            .collect();
         self
     }
 }
@@ -221,10 +1043,47 @@ impl Default for PersistedSettings {
             google_client_id: default_google_client_id(),
             tesseract_path: default_tesseract_path(),
             max_concurrent_requests: default_max_concurrent_requests(),
+            max_concurrent_downloads: None,
+            max_concurrent_parses: None,
             spreadsheet_batch_size: default_spreadsheet_batch_size(),
             max_retries: default_max_retries(),
             retry_delay_seconds: default_retry_delay_seconds(),
             job_retention_hours: default_job_retention_hours(),
+            custom_ca_cert_path: None,
+            email_preference: EmailPreference::default(),
+            user_agent_suffix: None,
+            skill_dictionary: Vec::new(),
+            disable_ocr: false,
+            status_write_interval_ms: default_status_write_interval_ms(),
+            preserve_text_columns: false,
+            spreadsheet_title_template: default_spreadsheet_title_template(),
+            color_by_confidence: false,
+            merge_ocr_fields: false,
+            max_queued_jobs: default_max_queued_jobs(),
+            debug_field_sources: false,
+            required_fields: Vec::new(),
+            confidence_as_percent: false,
+            confidence_weights: ConfidenceWeights::default(),
+            name_denylist: Vec::new(),
+            keep_ocr_temp: false,
+            retry_on: RetryPolicy::default(),
+            cleanup_interval_minutes: default_cleanup_interval_minutes(),
+            notice_period_column: false,
+            compact_results: false,
+            sheet_append_page_size: None,
+            missing_value_placeholder: String::new(),
+            unsupported_file_policy: UnsupportedFilePolicy::default(),
+            adaptive_concurrency: false,
+            custom_fields: Vec::new(),
+            sort_order: SortOrder::default(),
+            job_retry_budget: None,
+            sheet_append_delay_ms: 0,
+            deobfuscate_emails: default_deobfuscate_emails(),
+            education_column: false,
+            extra_headers: HashMap::new(),
+            require_international_phone: false,
+            national_phone_policy: NationalPhonePolicy::default(),
+            email_domain_column: false,
         }
     }
 }
@@ -235,12 +1094,52 @@ pub struct RuntimeSettingsView {
     pub google_client_id: String,
     pub google_client_secret_configured: bool,
     pub legacy_secret_scrubbed: bool,
+    /// A saved setting only takes effect after the app restarts.
+    pub restart_required: bool,
     pub tesseract_path: String,
+    pub tesseract_version: Option<String>,
     pub max_concurrent_requests: usize,
+    pub max_concurrent_downloads: Option<usize>,
+    pub max_concurrent_parses: Option<usize>,
     pub spreadsheet_batch_size: usize,
     pub max_retries: usize,
     pub retry_delay_seconds: f64,
     pub job_retention_hours: i64,
+    pub custom_ca_cert_path: Option<String>,
+    pub email_preference: EmailPreference,
+    pub user_agent_suffix: Option<String>,
+    pub skill_dictionary: Vec<String>,
+    pub disable_ocr: bool,
+    pub status_write_interval_ms: u64,
+    pub preserve_text_columns: bool,
+    pub spreadsheet_title_template: String,
+    pub color_by_confidence: bool,
+    pub merge_ocr_fields: bool,
+    pub max_queued_jobs: usize,
+    pub debug_field_sources: bool,
+    pub required_fields: Vec<FieldKind>,
+    pub confidence_as_percent: bool,
+    pub confidence_weights: ConfidenceWeights,
+    pub name_denylist: Vec<String>,
+    pub keep_ocr_temp: bool,
+    pub retry_on: RetryPolicy,
+    pub cleanup_interval_minutes: u64,
+    pub notice_period_column: bool,
+    pub compact_results: bool,
+    pub sheet_append_page_size: Option<usize>,
+    pub missing_value_placeholder: String,
+    pub unsupported_file_policy: UnsupportedFilePolicy,
+    pub adaptive_concurrency: bool,
+    pub custom_fields: Vec<CustomFieldRule>,
+    pub sort_order: SortOrder,
+    pub job_retry_budget: Option<usize>,
+    pub sheet_append_delay_ms: u64,
+    pub deobfuscate_emails: bool,
+    pub education_column: bool,
+    pub extra_headers: HashMap<String, String>,
+    pub require_international_phone: bool,
+    pub national_phone_policy: NationalPhonePolicy,
+    pub email_domain_column: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -252,10 +1151,125 @@ pub struct RuntimeSettingsUpdate {
     pub google_client_secret: Option<String>,
     pub tesseract_path: String,
     pub max_concurrent_requests: usize,
+    /// Unset stage limits fall back to `max_concurrent_requests`.
+    #[serde(default)]
+    pub max_concurrent_downloads: Option<usize>,
+    #[serde(default)]
+    pub max_concurrent_parses: Option<usize>,
     pub spreadsheet_batch_size: usize,
     pub max_retries: usize,
     pub retry_delay_seconds: f64,
     pub job_retention_hours: i64,
+    /// `None` keeps the current value; an empty string clears it.
+    #[serde(default)]
+    pub custom_ca_cert_path: Option<String>,
+    /// `None` keeps the current value.
+    #[serde(default)]
+    pub email_preference: Option<EmailPreference>,
+    /// `None` keeps the current value; an empty string clears it.
+    #[serde(default)]
+    pub user_agent_suffix: Option<String>,
+    /// `None` keeps the current dictionary; an empty list clears it.
+    #[serde(default)]
+    pub skill_dictionary: Option<Vec<String>>,
+    /// `None` keeps the current value.
+    #[serde(default)]
+    pub disable_ocr: Option<bool>,
+    /// `None` keeps the current value; `0` writes every status update.
+    #[serde(default)]
+    pub status_write_interval_ms: Option<u64>,
+    /// `None` keeps the current value.
+    #[serde(default)]
+    pub preserve_text_columns: Option<bool>,
+    /// `None` keeps the current template; blank restores the default.
+    #[serde(default)]
+    pub spreadsheet_title_template: Option<String>,
+    /// `None` keeps the current value.
+    #[serde(default)]
+    pub color_by_confidence: Option<bool>,
+    /// `None` keeps the current value.
+    #[serde(default)]
+    pub merge_ocr_fields: Option<bool>,
+    /// `None` keeps the current value. Applies on next launch.
+    #[serde(default)]
+    pub max_queued_jobs: Option<usize>,
+    /// `None` keeps the current value.
+    #[serde(default)]
+    pub debug_field_sources: Option<bool>,
+    /// Rows with none of these fields are left out of the sheet. Empty keeps every non-empty row.
+    #[serde(default)]
+    pub required_fields: Option<Vec<FieldKind>>,
+    /// Write the Confidence column as `85%` instead of `0.85`; new sheets get the column even
+    /// without `color_by_confidence`.
+    #[serde(default)]
+    pub confidence_as_percent: Option<bool>,
+    /// Points each found field adds to confidence. Use `rescore_job` to apply them to old jobs.
+    #[serde(default)]
+    pub confidence_weights: Option<ConfidenceWeights>,
+    /// Extra lines never taken as a name. `None` keeps the current list; an empty list clears it.
+    #[serde(default)]
+    pub name_denylist: Option<Vec<String>>,
+    /// Copies each OCR input and its output under the logs folder, where the periodic cleanup
+    /// deletes them once they outlive `job_retention_hours`. `None` keeps the current value.
+    #[serde(default)]
+    pub keep_ocr_temp: Option<bool>,
+    /// Which failure classes are retried. `None` keeps the current policy.
+    #[serde(default)]
+    pub retry_on: Option<RetryPolicy>,
+    /// Minutes between background sweeps of expired jobs; `0` turns the sweep off. `None` keeps the current value.
+    #[serde(default)]
+    pub cleanup_interval_minutes: Option<u64>,
+    /// Adds a Notice Period column to new sheets. `None` keeps the current value.
+    #[serde(default)]
+    pub notice_period_column: Option<bool>,
+    /// Writes `results.json` without indentation. `None` keeps the current value.
+    #[serde(default)]
+    pub compact_results: Option<bool>,
+    /// Most rows sent in one Sheets write. `None` writes each batch in a single request.
+    #[serde(default)]
+    pub sheet_append_page_size: Option<usize>,
+    /// Text written for fields a resume lacks. `None` keeps the current value; an empty string leaves the cell blank.
+    #[serde(default)]
+    pub missing_value_placeholder: Option<String>,
+    /// What batches do with files that are not PDF, DOCX, or ODT. `None` keeps the current value.
+    #[serde(default)]
+    pub unsupported_file_policy: Option<UnsupportedFilePolicy>,
+    /// Tunes download concurrency from Drive's rate limiting. `None` keeps the current value.
+    #[serde(default)]
+    pub adaptive_concurrency: Option<bool>,
+    /// Regex rules for extra fields; validated on save. `None` keeps the current rules.
+    #[serde(default)]
+    pub custom_fields: Option<Vec<CustomFieldRule>>,
+    /// Order of candidates in the sheet and results. `None` keeps the current value.
+    #[serde(default)]
+    pub sort_order: Option<SortOrder>,
+    /// Most download retries one job may spend across all its files. `Some(0)` turns retries
+    /// off and `None` means no limit.
+    #[serde(default)]
+    pub job_retry_budget: Option<usize>,
+    /// `None` keeps the current value. Paces job writes to Sheets ahead of the per-minute quota;
+    /// retries still handle any 429 that gets through. Adaptive concurrency only throttles Drive
+    /// downloads, so the two work independently.
+    #[serde(default)]
+    pub sheet_append_delay_ms: Option<u64>,
+    /// `None` keeps the current value.
+    #[serde(default)]
+    pub deobfuscate_emails: Option<bool>,
+    /// `None` keeps the current value.
+    #[serde(default)]
+    pub education_column: Option<bool>,
+    /// `None` keeps the current headers; an empty map clears them.
+    #[serde(default)]
+    pub extra_headers: Option<HashMap<String, String>>,
+    /// `None` keeps the current value.
+    #[serde(default)]
+    pub require_international_phone: Option<bool>,
+    /// `None` keeps the current value.
+    #[serde(default)]
+    pub national_phone_policy: Option<NationalPhonePolicy>,
+    /// Adds an Email Domain column to new sheets. `None` keeps the current value.
+    #[serde(default)]
+    pub email_domain_column: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -264,12 +1278,29 @@ pub struct ResumeExtractionResult {
     pub name: Option<String>,
     pub email: Option<String>,
     pub phone: Option<String>,
+    #[serde(default)]
+    pub phone_extension: Option<String>,
     pub linked_in: Option<String>,
     pub git_hub: Option<String>,
+    #[serde(default)]
+    pub skills: Vec<String>,
+    #[serde(default)]
+    pub headline: Option<String>,
+    /// Notice period or availability, e.g. "30 days" or "Immediate".
+    #[serde(default)]
+    pub notice_period: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field_sources: Option<HashMap<String, String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name_candidates: Option<Vec<String>>,
+    #[serde(default)]
+    pub custom: HashMap<String, String>,
+    #[serde(default)]
+    pub education: Option<String>,
     pub confidence: f64,
     pub ocr_used: bool,
     #[serde(default)]
-    pub errors: Vec<String>,
+    pub errors: Vec<ParseError>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -278,6 +1309,106 @@ pub struct CommandOk {
     pub ok: bool,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderPreviewResponse {
+    pub file_count: usize,
+    pub files: Vec<DriveFileRef>,
+}
+
+/// Rough, pre-run estimate for a batch over one folder. Every number here is approximate.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchEstimate {
+    pub file_count: usize,
+    /// File count keyed by lowercase extension, e.g. `pdf`.
+    pub files_by_type: BTreeMap<String, usize>,
+    /// PDFs large enough that they are probably scans and will need OCR.
+    pub likely_scanned_pdfs: usize,
+    pub estimated_min_seconds: u64,
+    pub estimated_max_seconds: u64,
+    /// Human-readable caveat shown next to the estimate.
+    pub note: String,
+}
+
+/// Extraction quality measured on a random sample of a folder's files. Every figure after
+/// `sampled_files` covers the sample only.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderSample {
+    pub total_files: usize,
+    pub sampled_files: usize,
+    /// Sampled files that could not be downloaded or parsed.
+    pub failed_files: usize,
+    pub average_confidence: f64,
+    /// Candidates per sheet color band: at least 0.8, at least 0.5, and below 0.5.
+    pub high_confidence: usize,
+    pub medium_confidence: usize,
+    pub low_confidence: usize,
+    pub field_hit_rates: Vec<FieldHitRate>,
+}
+
+/// Totals over the results of every stored job, for a lifetime dashboard.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AggregateStats {
+    pub job_count: usize,
+    /// Jobs whose results could not be read; every other figure leaves them out.
+    pub unreadable_jobs: usize,
+    /// Parsed results: one per file, or one per resume in a split file.
+    pub total_files: usize,
+    /// Results with an error that is not just a warning.
+    pub failed_files: usize,
+    /// Share of results whose text came from OCR, from 0.0 to 1.0.
+    pub ocr_rate: f64,
+    pub average_confidence: f64,
+    pub field_hit_rates: Vec<FieldHitRate>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldHitRate {
+    pub field: FieldKind,
+    /// Share of sampled candidates with the field found, from 0.0 to 1.0.
+    pub rate: f64,
+}
+
+/// Outcome of `abort_and_delete_job`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobDeletion {
+    pub job_id: String,
+    /// Whether the job was still pending, running, or paused and had to be stopped first.
+    pub cancelled: bool,
+    /// Whether the job's directory was removed; `false` when it was already gone.
+    pub deleted: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueStatus {
+    /// Jobs accepted by `start_batch_job` that the worker has not picked up yet.
+    pub queued_jobs: usize,
+    pub running_jobs: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileTypeInfo {
+    /// Lowercase extension without the dot, e.g. `pdf`.
+    pub extension: String,
+    pub mime_type: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppPaths {
+    pub data_root: String,
+    pub settings_file: String,
+    pub jobs_root: String,
+    pub logs_dir: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct StartJobResponse {
@@ -424,3 +1555,35 @@ fn default_retry_delay_seconds() -> f64 {
 fn default_job_retention_hours() -> i64 {
     24
 }
+
+pub fn default_spreadsheet_title_template() -> String {
+    "Resume Parse Results - {date} {time}".to_string()
+}
+
+fn default_status_write_interval_ms() -> u64 {
+    1000
+}
+
+fn default_deobfuscate_emails() -> bool {
+    true
+}
+
+fn default_max_queued_jobs() -> usize {
+    100
+}
+
+fn default_cleanup_interval_minutes() -> u64 {
+    60
+}
+
+/// Trims entries and drops blanks and case-insensitive duplicates, keeping the first spelling.
+pub fn sanitize_skill_dictionary(skills: Vec<String>) -> Vec<String> {
+    let mut sanitized: Vec<String> = Vec::new();
+    for skill in skills {
+        let skill = skill.trim();
+        if !skill.is_empty() && !sanitized.iter().any(|v| v.eq_ignore_ascii_case(skill)) {
+            sanitized.push(skill.to_string());
+        }
+    }
+    sanitized
+}
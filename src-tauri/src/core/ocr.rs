@@ -1,15 +1,20 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use std::time::Duration;
 
 use anyhow::Context;
 use tokio::process::Command;
 use tokio::time::timeout;
+use uuid::Uuid;
+
+const VERSION_PROBE_TIMEOUT: Duration = Duration::from_secs(10);
 
 #[derive(Clone)]
 pub struct TesseractCliOcrService {
     pub tesseract_executable_path: String,
     pub timeout: Duration,
+    /// When set, every OCR input and its output are copied into a fresh subfolder here.
+    pub kept_files_dir: Option<PathBuf>,
 }
 
 impl TesseractCliOcrService {
@@ -17,9 +22,41 @@ impl TesseractCliOcrService {
         Self {
             tesseract_executable_path,
             timeout,
+            kept_files_dir: None,
         }
     }
 
+    pub fn with_kept_files_dir(mut self, kept_files_dir: Option<PathBuf>) -> Self {
+        self.kept_files_dir = kept_files_dir;
+        self
+    }
+
+    /// Runs `tesseract --version` and returns the first line, e.g. `tesseract 5.3.4`.
+    pub async fn detect_version(tesseract_executable_path: &str) -> anyhow::Result<String> {
+        let mut command = Command::new(tesseract_executable_path);
+        command
+            .arg("--version")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+
+        let output = match timeout(VERSION_PROBE_TIMEOUT, command.output()).await {
+            Ok(result) => result.with_context(|| {
+                format!("failed to run tesseract at {tesseract_executable_path}")
+            })?,
+            Err(_) => anyhow::bail!("tesseract --version timed out"),
+        };
+
+        if !output.status.success() {
+            anyhow::bail!("tesseract --version exited with status {}", output.status);
+        }
+
+        // Older releases print the banner to stderr instead of stdout.
+        parse_version_banner(&String::from_utf8_lossy(&output.stdout))
+            .or_else(|| parse_version_banner(&String::from_utf8_lossy(&output.stderr)))
+            .ok_or_else(|| anyhow::anyhow!("tesseract --version returned no output"))
+    }
+
     pub async fn extract_text(&self, pdf_bytes: &[u8]) -> anyhow::Result<String> {
         let temp_dir = tempfile::Builder::new()
             .prefix("sourcestack-ocr-")
@@ -39,15 +76,169 @@ impl TesseractCliOcrService {
             .stderr(Stdio::piped())
             .kill_on_drop(true);
 
-        let output = match timeout(self.timeout, command.output()).await {
-            Ok(result) => result?,
-            Err(_) => return Ok(String::new()),
+        let text = match timeout(self.timeout, command.output()).await {
+            Ok(result) => {
+                let output = result?;
+                if output.status.success() {
+                    String::from_utf8_lossy(&output.stdout).to_string()
+                } else {
+                    String::new()
+                }
+            }
+            Err(_) => String::new(),
         };
 
-        if !output.status.success() {
-            return Ok(String::new());
+        if let Some(kept_files_dir) = &self.kept_files_dir {
+            if let Err(err) = keep_ocr_files(kept_files_dir, &input_path, &text).await {
+                eprintln!("ocr: failed to keep OCR files: {err:#}");
+            }
         }
 
-        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+        Ok(text)
+    }
+}
+
+/// Copies the OCR input and output into a new uniquely named folder under `dir`, so
+/// concurrent parses never overwrite each other's files.
+async fn keep_ocr_files(dir: &Path, input_path: &Path, text: &str) -> anyhow::Result<PathBuf> {
+    let target = dir.join(format!(
+        "{}-{}",
+        chrono::Utc::now().format("%Y%m%dT%H%M%S"),
+        Uuid::new_v4()
+    ));
+    tokio::fs::create_dir_all(&target)
+        .await
+        .with_context(|| format!("failed to create {}", target.display()))?;
+    tokio::fs::copy(input_path, target.join("resume.pdf")).await?;
+    tokio::fs::write(target.join("ocr.txt"), text).await?;
+    Ok(target)
+}
+
+/// Removes kept OCR folders under `dir` that were last modified more than `max_age` ago,
+/// returning how many were deleted. A missing `dir` means nothing was ever kept.
+pub async fn remove_kept_ocr_files_older_than(
+    dir: &Path,
+    max_age: Duration,
+) -> anyhow::Result<usize> {
+    let mut entries = match tokio::fs::read_dir(dir).await {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(err) => {
+            return Err(err).with_context(|| format!("failed to read {}", dir.display()));
+        }
+    };
+
+    let mut removed = 0;
+    while let Some(entry) = entries.next_entry().await? {
+        let metadata = entry.metadata().await?;
+        let expired = metadata
+            .modified()?
+            .elapsed()
+            .is_ok_and(|age| age > max_age);
+        if !expired {
+            continue;
+        }
+        let path = entry.path();
+        if metadata.is_dir() {
+            tokio::fs::remove_dir_all(&path).await
+        } else {
+            tokio::fs::remove_file(&path).await
+        }
+        .with_context(|| format!("failed to remove {}", path.display()))?;
+        removed += 1;
+    }
+    Ok(removed)
+}
+
+fn parse_version_banner(output: &str) -> Option<String> {
+    output
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn kept_ocr_folders_are_removed_once_they_pass_max_age() {
+        let root = tempfile::tempdir().unwrap();
+        let input = root.path().join("input.pdf");
+        std::fs::write(&input, b"%PDF").unwrap();
+        let kept_dir = root.path().join("ocr-files");
+        let old = keep_ocr_files(&kept_dir, &input, "old").await.unwrap();
+        let fresh = keep_ocr_files(&kept_dir, &input, "fresh").await.unwrap();
+
+        let two_days_ago = std::time::SystemTime::now() - Duration::from_secs(48 * 60 * 60);
+        std::fs::File::open(&old)
+            .unwrap()
+            .set_modified(two_days_ago)
+            .unwrap();
+
+        let removed =
+            remove_kept_ocr_files_older_than(&kept_dir, Duration::from_secs(24 * 60 * 60))
+                .await
+                .unwrap();
+
+        assert_eq!(removed, 1);
+        assert!(!old.exists());
+        assert!(fresh.join("ocr.txt").exists());
+        assert_eq!(
+            remove_kept_ocr_files_older_than(&root.path().join("missing"), Duration::ZERO)
+                .await
+                .unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn parse_version_banner_uses_first_non_empty_line() {
+        assert_eq!(
+            parse_version_banner("\ntesseract 5.3.4\n leptonica-1.84.1\n"),
+            Some("tesseract 5.3.4".to_string())
+        );
+        assert_eq!(parse_version_banner("  \n"), None);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn kept_files_land_in_separate_folders_per_run() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let tesseract = temp_dir.path().join("fake-tesseract");
+        std::fs::write(&tesseract, "#!/bin/sh\necho 'Jane Doe'\n").unwrap();
+        std::fs::set_permissions(&tesseract, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let kept = temp_dir.path().join("kept");
+
+        let ocr =
+            TesseractCliOcrService::new(tesseract.display().to_string(), Duration::from_secs(10))
+                .with_kept_files_dir(Some(kept.clone()));
+        assert_eq!(
+            ocr.extract_text(b"%PDF-1 first").await.unwrap().trim(),
+            "Jane Doe"
+        );
+        ocr.extract_text(b"%PDF-1 second").await.unwrap();
+
+        let mut runs: Vec<PathBuf> = std::fs::read_dir(&kept)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        runs.sort();
+        assert_eq!(runs.len(), 2);
+        let inputs: Vec<Vec<u8>> = runs
+            .iter()
+            .map(|run| std::fs::read(run.join("resume.pdf")).unwrap())
+            .collect();
+        assert!(inputs.contains(&b"%PDF-1 first".to_vec()));
+        assert!(inputs.contains(&b"%PDF-1 second".to_vec()));
+        assert_eq!(
+            std::fs::read_to_string(runs[0].join("ocr.txt"))
+                .unwrap()
+                .trim(),
+            "Jane Doe"
+        );
     }
 }
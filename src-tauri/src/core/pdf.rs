@@ -10,29 +10,82 @@ use regex::Regex;
 use tokio::process::Command;
 use tokio::time::timeout;
 
+use super::field_extractor;
+use super::models::{ParseError, ParseErrorKind};
 use super::ocr::TesseractCliOcrService;
 
 static URL_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"https?://[^\s<>'"\)]+"#).unwrap());
+/// `/Author` or `/Title` in an uncompressed Info dictionary, as a literal or hex string.
+static INFO_ENTRY_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"/(Author|Title)\s*(?:\(((?:\\.|[^\\)])*)\)|<([0-9A-Fa-f\s]*)>)").unwrap()
+});
+static XMP_PACKET_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)<x:xmpmeta.*?</x:xmpmeta>").unwrap());
+static XMP_CREATOR_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)<dc:creator>.*?<rdf:li[^>]*>([^<]+)</rdf:li>").unwrap());
 const PDF_EXTRACT_HELPER_FLAG: &str = "--source-stack-pdf-extract-helper";
 const PDF_EXTRACT_TIMEOUT: Duration = Duration::from_secs(30);
+/// How far down a form's field tree to look; real forms nest a level or two.
+const MAX_FORM_FIELD_DEPTH: usize = 8;
 
 pub struct PdfTextExtractor {
     ocr_service: TesseractCliOcrService,
+    ocr_disabled: bool,
+}
+
+pub struct PdfTextExtraction {
+    pub text: String,
+    pub ocr_used: bool,
+    /// Why the text may be incomplete, e.g. OCR was needed but is disabled.
+    pub warnings: Vec<ParseError>,
 }
 
 impl PdfTextExtractor {
     pub fn new(ocr_service: TesseractCliOcrService) -> Self {
-        Self { ocr_service }
+        Self {
+            ocr_service,
+            ocr_disabled: false,
+        }
     }
 
+    /// Skips the tesseract fallback entirely, for machines without OCR installed.
+    pub fn with_ocr_disabled(mut self, ocr_disabled: bool) -> Self {
+        self.ocr_disabled = ocr_disabled;
+        self
+    }
+
+    /// Text layer first, then OCR, plus any filled-in form field values. When all of that
+    /// comes back empty, the document's metadata is used as a last resort so an image-only
+    /// PDF can still yield a name or email.
     pub async fn extract_text_with_ocr_fallback(
         &self,
         data: &[u8],
-    ) -> anyhow::Result<(String, bool)> {
-        let mut ocr_used = false;
+    ) -> anyhow::Result<PdfTextExtraction> {
+        let mut extraction = self.extract_text_or_ocr(data).await?;
+        let form_text = extract_form_field_text(data);
+        if !form_text.is_empty() {
+            if !extraction.text.trim().is_empty() {
+                extraction.text.push('\n');
+            }
+            extraction.text.push_str(&form_text);
+        }
 
-        let extraction = self.extract_pdf_text(data).await;
-        let text = match extraction {
+        if extraction.text.trim().is_empty() {
+            let metadata = extract_metadata_text(data);
+            if !metadata.is_empty() {
+                extraction.text = metadata;
+                extraction.warnings.push(ParseError::new(
+                    ParseErrorKind::MetadataFallback,
+                    "Warning: no readable text; fields taken from PDF metadata",
+                ));
+            }
+        }
+
+        Ok(extraction)
+    }
+
+    async fn extract_text_or_ocr(&self, data: &[u8]) -> anyhow::Result<PdfTextExtraction> {
+        let extracted = match self.extract_pdf_text(data).await {
             Ok(mut text) => {
                 let links = extract_hyperlinks(data);
                 if !links.is_empty() {
@@ -40,20 +93,52 @@ impl PdfTextExtractor {
                     text.push_str(&links.join("\n"));
                 }
 
-                if text.trim().len() < 50 {
-                    ocr_used = true;
-                    self.ocr_service.extract_text(data).await?
-                } else {
-                    text
+                if text.trim().len() >= 50 {
+                    return Ok(PdfTextExtraction {
+                        text,
+                        ocr_used: false,
+                        warnings: Vec::new(),
+                    });
                 }
+                Ok(text)
             }
-            Err(_) => {
-                ocr_used = true;
-                self.ocr_service.extract_text(data).await?
-            }
+            Err(err) => Err(err),
         };
 
-        Ok((text, ocr_used))
+        if self.ocr_disabled {
+            let (text, warning) = match extracted {
+                Ok(text) => (
+                    text,
+                    "Warning: OCR disabled; PDF has little or no extractable text".to_string(),
+                ),
+                Err(err) => (
+                    String::new(),
+                    format!("Warning: OCR disabled; PDF text extraction failed: {err}"),
+                ),
+            };
+            let warning = ParseError::new(ParseErrorKind::OcrDisabled, warning);
+            return Ok(PdfTextExtraction {
+                text,
+                ocr_used: false,
+                warnings: vec![warning],
+            });
+        }
+
+        Ok(PdfTextExtraction {
+            text: self.ocr_service.extract_text(data).await?,
+            ocr_used: true,
+            warnings: Vec::new(),
+        })
+    }
+
+    /// Runs OCR regardless of how much text the PDF carries, for merging with a text pass.
+    /// Returns `None` when OCR is disabled.
+    pub async fn extract_ocr_text(&self, data: &[u8]) -> anyhow::Result<Option<String>> {
+        if self.ocr_disabled {
+            return Ok(None);
+        }
+
+        Ok(Some(self.ocr_service.extract_text(data).await?))
     }
 
     async fn extract_pdf_text(&self, data: &[u8]) -> anyhow::Result<String> {
@@ -96,11 +181,12 @@ impl PdfTextExtractor {
     }
 }
 
+/// Link annotation targets in page order, then any other URL found in the raw bytes.
 fn extract_hyperlinks(data: &[u8]) -> Vec<String> {
     let raw = String::from_utf8_lossy(data);
+    let raw_links = URL_RE.find_iter(&raw).map(|m| m.as_str().to_string());
     let mut links: Vec<String> = Vec::new();
-    for m in URL_RE.find_iter(&raw) {
-        let value = m.as_str().to_string();
+    for value in extract_annotation_links(data).into_iter().chain(raw_links) {
         if !links
             .iter()
             .any(|existing: &String| existing.eq_ignore_ascii_case(&value))
@@ -112,6 +198,205 @@ fn extract_hyperlinks(data: &[u8]) -> Vec<String> {
     links
 }
 
+/// `/URI` actions of each page's annotations. Parsing the document reaches annotations kept
+/// in compressed object streams or written as hex strings, which the raw scan misses. PDFs
+/// that fail to parse yield nothing.
+fn extract_annotation_links(data: &[u8]) -> Vec<String> {
+    let Ok(document) = lopdf::Document::load_mem(data) else {
+        return Vec::new();
+    };
+    let resolve = |object| document.dereference(object).map(|(_, object)| object);
+
+    let mut links = Vec::new();
+    for page_id in document.get_pages().into_values() {
+        let annotations = document
+            .get_dictionary(page_id)
+            .and_then(|page| page.get(b"Annots"))
+            .and_then(resolve)
+            .and_then(lopdf::Object::as_array);
+        let Ok(annotations) = annotations else {
+            continue;
+        };
+
+        for annotation in annotations {
+            let uri = resolve(annotation)
+                .and_then(lopdf::Object::as_dict)
+                .and_then(|annotation| annotation.get(b"A"))
+                .and_then(resolve)
+                .and_then(lopdf::Object::as_dict)
+                .and_then(|action| action.get(b"URI"))
+                .and_then(resolve)
+                .and_then(lopdf::Object::as_str);
+            let Ok(uri) = uri else {
+                continue;
+            };
+
+            let uri = String::from_utf8_lossy(uri).trim().to_string();
+            let lower = uri.to_ascii_lowercase();
+            if ["http://", "https://", "mailto:"]
+                .iter()
+                .any(|scheme| lower.starts_with(scheme))
+            {
+                links.push(uri);
+            }
+        }
+    }
+
+    links
+}
+
+/// Values of the filled-in text fields of a PDF form (AcroForm), one per line. Application
+/// forms keep their data there rather than in the page content, so the text layer misses
+/// it. Fields named like a name go first so name guessing sees them before e.g. a job title.
+/// PDFs that fail to parse or have no form yield nothing.
+fn extract_form_field_text(data: &[u8]) -> String {
+    let Ok(document) = lopdf::Document::load_mem(data) else {
+        return String::new();
+    };
+    let fields = document
+        .catalog()
+        .and_then(|catalog| catalog.get(b"AcroForm"))
+        .and_then(|form| document.dereference(form).map(|(_, form)| form))
+        .and_then(lopdf::Object::as_dict)
+        .and_then(|form| form.get(b"Fields"))
+        .and_then(|fields| document.dereference(fields).map(|(_, fields)| fields))
+        .and_then(lopdf::Object::as_array);
+    let Ok(fields) = fields else {
+        return String::new();
+    };
+
+    let mut entries = Vec::new();
+    collect_form_fields(&document, fields, "", 0, &mut entries);
+    entries.sort_by_key(|(name, _)| !name.to_ascii_lowercase().contains("name"));
+
+    let mut values: Vec<String> = Vec::new();
+    for (_, value) in entries {
+        if !values.contains(&value) {
+            values.push(value);
+        }
+    }
+    values.join("\n")
+}
+
+/// Walks a field tree depth-first, collecting `(name, value)` for fields holding a text
+/// value. Kids without their own name are widgets and take their parent's.
+fn collect_form_fields(
+    document: &lopdf::Document,
+    fields: &[lopdf::Object],
+    parent_name: &str,
+    depth: usize,
+    entries: &mut Vec<(String, String)>,
+) {
+    if depth > MAX_FORM_FIELD_DEPTH {
+        return;
+    }
+
+    for field in fields {
+        let Ok(field) = document
+            .dereference(field)
+            .and_then(|(_, field)| field.as_dict())
+        else {
+            continue;
+        };
+        let name = field
+            .get(b"T")
+            .and_then(lopdf::decode_text_string)
+            .unwrap_or_else(|_| parent_name.to_string());
+
+        // Checkboxes and radio buttons hold names like `/Yes`, which are not text.
+        let value = field
+            .get(b"V")
+            .and_then(|value| document.dereference(value).map(|(_, value)| value))
+            .and_then(lopdf::decode_text_string);
+        if let Ok(value) = value {
+            let value = value.trim().to_string();
+            if !value.is_empty() {
+                entries.push((name.clone(), value));
+            }
+        }
+
+        if let Ok(kids) = field.get(b"Kids").and_then(lopdf::Object::as_array) {
+            collect_form_fields(document, kids, &name, depth + 1, entries);
+        }
+    }
+}
+
+/// Author, title and XMP creator/email values, one per line, read from the raw bytes.
+/// Metadata inside compressed object streams is not seen.
+fn extract_metadata_text(data: &[u8]) -> String {
+    let raw = String::from_utf8_lossy(data);
+    let mut values: Vec<String> = Vec::new();
+    let mut push = |value: String| {
+        let value = value.trim().to_string();
+        if !value.is_empty() && !values.iter().any(|existing| existing == &value) {
+            values.push(value);
+        }
+    };
+
+    // Author goes first so name guessing sees it before a title like "Resume".
+    let mut entries = INFO_ENTRY_RE.captures_iter(&raw).collect::<Vec<_>>();
+    entries.sort_by_key(|entry| &entry[1] != "Author");
+    for entry in entries {
+        let value = match (entry.get(2), entry.get(3)) {
+            (Some(literal), _) => decode_pdf_literal(literal.as_str()),
+            (None, Some(hex)) => decode_pdf_hex(hex.as_str()),
+            (None, None) => continue,
+        };
+        push(value);
+    }
+
+    for packet in XMP_PACKET_RE.find_iter(&raw) {
+        for creator in XMP_CREATOR_RE.captures_iter(packet.as_str()) {
+            push(creator[1].to_string());
+        }
+        for email in field_extractor::extract_emails(packet.as_str()) {
+            push(email);
+        }
+    }
+
+    values.join("\n")
+}
+
+fn decode_pdf_literal(value: &str) -> String {
+    let mut decoded = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            decoded.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => decoded.push('\n'),
+            Some('r') => decoded.push('\r'),
+            Some('t') => decoded.push('\t'),
+            Some(other) => decoded.push(other),
+            None => {}
+        }
+    }
+    decoded
+}
+
+/// Hex strings are UTF-16BE when they start with a byte-order mark, otherwise bytes.
+fn decode_pdf_hex(value: &str) -> String {
+    let digits = value
+        .chars()
+        .filter(|ch| !ch.is_whitespace())
+        .collect::<Vec<_>>();
+    let bytes = digits
+        .chunks(2)
+        .filter_map(|pair| u8::from_str_radix(&pair.iter().collect::<String>(), 16).ok())
+        .collect::<Vec<u8>>();
+
+    if let Some(utf16) = bytes.strip_prefix(&[0xFE, 0xFF]) {
+        let units = utf16
+            .chunks_exact(2)
+            .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+            .collect::<Vec<_>>();
+        return String::from_utf16_lossy(&units);
+    }
+    String::from_utf8_lossy(&bytes).to_string()
+}
+
 pub fn maybe_run_pdf_extract_helper_from_args() -> anyhow::Result<bool> {
     let mut args = std::env::args_os();
     let _binary = args.next();
@@ -149,10 +434,214 @@ pub fn maybe_run_pdf_extract_helper_from_args() -> anyhow::Result<bool> {
 
 #[cfg(test)]
 mod tests {
-    use super::PDF_EXTRACT_HELPER_FLAG;
+    use super::*;
+
+    #[test]
+    fn hyperlinks_include_link_annotations_the_raw_scan_misses() {
+        use lopdf::{dictionary, Document, Object, StringFormat};
+
+        let mut document = Document::with_version("1.5");
+        let pages_id = document.new_object_id();
+        let uri_action = |uri: &str| {
+            dictionary! {
+                "S" => "URI",
+                // Hex strings keep the URL out of the raw bytes.
+                "URI" => Object::String(uri.as_bytes().to_vec(), StringFormat::Hexadecimal),
+            }
+        };
+        let link = document.add_object(dictionary! {
+            "Type" => "Annot",
+            "Subtype" => "Link",
+            "Rect" => vec![0.into(), 0.into(), 100.into(), 20.into()],
+            "A" => uri_action("https://www.linkedin.com/in/jane-doe"),
+        });
+        let page_one = document.add_object(dictionary! {
+            "Type" => "Page",
+            "Parent" => pages_id,
+            "Annots" => vec![link.into()],
+        });
+        let page_two = document.add_object(dictionary! {
+            "Type" => "Page",
+            "Parent" => pages_id,
+            "Annots" => vec![Object::Dictionary(dictionary! {
+                "Type" => "Annot",
+                "Subtype" => "Link",
+                "A" => uri_action("https://github.com/jane-doe"),
+            })],
+        });
+        document.objects.insert(
+            pages_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Kids" => vec![page_one.into(), page_two.into()],
+                "Count" => 2,
+                "MediaBox" => vec![0.into(), 0.into(), 595.into(), 842.into()],
+            }),
+        );
+        let catalog_id = document.add_object(dictionary! {
+            "Type" => "Catalog",
+            "Pages" => pages_id,
+        });
+        document.trailer.set("Root", catalog_id);
+
+        let mut data = Vec::new();
+        document.save_to(&mut data).unwrap();
+        assert!(!String::from_utf8_lossy(&data).contains("linkedin.com"));
+
+        assert_eq!(
+            extract_hyperlinks(&data),
+            vec![
+                "https://www.linkedin.com/in/jane-doe".to_string(),
+                "https://github.com/jane-doe".to_string(),
+            ]
+        );
+        assert!(extract_hyperlinks(b"%PDF-1.4 not really a pdf").is_empty());
+    }
+
+    #[tokio::test]
+    async fn form_field_values_are_added_to_the_text() {
+        use lopdf::{dictionary, text_string, Document, Object};
+
+        let mut document = Document::with_version("1.5");
+        let pages_id = document.new_object_id();
+        let page = document.add_object(dictionary! {
+            "Type" => "Page",
+            "Parent" => pages_id,
+        });
+        document.objects.insert(
+            pages_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Kids" => vec![page.into()],
+                "Count" => 1,
+                "MediaBox" => vec![0.into(), 0.into(), 595.into(), 842.into()],
+            }),
+        );
+        let role = document.add_object(dictionary! {
+            "FT" => "Tx",
+            "T" => text_string("Position"),
+            "V" => text_string("Software Engineer"),
+        });
+        let full_name = document.add_object(dictionary! {
+            "FT" => "Tx",
+            "T" => text_string("Full Name"),
+            "V" => text_string("Jane Doe"),
+        });
+        let contact = document.add_object(dictionary! {
+            "T" => text_string("Contact"),
+            "Kids" => vec![Object::Dictionary(dictionary! {
+                "FT" => "Tx",
+                "T" => text_string("Email"),
+                // UTF-16 keeps the address out of the raw bytes.
+                "V" => Object::String(
+                    lopdf::encode_utf16_be("jane.doe@example.com"),
+                    lopdf::StringFormat::Hexadecimal,
+                ),
+            })],
+        });
+        let consent = document.add_object(dictionary! {
+            "FT" => "Btn",
+            "T" => text_string("Consent"),
+            "V" => Object::Name(b"Yes".to_vec()),
+        });
+        let form = document.add_object(dictionary! {
+            "Fields" => vec![role.into(), full_name.into(), contact.into(), consent.into()],
+        });
+        let catalog_id = document.add_object(dictionary! {
+            "Type" => "Catalog",
+            "Pages" => pages_id,
+            "AcroForm" => form,
+        });
+        document.trailer.set("Root", catalog_id);
+
+        let mut data = Vec::new();
+        document.save_to(&mut data).unwrap();
+        assert!(!String::from_utf8_lossy(&data).contains("jane.doe@example.com"));
+
+        assert_eq!(
+            extract_form_field_text(&data),
+            "Jane Doe\nSoftware Engineer\njane.doe@example.com"
+        );
+        assert!(extract_form_field_text(b"%PDF-1.4 not really a pdf").is_empty());
+
+        let ocr = TesseractCliOcrService::new(
+            "/nonexistent/tesseract".to_string(),
+            Duration::from_secs(1),
+        );
+        let extraction = PdfTextExtractor::new(ocr)
+            .with_ocr_disabled(true)
+            .extract_text_with_ocr_fallback(&data)
+            .await
+            .unwrap();
+        assert!(extraction.text.contains("jane.doe@example.com"));
+        assert!(!extraction
+            .warnings
+            .iter()
+            .any(|warning| warning.code == ParseErrorKind::MetadataFallback));
+    }
 
     #[test]
     fn helper_flag_is_stable() {
         assert_eq!(PDF_EXTRACT_HELPER_FLAG, "--source-stack-pdf-extract-helper");
     }
+
+    #[tokio::test]
+    async fn disabled_ocr_never_spawns_tesseract() {
+        // A tesseract path that cannot be spawned makes any OCR attempt an error.
+        let ocr = || {
+            TesseractCliOcrService::new(
+                "/nonexistent/tesseract".to_string(),
+                Duration::from_secs(1),
+            )
+        };
+        let scanned = b"%PDF-1.4 image only";
+
+        let enabled = PdfTextExtractor::new(ocr());
+        assert!(enabled
+            .extract_text_with_ocr_fallback(scanned)
+            .await
+            .is_err());
+
+        let disabled = PdfTextExtractor::new(ocr()).with_ocr_disabled(true);
+        let extraction = disabled
+            .extract_text_with_ocr_fallback(scanned)
+            .await
+            .unwrap();
+        assert!(!extraction.ocr_used);
+        assert!(extraction.text.trim().is_empty());
+        assert_eq!(extraction.warnings[0].code, ParseErrorKind::OcrDisabled);
+        assert!(extraction.warnings[0].message.contains("OCR disabled"));
+    }
+
+    #[tokio::test]
+    async fn metadata_fills_in_when_no_text_is_readable() {
+        let ocr = TesseractCliOcrService::new(
+            "/nonexistent/tesseract".to_string(),
+            Duration::from_secs(1),
+        );
+        let scanned = b"%PDF-1.4\n1 0 obj\n<< /Title (Resume) /Author (Jane Doe \\(jane.doe@example.com\\)) >>\nendobj\ntrailer\n<< /Info 1 0 R >>\n%%EOF";
+
+        let extraction = PdfTextExtractor::new(ocr)
+            .with_ocr_disabled(true)
+            .extract_text_with_ocr_fallback(scanned)
+            .await
+            .unwrap();
+        assert_eq!(extraction.text, "Jane Doe (jane.doe@example.com)\nResume");
+        assert!(extraction
+            .warnings
+            .iter()
+            .any(|warning| warning.code == ParseErrorKind::MetadataFallback));
+    }
+
+    #[test]
+    fn metadata_reads_hex_strings_and_xmp_packets() {
+        let pdf = br#"<< /Author <FEFF004A0061006E0065> >>
+<x:xmpmeta xmlns:x="adobe:ns:meta/"><dc:creator><rdf:Seq><rdf:li>Jane Doe</rdf:li></rdf:Seq></dc:creator>
+<pdf:Keywords>contact: Jane.Doe@Example.com</pdf:Keywords></x:xmpmeta>"#;
+
+        assert_eq!(
+            extract_metadata_text(pdf),
+            "Jane\nJane Doe\njane.doe@example.com"
+        );
+    }
 }
@@ -1,26 +1,21 @@
+use super::credential_store::CredentialStore;
+
 const KEYRING_SERVICE: &str = "com.sourcestack.desktop.google.client_secret";
-const KEYRING_USERNAME: &str = "default";
+const FALLBACK_FILE: &str = "google-client-secret.json";
 
-pub struct GoogleClientSecretStore;
+pub struct GoogleClientSecretStore {
+    store: CredentialStore,
+}
 
 impl GoogleClientSecretStore {
     pub fn new() -> Self {
-        Self
+        Self {
+            store: CredentialStore::new(KEYRING_SERVICE, FALLBACK_FILE),
+        }
     }
 
     pub fn load(&self) -> anyhow::Result<Option<String>> {
-        let entry = keyring::Entry::new(KEYRING_SERVICE, KEYRING_USERNAME)?;
-        let value = match entry.get_password() {
-            Ok(v) => v,
-            Err(keyring::Error::NoEntry) => return Ok(None),
-            Err(err) => return Err(err.into()),
-        };
-
-        if value.trim().is_empty() {
-            return Ok(None);
-        }
-
-        Ok(Some(value))
+        Ok(self.store.load()?.filter(|value| !value.trim().is_empty()))
     }
 
     pub fn save(&self, secret: &str) -> anyhow::Result<()> {
@@ -29,16 +24,10 @@ impl GoogleClientSecretStore {
             return Ok(());
         }
 
-        let entry = keyring::Entry::new(KEYRING_SERVICE, KEYRING_USERNAME)?;
-        entry.set_password(trimmed)?;
-        Ok(())
+        self.store.save(trimmed)
     }
 
     pub fn clear(&self) -> anyhow::Result<()> {
-        let entry = keyring::Entry::new(KEYRING_SERVICE, KEYRING_USERNAME)?;
-        match entry.delete_credential() {
-            Ok(_) | Err(keyring::Error::NoEntry) => Ok(()),
-            Err(err) => Err(err.into()),
-        }
+        self.store.clear()
     }
 }
@@ -1,62 +1,150 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::future::Future;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use anyhow::Context;
 use chrono::Utc;
 use futures::stream::{self, StreamExt};
-use tokio::sync::{mpsc, Mutex, RwLock};
+use futures::SinkExt;
+use once_cell::sync::Lazy;
+use rand::seq::IndexedRandom;
+use regex::Regex;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use tokio::sync::{mpsc, Mutex, Notify, RwLock};
 use tokio::task::AbortHandle;
 use tokio_util::sync::CancellationToken;
+use url::Url;
 use uuid::Uuid;
 
 use super::auth::GoogleAuthService;
-use super::document_parser::ResumeDocumentParser;
-use super::errors::{AuthErrorCode, CoreError};
-use super::google_drive::GoogleDriveClient;
-use super::google_sheets::GoogleSheetsClient;
-use super::job_store::JsonJobStore;
+use super::auth_provider::AuthProvider;
+use super::concurrency::AdaptiveConcurrency;
+use super::document_parser::{self, ResumeDocumentParser};
+use super::errors::{AuthErrorCode, CoreError, RetryClass};
+use super::events::{EventSink, JOB_CANCELLED, JOB_COMPLETE, JOB_FAILED};
+use super::field_extractor::{self, PhoneMode};
+use super::google_drive::{DriveFilePage, GoogleDriveClient};
+use super::google_sheets::{
+    column_letter, AppendMode, GoogleSheetsClient, ValueInputOption, HIGH_CONFIDENCE,
+    MEDIUM_CONFIDENCE,
+};
+use super::job_store::{self, JsonJobStore};
 use super::models::{
-    AuthStatus, BatchParseRequest, DriveBrowserFile, DriveFileRef, DriveFolderEntry,
-    DrivePathEntry, GoogleSignInResult, JobProcessingState, JobStatus, ManualAuthChallenge,
-    ManualAuthCompleteRequest, ParsedCandidate, RuntimeSettings, RuntimeSettingsUpdate,
-    RuntimeSettingsView,
+    default_spreadsheet_title_template, sanitize_skill_dictionary, AggregateStats, AppPaths,
+    AuthStatus, BatchEstimate, BatchParseRequest, ConfidenceWeights, CustomFieldRule,
+    DriveBrowserFile, DriveFileRef, DriveFolderEntry, DrivePathEntry, ExtractedText, FieldHitRate,
+    FieldKind, FileTypeInfo, FolderAccess, FolderSample, GoogleSignInResult, JobCheckpoint,
+    JobCompletionWebhookPayload, JobDeletion, JobFinishedEvent, JobProcessingState, JobStatus,
+    ManualAuthChallenge, ManualAuthCompleteRequest, ParseError, ParseErrorKind, ParsedCandidate,
+    PersistedSettings, QueueStatus, ResumeExport, ResumeExportFailure, ResumeExtractionResult,
+    RuntimeSettings, RuntimeSettingsUpdate, RuntimeSettingsView, SheetColumn, SortOrder,
+    UnsupportedFilePolicy, WatchFolderRequest, WatchState,
 };
-use super::ocr::TesseractCliOcrService;
+use super::ocr::{self, TesseractCliOcrService};
 use super::pdf::PdfTextExtractor;
 use super::secret_store::GoogleClientSecretStore;
-use super::settings_store::SettingsStore;
-
-const HEADER_COLUMNS: [&str; 6] = [
-    "Name",
-    "Resume Link",
-    "Phone Number",
-    "Email ID",
-    "LinkedIn",
-    "GitHub",
-];
+use super::settings_store::{app_data_root, logs_root, SettingsStore};
+
+static COPY_MARKER_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)^copy of\s+|\s*(\(\d+\)|\[\d+\]|[-_ ]copy(\s*\d+)?)$").unwrap());
+
 const HTTP_REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
 const HTTP_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
 const FILE_PROCESS_TIMEOUT: Duration = Duration::from_secs(180);
+/// Smallest download that can hold a supported file; every PDF, DOCX, and ODT starts with a
+/// four-byte signature.
+const MIN_DOWNLOAD_BYTES: usize = 4;
+const COMPLETION_WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+const IDEMPOTENCY_KEY_TTL: Duration = Duration::from_secs(5 * 60);
+/// How often the cleanup task rechecks its setting while the sweep is turned off.
+const CLEANUP_DISABLED_RECHECK: Duration = Duration::from_secs(5 * 60);
+/// Jobs writing to a sheet they created buffer their rows and write them in one request
+/// when they have at most this many files; larger jobs append chunk by chunk.
+const SINGLE_WRITE_MAX_FILES: usize = 200;
+/// Minimum gap between per-file progress saves; each chunk still flushes when it finishes.
+const PROGRESS_SAVE_INTERVAL: Duration = Duration::from_millis(500);
+/// Batch estimates treat PDFs at least this large as likely scans that need OCR.
+const LIKELY_SCANNED_PDF_BYTES: u64 = 500 * 1024;
+/// Folder under the logs root that holds OCR inputs kept by `keep_ocr_temp`.
+const KEPT_OCR_FILES_DIR: &str = "ocr-files";
+/// Per-file costs behind batch estimates, in seconds of one worker's time.
+const ESTIMATE_DOWNLOAD_SECONDS: f64 = 1.0;
+const ESTIMATE_TEXT_PARSE_SECONDS: f64 = 0.5;
+const ESTIMATE_OCR_PARSE_SECONDS: f64 = 12.0;
+/// Resume exports stop adding files once the zip would grow past this; the rest are
+/// reported as failed.
+const MAX_RESUME_EXPORT_BYTES: u64 = 250 * 1024 * 1024;
+/// With adaptive concurrency, downloads may grow to this multiple of the configured limit
+/// while Drive does not throttle.
+const ADAPTIVE_CONCURRENCY_HEADROOM: usize = 2;
+/// Longest text `debug_extract_text` returns; longer text is cut off with a note.
+const DEBUG_TEXT_MAX_CHARS: usize = 50_000;
+/// Shortest watch interval accepted, so a watch cannot hammer the Drive API.
+const MIN_WATCH_INTERVAL_SECONDS: u64 = 30;
+/// Upper bound for `sample_folder`, which downloads and parses while the user waits.
+const MAX_FOLDER_SAMPLE_SIZE: usize = 50;
+/// How often a watch cycle checks whether its job has finished.
+const WATCH_STATUS_POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// How long `abort_and_delete_job` lets a cancelled job wind down before killing it.
+const ABORT_GRACE_PERIOD: Duration = Duration::from_secs(5);
+const ABORT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+/// Deleting can race a last write from the worker, so it is retried a few times.
+const DELETE_ATTEMPTS: usize = 5;
+const DELETE_RETRY_DELAY: Duration = Duration::from_millis(200);
+/// Left on jobs stopped by `shutdown`; they resume from their last checkpoint.
+const SHUTDOWN_MESSAGE: &str =
+    "Stopped when SourceStack closed. Resume the job to continue from its last checkpoint.";
+
+/// Result of probing the configured tesseract binary, kept until the path changes.
+#[derive(Clone)]
+struct TesseractProbe {
+    path: String,
+    version: Option<String>,
+}
 
 struct BatchJobWorkItem {
     job_id: String,
     request: BatchParseRequest,
+    /// Set when a failed job is resumed; processing continues from this point.
+    checkpoint: Option<JobCheckpoint>,
 }
 
 pub struct CoreService {
     settings_store: SettingsStore,
     client_secret_store: GoogleClientSecretStore,
     settings: RwLock<RuntimeSettings>,
+    /// Settings at startup; the HTTP clients and the job queue were built from them.
+    started_with: RuntimeSettings,
     legacy_secret_scrubbed: RwLock<bool>,
-    auth: GoogleAuthService,
+    tesseract_probe: RwLock<Option<TesseractProbe>>,
+    auth: Box<dyn AuthProvider>,
     drive: GoogleDriveClient,
     sheets: GoogleSheetsClient,
+    /// Posts completion webhooks. Unlike the Google clients it never sends `extra_headers`,
+    /// which are meant for Google only.
+    webhook_client: reqwest::Client,
     job_store: Arc<JsonJobStore>,
-    queue_tx: mpsc::UnboundedSender<BatchJobWorkItem>,
+    queue_tx: mpsc::Sender<BatchJobWorkItem>,
+    queued_jobs: AtomicUsize,
+    /// Idempotency key -> (job id, submitted at) for recent `start_batch_job` calls.
+    recent_idempotency_keys: Mutex<HashMap<String, (String, Instant)>>,
     active_job_handles: Mutex<HashMap<String, AbortHandle>>,
     cancellation_tokens: Mutex<HashMap<String, CancellationToken>>,
     killed_jobs: Mutex<HashSet<String>>,
+    paused_jobs: Mutex<HashSet<String>>,
+    pause_changed: Notify,
+    /// Watched folder id -> token that stops its watch loop.
+    watches: Mutex<HashMap<String, CancellationToken>>,
+    /// Last `aggregate_stats` result and the job ids it covered. Cleared when a job's
+    /// results change; added or deleted jobs change the ids.
+    aggregate_stats: Mutex<Option<(Vec<String>, AggregateStats)>>,
+    event_sink: std::sync::RwLock<Option<EventSink>>,
+    /// Cancelled once the app is closing. Job and watch tokens are its children, so it stops
+    /// them all; the queue worker stops taking new jobs.
+    shutdown: CancellationToken,
 }
 
 impl CoreService {
@@ -81,33 +169,58 @@ impl CoreService {
         };
         let settings = RuntimeSettings::from_parts(loaded.persisted.sanitized(), secret);
 
-        let client = reqwest::Client::builder()
-            .connect_timeout(HTTP_CONNECT_TIMEOUT)
-            .timeout(HTTP_REQUEST_TIMEOUT)
-            .user_agent("SourceStackDesktop/1.0")
-            .build()
-            .context("failed to build HTTP client")?;
+        let http_settings = match build_http_client(&settings) {
+            Ok(_) => settings.clone(),
+            Err(err) => {
+                // A missing or broken CA file or header must not keep the app from starting.
+                eprintln!(
+                    "core: {err:#}; falling back to system certificate roots and no extra headers"
+                );
+                RuntimeSettings {
+                    custom_ca_cert_path: None,
+                    extra_headers: HashMap::new(),
+                    ..settings.clone()
+                }
+            }
+        };
+        let client = build_http_client(&http_settings)?;
+        let webhook_client = build_webhook_client(&http_settings)?;
 
-        let auth = GoogleAuthService::new(client.clone());
+        let auth: Box<dyn AuthProvider> = Box::new(GoogleAuthService::new(client.clone()));
         let drive = GoogleDriveClient::new(client.clone());
-        let sheets = GoogleSheetsClient::new(client);
+        let sheets = GoogleSheetsClient::new(client.clone());
         let job_store = Arc::new(JsonJobStore::new(settings.job_retention_hours));
+        job_store
+            .set_status_write_interval(Duration::from_millis(settings.status_write_interval_ms));
+        job_store.set_compact_results(settings.compact_results);
 
-        let (queue_tx, queue_rx) = mpsc::unbounded_channel();
+        // Bounded so a runaway script cannot pile up pending requests in memory.
+        let (queue_tx, queue_rx) = mpsc::channel(settings.max_queued_jobs);
 
         let service = Arc::new(Self {
             settings_store,
             client_secret_store,
+            started_with: settings.clone(),
             settings: RwLock::new(settings),
             legacy_secret_scrubbed: RwLock::new(loaded.legacy_secret_scrubbed),
+            tesseract_probe: RwLock::new(None),
             auth,
             drive,
             sheets,
+            webhook_client,
             job_store,
             queue_tx,
+            queued_jobs: AtomicUsize::new(0),
+            recent_idempotency_keys: Mutex::new(HashMap::new()),
             active_job_handles: Mutex::new(HashMap::new()),
             cancellation_tokens: Mutex::new(HashMap::new()),
             killed_jobs: Mutex::new(HashSet::new()),
+            paused_jobs: Mutex::new(HashSet::new()),
+            pause_changed: Notify::new(),
+            watches: Mutex::new(HashMap::new()),
+            aggregate_stats: Mutex::new(None),
+            event_sink: std::sync::RwLock::new(None),
+            shutdown: CancellationToken::new(),
         });
 
         service.recover_orphaned_jobs().await?;
@@ -117,13 +230,67 @@ impl CoreService {
             worker_service.process_queue(queue_rx).await;
         });
 
+        let cleanup_service = Arc::clone(&service);
+        tokio::spawn(async move {
+            cleanup_service.run_periodic_cleanup().await;
+        });
+
         Ok(service)
     }
 
+    /// Enforces job retention while the app sits idle. The interval is re-read every round,
+    /// so a changed setting applies without a restart.
+    async fn run_periodic_cleanup(&self) {
+        loop {
+            let Some(interval) = cleanup_interval(&*self.settings.read().await) else {
+                tokio::time::sleep(CLEANUP_DISABLED_RECHECK).await;
+                continue;
+            };
+            tokio::time::sleep(interval).await;
+            self.run_scheduled_cleanup().await;
+        }
+    }
+
+    /// One round of periodic cleanup, skipped when cleanup was turned off while waiting.
+    async fn run_scheduled_cleanup(&self) {
+        let retention_hours = {
+            let settings = self.settings.read().await;
+            if cleanup_interval(&settings).is_none() {
+                return;
+            }
+            settings.job_retention_hours
+        };
+        if let Err(err) = self.job_store.cleanup_expired_jobs().await {
+            eprintln!("periodic job cleanup failed: {err:#}");
+        }
+        // Kept OCR inputs are resumes, so they expire together with the jobs that made them.
+        let max_age = Duration::from_secs(
+            u64::try_from(retention_hours)
+                .unwrap_or(0)
+                .saturating_mul(60 * 60),
+        );
+        if let Err(err) =
+            ocr::remove_kept_ocr_files_older_than(&logs_root().join(KEPT_OCR_FILES_DIR), max_age)
+                .await
+        {
+            eprintln!("periodic OCR file cleanup failed: {err:#}");
+        }
+    }
+
+    pub fn set_event_sink(&self, sink: EventSink) {
+        self.auth.set_event_sink(Arc::clone(&sink));
+        if let Ok(mut guard) = self.event_sink.write() {
+            *guard = Some(sink);
+        }
+    }
+
     pub async fn get_settings(&self) -> RuntimeSettingsView {
         let settings = self.settings.read().await.clone();
         let legacy_secret_scrubbed = *self.legacy_secret_scrubbed.read().await;
-        settings.to_view(legacy_secret_scrubbed)
+        let mut view = settings.to_view(legacy_secret_scrubbed);
+        view.restart_required = settings.differs_in_startup_settings(&self.started_with);
+        view.tesseract_version = self.tesseract_version(&settings.tesseract_path).await;
+        view
     }
 
     pub async fn save_settings(
@@ -138,12 +305,135 @@ impl CoreService {
             google_client_secret: previous.google_client_secret.clone(),
             tesseract_path: new_settings.tesseract_path,
             max_concurrent_requests: new_settings.max_concurrent_requests.max(1),
+            max_concurrent_downloads: new_settings.max_concurrent_downloads.map(|v| v.max(1)),
+            max_concurrent_parses: new_settings.max_concurrent_parses.map(|v| v.max(1)),
             spreadsheet_batch_size: new_settings.spreadsheet_batch_size.max(1),
             max_retries: new_settings.max_retries.max(1),
             retry_delay_seconds: new_settings.retry_delay_seconds.max(0.1),
             job_retention_hours: new_settings.job_retention_hours.max(1),
+            custom_ca_cert_path: merge_optional_setting(
+                new_settings.custom_ca_cert_path,
+                previous.custom_ca_cert_path.clone(),
+            ),
+            email_preference: new_settings
+                .email_preference
+                .unwrap_or(previous.email_preference),
+            user_agent_suffix: merge_optional_setting(
+                new_settings.user_agent_suffix,
+                previous.user_agent_suffix.clone(),
+            ),
+            skill_dictionary: new_settings
+                .skill_dictionary
+                .map(sanitize_skill_dictionary)
+                .unwrap_or(previous.skill_dictionary.clone()),
+            disable_ocr: new_settings.disable_ocr.unwrap_or(previous.disable_ocr),
+            status_write_interval_ms: new_settings
+                .status_write_interval_ms
+                .unwrap_or(previous.status_write_interval_ms),
+            preserve_text_columns: new_settings
+                .preserve_text_columns
+                .unwrap_or(previous.preserve_text_columns),
+            spreadsheet_title_template: new_settings
+                .spreadsheet_title_template
+                .map(|v| {
+                    if v.trim().is_empty() {
+                        default_spreadsheet_title_template()
+                    } else {
+                        v
+                    }
+                })
+                .unwrap_or(previous.spreadsheet_title_template.clone()),
+            color_by_confidence: new_settings
+                .color_by_confidence
+                .unwrap_or(previous.color_by_confidence),
+            merge_ocr_fields: new_settings
+                .merge_ocr_fields
+                .unwrap_or(previous.merge_ocr_fields),
+            max_queued_jobs: new_settings
+                .max_queued_jobs
+                .map(|v| v.max(1))
+                .unwrap_or(previous.max_queued_jobs),
+            debug_field_sources: new_settings
+                .debug_field_sources
+                .unwrap_or(previous.debug_field_sources),
+            required_fields: new_settings
+                .required_fields
+                .unwrap_or(previous.required_fields.clone()),
+            confidence_as_percent: new_settings
+                .confidence_as_percent
+                .unwrap_or(previous.confidence_as_percent),
+            confidence_weights: new_settings
+                .confidence_weights
+                .map(ConfidenceWeights::sanitized)
+                .unwrap_or(previous.confidence_weights),
+            name_denylist: new_settings
+                .name_denylist
+                .map(sanitize_skill_dictionary)
+                .unwrap_or(previous.name_denylist.clone()),
+            keep_ocr_temp: new_settings.keep_ocr_temp.unwrap_or(previous.keep_ocr_temp),
+            retry_on: new_settings.retry_on.unwrap_or(previous.retry_on),
+            cleanup_interval_minutes: new_settings
+                .cleanup_interval_minutes
+                .unwrap_or(previous.cleanup_interval_minutes),
+            notice_period_column: new_settings
+                .notice_period_column
+                .unwrap_or(previous.notice_period_column),
+            compact_results: new_settings
+                .compact_results
+                .unwrap_or(previous.compact_results),
+            sheet_append_page_size: new_settings.sheet_append_page_size.map(|v| v.max(1)),
+            missing_value_placeholder: new_settings
+                .missing_value_placeholder
+                .unwrap_or(previous.missing_value_placeholder.clone()),
+            unsupported_file_policy: new_settings
+                .unsupported_file_policy
+                .unwrap_or(previous.unsupported_file_policy),
+            adaptive_concurrency: new_settings
+                .adaptive_concurrency
+                .unwrap_or(previous.adaptive_concurrency),
+            custom_fields: new_settings
+                .custom_fields
+                .map(sanitize_custom_fields)
+                .unwrap_or(previous.custom_fields.clone()),
+            sort_order: new_settings.sort_order.unwrap_or(previous.sort_order),
+            job_retry_budget: new_settings.job_retry_budget,
+            sheet_append_delay_ms: new_settings
+                .sheet_append_delay_ms
+                .unwrap_or(previous.sheet_append_delay_ms),
+            deobfuscate_emails: new_settings
+                .deobfuscate_emails
+                .unwrap_or(previous.deobfuscate_emails),
+            education_column: new_settings
+                .education_column
+                .unwrap_or(previous.education_column),
+            extra_headers: new_settings
+                .extra_headers
+                .unwrap_or(previous.extra_headers.clone()),
+            require_international_phone: new_settings
+                .require_international_phone
+                .unwrap_or(previous.require_international_phone),
+            national_phone_policy: new_settings
+                .national_phone_policy
+                .unwrap_or(previous.national_phone_policy),
+            email_domain_column: new_settings
+                .email_domain_column
+                .unwrap_or(previous.email_domain_column),
         };
 
+        if runtime.custom_fields != previous.custom_fields {
+            if let Err(problems) = field_extractor::compile_custom_fields(&runtime.custom_fields) {
+                return Err(CoreError::InvalidRequest(problems.join("; ")).into());
+            }
+        }
+
+        if runtime.custom_ca_cert_path != previous.custom_ca_cert_path
+            || runtime.user_agent_suffix != previous.user_agent_suffix
+            || runtime.extra_headers != previous.extra_headers
+        {
+            // Validate eagerly; the HTTP clients pick up the change on next launch.
+            build_http_client(&runtime)?;
+        }
+
         if let Some(secret_update) = new_settings.google_client_secret {
             let trimmed = secret_update.trim();
             if !trimmed.is_empty() {
@@ -155,11 +445,57 @@ impl CoreService {
         }
 
         self.settings_store.save(&runtime.to_persisted()).await?;
-        let mut settings = self.settings.write().await;
-        *settings = runtime.clone();
+        {
+            let mut settings = self.settings.write().await;
+            *settings = runtime.clone();
+        }
+        self.job_store.set_compact_results(runtime.compact_results);
+        self.job_store
+            .set_status_write_interval(Duration::from_millis(runtime.status_write_interval_ms));
+        if runtime.tesseract_path != previous.tesseract_path {
+            let mut probe = self.tesseract_probe.write().await;
+            *probe = None;
+        }
 
         let legacy_secret_scrubbed = *self.legacy_secret_scrubbed.read().await;
-        Ok(runtime.to_view(legacy_secret_scrubbed))
+        let mut view = runtime.to_view(legacy_secret_scrubbed);
+        view.restart_required = runtime.differs_in_startup_settings(&self.started_with);
+        view.tesseract_version = self.tesseract_version(&runtime.tesseract_path).await;
+        Ok(view)
+    }
+
+    /// Returns the cached `tesseract --version` banner, probing the binary only when the
+    /// configured path has not been checked yet.
+    async fn tesseract_version(&self, tesseract_path: &str) -> Option<String> {
+        {
+            let probe = self.tesseract_probe.read().await;
+            if let Some(probe) = probe.as_ref().filter(|p| p.path == tesseract_path) {
+                return probe.version.clone();
+            }
+        }
+
+        let version = TesseractCliOcrService::detect_version(tesseract_path)
+            .await
+            .ok();
+        let mut probe = self.tesseract_probe.write().await;
+        *probe = Some(TesseractProbe {
+            path: tesseract_path.to_string(),
+            version: version.clone(),
+        });
+        version
+    }
+
+    pub fn get_paths(&self) -> AppPaths {
+        AppPaths {
+            data_root: app_data_root().display().to_string(),
+            settings_file: self.settings_store.path().display().to_string(),
+            jobs_root: self.job_store.jobs_root().display().to_string(),
+            logs_dir: logs_root().display().to_string(),
+        }
+    }
+
+    pub fn supported_file_types(&self) -> Vec<FileTypeInfo> {
+        document_parser::supported_file_types()
     }
 
     pub async fn parse_single(
@@ -168,32 +504,84 @@ impl CoreService {
         file_bytes: Vec<u8>,
     ) -> anyhow::Result<ParsedCandidate> {
         let settings = self.settings.read().await.clone();
-        let parser = self.build_parser(&settings);
+        let parser = self
+            .build_parser(&settings)
+            .with_field_sources(settings.debug_field_sources)
+            .with_name_candidates(true);
+        let started = Instant::now();
         let parsed = parser.parse_resume_bytes(&file_name, &file_bytes).await;
+        let parse_duration_ms = started.elapsed().as_millis() as u64;
 
         Ok(ParsedCandidate {
-            drive_file_id: None,
-            source_file: Some(file_name),
-            name: parsed.name,
-            email: parsed.email,
-            phone: parsed.phone,
-            linked_in: parsed.linked_in,
-            git_hub: parsed.git_hub,
-            confidence: parsed.confidence,
-            errors: parsed.errors,
+            parse_duration_ms: Some(parse_duration_ms),
+            ..candidate_from_extraction(None, Some(file_name), parsed)
         })
     }
 
-    pub async fn start_batch_job(&self, request: BatchParseRequest) -> anyhow::Result<String> {
-        if request.folder_id.trim().is_empty() {
-            return Err(CoreError::InvalidRequest("FolderId is required".to_string()).into());
-        }
+    /// Runs only the extraction stage on one file and returns the raw text fields would be
+    /// parsed from. Nothing is stored.
+    pub async fn debug_extract_text(
+        &self,
+        file_name: String,
+        file_bytes: Vec<u8>,
+    ) -> anyhow::Result<ExtractedText> {
+        let settings = self.settings.read().await.clone();
+        let parser = self.build_parser(&settings);
+        let (text, ocr_used, errors) = parser.extract_text(&file_name, &file_bytes).await;
+
+        let total_chars = text.chars().count();
+        let (text, note) = match text.char_indices().nth(DEBUG_TEXT_MAX_CHARS) {
+            Some((cut, _)) => (
+                text[..cut].to_string(),
+                Some(format!(
+                    "Truncated: showing the first {DEBUG_TEXT_MAX_CHARS} of {total_chars} characters."
+                )),
+            ),
+            None => (text, None),
+        };
+
+        Ok(ExtractedText {
+            text,
+            ocr_used,
+            errors,
+            note,
+        })
+    }
+
+    /// Parses one file into a sheet row. `columns` overrides the layout a new batch sheet would
+    /// get with the current settings.
+    pub async fn parse_single_as_row(
+        &self,
+        file_name: String,
+        file_bytes: Vec<u8>,
+        columns: Option<Vec<SheetColumn>>,
+    ) -> anyhow::Result<Vec<String>> {
+        let candidate = self.parse_single(file_name, file_bytes).await?;
+        let settings = self.settings.read().await.clone();
+        let (layout, custom_columns) = match columns.filter(|columns| !columns.is_empty()) {
+            Some(columns) => (columns, Vec::new()),
+            None => (sheet_layout(&settings), custom_column_names(&settings)),
+        };
+        let mut row = candidate_to_row(
+            &candidate,
+            &layout,
+            &custom_columns,
+            settings.confidence_as_percent,
+        );
+        fill_missing_cells(&mut row, &settings.missing_value_placeholder);
+        Ok(row)
+    }
 
+    /// Validates the whole request before queueing it, so misconfigurations are reported at
+    /// once instead of failing the job later. All problems found are listed in one
+    /// `InvalidRequest`.
+    pub async fn start_batch_job(&self, request: BatchParseRequest) -> anyhow::Result<String> {
+        let mut problems = batch_request_problems(&request);
         let settings = self.settings.read().await.clone();
-        self.auth
+        let access_token = self
+            .auth
             .get_access_token_non_interactive(&settings)
             .await
-            .map(|_| ())
             .map_err(|err| {
                 if let Some(CoreError::Auth { code, .. }) = err.downcast_ref::<CoreError>() {
                     if matches!(
@@ -210,9 +598,82 @@ impl CoreService {
                 err
             })?;
 
-        self.job_store.cleanup_expired_jobs().await?;
+        let target_spreadsheet_id = request
+            .spreadsheet_id
+            .as_deref()
+            .map(str::trim)
+            .filter(|id| !id.is_empty() && !request.skip_spreadsheet);
+        let targets = [
+            ("SpreadsheetId", target_spreadsheet_id),
+            (
+                "ReviewSpreadsheetId",
+                review_route(&request).map(|(id, _)| id),
+            ),
+        ];
+        for (field, spreadsheet_id) in targets {
+            let Some(spreadsheet_id) = spreadsheet_id else {
+                continue;
+            };
+            if let Err(err) = self
+                .sheets
+                .check_access(&access_token, spreadsheet_id)
+                .await
+            {
+                match err.downcast_ref::<CoreError>() {
+                    Some(CoreError::GoogleApi {
+                        status: 400 | 403 | 404,
+                        ..
+                    }) => problems.push(format!(
+                        "{field} {spreadsheet_id} was not found or is not shared with the signed-in account"
+                    )),
+                    _ => return Err(err),
+                }
+            }
+        }
+
+        if !problems.is_empty() {
+            return Err(CoreError::InvalidRequest(problems.join("; ")).into());
+        }
 
         let job_id = Uuid::new_v4().to_string();
+        let idempotency_key = request
+            .idempotency_key
+            .as_deref()
+            .map(str::trim)
+            .filter(|key| !key.is_empty())
+            .map(str::to_string);
+        if let Some(key) = &idempotency_key {
+            let mut recent_keys = self.recent_idempotency_keys.lock().await;
+            recent_keys.retain(|_, (_, submitted_at)| submitted_at.elapsed() < IDEMPOTENCY_KEY_TTL);
+            if let Some((existing_job_id, _)) = recent_keys.get(key) {
+                return Ok(existing_job_id.clone());
+            }
+            // Claimed before the job is queued so a second rapid submission gets this job's id
+            // instead of starting another; released again if queueing fails.
+            recent_keys.insert(key.clone(), (job_id.clone(), Instant::now()));
+        }
+
+        let queued = self.queue_new_job(&job_id, request).await;
+        if let (Err(_), Some(key)) = (&queued, &idempotency_key) {
+            let mut recent_keys = self.recent_idempotency_keys.lock().await;
+            if recent_keys
+                .get(key)
+                .is_some_and(|(claimed_by, _)| *claimed_by == job_id)
+            {
+                recent_keys.remove(key);
+            }
+        }
+        queued.map(|()| job_id)
+    }
+
+    /// Saves the new job as pending and hands it to the queue.
+    async fn queue_new_job(&self, job_id: &str, request: BatchParseRequest) -> anyhow::Result<()> {
+        self.job_store.cleanup_expired_jobs().await?;
+
+        // Reserve the queue slot first so a full queue leaves no orphaned pending job.
+        let queue_slot = self.reserve_queue_slot()?;
+
+        let job_id = job_id.to_string();
         let pending = JobStatus {
             job_id: job_id.clone(),
             status: JobProcessingState::Pending,
@@ -229,14 +690,50 @@ impl CoreService {
         };
 
         self.job_store.save_status(&pending).await?;
-        self.queue_tx
-            .send(BatchJobWorkItem {
-                job_id: job_id.clone(),
-                request,
-            })
-            .map_err(|_| anyhow::anyhow!("failed to queue batch job"))?;
+        self.job_store
+            .save_checkpoint(
+                &job_id,
+                &JobCheckpoint {
+                    request: request.clone(),
+                    spreadsheet_id: request.spreadsheet_id.clone(),
+                    page_token: None,
+                    processed_file_ids: Vec::new(),
+                },
+            )
+            .await?;
+        self.queued_jobs.fetch_add(1, Ordering::SeqCst);
+        queue_slot.send(BatchJobWorkItem {
+            job_id,
+            request,
+            checkpoint: None,
+        });
+        Ok(())
+    }
+
+    fn reserve_queue_slot(&self) -> anyhow::Result<mpsc::Permit<'_, BatchJobWorkItem>> {
+        if self.shutdown.is_cancelled() {
+            return Err(CoreError::InvalidRequest(
+                "SourceStack is shutting down; no new jobs are accepted".to_string(),
+            )
+            .into());
+        }
+        match self.queue_tx.try_reserve() {
+            Ok(permit) => Ok(permit),
+            Err(mpsc::error::TrySendError::Full(())) => {
+                Err(CoreError::QueueFull(self.queue_tx.max_capacity()).into())
+            }
+            Err(mpsc::error::TrySendError::Closed(())) => {
+                Err(anyhow::anyhow!("failed to queue batch job"))
+            }
+        }
+    }
 
-        Ok(job_id)
+    pub async fn queue_status(&self) -> QueueStatus {
+        let running_jobs = self.active_job_handles.lock().await.len();
+        QueueStatus {
+            queued_jobs: self.queued_jobs.load(Ordering::SeqCst),
+            running_jobs,
+        }
     }
 
     pub async fn get_job_status(&self, job_id: &str) -> anyhow::Result<JobStatus> {
@@ -246,6 +743,16 @@ impl CoreService {
             .ok_or_else(|| CoreError::JobNotFound(job_id.to_string()).into())
     }
 
+    /// Settings the job last ran with, or `None` for jobs from before snapshots were kept.
+    pub async fn get_job_config(&self, job_id: &str) -> anyhow::Result<Option<PersistedSettings>> {
+        if let Some(config) = self.job_store.load_config(job_id).await? {
+            return Ok(Some(config));
+        }
+
+        self.get_job_status(job_id).await?;
+        Ok(None)
+    }
+
     pub async fn get_job_results(&self, job_id: &str) -> anyhow::Result<Vec<ParsedCandidate>> {
         if let Some(results) = self.job_store.load_results(job_id).await? {
             return Ok(results);
@@ -264,10 +771,291 @@ impl CoreService {
         Ok(Vec::new())
     }
 
+    /// Every stored result for one Drive file, in result order. A file split into several
+    /// candidates returns all of them; an empty list means the job has no result for it.
+    pub async fn get_job_candidates(
+        &self,
+        job_id: &str,
+        drive_file_id: &str,
+    ) -> anyhow::Result<Vec<ParsedCandidate>> {
+        Ok(self
+            .get_job_results(job_id)
+            .await?
+            .into_iter()
+            .filter(|candidate| candidate.drive_file_id.as_deref() == Some(drive_file_id))
+            .collect())
+    }
+
+    /// Recomputes confidence for a finished job's stored results with the current weights.
+    /// Fields are not re-extracted and the job's sheet is not rewritten.
+    pub async fn rescore_job(&self, job_id: &str) -> anyhow::Result<Vec<ParsedCandidate>> {
+        let status = self
+            .job_store
+            .load_status(job_id)
+            .await?
+            .ok_or_else(|| CoreError::JobNotFound(job_id.to_string()))?;
+        if matches!(
+            status.status,
+            JobProcessingState::Pending
+                | JobProcessingState::Processing
+                | JobProcessingState::Paused
+        ) {
+            return Err(CoreError::JobNotCompleted(job_id.to_string()).into());
+        }
+
+        let weights = self.settings.read().await.confidence_weights;
+        let mut results = self
+            .job_store
+            .load_results(job_id)
+            .await?
+            .unwrap_or_default();
+        for candidate in &mut results {
+            rescore_candidate(candidate, &weights);
+        }
+        self.job_store.save_results(job_id, &results).await?;
+        self.invalidate_aggregate_stats().await;
+        Ok(results)
+    }
+
+    pub async fn export_job_archive(&self, job_id: &str, dest_path: &str) -> anyhow::Result<()> {
+        job_store::ensure_plain_job_id(job_id)?;
+        if dest_path.trim().is_empty() {
+            return Err(CoreError::InvalidRequest("archive path is required".to_string()).into());
+        }
+
+        let exported = self
+            .job_store
+            .export_archive(job_id, Path::new(dest_path.trim()))
+            .await?;
+        if !exported {
+            return Err(CoreError::JobNotFound(job_id.to_string()).into());
+        }
+        Ok(())
+    }
+
+    /// Re-downloads the source resumes of a completed job from Drive and zips them, e.g. to
+    /// hand a shortlist to a hiring manager. `only_drive_ids` narrows the export to those
+    /// candidates; files that fail to download are reported instead of failing the export.
+    pub async fn export_job_resumes(
+        &self,
+        job_id: &str,
+        dest_path: &str,
+        only_drive_ids: Option<Vec<String>>,
+    ) -> anyhow::Result<ResumeExport> {
+        if dest_path.trim().is_empty() {
+            return Err(CoreError::InvalidRequest("zip path is required".to_string()).into());
+        }
+
+        let status = self
+            .job_store
+            .load_status(job_id)
+            .await?
+            .ok_or_else(|| CoreError::JobNotFound(job_id.to_string()))?;
+        if status.status != JobProcessingState::Completed {
+            return Err(CoreError::JobNotCompleted(job_id.to_string()).into());
+        }
+
+        let results = self
+            .job_store
+            .load_results(job_id)
+            .await?
+            .unwrap_or_default();
+        let files = resume_export_files(&results, only_drive_ids.as_deref());
+        if files.is_empty() {
+            return Err(CoreError::InvalidRequest(
+                "no resumes with a Drive file to export".to_string(),
+            )
+            .into());
+        }
+
+        let settings = self.settings.read().await.clone();
+        let access_token = self
+            .auth
+            .get_access_token_non_interactive(&settings)
+            .await?;
+
+        let dest_path = Path::new(dest_path.trim());
+        if let Some(parent) = dest_path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let file = std::fs::File::create(dest_path)
+            .with_context(|| format!("failed writing {}", dest_path.display()))?;
+        let mut zip = zip::ZipWriter::new(file);
+        let exported = self
+            .download_resumes_into_zip(
+                files,
+                &access_token,
+                &settings,
+                MAX_RESUME_EXPORT_BYTES,
+                &mut zip,
+            )
+            .await
+            .and_then(|export| {
+                zip.finish()?;
+                Ok(export)
+            });
+        if exported.is_err() {
+            let _ = std::fs::remove_file(dest_path);
+        }
+        exported
+    }
+
+    /// Downloads `files` one at a time, writing each into `zip` as it arrives so only one
+    /// resume is held in memory, until they reach `max_bytes`. The file that would pass the
+    /// cap and every file after it are reported as skipped without being downloaded.
+    async fn download_resumes_into_zip<W: std::io::Write + std::io::Seek>(
+        &self,
+        files: Vec<DriveFileRef>,
+        access_token: &str,
+        settings: &RuntimeSettings,
+        max_bytes: u64,
+        zip: &mut zip::ZipWriter<W>,
+    ) -> anyhow::Result<ResumeExport> {
+        let mut exported_count = 0;
+        let mut entry_names = HashSet::new();
+        let mut total_bytes = 0_u64;
+        let mut failed = Vec::new();
+        let mut cap_reached = false;
+        for file in files {
+            let drive_file_id = file.id.clone();
+            let source_file = Some(file.name.clone()).filter(|name| !name.is_empty());
+            let mime_type = file.mime_type.clone();
+            if cap_reached {
+                failed.push(ResumeExportFailure {
+                    drive_file_id,
+                    source_file,
+                    message: resume_export_cap_message(max_bytes),
+                });
+                continue;
+            }
+            let bytes = match self
+                .download_file_with_retry(file, access_token, settings, None, None)
+                .await
+            {
+                Ok((_, bytes)) => bytes,
+                Err(candidate) => {
+                    failed.push(ResumeExportFailure {
+                        drive_file_id,
+                        source_file,
+                        message: candidate
+                            .errors
+                            .iter()
+                            .map(|error| error.message.as_str())
+                            .collect::<Vec<_>>()
+                            .join("; "),
+                    });
+                    continue;
+                }
+            };
+            if total_bytes + bytes.len() as u64 > max_bytes {
+                cap_reached = true;
+                failed.push(ResumeExportFailure {
+                    drive_file_id,
+                    source_file,
+                    message: resume_export_cap_message(max_bytes),
+                });
+                continue;
+            }
+
+            total_bytes += bytes.len() as u64;
+            let name = unique_entry_name(
+                &ensure_filename_extension(
+                    source_file.as_deref().unwrap_or(&drive_file_id),
+                    &mime_type,
+                ),
+                &mut entry_names,
+            );
+            zip.start_file(name, zip::write::SimpleFileOptions::default())?;
+            std::io::Write::write_all(zip, &bytes)?;
+            exported_count += 1;
+        }
+
+        Ok(ResumeExport {
+            exported_count,
+            total_bytes,
+            failed,
+        })
+    }
+
+    pub async fn import_job_archive(&self, src_path: &str) -> anyhow::Result<String> {
+        if src_path.trim().is_empty() {
+            return Err(CoreError::InvalidRequest("archive path is required".to_string()).into());
+        }
+
+        self.job_store
+            .import_archive(Path::new(src_path.trim()))
+            .await
+    }
+
     pub async fn list_jobs(&self) -> anyhow::Result<Vec<String>> {
         self.job_store.list_jobs().await
     }
 
+    /// Totals across every stored job's results. The scan is cached until a job finishes,
+    /// is rescored, or the set of stored jobs changes.
+    pub async fn aggregate_stats(&self) -> anyhow::Result<AggregateStats> {
+        let job_ids = self.job_store.list_jobs().await?;
+        // Held through the scan so concurrent callers wait for it instead of repeating it.
+        let mut cached = self.aggregate_stats.lock().await;
+        if let Some((cached_ids, stats)) = cached.as_ref() {
+            if *cached_ids == job_ids {
+                return Ok(stats.clone());
+            }
+        }
+
+        let mut stats = AggregateStats::default();
+        let mut field_hits = [0_usize; FieldKind::ALL.len()];
+        let mut ocr_files = 0;
+        let mut confidence_sum = 0.0;
+        for job_id in &job_ids {
+            let results = match self.job_store.load_results(job_id).await {
+                Ok(results) => results.unwrap_or_default(),
+                Err(err) => {
+                    eprintln!("aggregate stats: skipping job {job_id}: {err}");
+                    stats.unreadable_jobs += 1;
+                    continue;
+                }
+            };
+            stats.job_count += 1;
+            for candidate in &results {
+                stats.total_files += 1;
+                if candidate.errors.iter().any(|error| !error.is_warning()) {
+                    stats.failed_files += 1;
+                }
+                if candidate.ocr_used {
+                    ocr_files += 1;
+                }
+                confidence_sum += candidate.confidence;
+                for (hits, field) in field_hits.iter_mut().zip(FieldKind::ALL) {
+                    if field.is_present(candidate) {
+                        *hits += 1;
+                    }
+                }
+            }
+        }
+
+        let count = stats.total_files.max(1) as f64;
+        stats.ocr_rate = ocr_files as f64 / count;
+        stats.average_confidence = confidence_sum / count;
+        stats.field_hit_rates = FieldKind::ALL
+            .into_iter()
+            .zip(field_hits)
+            .map(|(field, hits)| FieldHitRate {
+                field,
+                rate: hits as f64 / count,
+            })
+            .collect();
+        *cached = Some((job_ids, stats.clone()));
+        Ok(stats)
+    }
+
+    async fn invalidate_aggregate_stats(&self) {
+        *self.aggregate_stats.lock().await = None;
+    }
+
     pub async fn cancel_job(&self, job_id: &str) -> anyhow::Result<bool> {
         let token = {
             let map = self.cancellation_tokens.lock().await;
@@ -282,38 +1070,181 @@ impl CoreService {
         Ok(false)
     }
 
-    pub async fn kill_job(&self, job_id: &str) -> anyhow::Result<bool> {
+    /// Requests that a running job pause. The pause takes effect at the next chunk boundary:
+    /// files already in flight finish first, and a job on its last chunk completes instead.
+    /// Returns `Ok(false)` for jobs that are not processing. A paused job keeps its slot in
+    /// the single-worker queue, so jobs queued behind it wait until it is resumed, cancelled,
+    /// or killed.
+    pub async fn pause_job(&self, job_id: &str) -> anyhow::Result<bool> {
         let Some(status) = self.job_store.load_status(job_id).await? else {
-            return Ok(false);
+            return Err(CoreError::JobNotFound(job_id.to_string()).into());
         };
 
-        if matches!(
-            status.status,
-            JobProcessingState::Completed
-                | JobProcessingState::Failed
-                | JobProcessingState::Revoked
-        ) {
+        if status.status != JobProcessingState::Processing {
             return Ok(false);
         }
 
-        {
-            let mut killed_jobs = self.killed_jobs.lock().await;
-            killed_jobs.insert(job_id.to_string());
-        }
+        let mut paused_jobs = self.paused_jobs.lock().await;
+        Ok(paused_jobs.insert(job_id.to_string()))
+    }
 
-        let kill_message = if status.status == JobProcessingState::Pending {
-            "Job killed before processing started."
-        } else {
-            "Job killed by user."
+    /// Resumes a paused job, or re-queues a failed or killed job from its last checkpoint.
+    /// A re-queued job reuses its sheet and skips files that were already processed.
+    pub async fn resume_job(&self, job_id: &str) -> anyhow::Result<bool> {
+        let resumed = {
+            let mut paused_jobs = self.paused_jobs.lock().await;
+            paused_jobs.remove(job_id)
         };
-        self.mark_job_killed(job_id, kill_message).await?;
+        self.pause_changed.notify_waiters();
+        if resumed {
+            return Ok(true);
+        }
 
-        let cancellation_token = {
-            let map = self.cancellation_tokens.lock().await;
-            map.get(job_id).cloned()
+        let Some(status) = self.job_store.load_status(job_id).await? else {
+            return Ok(false);
         };
-        if let Some(token) = cancellation_token {
-            token.cancel();
+        if !matches!(
+            status.status,
+            JobProcessingState::Failed | JobProcessingState::Revoked
+        ) {
+            return Ok(false);
+        }
+        let Some(checkpoint) = self.job_store.load_checkpoint(job_id).await? else {
+            return Ok(false);
+        };
+
+        let queue_slot = self.reserve_queue_slot()?;
+        self.job_store
+            .save_status(&JobStatus {
+                status: JobProcessingState::Pending,
+                spreadsheet_id: checkpoint.spreadsheet_id.clone(),
+                error: None,
+                completed_at: None,
+                duration_seconds: None,
+                ..status
+            })
+            .await?;
+        self.queued_jobs.fetch_add(1, Ordering::SeqCst);
+        queue_slot.send(BatchJobWorkItem {
+            job_id: job_id.to_string(),
+            request: BatchParseRequest {
+                spreadsheet_id: checkpoint.spreadsheet_id.clone(),
+                ..checkpoint.request.clone()
+            },
+            checkpoint: Some(checkpoint),
+        });
+        Ok(true)
+    }
+
+    /// Stops a job that has not finished and deletes it with its results and logs. A running
+    /// job is cancelled first and killed if it does not stop within `ABORT_GRACE_PERIOD`.
+    pub async fn abort_and_delete_job(&self, job_id: &str) -> anyhow::Result<JobDeletion> {
+        job_store::ensure_plain_job_id(job_id)?;
+        let status = self.get_job_status(job_id).await?;
+        let cancelled = !matches!(
+            status.status,
+            JobProcessingState::Completed
+                | JobProcessingState::Failed
+                | JobProcessingState::Revoked
+        );
+
+        if cancelled {
+            // A queued job has no worker to cancel, so it is killed straight away.
+            if !self.cancel_job(job_id).await?
+                || !self.wait_for_job_to_stop(job_id, ABORT_GRACE_PERIOD).await
+            {
+                self.kill_job(job_id).await?;
+                self.wait_for_job_to_stop(job_id, ABORT_GRACE_PERIOD).await;
+            }
+        }
+
+        let mut attempt = 1;
+        let deleted = loop {
+            match self.job_store.delete_job(job_id).await {
+                Ok(deleted) => break deleted,
+                Err(err) if attempt < DELETE_ATTEMPTS => {
+                    eprintln!("deleting job {job_id} failed (attempt {attempt}), retrying: {err}");
+                    attempt += 1;
+                    tokio::time::sleep(DELETE_RETRY_DELAY).await;
+                }
+                Err(err) => return Err(err),
+            }
+        };
+
+        self.recent_idempotency_keys
+            .lock()
+            .await
+            .retain(|_, (existing_job_id, _)| existing_job_id != job_id);
+        self.invalidate_aggregate_stats().await;
+        Ok(JobDeletion {
+            job_id: job_id.to_string(),
+            cancelled,
+            deleted,
+        })
+    }
+
+    /// Waits until the worker has let go of the job and its stored status is final.
+    /// Returns `false` on timeout.
+    async fn wait_for_job_to_stop(&self, job_id: &str, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let running = self.active_job_handles.lock().await.contains_key(job_id);
+            let finished = self
+                .job_store
+                .load_status(job_id)
+                .await
+                .ok()
+                .flatten()
+                .is_none_or(|status| {
+                    matches!(
+                        status.status,
+                        JobProcessingState::Completed
+                            | JobProcessingState::Failed
+                            | JobProcessingState::Revoked
+                    )
+                });
+            if !running && finished {
+                return true;
+            }
+            if Instant::now() >= deadline {
+                return false;
+            }
+            tokio::time::sleep(ABORT_POLL_INTERVAL).await;
+        }
+    }
+
+    pub async fn kill_job(&self, job_id: &str) -> anyhow::Result<bool> {
+        let Some(status) = self.job_store.load_status(job_id).await? else {
+            return Ok(false);
+        };
+
+        if matches!(
+            status.status,
+            JobProcessingState::Completed
+                | JobProcessingState::Failed
+                | JobProcessingState::Revoked
+        ) {
+            return Ok(false);
+        }
+
+        {
+            let mut killed_jobs = self.killed_jobs.lock().await;
+            killed_jobs.insert(job_id.to_string());
+        }
+
+        let kill_message = if status.status == JobProcessingState::Pending {
+            "Job killed before processing started."
+        } else {
+            "Job killed by user."
+        };
+        self.mark_job_killed(job_id, kill_message).await?;
+
+        let cancellation_token = {
+            let map = self.cancellation_tokens.lock().await;
+            map.get(job_id).cloned()
+        };
+        if let Some(token) = cancellation_token {
+            token.cancel();
         }
 
         let abort_handle = {
@@ -327,6 +1258,185 @@ impl CoreService {
         Ok(true)
     }
 
+    /// Processes a folder every `interval_seconds` until `stop_watch`. Each cycle queues a
+    /// regular batch job over the files added or modified since the last completed cycle and
+    /// appends them to the watch's sheet. Returns `false` when the folder is already watched.
+    pub async fn start_watch(
+        self: &Arc<Self>,
+        request: WatchFolderRequest,
+    ) -> anyhow::Result<bool> {
+        let folder_id = request.folder_id.trim().to_string();
+        let mut problems = Vec::new();
+        if folder_id.is_empty() {
+            problems.push("FolderId is required".to_string());
+        }
+        if request.interval_seconds < MIN_WATCH_INTERVAL_SECONDS {
+            problems.push(format!(
+                "IntervalSeconds must be at least {MIN_WATCH_INTERVAL_SECONDS}"
+            ));
+        }
+        if !problems.is_empty() {
+            return Err(CoreError::InvalidRequest(problems.join("; ")).into());
+        }
+
+        let cancel = self.shutdown.child_token();
+        {
+            let mut watches = self.watches.lock().await;
+            if watches.contains_key(&folder_id) {
+                return Ok(false);
+            }
+            watches.insert(folder_id.clone(), cancel.clone());
+        }
+
+        let service = Arc::clone(self);
+        let request = WatchFolderRequest {
+            folder_id,
+            ..request
+        };
+        tokio::spawn(async move {
+            service.watch_folder(request, cancel).await;
+        });
+        Ok(true)
+    }
+
+    /// Stops watching a folder. A cycle still in flight has its job cancelled, or killed when
+    /// it has not started yet. Returns whether the folder was watched.
+    pub async fn stop_watch(&self, folder_id: &str) -> anyhow::Result<bool> {
+        let token = self.watches.lock().await.remove(folder_id.trim());
+        let Some(token) = token else {
+            return Ok(false);
+        };
+        token.cancel();
+        Ok(true)
+    }
+
+    async fn watch_folder(&self, request: WatchFolderRequest, cancel: CancellationToken) {
+        let interval = Duration::from_secs(request.interval_seconds);
+        while !cancel.is_cancelled() {
+            if let Err(err) = self.run_watch_cycle(&request, &cancel).await {
+                eprintln!("watch {}: cycle failed: {err:#}", request.folder_id);
+            }
+            tokio::select! {
+                _ = cancel.cancelled() => {}
+                _ = tokio::time::sleep(interval) => {}
+            }
+        }
+    }
+
+    /// Runs one batch job over the files changed since the last completed cycle and records
+    /// how far the folder has been processed. A failed job leaves the last run time alone, so
+    /// the next cycle retries the same files.
+    async fn run_watch_cycle(
+        &self,
+        request: &WatchFolderRequest,
+        cancel: &CancellationToken,
+    ) -> anyhow::Result<()> {
+        let mut state = self
+            .job_store
+            .load_watch_state(&request.folder_id)
+            .await?
+            .unwrap_or_else(|| WatchState {
+                folder_id: request.folder_id.clone(),
+                ..WatchState::default()
+            });
+        let spreadsheet_id = request
+            .spreadsheet_id
+            .clone()
+            .filter(|id| !id.trim().is_empty())
+            .or(state.spreadsheet_id.clone());
+
+        let cycle_started_at = Utc::now();
+        let settings = self.settings.read().await.clone();
+        let access_token = self
+            .auth
+            .get_access_token_non_interactive(&settings)
+            .await?;
+        let include_unsupported = settings.unsupported_file_policy != UnsupportedFilePolicy::Skip;
+        let listed_file_ids = self
+            .list_watched_file_ids(&access_token, &request.folder_id, include_unsupported)
+            .await?;
+        let new_file_ids = listed_file_ids
+            .difference(&state.seen_file_ids)
+            .cloned()
+            .collect::<Vec<_>>();
+        if new_file_ids.is_empty() {
+            return Ok(());
+        }
+
+        let job_id = self
+            .start_batch_job(BatchParseRequest {
+                folder_id: request.folder_id.clone(),
+                // A candidate who uploads a new copy of their resume is already in the sheet;
+                // don't append them twice.
+                skip_existing_in_sheet: spreadsheet_id.is_some(),
+                spreadsheet_id,
+                file_ids: new_file_ids.clone(),
+                ..BatchParseRequest::default()
+            })
+            .await?;
+
+        let status = tokio::select! {
+            biased;
+            _ = cancel.cancelled() => {
+                if !self.cancel_job(&job_id).await? {
+                    self.kill_job(&job_id).await?;
+                }
+                return Ok(());
+            }
+            status = self.wait_for_job_to_finish(&job_id) => status?,
+        };
+
+        if status.spreadsheet_id.is_some() {
+            state.spreadsheet_id = status.spreadsheet_id;
+        }
+        if status.status == JobProcessingState::Completed {
+            state.last_run_at = Some(cycle_started_at);
+            state.seen_file_ids.extend(new_file_ids);
+        }
+        state.last_job_id = Some(job_id);
+        self.job_store.save_watch_state(&state).await
+    }
+
+    /// Ids of the resume files in a watched folder.
+    async fn list_watched_file_ids(
+        &self,
+        access_token: &str,
+        folder_id: &str,
+        include_unsupported: bool,
+    ) -> anyhow::Result<BTreeSet<String>> {
+        let pages = self
+            .drive
+            .list_resume_file_pages(
+                access_token,
+                folder_id,
+                None,
+                include_unsupported,
+                &[],
+                None,
+            )
+            .await?;
+        Ok(pages
+            .into_iter()
+            .flat_map(|page| page.files)
+            .map(|file| file.id)
+            .collect())
+    }
+
+    async fn wait_for_job_to_finish(&self, job_id: &str) -> anyhow::Result<JobStatus> {
+        loop {
+            let status = self.get_job_status(job_id).await?;
+            if matches!(
+                status.status,
+                JobProcessingState::Completed
+                    | JobProcessingState::Failed
+                    | JobProcessingState::Revoked
+            ) {
+                return Ok(status);
+            }
+            tokio::time::sleep(WATCH_STATUS_POLL_INTERVAL).await;
+        }
+    }
+
     pub async fn google_auth_sign_in(&self) -> anyhow::Result<GoogleSignInResult> {
         let settings = self.settings.read().await.clone();
         self.auth.sign_in(&settings).await
@@ -345,6 +1455,11 @@ impl CoreService {
         self.auth.complete_manual_sign_in(&settings, request).await
     }
 
+    pub async fn google_auth_complete_deep_link(&self, url: &str) -> anyhow::Result<AuthStatus> {
+        let settings = self.settings.read().await.clone();
+        self.auth.complete_deep_link_sign_in(&settings, url).await
+    }
+
     pub async fn list_drive_folders(
         &self,
         parent_folder_id: Option<String>,
@@ -371,6 +1486,79 @@ impl CoreService {
         self.drive.list_files(&access_token, &folder_id).await
     }
 
+    pub async fn preview_folder(&self, folder_id: String) -> anyhow::Result<Vec<DriveFileRef>> {
+        if folder_id.trim().is_empty() {
+            return Err(CoreError::InvalidRequest("FolderId is required".to_string()).into());
+        }
+
+        let settings = self.settings.read().await.clone();
+        let access_token = self
+            .auth
+            .get_access_token_non_interactive(&settings)
+            .await?;
+        self.drive
+            .list_resume_files(&access_token, &folder_id)
+            .await
+    }
+
+    /// Lists the folder and estimates how long a batch over it would take. Heuristic only.
+    pub async fn estimate_batch(&self, folder_id: String) -> anyhow::Result<BatchEstimate> {
+        let settings = self.settings.read().await.clone();
+        let files = self.preview_folder(folder_id).await?;
+        Ok(estimate_batch_for_files(&files, &settings))
+    }
+
+    /// Downloads and parses up to `sample_size` randomly chosen files from the folder to
+    /// preview extraction quality before a full run. Nothing is written to a job or sheet.
+    pub async fn sample_folder(
+        &self,
+        folder_id: String,
+        sample_size: usize,
+    ) -> anyhow::Result<FolderSample> {
+        if sample_size == 0 || sample_size > MAX_FOLDER_SAMPLE_SIZE {
+            return Err(CoreError::InvalidRequest(format!(
+                "SampleSize must be between 1 and {MAX_FOLDER_SAMPLE_SIZE}"
+            ))
+            .into());
+        }
+
+        let files = self.preview_folder(folder_id).await?;
+        let settings = self.settings.read().await.clone();
+        let access_token = self
+            .auth
+            .get_access_token_non_interactive(&settings)
+            .await?;
+        let parser = self.build_parser(&settings);
+        let sample = files
+            .choose_multiple(&mut rand::rng(), sample_size)
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let candidates = stream::iter(sample.iter().cloned())
+            .map(|file| {
+                let access_token = &access_token;
+                let settings = &settings;
+                let parser = &parser;
+                async move {
+                    match self
+                        .download_file_with_retry(file, access_token, settings, None, None)
+                        .await
+                    {
+                        Ok((file, bytes)) => {
+                            self.parse_downloaded_file(file, bytes, parser, false).await
+                        }
+                        Err(failed) => vec![failed],
+                    }
+                }
+            })
+            .buffer_unordered(settings.download_concurrency())
+            .flat_map(stream::iter)
+            .collect::<Vec<_>>()
+            .await;
+
+        Ok(summarize_sample(files.len(), sample.len(), &candidates))
+    }
+
     pub async fn get_drive_folder_path(
         &self,
         folder_id: String,
@@ -383,19 +1571,64 @@ impl CoreService {
         self.drive.get_folder_path(&access_token, &folder_id).await
     }
 
+    pub async fn check_folder_access(&self, folder_id: String) -> anyhow::Result<FolderAccess> {
+        let settings = self.settings.read().await.clone();
+        let access_token = self
+            .auth
+            .get_access_token_non_interactive(&settings)
+            .await?;
+        self.drive
+            .check_folder_access(&access_token, folder_id.trim())
+            .await
+    }
+
     pub fn google_auth_sign_out(&self) -> anyhow::Result<()> {
         self.auth.sign_out()
     }
 
+    pub fn cancel_sign_in(&self) -> bool {
+        self.auth.cancel_sign_in()
+    }
+
     pub fn google_auth_status(&self) -> anyhow::Result<AuthStatus> {
         self.auth.status()
     }
 
-    async fn process_queue(
-        self: Arc<Self>,
-        mut queue_rx: mpsc::UnboundedReceiver<BatchJobWorkItem>,
-    ) {
-        while let Some(work_item) = queue_rx.recv().await {
+    /// True when batch jobs can authenticate without user interaction. Unlike
+    /// `google_auth_status`, a cached access token without a refresh token doesn't count.
+    pub fn can_run_unattended(&self) -> anyhow::Result<bool> {
+        self.auth.has_refresh_token()
+    }
+
+    /// Stops taking new jobs and cancels running jobs and watches, then waits up to `timeout`
+    /// for the running job to save its results and status. Jobs left queued are marked
+    /// failed on the next launch; both kinds can be resumed from their checkpoint.
+    pub async fn shutdown(&self, timeout: Duration) {
+        self.shutdown.cancel();
+        let deadline = Instant::now() + timeout;
+        while !self.active_job_handles.lock().await.is_empty() {
+            if Instant::now() >= deadline {
+                eprintln!("core: shutdown timed out with a job still running");
+                break;
+            }
+            tokio::time::sleep(ABORT_POLL_INTERVAL).await;
+        }
+        if let Err(err) = self.job_store.flush_pending_statuses().await {
+            eprintln!("core: failed to flush job statuses on shutdown: {err:#}");
+        }
+    }
+
+    async fn process_queue(self: Arc<Self>, mut queue_rx: mpsc::Receiver<BatchJobWorkItem>) {
+        loop {
+            let work_item = tokio::select! {
+                biased;
+                _ = self.shutdown.cancelled() => break,
+                work_item = queue_rx.recv() => match work_item {
+                    Some(work_item) => work_item,
+                    None => break,
+                },
+            };
+            self.queued_jobs.fetch_sub(1, Ordering::SeqCst);
             let job_id = work_item.job_id.clone();
 
             if self.take_killed_job(&job_id).await {
@@ -405,6 +1638,7 @@ impl CoreService {
                 {
                     eprintln!("batch worker kill cleanup error for {job_id}: {err}");
                 }
+                self.emit_stored_job_finished(&job_id).await;
                 self.clear_runtime_job_state(&job_id).await;
                 continue;
             }
@@ -428,6 +1662,7 @@ impl CoreService {
                     {
                         eprintln!("batch worker kill cleanup error for {job_id}: {save_err}");
                     }
+                    self.emit_stored_job_finished(&job_id).await;
                 }
                 Err(err) => {
                     eprintln!("batch worker task failed for {job_id}: {err}");
@@ -444,6 +1679,17 @@ impl CoreService {
     ) -> anyhow::Result<()> {
         let settings = self.settings.read().await.clone();
         let parser = self.build_parser(&settings);
+        // A resumed job runs with today's settings, so the snapshot is rewritten to match.
+        if let Err(err) = self
+            .job_store
+            .save_config(&work_item.job_id, &settings.to_persisted())
+            .await
+        {
+            eprintln!(
+                "failed to save config snapshot for job {}: {err:?}",
+                work_item.job_id
+            );
+        }
 
         let started_at = Utc::now();
         let start_ts = Utc::now();
@@ -455,7 +1701,7 @@ impl CoreService {
             .and_then(|s| s.created_at)
             .or(Some(Utc::now()));
 
-        let cancellation_token = CancellationToken::new();
+        let cancellation_token = self.shutdown.child_token();
         {
             let mut map = self.cancellation_tokens.lock().await;
             map.insert(work_item.job_id.clone(), cancellation_token.clone());
@@ -465,8 +1711,27 @@ impl CoreService {
         let mut results: Vec<ParsedCandidate> = Vec::new();
         let mut processed_count = 0_i32;
         let mut total_files = 0_i32;
+        if let Some(checkpoint) = work_item.checkpoint.as_ref() {
+            // Only results backed by the checkpoint are kept; anything saved after it is
+            // processed again.
+            let processed = checkpoint.processed_file_ids.iter().collect::<HashSet<_>>();
+            results = self
+                .job_store
+                .load_results(&work_item.job_id)
+                .await?
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|candidate| {
+                    candidate
+                        .drive_file_id
+                        .as_ref()
+                        .is_some_and(|id| processed.contains(id))
+                })
+                .collect();
+            processed_count = processed.len() as i32;
+        }
 
-        let status_result = self
+        let pipeline_result = self
             .run_batch_pipeline(
                 &work_item,
                 &settings,
@@ -480,6 +1745,10 @@ impl CoreService {
                 started_at,
             )
             .await;
+        let (status_result, warning) = match pipeline_result {
+            Ok(warning) => (Ok(()), warning),
+            Err(err) => (Err(err), None),
+        };
 
         {
             let mut map = self.cancellation_tokens.lock().await;
@@ -488,6 +1757,9 @@ impl CoreService {
 
         let was_killed = self.has_kill_request(&work_item.job_id).await;
         let was_cancelled = cancellation_token.is_cancelled();
+        let shut_down = was_cancelled && !was_killed && self.shutdown.is_cancelled();
+        let job_id = work_item.job_id.clone();
+        let completion_webhook_url = work_item.request.completion_webhook_url.clone();
 
         match status_result {
             Ok(()) if !(was_killed || was_cancelled) => {
@@ -495,6 +1767,7 @@ impl CoreService {
                 self.job_store
                     .save_results(&work_item.job_id, &results)
                     .await?;
+                self.job_store.clear_checkpoint(&work_item.job_id).await?;
 
                 self.job_store
                     .save_status(&JobStatus {
@@ -505,7 +1778,7 @@ impl CoreService {
                         processed_files: processed_count,
                         spreadsheet_id,
                         results_count: Some(results.len() as i32),
-                        error: None,
+                        error: warning,
                         created_at,
                         started_at: Some(started_at),
                         completed_at: Some(completed_at),
@@ -519,19 +1792,34 @@ impl CoreService {
                 self.job_store
                     .save_results(&work_item.job_id, &results)
                     .await?;
-                self.mark_job_killed(&work_item.job_id, "Job killed by user.")
+                if shut_down {
+                    self.mark_job_stopped(
+                        &work_item.job_id,
+                        JobProcessingState::Failed,
+                        SHUTDOWN_MESSAGE,
+                    )
                     .await?;
+                } else {
+                    self.mark_job_killed(&work_item.job_id, "Job killed by user.")
+                        .await?;
+                }
             }
             Err(err) => {
                 let completed_at = Utc::now();
-                let status = if was_killed || was_cancelled {
+                let status = if shut_down {
+                    JobProcessingState::Failed
+                } else if was_killed || was_cancelled {
                     JobProcessingState::Revoked
                 } else {
                     JobProcessingState::Failed
                 };
                 let error_message = if was_killed {
                     "Job killed by user.".to_string()
+                } else if shut_down {
+                    SHUTDOWN_MESSAGE.to_string()
                 } else {
+                    // The debug form keeps raw Google response bodies for the logs.
+                    eprintln!("batch job {} failed: {err:?}", work_item.job_id);
                     err.to_string()
                 };
 
@@ -545,12 +1833,7 @@ impl CoreService {
                     .save_status(&JobStatus {
                         job_id: work_item.job_id,
                         status,
-                        progress: if total_files == 0 {
-                            0
-                        } else {
-                            (((processed_count as f64) * 100.0 / total_files as f64).floor() as i32)
-                                .min(99)
-                        },
+                        progress: progress_percent(processed_count, total_files),
                         total_files,
                         processed_files: processed_count,
                         spreadsheet_id,
@@ -567,9 +1850,82 @@ impl CoreService {
             }
         }
 
+        self.invalidate_aggregate_stats().await;
+        if let Some(final_status) = self.job_store.load_status(&job_id).await? {
+            if let Some(url) = completion_webhook_url {
+                self.send_completion_webhook(&url, final_status.clone())
+                    .await;
+            }
+            self.emit_job_finished(final_status, &results);
+        }
+
         Ok(())
     }
 
+    /// Tells the UI a job reached a terminal state, so it can link to the sheet without polling.
+    fn emit_job_finished(&self, status: JobStatus, results: &[ParsedCandidate]) {
+        let event = match status.status {
+            JobProcessingState::Completed => JOB_COMPLETE,
+            JobProcessingState::Failed => JOB_FAILED,
+            JobProcessingState::Revoked => JOB_CANCELLED,
+            _ => return,
+        };
+        let sink = self
+            .event_sink
+            .read()
+            .ok()
+            .and_then(|guard| guard.as_ref().cloned());
+        let Some(sink) = sink else {
+            return;
+        };
+
+        match serde_json::to_value(job_finished_event(status, results)) {
+            Ok(payload) => sink(event, payload),
+            Err(err) => eprintln!("failed to serialize {event} event: {err}"),
+        }
+    }
+
+    /// `emit_job_finished` for jobs stopped outside `process_batch_job`, read back from the store.
+    async fn emit_stored_job_finished(&self, job_id: &str) {
+        let status = match self.job_store.load_status(job_id).await {
+            Ok(Some(status)) => status,
+            Ok(None) => return,
+            Err(err) => {
+                eprintln!("failed to load final status for {job_id}: {err}");
+                return;
+            }
+        };
+        let results = self
+            .job_store
+            .load_results(job_id)
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_default();
+        self.emit_job_finished(status, &results);
+    }
+
+    /// Best-effort notification: failures are logged and never affect the job outcome.
+    async fn send_completion_webhook(&self, url: &str, status: JobStatus) {
+        let job_id = status.job_id.clone();
+        let payload = JobCompletionWebhookPayload {
+            spreadsheet_url: status.spreadsheet_id.as_deref().map(spreadsheet_url),
+            status,
+        };
+
+        let result = self
+            .webhook_client
+            .post(url.trim())
+            .timeout(COMPLETION_WEBHOOK_TIMEOUT)
+            .json(&payload)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status());
+        if let Err(err) = result {
+            eprintln!("completion webhook for {job_id} failed: {err}");
+        }
+    }
+
     #[allow(clippy::too_many_arguments)]
     async fn run_batch_pipeline(
         &self,
@@ -583,7 +1939,7 @@ impl CoreService {
         total_files: &mut i32,
         created_at: Option<chrono::DateTime<Utc>>,
         started_at: chrono::DateTime<Utc>,
-    ) -> anyhow::Result<()> {
+    ) -> anyhow::Result<Option<String>> {
         self.ensure_job_not_stopped(&work_item.job_id, cancellation_token)
             .await?;
         self.job_store
@@ -606,47 +1962,152 @@ impl CoreService {
         self.ensure_job_not_stopped(&work_item.job_id, cancellation_token)
             .await?;
         let access_token = self.auth.get_access_token_non_interactive(settings).await?;
-        let drive_files = self
-            .drive
-            .list_resume_files(&access_token, &work_item.request.folder_id)
+        let name_filter = FileNameFilter::from_request(&work_item.request)?;
+        let requested_file_ids = work_item
+            .request
+            .file_ids
+            .iter()
+            .map(String::as_str)
+            .collect::<HashSet<_>>();
+        let include_unsupported = settings.unsupported_file_policy != UnsupportedFilePolicy::Skip;
+        let pages = self
+            .list_pages_from_checkpoint(&access_token, work_item, include_unsupported)
             .await?;
+        let mut listing = ListingProgress::new(
+            work_item.checkpoint.as_ref(),
+            pages
+                .into_iter()
+                .map(|page| DriveFilePage {
+                    files: name_filter
+                        .apply(page.files)
+                        .into_iter()
+                        .filter(|file| {
+                            requested_file_ids.is_empty()
+                                || requested_file_ids.contains(file.id.as_str())
+                        })
+                        .collect(),
+                    ..page
+                })
+                .collect(),
+        );
+        let drive_files = listing.unprocessed_files();
 
         if drive_files.is_empty() {
-            self.job_store.save_results(&work_item.job_id, &[]).await?;
-            *total_files = 0;
-            *processed_count = 0;
-            return Ok(());
+            sort_candidates(results, settings.sort_order);
+            self.job_store
+                .save_results(&work_item.job_id, results)
+                .await?;
+            *total_files = *processed_count;
+            return Ok(None);
         }
 
-        *total_files = drive_files.len() as i32;
+        *total_files = *processed_count + drive_files.len() as i32;
+        let duplicate_warnings = if work_item.request.flag_possible_duplicates {
+            possible_duplicate_warnings(&drive_files)
+        } else {
+            HashMap::new()
+        };
+        let mime_types = drive_files
+            .iter()
+            .map(|file| (file.id.clone(), file.mime_type.clone()))
+            .collect::<HashMap<_, _>>();
 
         self.ensure_job_not_stopped(&work_item.job_id, cancellation_token)
             .await?;
-        if spreadsheet_id.as_deref().unwrap_or_default().is_empty() {
+        // Emails already written to a reused sheet; grows as rows are appended below.
+        let mut sheet_emails = HashSet::new();
+        if work_item.request.skip_existing_in_sheet {
+            if let Some(sheet_id) = spreadsheet_id.as_deref().filter(|id| !id.is_empty()) {
+                sheet_emails = self
+                    .sheets
+                    .read_column(&access_token, sheet_id, &email_column_letter())
+                    .await?
+                    .into_iter()
+                    .map(|email| email.trim().to_lowercase())
+                    .filter(|email| !email.is_empty())
+                    .collect::<HashSet<String>>();
+            }
+        }
+
+        let value_input = if settings.preserve_text_columns {
+            ValueInputOption::Raw
+        } else {
+            ValueInputOption::UserEntered
+        };
+        let creating_sheet = !work_item.request.skip_spreadsheet
+            && spreadsheet_id.as_deref().unwrap_or_default().is_empty();
+        // Optional columns only go to sheets we lay out ourselves.
+        let layout = if creating_sheet {
+            sheet_layout(settings)
+        } else {
+            SheetColumn::DEFAULT_LAYOUT.to_vec()
+        };
+        let color_confidence =
+            settings.color_by_confidence && layout.contains(&SheetColumn::Confidence);
+        let custom_columns = if creating_sheet {
+            custom_column_names(settings)
+        } else {
+            Vec::new()
+        };
+        let header_row = layout
+            .iter()
+            .map(|column| column.header().to_string())
+            .chain(custom_columns.iter().cloned())
+            .collect::<Vec<String>>();
+        let column_count = header_row.len();
+        let review_route = review_route(&work_item.request);
+        // Sheets the app creates get their header up front; a reused sheet (and the review
+        // sheet) only gets one when empty.
+        let data_append_mode = if creating_sheet {
+            AppendMode::AppendAlways
+        } else {
+            AppendMode::SkipIfHeaderMatches
+        };
+        let mut unwritten = UnwrittenRows::new(&header_row);
+        let single_write = creating_sheet && drive_files.len() <= SINGLE_WRITE_MAX_FILES;
+        // Rows held back until every chunk is parsed or the job stops, for a single write
+        // into a freshly created sheet or so they can be sorted. Each row carries its
+        // candidate's index in `results`.
+        let mut buffered_rows: Option<Vec<(usize, Vec<String>)>> =
+            (single_write || settings.sort_order != SortOrder::None).then(Vec::new);
+        if creating_sheet {
+            let title = render_spreadsheet_title(
+                work_item
+                    .request
+                    .spreadsheet_title
+                    .as_deref()
+                    .filter(|title| !title.trim().is_empty())
+                    .unwrap_or(&settings.spreadsheet_title_template),
+                Utc::now(),
+                drive_files.len(),
+            );
             let created_sheet = self
                 .sheets
-                .create_spreadsheet(
-                    &access_token,
-                    &format!(
-                        "Resume Parse Results - {}",
-                        Utc::now().format("%Y-%m-%d %H:%M:%S")
-                    ),
-                )
+                .create_spreadsheet(&access_token, &title)
                 .await?;
 
-            self.sheets
-                .append_rows(
-                    &access_token,
-                    &created_sheet,
-                    &[HEADER_COLUMNS
-                        .iter()
-                        .map(|v| v.to_string())
-                        .collect::<Vec<String>>()],
-                    false,
-                )
-                .await?;
+            if !single_write {
+                self.sheets
+                    .append_rows(
+                        &access_token,
+                        &created_sheet,
+                        std::slice::from_ref(&header_row),
+                        AppendMode::OverwriteHeaders,
+                        value_input,
+                    )
+                    .await?;
+            }
 
             *spreadsheet_id = Some(created_sheet);
+            // Saved before any rows, so a resume after a crash reuses the sheet instead of
+            // orphaning it. One still awaiting its single write is empty, and the resumed
+            // job lays it out from A1.
+            self.job_store
+                .save_checkpoint(
+                    &work_item.job_id,
+                    &listing.checkpoint(&work_item.request, spreadsheet_id),
+                )
+                .await?;
         }
 
         self.ensure_job_not_stopped(&work_item.job_id, cancellation_token)
@@ -655,9 +2116,9 @@ impl CoreService {
             .save_status(&JobStatus {
                 job_id: work_item.job_id.clone(),
                 status: JobProcessingState::Processing,
-                progress: 0,
+                progress: progress_percent(*processed_count, *total_files),
                 total_files: *total_files,
-                processed_files: 0,
+                processed_files: *processed_count,
                 spreadsheet_id: spreadsheet_id.clone(),
                 results_count: None,
                 error: None,
@@ -669,153 +2130,625 @@ impl CoreService {
             .await?;
 
         let chunk_size = settings.spreadsheet_batch_size.max(1);
-        for batch in drive_files.chunks(chunk_size) {
-            self.ensure_job_not_stopped(&work_item.job_id, cancellation_token)
-                .await?;
-
-            let max_concurrency = settings.max_concurrent_requests.max(1);
-            let mut batch_stream = stream::iter(batch.iter().cloned())
-                .map(|file| {
-                    let access_token = access_token.clone();
-                    let settings = settings.clone();
-                    async move {
-                        self.process_single_file_with_retry(file, parser, &access_token, &settings)
-                            .await
-                    }
-                })
-                .buffer_unordered(max_concurrency);
-
-            while let Some(candidate) = batch_stream.next().await {
+        let parse_concurrency = settings.parse_concurrency();
+        // Shared across chunks, so what it learns about the account's quota carries over.
+        let adaptive = settings.adaptive_concurrency.then(|| {
+            AdaptiveConcurrency::new(
+                settings.download_concurrency(),
+                settings.download_concurrency() * ADAPTIVE_CONCURRENCY_HEADROOM,
+            )
+        });
+        let download_concurrency = adaptive
+            .as_ref()
+            .map_or(settings.download_concurrency(), AdaptiveConcurrency::max);
+        let retry_budget = settings.job_retry_budget.map(AtomicUsize::new);
+        let mut last_progress_save: Option<Instant> = None;
+        let chunks_processed = async {
+            for batch in drive_files.chunks(chunk_size) {
                 self.ensure_job_not_stopped(&work_item.job_id, cancellation_token)
                     .await?;
+                self.wait_while_paused(&work_item.job_id, cancellation_token)
+                    .await?;
 
-                *processed_count += 1;
+                // Downloads are I/O-bound and parsing is CPU-bound, so each stage gets its own
+                // limit. The bounded channel keeps downloads from racing far ahead of parsing.
+                let (mut download_tx, download_rx) =
+                    futures::channel::mpsc::channel(parse_concurrency);
+                let download_stage = async {
+                    let mut downloads = stream::iter(batch.iter().cloned())
+                        .map(|file| {
+                            let access_token = access_token.clone();
+                            let adaptive = adaptive.as_ref();
+                            let retry_budget = retry_budget.as_ref();
+                            async move {
+                                if settings.unsupported_file_policy == UnsupportedFilePolicy::Warn
+                                    && !document_parser::is_supported_file(
+                                        &file.name,
+                                        &file.mime_type,
+                                    )
+                                {
+                                    return Err(skipped_file_candidate(file));
+                                }
+                                self.download_file_with_retry(
+                                    file,
+                                    &access_token,
+                                    settings,
+                                    adaptive,
+                                    retry_budget,
+                                )
+                                .await
+                            }
+                        })
+                        .buffer_unordered(download_concurrency);
 
-                let row = candidate_to_sheet_row(&candidate);
-                if row.iter().any(|cell| !cell.trim().is_empty()) {
-                    if let Some(sheet_id) = spreadsheet_id.as_deref() {
-                        self.sheets
-                            .append_rows(&access_token, sheet_id, &[row], true)
-                            .await?;
+                    while let Some(download) = downloads.next().await {
+                        if download_tx.send(download).await.is_err() {
+                            break;
+                        }
                     }
-                }
+                    drop(download_tx);
+                };
 
-                results.push(candidate);
-                self.job_store
-                    .save_results(&work_item.job_id, results)
-                    .await?;
+                let split_multi_resume = work_item.request.split_multi_resume;
+                let parse_stage = async {
+                    let mut parsed_stream = download_rx
+                        .map(|download| async move {
+                            match download {
+                                Ok((file, bytes)) => {
+                                    self.parse_downloaded_file(
+                                        file,
+                                        bytes,
+                                        parser,
+                                        split_multi_resume,
+                                    )
+                                    .await
+                                }
+                                Err(failed) => vec![failed],
+                            }
+                        })
+                        .buffer_unordered(parse_concurrency);
 
-                let progress = if *total_files == 0 {
-                    0
-                } else {
-                    (((*processed_count as f64) * 100.0 / *total_files as f64).floor() as i32)
-                        .min(99)
+                    let mut unsaved_progress = false;
+                    loop {
+                        let next = parsed_stream.next().await;
+                        let chunk_finished = next.is_none();
+                        if let Some(mut candidates) = next {
+                            self.ensure_job_not_stopped(&work_item.job_id, cancellation_token)
+                                .await?;
+
+                            for candidate in &mut candidates {
+                                candidate.mime_type = candidate
+                                    .drive_file_id
+                                    .as_ref()
+                                    .and_then(|id| mime_types.get(id))
+                                    .filter(|mime_type| !mime_type.is_empty())
+                                    .cloned();
+                                if let Some(warning) = candidate
+                                    .drive_file_id
+                                    .as_ref()
+                                    .and_then(|id| duplicate_warnings.get(id))
+                                {
+                                    candidate.errors.push(warning.clone());
+                                }
+                                if !has_required_fields(candidate, &settings.required_fields) {
+                                    candidate.errors.push(missing_required_fields_warning(
+                                        &settings.required_fields,
+                                    ));
+                                }
+                            }
+
+                            let first_index = results.len();
+                            let rows = candidates
+                                .iter()
+                                .enumerate()
+                                .filter(|(_, candidate)| {
+                                    has_required_fields(candidate, &settings.required_fields)
+                                })
+                                .filter(|(_, candidate)| {
+                                    !work_item.request.skip_existing_in_sheet
+                                        || candidate.email.as_deref().is_none_or(|email| {
+                                            sheet_emails.insert(email.trim().to_lowercase())
+                                        })
+                                })
+                                .filter_map(|(index, candidate)| {
+                                    let for_review = review_route.is_some_and(|(_, threshold)| {
+                                        candidate.confidence < threshold
+                                    });
+                                    let mut row = candidate_to_row(
+                                        candidate,
+                                        &layout,
+                                        &custom_columns,
+                                        settings.confidence_as_percent,
+                                    );
+                                    // Optional columns alone do not make a row worth writing.
+                                    let has_data = layout.iter().zip(&row).any(|(column, cell)| {
+                                        SheetColumn::DEFAULT_LAYOUT.contains(column)
+                                            && !cell.trim().is_empty()
+                                    });
+                                    has_data.then(|| {
+                                        fill_missing_cells(
+                                            &mut row,
+                                            &settings.missing_value_placeholder,
+                                        );
+                                        (for_review, first_index + index, row)
+                                    })
+                                })
+                                .collect::<Vec<_>>();
+                            let (review_rows, rows): (Vec<_>, Vec<_>) =
+                                rows.into_iter().partition(|(for_review, _, _)| *for_review);
+                            let rows = rows
+                                .into_iter()
+                                .map(|(_, index, row)| (index, row))
+                                .collect::<Vec<_>>();
+                            if let (Some((review_sheet_id, _)), false) =
+                                (review_route, review_rows.is_empty())
+                            {
+                                let review_rows = review_rows
+                                    .into_iter()
+                                    .map(|(_, _, row)| row)
+                                    .collect::<Vec<_>>();
+                                for page in sheet_pages(&review_rows, settings) {
+                                    let page_rows = rows_for_append(
+                                        AppendMode::SkipIfHeaderMatches,
+                                        &header_row,
+                                        page,
+                                    );
+                                    self.write_rows_or_keep(
+                                        &work_item.job_id,
+                                        settings,
+                                        review_sheet_id,
+                                        page,
+                                        &mut unwritten,
+                                        || {
+                                            self.sheets.append_rows(
+                                                &access_token,
+                                                review_sheet_id,
+                                                &page_rows,
+                                                AppendMode::SkipIfHeaderMatches,
+                                                value_input,
+                                            )
+                                        },
+                                    )
+                                    .await?;
+                                }
+                            }
+                            if let Some(buffer) = buffered_rows.as_mut() {
+                                buffer.extend(rows);
+                            } else if !rows.is_empty() {
+                                let rows = rows.into_iter().map(|(_, row)| row).collect::<Vec<_>>();
+                                if let Some(sheet_id) = spreadsheet_id.as_deref() {
+                                    for page in sheet_pages(&rows, settings) {
+                                        let page_rows =
+                                            rows_for_append(data_append_mode, &header_row, page);
+                                        self.write_rows_or_keep(
+                                            &work_item.job_id,
+                                            settings,
+                                            sheet_id,
+                                            page,
+                                            &mut unwritten,
+                                            || {
+                                                self.sheets.append_rows(
+                                                    &access_token,
+                                                    sheet_id,
+                                                    &page_rows,
+                                                    data_append_mode,
+                                                    value_input,
+                                                )
+                                            },
+                                        )
+                                        .await?;
+                                    }
+                                }
+                            }
+
+                            // Only counted once its rows are written or buffered, so a checkpoint
+                            // never skips a file whose rows were lost.
+                            *processed_count += 1;
+                            if let Some(file_id) = candidates
+                                .first()
+                                .and_then(|candidate| candidate.drive_file_id.as_deref())
+                            {
+                                listing.mark_processed(file_id);
+                            }
+                            results.extend(candidates);
+                            unsaved_progress = true;
+                        }
+
+                        let save_due = last_progress_save
+                            .is_none_or(|saved| saved.elapsed() >= PROGRESS_SAVE_INTERVAL);
+                        if unsaved_progress && (chunk_finished || save_due) {
+                            self.job_store
+                                .save_results(&work_item.job_id, results)
+                                .await?;
+
+                            // Buffered rows are not in the sheet yet, so their files are only
+                            // checkpointed once the buffer is written.
+                            if buffered_rows.is_none() {
+                                self.job_store
+                                    .save_checkpoint(
+                                        &work_item.job_id,
+                                        &listing.checkpoint(&work_item.request, spreadsheet_id),
+                                    )
+                                    .await?;
+                            }
+
+                            self.job_store
+                                .save_status(&JobStatus {
+                                    job_id: work_item.job_id.clone(),
+                                    status: JobProcessingState::Processing,
+                                    progress: progress_percent(*processed_count, *total_files),
+                                    total_files: *total_files,
+                                    processed_files: *processed_count,
+                                    spreadsheet_id: spreadsheet_id.clone(),
+                                    results_count: Some(results.len() as i32),
+                                    error: None,
+                                    created_at,
+                                    started_at: Some(started_at),
+                                    completed_at: None,
+                                    duration_seconds: None,
+                                })
+                                .await?;
+                            unsaved_progress = false;
+                            last_progress_save = Some(Instant::now());
+                        }
+
+                        if chunk_finished {
+                            break;
+                        }
+                    }
+
+                    Ok::<(), anyhow::Error>(())
                 };
 
-                self.job_store
-                    .save_status(&JobStatus {
-                        job_id: work_item.job_id.clone(),
-                        status: JobProcessingState::Processing,
-                        progress,
-                        total_files: *total_files,
-                        processed_files: *processed_count,
-                        spreadsheet_id: spreadsheet_id.clone(),
-                        results_count: Some(results.len() as i32),
-                        error: None,
-                        created_at,
-                        started_at: Some(started_at),
-                        completed_at: None,
-                        duration_seconds: None,
-                    })
+                let ((), parsed) = tokio::join!(download_stage, parse_stage);
+                parsed?;
+            }
+            Ok::<(), anyhow::Error>(())
+        }
+        .await;
+
+        // Buffered rows are written even when the job stops early (an error, cancel, or
+        // shutdown), so the sheet holds every processed file and the checkpoint can count
+        // them as done. Only a kill or crash loses them; the resume then redoes those files
+        // from the checkpoint saved when the sheet was created.
+        if let (Some(mut rows), Some(sheet_id)) = (buffered_rows, spreadsheet_id.as_deref()) {
+            // `results` is still in listing order, so the indexes are valid.
+            rows.sort_by(|(a, _), (b, _)| {
+                compare_candidates(settings.sort_order, &results[*a], &results[*b])
+            });
+            let rows = rows.into_iter().map(|(_, row)| row).collect::<Vec<_>>();
+            let flushed = async {
+                let mut pages = sheet_pages(&rows, settings);
+                if single_write {
+                    // The first page lays out the sheet from A1; later pages are appended below it.
+                    let first_page = std::iter::once(header_row.clone())
+                        .chain(pages.next().unwrap_or_default().iter().cloned())
+                        .collect::<Vec<_>>();
+                    self.write_rows_or_keep(
+                        &work_item.job_id,
+                        settings,
+                        sheet_id,
+                        &first_page[1..],
+                        &mut unwritten,
+                        || {
+                            self.sheets.write_all_values(
+                                &access_token,
+                                sheet_id,
+                                &first_page,
+                                value_input,
+                            )
+                        },
+                    )
+                    .await?;
+                }
+                for page in pages {
+                    let page_rows = rows_for_append(data_append_mode, &header_row, page);
+                    self.write_rows_or_keep(
+                        &work_item.job_id,
+                        settings,
+                        sheet_id,
+                        page,
+                        &mut unwritten,
+                        || {
+                            self.sheets.append_rows(
+                                &access_token,
+                                sheet_id,
+                                &page_rows,
+                                data_append_mode,
+                                value_input,
+                            )
+                        },
+                    )
                     .await?;
+                }
+                Ok::<(), anyhow::Error>(())
+            }
+            .await;
+
+            match (&chunks_processed, flushed) {
+                (_, Ok(())) => {
+                    self.job_store
+                        .save_checkpoint(
+                            &work_item.job_id,
+                            &listing.checkpoint(&work_item.request, spreadsheet_id),
+                        )
+                        .await?;
+                }
+                (Ok(()), Err(err)) => return Err(err),
+                // The stop is what gets reported; the checkpoint stays at the last flush.
+                (Err(_), Err(err)) => eprintln!(
+                    "job {}: could not write buffered rows after stopping: {err:?}",
+                    work_item.job_id
+                ),
+            }
+        }
+        chunks_processed?;
+
+        if color_confidence {
+            if let Some(sheet_id) = spreadsheet_id.as_deref() {
+                // Confidence ends the layout; custom columns come after it.
+                let confidence_index = layout.len() - 1;
+                // Formatting is cosmetic; the rows are already written.
+                if let Err(err) = self
+                    .sheets
+                    .add_confidence_colors(&access_token, sheet_id, confidence_index, column_count)
+                    .await
+                {
+                    eprintln!("confidence colors for sheet {sheet_id} failed: {err:?}");
+                }
+            }
+        }
+
+        sort_candidates(results, settings.sort_order);
+        Ok(unwritten.warning())
+    }
+
+    /// Runs a Sheets write with retries. Once Google keeps throttling, this write and every
+    /// later one are kept in the job's `unwritten-rows.csv` instead, so the job can finish.
+    async fn write_rows_or_keep<F, Fut>(
+        &self,
+        job_id: &str,
+        settings: &RuntimeSettings,
+        sheet_id: &str,
+        rows: &[Vec<String>],
+        unwritten: &mut UnwrittenRows,
+        write: F,
+    ) -> anyhow::Result<()>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = anyhow::Result<()>>,
+    {
+        if !unwritten.sheets_exhausted {
+            let delay = Duration::from_millis(settings.sheet_append_delay_ms);
+            if let Some(wait) = unwritten
+                .last_write
+                .map(|last| delay.saturating_sub(last.elapsed()))
+                .filter(|wait| !wait.is_zero())
+            {
+                tokio::time::sleep(wait).await;
+            }
+            let written = retry_sheets_write(settings, write).await;
+            unwritten.last_write = Some(Instant::now());
+            match written {
+                Ok(()) => return Ok(()),
+                Err(err) if retry_class(&err) == Some(RetryClass::RateLimit) => {
+                    eprintln!(
+                        "Google Sheets quota exhausted for job {job_id}; keeping rows locally: {err}"
+                    );
+                    unwritten.sheets_exhausted = true;
+                }
+                Err(err) => return Err(err),
             }
         }
 
+        unwritten.keep(sheet_id, rows);
+        unwritten.path = Some(
+            self.job_store
+                .save_unwritten_rows(job_id, &unwritten.rows)
+                .await?,
+        );
         Ok(())
     }
 
-    async fn process_single_file_with_retry(
+    /// Lists the job's folder from its checkpoint page, or from the start when there is no
+    /// checkpoint or Drive no longer accepts the saved page token.
+    async fn list_pages_from_checkpoint(
+        &self,
+        access_token: &str,
+        work_item: &BatchJobWorkItem,
+        include_unsupported: bool,
+    ) -> anyhow::Result<Vec<DriveFilePage>> {
+        let folder_id = &work_item.request.folder_id;
+        let mime_types = &work_item.request.allowed_mime_types;
+        let modified_after = work_item.request.modified_after;
+        let page_token = work_item
+            .checkpoint
+            .as_ref()
+            .and_then(|checkpoint| checkpoint.page_token.as_deref());
+        let listed = self
+            .drive
+            .list_resume_file_pages(
+                access_token,
+                folder_id,
+                page_token,
+                include_unsupported,
+                mime_types,
+                modified_after,
+            )
+            .await;
+
+        match listed {
+            Err(err)
+                if page_token.is_some()
+                    && matches!(
+                        err.downcast_ref::<CoreError>(),
+                        Some(CoreError::GoogleApi { status: 400, .. })
+                    ) =>
+            {
+                eprintln!(
+                    "job {}: saved Drive page token rejected, listing from the start",
+                    work_item.job_id
+                );
+                self.drive
+                    .list_resume_file_pages(
+                        access_token,
+                        folder_id,
+                        None,
+                        include_unsupported,
+                        mime_types,
+                        modified_after,
+                    )
+                    .await
+            }
+            listed => listed,
+        }
+    }
+
+    /// Downloads one file, retrying transient failures. With `adaptive`, each attempt waits
+    /// for a slot and reports whether Drive throttled it. Each retry is taken from
+    /// `retry_budget`, the retries left for the whole job; once it is empty, files fail on
+    /// their first error.
+    async fn download_file_with_retry(
         &self,
         file: DriveFileRef,
-        parser: &ResumeDocumentParser,
         access_token: &str,
         settings: &RuntimeSettings,
-    ) -> ParsedCandidate {
+        adaptive: Option<&AdaptiveConcurrency>,
+        retry_budget: Option<&AtomicUsize>,
+    ) -> Result<(DriveFileRef, Vec<u8>), ParsedCandidate> {
         if file.id.trim().is_empty() {
-            return ParsedCandidate::empty(
+            return Err(ParsedCandidate::empty(
                 Some(file.name),
                 None,
-                vec!["Missing file ID".to_string()],
-            );
+                vec![ParseError::new(
+                    ParseErrorKind::MissingFileId,
+                    "Missing file ID",
+                )],
+            ));
         }
 
         let mut errors = Vec::new();
 
         for attempt in 0..settings.max_retries {
-            let processed = match tokio::time::timeout(
+            let slot = match adaptive {
+                Some(adaptive) => Some(adaptive.acquire().await),
+                None => None,
+            };
+            let downloaded = match tokio::time::timeout(
                 FILE_PROCESS_TIMEOUT,
-                self.process_single_file_once(&file, parser, access_token),
+                self.drive.download_file(access_token, &file.id),
             )
             .await
             {
                 Ok(result) => result,
                 Err(timeout_error) => Err(timeout_error.into()),
-            };
+            }
+            .and_then(|bytes| {
+                if bytes.len() < MIN_DOWNLOAD_BYTES {
+                    Err(CoreError::EmptyDownload(bytes.len()).into())
+                } else {
+                    Ok(bytes)
+                }
+            });
+            drop(slot);
+            if let Some(adaptive) = adaptive {
+                match &downloaded {
+                    Ok(_) => adaptive.on_success(),
+                    Err(err) if retry_class(err) == Some(RetryClass::RateLimit) => {
+                        adaptive.on_rate_limited()
+                    }
+                    Err(_) => {}
+                }
+            }
 
-            match processed {
-                Ok(candidate) => return candidate,
+            match downloaded {
+                Ok(bytes) => return Ok((file, bytes)),
                 Err(err) => {
-                    let retryable = is_retryable_error(&err);
+                    let retryable =
+                        retry_class(&err).is_some_and(|class| settings.retry_on.allows(class));
                     let is_last_attempt = attempt + 1 >= settings.max_retries;
+                    let mut budget_exhausted = false;
                     if retryable && !is_last_attempt {
-                        let backoff_seconds =
-                            settings.retry_delay_seconds * 2_f64.powf(attempt as f64);
-                        tokio::time::sleep(Duration::from_secs_f64(backoff_seconds.max(0.1))).await;
-                        continue;
+                        if take_retry(retry_budget) {
+                            let backoff_seconds =
+                                settings.retry_delay_seconds * 2_f64.powf(attempt as f64);
+                            tokio::time::sleep(Duration::from_secs_f64(backoff_seconds.max(0.1)))
+                                .await;
+                            continue;
+                        }
+                        budget_exhausted = true;
                     }
 
-                    errors.push(format!("Error processing file: {err}"));
+                    let code = if err.is::<tokio::time::error::Elapsed>() {
+                        ParseErrorKind::Timeout
+                    } else if matches!(
+                        err.downcast_ref::<CoreError>(),
+                        Some(CoreError::FileUnavailable(_))
+                    ) {
+                        ParseErrorKind::FileUnavailable
+                    } else {
+                        ParseErrorKind::DownloadFailed
+                    };
+                    let mut message = format!("Error processing file: {err}");
+                    if budget_exhausted {
+                        message.push_str(" (not retried: the job's retry budget is used up)");
+                    }
+                    errors.push(ParseError::new(code, message));
                     break;
                 }
             }
         }
 
-        ParsedCandidate {
-            drive_file_id: Some(file.id),
-            source_file: Some(file.name),
-            name: None,
-            email: None,
-            phone: None,
-            linked_in: None,
-            git_hub: None,
-            confidence: 0.0,
+        Err(ParsedCandidate::empty(
+            Some(file.name),
+            Some(file.id),
             errors,
-        }
+        ))
     }
 
-    async fn process_single_file_once(
+    /// Parses one downloaded file. With `split_multi_resume` a file holding several resumes
+    /// yields one candidate per resume, all pointing at the same Drive file.
+    async fn parse_downloaded_file(
         &self,
-        file: &DriveFileRef,
+        file: DriveFileRef,
+        bytes: Vec<u8>,
         parser: &ResumeDocumentParser,
-        access_token: &str,
-    ) -> anyhow::Result<ParsedCandidate> {
-        let bytes = self.drive.download_file(access_token, &file.id).await?;
+        split_multi_resume: bool,
+    ) -> Vec<ParsedCandidate> {
         let normalized_file_name = ensure_filename_extension(&file.name, &file.mime_type);
-        let parsed = parser
-            .parse_resume_bytes(&normalized_file_name, &bytes)
-            .await;
+        let parse = async {
+            if split_multi_resume {
+                parser
+                    .parse_resume_bytes_split(&normalized_file_name, &bytes)
+                    .await
+            } else {
+                vec![
+                    parser
+                        .parse_resume_bytes(&normalized_file_name, &bytes)
+                        .await,
+                ]
+            }
+        };
+        let parsed = match tokio::time::timeout(FILE_PROCESS_TIMEOUT, parse).await {
+            Ok(parsed) => parsed,
+            Err(timeout_error) => {
+                return vec![ParsedCandidate::empty(
+                    Some(file.name),
+                    Some(file.id),
+                    vec![ParseError::new(
+                        ParseErrorKind::Timeout,
+                        format!("Error processing file: {timeout_error}"),
+                    )],
+                )];
+            }
+        };
 
-        Ok(ParsedCandidate {
-            drive_file_id: Some(file.id.clone()),
-            source_file: Some(file.name.clone()),
-            name: parsed.name,
-            email: parsed.email,
-            phone: parsed.phone,
-            linked_in: parsed.linked_in,
-            git_hub: parsed.git_hub,
-            confidence: parsed.confidence,
-            errors: parsed.errors,
-        })
+        parsed
+            .into_iter()
+            .map(|parsed| {
+                candidate_from_extraction(Some(file.id.clone()), Some(file.name.clone()), parsed)
+            })
+            .collect()
     }
 
     fn build_parser(&self, settings: &RuntimeSettings) -> ResumeDocumentParser {
@@ -826,183 +2759,4694 @@ impl CoreService {
                 settings.tesseract_path.clone()
             },
             Duration::from_secs(120),
+        )
+        .with_kept_files_dir(
+            settings
+                .keep_ocr_temp
+                .then(|| logs_root().join(KEPT_OCR_FILES_DIR)),
         );
 
-        let pdf = PdfTextExtractor::new(ocr);
+        let pdf = PdfTextExtractor::new(ocr).with_ocr_disabled(settings.disable_ocr);
         ResumeDocumentParser::new(pdf)
+            .with_email_preference(settings.email_preference)
+            .with_phone_mode(PhoneMode::from_settings(
+                settings.require_international_phone,
+                settings.national_phone_policy,
+            ))
+            .with_skill_dictionary(settings.skill_dictionary.clone())
+            .with_name_denylist(settings.name_denylist.clone())
+            .with_merge_ocr_fields(settings.merge_ocr_fields)
+            .with_deobfuscate_emails(settings.deobfuscate_emails)
+            .with_plain_text_fallback(
+                settings.unsupported_file_policy == UnsupportedFilePolicy::ExtractText,
+            )
+            .with_confidence_weights(settings.confidence_weights)
+            .with_custom_fields(
+                field_extractor::compile_custom_fields(&settings.custom_fields).unwrap_or_else(
+                    |problems| {
+                        eprintln!("ignoring custom fields: {}", problems.join("; "));
+                        Vec::new()
+                    },
+                ),
+            )
     }
 }
 
-fn ensure_filename_extension(file_name: &str, mime_type: &str) -> String {
-    match mime_type {
-        "application/pdf" if !file_name.to_ascii_lowercase().ends_with(".pdf") => {
-            format!("{file_name}.pdf")
-        }
-        "application/vnd.openxmlformats-officedocument.wordprocessingml.document"
-            if !file_name.to_ascii_lowercase().ends_with(".docx") =>
-        {
-            format!("{file_name}.docx")
-        }
-        _ => file_name.to_string(),
+/// Files that failed to parse were never scored, so they keep a confidence of zero. Results
+/// that only carry warnings were scored and are rescored like any other.
+fn rescore_candidate(candidate: &mut ParsedCandidate, weights: &ConfidenceWeights) {
+    if candidate.errors.iter().any(|err| !err.is_warning()) {
+        return;
     }
+    candidate.confidence = field_extractor::score_confidence(
+        candidate.name.as_deref(),
+        candidate.email.as_deref(),
+        candidate.phone.as_deref(),
+        candidate.linked_in.as_deref(),
+        candidate.git_hub.as_deref(),
+        candidate.ocr_used,
+        weights,
+    );
 }
 
-fn candidate_to_sheet_row(candidate: &ParsedCandidate) -> Vec<String> {
-    vec![
-        candidate.name.clone().unwrap_or_default(),
-        candidate
-            .drive_file_id
-            .as_ref()
-            .map(|v| format!("https://drive.google.com/file/d/{v}/view"))
-            .unwrap_or_default(),
-        candidate.phone.clone().unwrap_or_default(),
-        candidate.email.clone().unwrap_or_default(),
-        candidate.linked_in.clone().unwrap_or_default(),
-        candidate.git_hub.clone().unwrap_or_default(),
-    ]
+/// Builds the shared HTTP client used by the auth, Drive, and Sheets clients.
+fn build_http_client(settings: &RuntimeSettings) -> anyhow::Result<reqwest::Client> {
+    http_client_builder(settings)?
+        .default_headers(extra_header_map(&settings.extra_headers)?)
+        .build()
+        .context("failed to build HTTP client")
 }
 
-impl CoreService {
-    async fn recover_orphaned_jobs(&self) -> anyhow::Result<()> {
-        let job_ids = self.job_store.list_jobs().await?;
-        let now = Utc::now();
+/// Builds the client for completion webhooks: same timeouts, user agent, and CA roots as
+/// `build_http_client`, but none of the Google-only `extra_headers`.
+fn build_webhook_client(settings: &RuntimeSettings) -> anyhow::Result<reqwest::Client> {
+    http_client_builder(settings)?
+        .build()
+        .context("failed to build webhook HTTP client")
+}
 
-        for job_id in job_ids {
-            let Some(existing_status) = self.job_store.load_status(&job_id).await? else {
-                continue;
-            };
+fn http_client_builder(settings: &RuntimeSettings) -> anyhow::Result<reqwest::ClientBuilder> {
+    let mut builder = reqwest::Client::builder()
+        .connect_timeout(HTTP_CONNECT_TIMEOUT)
+        .timeout(HTTP_REQUEST_TIMEOUT)
+        .user_agent(user_agent(settings));
 
-            if !matches!(
-                existing_status.status,
-                JobProcessingState::Pending | JobProcessingState::Processing
-            ) {
-                continue;
-            }
+    if let Some(path) = settings.custom_ca_cert_path.as_deref() {
+        for certificate in load_ca_certificates(path)? {
+            builder = builder.add_root_certificate(certificate);
+        }
+    }
 
-            let duration_seconds = existing_status
-                .started_at
-                .map(|started_at| (now - started_at).num_milliseconds().max(0) as f64 / 1000.0);
+    Ok(builder)
+}
 
-            self.job_store
-                .save_status(&JobStatus {
-                    job_id: existing_status.job_id,
-                    status: JobProcessingState::Failed,
-                    progress: existing_status.progress,
-                    total_files: existing_status.total_files,
-                    processed_files: existing_status.processed_files,
-                    spreadsheet_id: existing_status.spreadsheet_id,
-                    results_count: existing_status.results_count,
-                    error: Some(
-                        "Previous app instance stopped before this job completed.".to_string(),
-                    ),
-                    created_at: existing_status.created_at,
-                    started_at: existing_status.started_at,
-                    completed_at: Some(now),
-                    duration_seconds,
-                })
-                .await?;
-        }
+/// `SourceStackDesktop/{version} ({os}; {arch})`, plus the optional suffix from settings.
+fn user_agent(settings: &RuntimeSettings) -> String {
+    let base = format!(
+        "SourceStackDesktop/{} ({}; {})",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    );
 
-        Ok(())
+    match settings.user_agent_suffix.as_deref() {
+        Some(suffix) => format!("{base} {suffix}"),
+        None => base,
     }
+}
 
-    async fn has_kill_request(&self, job_id: &str) -> bool {
-        let killed_jobs = self.killed_jobs.lock().await;
-        killed_jobs.contains(job_id)
-    }
+/// Headers the HTTP client builds and the Google APIs rely on; a custom value could break
+/// requests or leak the signed-in user's token to a proxy.
+const RESERVED_HEADERS: [&str; 8] = [
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "host",
+    "content-length",
+    "content-type",
+    "transfer-encoding",
+    "user-agent",
+];
 
-    async fn take_killed_job(&self, job_id: &str) -> bool {
-        let mut killed_jobs = self.killed_jobs.lock().await;
-        killed_jobs.remove(job_id)
+/// Turns the `extra_headers` setting into headers sent with every Google request.
+fn extra_header_map(headers: &HashMap<String, String>) -> Result<HeaderMap, CoreError> {
+    let mut map = HeaderMap::new();
+    for (name, value) in headers {
+        let header_name = HeaderName::from_bytes(name.trim().as_bytes()).map_err(|_| {
+            CoreError::InvalidRequest(format!("extra header name \"{name}\" is not valid"))
+        })?;
+        if RESERVED_HEADERS.contains(&header_name.as_str()) {
+            return Err(CoreError::InvalidRequest(format!(
+                "extra header \"{name}\" is set by the app and cannot be overridden"
+            )));
+        }
+        let header_value = HeaderValue::from_str(value.trim()).map_err(|_| {
+            CoreError::InvalidRequest(format!("extra header \"{name}\" has an invalid value"))
+        })?;
+        map.insert(header_name, header_value);
     }
+    Ok(map)
+}
 
-    async fn ensure_job_not_stopped(
-        &self,
-        job_id: &str,
-        cancellation_token: &CancellationToken,
-    ) -> anyhow::Result<()> {
-        if cancellation_token.is_cancelled() || self.has_kill_request(job_id).await {
-            return Err(anyhow::anyhow!("job canceled"));
-        }
+fn load_ca_certificates(path: &str) -> anyhow::Result<Vec<reqwest::Certificate>> {
+    let pem = std::fs::read(path)
+        .with_context(|| format!("failed to read custom CA certificate {path}"))?;
+    let certificates = reqwest::Certificate::from_pem_bundle(&pem).map_err(|err| {
+        CoreError::InvalidRequest(format!(
+            "custom CA certificate {path} is not a valid PEM certificate: {err}"
+        ))
+    })?;
 
-        Ok(())
+    if certificates.is_empty() {
+        return Err(CoreError::InvalidRequest(format!(
+            "custom CA certificate {path} does not contain a PEM certificate"
+        ))
+        .into());
     }
 
-    async fn clear_runtime_job_state(&self, job_id: &str) {
-        {
-            let mut active_job_handles = self.active_job_handles.lock().await;
-            active_job_handles.remove(job_id);
+    Ok(certificates)
+}
+
+/// Every problem with `request` that can be found without calling Google.
+fn batch_request_problems(request: &BatchParseRequest) -> Vec<String> {
+    let mut problems = Vec::new();
+    if request.folder_id.trim().is_empty() {
+        problems.push("FolderId is required".to_string());
+    }
+    let patterns = [
+        compile_name_pattern("nameIncludeRegex", &request.name_include_regex),
+        compile_name_pattern("nameExcludeRegex", &request.name_exclude_regex),
+    ];
+    for err in patterns.into_iter().filter_map(Result::err) {
+        problems.push(invalid_request_message(err));
+    }
+    if request.skip_spreadsheet
+        && !request
+            .spreadsheet_id
+            .as_deref()
+            .unwrap_or_default()
+            .trim()
+            .is_empty()
+    {
+        problems.push("SpreadsheetId cannot be set when the spreadsheet is skipped".to_string());
+    }
+    if let Err(err) = validate_webhook_url(request.completion_webhook_url.as_deref()) {
+        problems.push(invalid_request_message(err));
+    }
+    let supported_mime_types = document_parser::supported_file_types()
+        .into_iter()
+        .map(|file_type| file_type.mime_type)
+        .collect::<Vec<_>>();
+    for mime_type in &request.allowed_mime_types {
+        if !supported_mime_types.contains(&mime_type.trim().to_string()) {
+            problems.push(format!(
+                "AllowedMimeTypes entry is not a supported type: {mime_type}"
+            ));
         }
-        {
-            let mut cancellation_tokens = self.cancellation_tokens.lock().await;
-            cancellation_tokens.remove(job_id);
+    }
+    let review_sheet = request
+        .review_spreadsheet_id
+        .as_deref()
+        .is_some_and(|id| !id.trim().is_empty());
+    match request.review_confidence_threshold {
+        Some(threshold) if !(0.0..=1.0).contains(&threshold) => {
+            problems.push("ReviewConfidenceThreshold must be between 0 and 1".to_string())
         }
-        {
-            let mut killed_jobs = self.killed_jobs.lock().await;
-            killed_jobs.remove(job_id);
+        Some(_) if !review_sheet => {
+            problems.push("ReviewConfidenceThreshold requires a ReviewSpreadsheetId".to_string())
+        }
+        None if review_sheet => {
+            problems.push("ReviewSpreadsheetId requires a ReviewConfidenceThreshold".to_string())
         }
+        _ => {}
     }
+    if review_sheet && request.skip_spreadsheet {
+        problems
+            .push("ReviewSpreadsheetId cannot be set when the spreadsheet is skipped".to_string());
+    }
+    problems
+}
 
-    async fn mark_job_killed(&self, job_id: &str, message: &str) -> anyhow::Result<()> {
-        let Some(existing_status) = self.job_store.load_status(job_id).await? else {
-            return Ok(());
-        };
+/// Review sheet id and threshold when the request routes low-confidence candidates away
+/// from the main sheet.
+fn review_route(request: &BatchParseRequest) -> Option<(&str, f64)> {
+    let spreadsheet_id = request
+        .review_spreadsheet_id
+        .as_deref()
+        .map(str::trim)
+        .filter(|id| !id.is_empty() && !request.skip_spreadsheet)?;
+    Some((spreadsheet_id, request.review_confidence_threshold?))
+}
 
-        if matches!(
-            existing_status.status,
-            JobProcessingState::Completed
-                | JobProcessingState::Failed
-                | JobProcessingState::Revoked
-        ) {
-            return Ok(());
-        }
+fn invalid_request_message(err: CoreError) -> String {
+    match err {
+        CoreError::InvalidRequest(message) => message,
+        other => other.to_string(),
+    }
+}
 
-        let completed_at = Utc::now();
-        let duration_seconds = existing_status.started_at.map(|started_at| {
-            (completed_at - started_at).num_milliseconds().max(0) as f64 / 1000.0
-        });
+fn validate_webhook_url(url: Option<&str>) -> Result<(), CoreError> {
+    let Some(url) = url else {
+        return Ok(());
+    };
 
-        self.job_store
-            .save_status(&JobStatus {
-                job_id: existing_status.job_id,
-                status: JobProcessingState::Revoked,
-                progress: existing_status.progress,
-                total_files: existing_status.total_files,
-                processed_files: existing_status.processed_files,
-                spreadsheet_id: existing_status.spreadsheet_id,
-                results_count: existing_status.results_count,
-                error: Some(message.to_string()),
-                created_at: existing_status.created_at,
-                started_at: existing_status.started_at,
-                completed_at: Some(completed_at),
-                duration_seconds,
-            })
-            .await
+    match Url::parse(url.trim()) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => Ok(()),
+        _ => Err(CoreError::InvalidRequest(
+            "CompletionWebhookUrl must be an http(s) URL".to_string(),
+        )),
     }
 }
 
-fn is_retryable_error(error: &anyhow::Error) -> bool {
-    if error
-        .downcast_ref::<tokio::time::error::Elapsed>()
-        .is_some()
-    {
-        return true;
+fn spreadsheet_url(spreadsheet_id: &str) -> String {
+    format!("https://docs.google.com/spreadsheets/d/{spreadsheet_id}")
+}
+
+fn job_finished_event(status: JobStatus, results: &[ParsedCandidate]) -> JobFinishedEvent {
+    let failed_count = results
+        .iter()
+        .filter(|candidate| candidate.errors.iter().any(|error| !error.is_warning()))
+        .count();
+    JobFinishedEvent {
+        spreadsheet_url: status.spreadsheet_id.as_deref().map(spreadsheet_url),
+        status,
+        parsed_count: results.len() - failed_count,
+        failed_count,
+        ocr_count: results
+            .iter()
+            .filter(|candidate| candidate.ocr_used)
+            .count(),
     }
+}
 
-    if let Some(core_error) = error.downcast_ref::<CoreError>() {
-        return core_error.is_retryable();
+/// Settings updates use `None` to keep the current value and an empty string to clear it.
+fn merge_optional_setting(update: Option<String>, previous: Option<String>) -> Option<String> {
+    match update {
+        Some(value) => Some(value.trim().to_string()).filter(|v| !v.is_empty()),
+        None => previous,
     }
+}
 
-    if let Some(reqwest_error) = error.downcast_ref::<reqwest::Error>() {
-        if reqwest_error.is_timeout() || reqwest_error.is_connect() {
-            return true;
+fn progress_percent(processed: i32, total: i32) -> i32 {
+    if total == 0 {
+        0
+    } else {
+        (((processed as f64) * 100.0 / total as f64).floor() as i32).min(99)
+    }
+}
+
+/// Tracks which listed Drive pages are fully processed, so a checkpoint can name the first
+/// page that still has work left.
+struct ListingProgress {
+    pages: Vec<DriveFilePage>,
+    page_of_file: HashMap<String, usize>,
+    remaining_per_page: Vec<usize>,
+    processed_file_ids: Vec<String>,
+}
+
+impl ListingProgress {
+    fn new(checkpoint: Option<&JobCheckpoint>, pages: Vec<DriveFilePage>) -> Self {
+        let processed_file_ids = checkpoint
+            .map(|checkpoint| checkpoint.processed_file_ids.clone())
+            .unwrap_or_default();
+        let processed = processed_file_ids.iter().collect::<HashSet<_>>();
+
+        let mut page_of_file = HashMap::new();
+        let mut remaining_per_page = Vec::with_capacity(pages.len());
+        for (index, page) in pages.iter().enumerate() {
+            let mut remaining = 0;
+            for file in page
+                .files
+                .iter()
+                .filter(|file| !processed.contains(&file.id))
+            {
+                if page_of_file.insert(file.id.clone(), index).is_none() {
+                    remaining += 1;
+                }
+            }
+            remaining_per_page.push(remaining);
         }
 
-        if let Some(status) = reqwest_error.status() {
-            let code = status.as_u16();
-            return code == 429 || code >= 500;
+        Self {
+            pages,
+            page_of_file,
+            remaining_per_page,
+            processed_file_ids,
         }
     }
 
-    false
+    /// Listed files not yet processed, in listing order.
+    fn unprocessed_files(&self) -> Vec<DriveFileRef> {
+        let mut seen = HashSet::new();
+        self.pages
+            .iter()
+            .flat_map(|page| &page.files)
+            .filter(|file| self.page_of_file.contains_key(&file.id) && seen.insert(&file.id))
+            .cloned()
+            .collect()
+    }
+
+    fn mark_processed(&mut self, file_id: &str) {
+        if let Some(index) = self.page_of_file.remove(file_id) {
+            self.remaining_per_page[index] -= 1;
+            self.processed_file_ids.push(file_id.to_string());
+        }
+    }
+
+    fn checkpoint(
+        &self,
+        request: &BatchParseRequest,
+        spreadsheet_id: &Option<String>,
+    ) -> JobCheckpoint {
+        // With every page done, restarting at the last page re-lists the least.
+        let page = self
+            .remaining_per_page
+            .iter()
+            .position(|remaining| *remaining > 0)
+            .or(self.pages.len().checked_sub(1));
+
+        JobCheckpoint {
+            request: request.clone(),
+            spreadsheet_id: spreadsheet_id.clone(),
+            page_token: page.and_then(|index| self.pages[index].page_token.clone()),
+            processed_file_ids: self.processed_file_ids.clone(),
+        }
+    }
+}
+
+/// Optional include/exclude patterns matched against Drive file names before processing.
+struct FileNameFilter {
+    include: Option<Regex>,
+    exclude: Option<Regex>,
+}
+
+impl FileNameFilter {
+    fn from_request(request: &BatchParseRequest) -> Result<Self, CoreError> {
+        Ok(Self {
+            include: compile_name_pattern("nameIncludeRegex", &request.name_include_regex)?,
+            exclude: compile_name_pattern("nameExcludeRegex", &request.name_exclude_regex)?,
+        })
+    }
+
+    fn apply(&self, files: Vec<DriveFileRef>) -> Vec<DriveFileRef> {
+        files
+            .into_iter()
+            .filter(|file| {
+                self.include
+                    .as_ref()
+                    .is_none_or(|regex| regex.is_match(&file.name))
+                    && !self
+                        .exclude
+                        .as_ref()
+                        .is_some_and(|regex| regex.is_match(&file.name))
+            })
+            .collect()
+    }
+}
+
+/// File name with its extension and copy markers (`(1)`, `- Copy`, `Copy of`) removed,
+/// lowercased, so re-downloads of the same file compare equal.
+fn normalized_file_stem(file_name: &str) -> String {
+    let stem = std::path::Path::new(file_name)
+        .file_stem()
+        .and_then(|v| v.to_str())
+        .unwrap_or(file_name);
+    let mut normalized = stem.trim().to_lowercase();
+    loop {
+        let stripped = COPY_MARKER_RE
+            .replace_all(&normalized, "")
+            .trim()
+            .to_string();
+        if stripped == normalized || stripped.is_empty() {
+            break;
+        }
+        normalized = stripped;
+    }
+
+    normalized
+        .split(|c: char| c.is_whitespace() || c == '_' || c == '-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// One Drive file per candidate with a `drive_file_id`, in result order. Split
+/// multi-resume documents share a file and are only listed once.
+fn resume_export_files(
+    results: &[ParsedCandidate],
+    only_drive_ids: Option<&[String]>,
+) -> Vec<DriveFileRef> {
+    let mut seen = HashSet::new();
+    results
+        .iter()
+        .filter_map(|candidate| {
+            let id = candidate.drive_file_id.as_deref()?.trim();
+            let selected = only_drive_ids.is_none_or(|ids| ids.iter().any(|wanted| wanted == id));
+            (selected && !id.is_empty() && seen.insert(id.to_string())).then(|| DriveFileRef {
+                id: id.to_string(),
+                name: candidate.source_file.clone().unwrap_or_default(),
+                mime_type: candidate.mime_type.clone().unwrap_or_default(),
+                size: None,
+            })
+        })
+        .collect()
+}
+
+fn resume_export_cap_message(max_bytes: u64) -> String {
+    format!(
+        "Skipped: the export is capped at {} MB",
+        max_bytes / (1024 * 1024)
+    )
+}
+
+/// A zip entry name for `file_name` that no earlier entry uses, e.g. `cv (2).pdf`.
+fn unique_entry_name(file_name: &str, used: &mut HashSet<String>) -> String {
+    let file_name = file_name.replace(['/', '\\'], "_");
+    let path = Path::new(&file_name);
+    let stem = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or(&file_name);
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(|extension| format!(".{extension}"))
+        .unwrap_or_default();
+
+    let mut name = file_name.clone();
+    let mut copy = 2;
+    while !used.insert(name.to_ascii_lowercase()) {
+        name = format!("{stem} ({copy}){extension}");
+        copy += 1;
+    }
+    name
+}
+
+/// Warnings keyed by Drive file id for files sharing a normalized stem with another file.
+fn possible_duplicate_warnings(files: &[DriveFileRef]) -> HashMap<String, ParseError> {
+    let mut groups: HashMap<String, Vec<&DriveFileRef>> = HashMap::new();
+    for file in files {
+        groups
+            .entry(normalized_file_stem(&file.name))
+            .or_default()
+            .push(file);
+    }
+
+    let mut warnings = HashMap::new();
+    for group in groups.values().filter(|group| group.len() > 1) {
+        for file in group {
+            let others = group
+                .iter()
+                .filter(|other| other.id != file.id)
+                .map(|other| other.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            warnings.insert(
+                file.id.clone(),
+                ParseError::new(
+                    ParseErrorKind::PossibleDuplicate,
+                    format!("Warning: possible duplicate of {others}"),
+                ),
+            );
+        }
+    }
+    warnings
+}
+
+fn compile_name_pattern(field: &str, pattern: &Option<String>) -> Result<Option<Regex>, CoreError> {
+    let Some(pattern) = pattern.as_deref().filter(|v| !v.trim().is_empty()) else {
+        return Ok(None);
+    };
+
+    Regex::new(pattern)
+        .map(Some)
+        .map_err(|err| CoreError::InvalidRequest(format!("{field} is not a valid regex: {err}")))
+}
+
+/// Stands in for a file of a type the parser cannot read, which is listed but not downloaded.
+fn skipped_file_candidate(file: DriveFileRef) -> ParsedCandidate {
+    let message = format!(
+        "Warning: skipped {} because {} files are not supported",
+        file.name, file.mime_type
+    );
+    ParsedCandidate::empty(
+        Some(file.name),
+        Some(file.id),
+        vec![ParseError::new(ParseErrorKind::SkippedFileType, message)],
+    )
+}
+
+fn ensure_filename_extension(file_name: &str, mime_type: &str) -> String {
+    match mime_type {
+        "application/pdf" if !file_name.to_ascii_lowercase().ends_with(".pdf") => {
+            format!("{file_name}.pdf")
+        }
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document"
+            if !file_name.to_ascii_lowercase().ends_with(".docx") =>
+        {
+            format!("{file_name}.docx")
+        }
+        "application/vnd.oasis.opendocument.text"
+            if !file_name.to_ascii_lowercase().ends_with(".odt") =>
+        {
+            format!("{file_name}.odt")
+        }
+        _ => file_name.to_string(),
+    }
+}
+
+/// `page` laid out as `append_rows` expects for `mode`: led by the header unless every row
+/// is data.
+fn rows_for_append(mode: AppendMode, header: &[String], page: &[Vec<String>]) -> Vec<Vec<String>> {
+    match mode {
+        AppendMode::AppendAlways => page.to_vec(),
+        AppendMode::SkipIfHeaderMatches | AppendMode::OverwriteHeaders => {
+            std::iter::once(header.to_vec())
+                .chain(page.iter().cloned())
+                .collect()
+        }
+    }
+}
+
+fn candidate_from_extraction(
+    drive_file_id: Option<String>,
+    source_file: Option<String>,
+    parsed: ResumeExtractionResult,
+) -> ParsedCandidate {
+    ParsedCandidate {
+        drive_file_id,
+        source_file,
+        mime_type: None,
+        name: parsed.name,
+        email_domain: parsed
+            .email
+            .as_deref()
+            .and_then(field_extractor::email_domain),
+        email: parsed.email,
+        phone: parsed.phone,
+        phone_extension: parsed.phone_extension,
+        linked_in: parsed.linked_in,
+        git_hub: parsed.git_hub,
+        skills: parsed.skills,
+        headline: parsed.headline,
+        notice_period: parsed.notice_period,
+        field_sources: parsed.field_sources,
+        name_candidates: parsed.name_candidates,
+        ocr_used: parsed.ocr_used,
+        parse_duration_ms: None,
+        custom: parsed.custom,
+        education: parsed.education,
+        confidence: parsed.confidence,
+        errors: parsed.errors,
+    }
+}
+
+/// Estimates a batch from listed file types and sizes: downloads and parses run as
+/// separate stages, so the slower stage at the configured concurrency sets the pace.
+fn estimate_batch_for_files(files: &[DriveFileRef], settings: &RuntimeSettings) -> BatchEstimate {
+    let extensions_by_mime = document_parser::supported_file_types()
+        .into_iter()
+        .map(|file_type| (file_type.mime_type, file_type.extension))
+        .collect::<HashMap<_, _>>();
+
+    let mut files_by_type = BTreeMap::new();
+    let mut likely_scanned_pdfs = 0;
+    for file in files {
+        let extension = extensions_by_mime
+            .get(&file.mime_type)
+            .cloned()
+            .or_else(|| {
+                Path::new(&file.name)
+                    .extension()
+                    .map(|extension| extension.to_string_lossy().to_ascii_lowercase())
+            })
+            .unwrap_or_else(|| "other".to_string());
+        if extension == "pdf"
+            && file
+                .size
+                .is_some_and(|size| size >= LIKELY_SCANNED_PDF_BYTES)
+        {
+            likely_scanned_pdfs += 1;
+        }
+        *files_by_type.entry(extension).or_insert(0) += 1;
+    }
+
+    let ocr_files = if settings.disable_ocr {
+        0
+    } else {
+        likely_scanned_pdfs
+    };
+    let download_seconds =
+        files.len() as f64 * ESTIMATE_DOWNLOAD_SECONDS / settings.download_concurrency() as f64;
+    let parse_seconds = ((files.len() - ocr_files) as f64 * ESTIMATE_TEXT_PARSE_SECONDS
+        + ocr_files as f64 * ESTIMATE_OCR_PARSE_SECONDS)
+        / settings.parse_concurrency() as f64;
+    let expected_seconds = download_seconds.max(parse_seconds);
+
+    BatchEstimate {
+        file_count: files.len(),
+        files_by_type,
+        likely_scanned_pdfs,
+        estimated_min_seconds: (expected_seconds * 0.5).floor() as u64,
+        estimated_max_seconds: (expected_seconds * 2.0).ceil() as u64,
+        note: "Approximate. Scans are guessed from file size, and actual time depends on \
+               OCR, network speed, and Google API limits."
+            .to_string(),
+    }
+}
+
+fn summarize_sample(
+    total_files: usize,
+    sampled_files: usize,
+    candidates: &[ParsedCandidate],
+) -> FolderSample {
+    let failed_files = candidates
+        .iter()
+        .filter(|candidate| candidate.errors.iter().any(|error| !error.is_warning()))
+        .count();
+    let count = candidates.len().max(1) as f64;
+    let band = |range: fn(f64) -> bool| {
+        candidates
+            .iter()
+            .filter(|candidate| range(candidate.confidence))
+            .count()
+    };
+
+    FolderSample {
+        total_files,
+        sampled_files,
+        failed_files,
+        average_confidence: candidates
+            .iter()
+            .map(|candidate| candidate.confidence)
+            .sum::<f64>()
+            / count,
+        high_confidence: band(|confidence| confidence >= HIGH_CONFIDENCE),
+        medium_confidence: band(|confidence| {
+            (MEDIUM_CONFIDENCE..HIGH_CONFIDENCE).contains(&confidence)
+        }),
+        low_confidence: band(|confidence| confidence < MEDIUM_CONFIDENCE),
+        field_hit_rates: FieldKind::ALL
+            .into_iter()
+            .map(|field| FieldHitRate {
+                field,
+                rate: candidates
+                    .iter()
+                    .filter(|candidate| field.is_present(candidate))
+                    .count() as f64
+                    / count,
+            })
+            .collect(),
+    }
+}
+
+/// How `a` and `b` compare in output `order`. Candidates that compare equal keep their
+/// listing order, since the sorts are stable.
+fn compare_candidates(
+    order: SortOrder,
+    a: &ParsedCandidate,
+    b: &ParsedCandidate,
+) -> std::cmp::Ordering {
+    use std::cmp::Ordering;
+
+    match order {
+        SortOrder::None => Ordering::Equal,
+        SortOrder::ConfidenceDesc => b.confidence.total_cmp(&a.confidence),
+        SortOrder::NameAsc => {
+            let name = |candidate: &ParsedCandidate| {
+                candidate
+                    .name
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .map(str::to_lowercase)
+            };
+            match (name(a), name(b)) {
+                (Some(a), Some(b)) => a.cmp(&b),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            }
+        }
+    }
+}
+
+fn sort_candidates(candidates: &mut [ParsedCandidate], order: SortOrder) {
+    if order != SortOrder::None {
+        candidates.sort_by(|a, b| compare_candidates(order, a, b));
+    }
+}
+
+/// True when the candidate has at least one of `required`; an empty list requires nothing.
+fn has_required_fields(candidate: &ParsedCandidate, required: &[FieldKind]) -> bool {
+    required.is_empty() || required.iter().any(|field| field.is_present(candidate))
+}
+
+fn missing_required_fields_warning(required: &[FieldKind]) -> ParseError {
+    let fields = required
+        .iter()
+        .map(|field| field.label())
+        .collect::<Vec<_>>()
+        .join(", ");
+    ParseError::new(
+        ParseErrorKind::MissingRequiredFields,
+        format!(
+            "Warning: left out of the sheet because none of the required fields ({fields}) were found"
+        ),
+    )
+}
+
+/// Confidence cell text: `0.85`, or `85%` when `as_percent` is set.
+fn format_confidence(confidence: f64, as_percent: bool) -> String {
+    if as_percent {
+        format!("{:.0}%", confidence * 100.0)
+    } else {
+        format!("{confidence:.2}")
+    }
+}
+
+/// Column letter of the "Email ID" header, used to read back existing emails.
+fn email_column_letter() -> String {
+    let index = SheetColumn::DEFAULT_LAYOUT
+        .iter()
+        .position(|column| *column == SheetColumn::Email)
+        .unwrap_or(3);
+    column_letter(index)
+}
+
+/// Fills `{date}`, `{time}`, and `{count}` (files in the job) into a sheet title template.
+fn render_spreadsheet_title(
+    template: &str,
+    now: chrono::DateTime<Utc>,
+    file_count: usize,
+) -> String {
+    template
+        .replace("{date}", &now.format("%Y-%m-%d").to_string())
+        .replace("{time}", &now.format("%H:%M:%S").to_string())
+        .replace("{count}", &file_count.to_string())
+        .trim()
+        .to_string()
+}
+
+/// The phone number with any extension appended as " ext. 89", which Sheets keeps as text.
+fn phone_cell(candidate: &ParsedCandidate) -> String {
+    match (&candidate.phone, &candidate.phone_extension) {
+        (Some(phone), Some(extension)) => format!("{phone} ext. {extension}"),
+        (phone, _) => phone.clone().unwrap_or_default(),
+    }
+}
+
+/// Columns of a sheet the app lays out: the defaults, then the enabled optional columns.
+/// Confidence stays last because confidence colors are keyed to the final column.
+fn sheet_layout(settings: &RuntimeSettings) -> Vec<SheetColumn> {
+    let mut layout = SheetColumn::DEFAULT_LAYOUT.to_vec();
+    if !settings.skill_dictionary.is_empty() {
+        layout.push(SheetColumn::Skills);
+    }
+    if settings.email_domain_column {
+        layout.push(SheetColumn::EmailDomain);
+    }
+    if settings.notice_period_column {
+        layout.push(SheetColumn::NoticePeriod);
+    }
+    if settings.education_column {
+        layout.push(SheetColumn::Education);
+    }
+    // Confidence colors need their column, and the percent format is pointless without it.
+    if settings.color_by_confidence || settings.confidence_as_percent {
+        layout.push(SheetColumn::Confidence);
+    }
+    layout
+}
+
+/// Trims rules and drops blank ones, so an empty line in the editor is not an error.
+fn sanitize_custom_fields(rules: Vec<CustomFieldRule>) -> Vec<CustomFieldRule> {
+    rules
+        .into_iter()
+        .map(|rule| CustomFieldRule {
+            name: rule.name.trim().to_string(),
+            pattern: rule.pattern.trim().to_string(),
+        })
+        .filter(|rule| !rule.name.is_empty() || !rule.pattern.is_empty())
+        .collect()
+}
+
+/// Custom field columns new sheets get after the layout, in rule order.
+fn custom_column_names(settings: &RuntimeSettings) -> Vec<String> {
+    settings
+        .custom_fields
+        .iter()
+        .map(|rule| rule.name.trim().to_string())
+        .collect()
+}
+
+/// One cell per column of `layout` followed by one per custom column, shared by batch
+/// sheets and `parse_single_as_row`.
+fn candidate_to_row(
+    candidate: &ParsedCandidate,
+    layout: &[SheetColumn],
+    custom_columns: &[String],
+    confidence_as_percent: bool,
+) -> Vec<String> {
+    let custom_cells = custom_columns
+        .iter()
+        .map(|name| candidate.custom.get(name).cloned().unwrap_or_default());
+    layout
+        .iter()
+        .map(|column| match column {
+            SheetColumn::Name => candidate.name.clone().unwrap_or_default(),
+            SheetColumn::ResumeLink => candidate
+                .drive_file_id
+                .as_ref()
+                .map(|v| format!("https://drive.google.com/file/d/{v}/view"))
+                .unwrap_or_default(),
+            SheetColumn::Phone => phone_cell(candidate),
+            SheetColumn::Email => candidate.email.clone().unwrap_or_default(),
+            SheetColumn::EmailDomain => candidate.email_domain.clone().unwrap_or_default(),
+            SheetColumn::LinkedIn => candidate.linked_in.clone().unwrap_or_default(),
+            SheetColumn::GitHub => candidate.git_hub.clone().unwrap_or_default(),
+            SheetColumn::Skills => candidate.skills.join(", "),
+            SheetColumn::NoticePeriod => candidate.notice_period.clone().unwrap_or_default(),
+            SheetColumn::Education => candidate.education.clone().unwrap_or_default(),
+            SheetColumn::Confidence => {
+                format_confidence(candidate.confidence, confidence_as_percent)
+            }
+        })
+        .chain(custom_cells)
+        .collect()
+}
+
+/// Replaces blank cells with `placeholder`; an empty placeholder leaves them blank.
+fn fill_missing_cells(row: &mut [String], placeholder: &str) {
+    if placeholder.is_empty() {
+        return;
+    }
+    for cell in row.iter_mut().filter(|cell| cell.trim().is_empty()) {
+        *cell = placeholder.to_string();
+    }
+}
+
+impl CoreService {
+    async fn recover_orphaned_jobs(&self) -> anyhow::Result<()> {
+        let job_ids = self.job_store.list_jobs().await?;
+        let now = Utc::now();
+
+        for job_id in job_ids {
+            let Some(existing_status) = self.job_store.load_status(&job_id).await? else {
+                continue;
+            };
+
+            if !matches!(
+                existing_status.status,
+                JobProcessingState::Pending
+                    | JobProcessingState::Processing
+                    | JobProcessingState::Paused
+            ) {
+                continue;
+            }
+
+            let duration_seconds = existing_status
+                .started_at
+                .map(|started_at| (now - started_at).num_milliseconds().max(0) as f64 / 1000.0);
+
+            self.job_store
+                .save_status(&JobStatus {
+                    job_id: existing_status.job_id,
+                    status: JobProcessingState::Failed,
+                    progress: existing_status.progress,
+                    total_files: existing_status.total_files,
+                    processed_files: existing_status.processed_files,
+                    spreadsheet_id: existing_status.spreadsheet_id,
+                    results_count: existing_status.results_count,
+                    error: Some(
+                        "Previous app instance stopped before this job completed.".to_string(),
+                    ),
+                    created_at: existing_status.created_at,
+                    started_at: existing_status.started_at,
+                    completed_at: Some(now),
+                    duration_seconds,
+                })
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn has_kill_request(&self, job_id: &str) -> bool {
+        let killed_jobs = self.killed_jobs.lock().await;
+        killed_jobs.contains(job_id)
+    }
+
+    async fn take_killed_job(&self, job_id: &str) -> bool {
+        let mut killed_jobs = self.killed_jobs.lock().await;
+        killed_jobs.remove(job_id)
+    }
+
+    async fn ensure_job_not_stopped(
+        &self,
+        job_id: &str,
+        cancellation_token: &CancellationToken,
+    ) -> anyhow::Result<()> {
+        if cancellation_token.is_cancelled() || self.has_kill_request(job_id).await {
+            return Err(anyhow::anyhow!("job canceled"));
+        }
+
+        Ok(())
+    }
+
+    async fn is_job_paused(&self, job_id: &str) -> bool {
+        let paused_jobs = self.paused_jobs.lock().await;
+        paused_jobs.contains(job_id)
+    }
+
+    /// Parks the pipeline while the job is paused, flipping its persisted state to `Paused`
+    /// and back to `Processing` on resume. Cancelling or killing a paused job ends the wait.
+    async fn wait_while_paused(
+        &self,
+        job_id: &str,
+        cancellation_token: &CancellationToken,
+    ) -> anyhow::Result<()> {
+        if !self.is_job_paused(job_id).await {
+            return Ok(());
+        }
+
+        self.set_job_state(job_id, JobProcessingState::Paused)
+            .await?;
+        loop {
+            let pause_changed = self.pause_changed.notified();
+            tokio::pin!(pause_changed);
+            pause_changed.as_mut().enable();
+
+            if !self.is_job_paused(job_id).await {
+                break;
+            }
+
+            tokio::select! {
+                _ = pause_changed => {}
+                _ = cancellation_token.cancelled() => {}
+            }
+            self.ensure_job_not_stopped(job_id, cancellation_token)
+                .await?;
+        }
+        self.set_job_state(job_id, JobProcessingState::Processing)
+            .await
+    }
+
+    async fn set_job_state(&self, job_id: &str, state: JobProcessingState) -> anyhow::Result<()> {
+        let Some(existing_status) = self.job_store.load_status(job_id).await? else {
+            return Ok(());
+        };
+
+        self.job_store
+            .save_status(&JobStatus {
+                status: state,
+                ..existing_status
+            })
+            .await
+    }
+
+    async fn clear_runtime_job_state(&self, job_id: &str) {
+        {
+            let mut active_job_handles = self.active_job_handles.lock().await;
+            active_job_handles.remove(job_id);
+        }
+        {
+            let mut cancellation_tokens = self.cancellation_tokens.lock().await;
+            cancellation_tokens.remove(job_id);
+        }
+        {
+            let mut killed_jobs = self.killed_jobs.lock().await;
+            killed_jobs.remove(job_id);
+        }
+        {
+            let mut paused_jobs = self.paused_jobs.lock().await;
+            paused_jobs.remove(job_id);
+        }
+    }
+
+    async fn mark_job_killed(&self, job_id: &str, message: &str) -> anyhow::Result<()> {
+        self.mark_job_stopped(job_id, JobProcessingState::Revoked, message)
+            .await
+    }
+
+    /// Moves an unfinished job to the terminal `state`; finished jobs are left alone.
+    async fn mark_job_stopped(
+        &self,
+        job_id: &str,
+        state: JobProcessingState,
+        message: &str,
+    ) -> anyhow::Result<()> {
+        let Some(existing_status) = self.job_store.load_status(job_id).await? else {
+            return Ok(());
+        };
+
+        if matches!(
+            existing_status.status,
+            JobProcessingState::Completed
+                | JobProcessingState::Failed
+                | JobProcessingState::Revoked
+        ) {
+            return Ok(());
+        }
+
+        let completed_at = Utc::now();
+        let duration_seconds = existing_status.started_at.map(|started_at| {
+            (completed_at - started_at).num_milliseconds().max(0) as f64 / 1000.0
+        });
+
+        self.job_store
+            .save_status(&JobStatus {
+                job_id: existing_status.job_id,
+                status: state,
+                progress: existing_status.progress,
+                total_files: existing_status.total_files,
+                processed_files: existing_status.processed_files,
+                spreadsheet_id: existing_status.spreadsheet_id,
+                results_count: existing_status.results_count,
+                error: Some(message.to_string()),
+                created_at: existing_status.created_at,
+                started_at: existing_status.started_at,
+                completed_at: Some(completed_at),
+                duration_seconds,
+            })
+            .await
+    }
+}
+
+fn cleanup_interval(settings: &RuntimeSettings) -> Option<Duration> {
+    (settings.cleanup_interval_minutes > 0)
+        .then(|| Duration::from_secs(settings.cleanup_interval_minutes.saturating_mul(60)))
+}
+
+/// Takes one retry from a job's budget, or returns false when none are left. `None` is an
+/// unlimited budget.
+fn take_retry(budget: Option<&AtomicUsize>) -> bool {
+    budget.is_none_or(|remaining| {
+        remaining
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |left| {
+                left.checked_sub(1)
+            })
+            .is_ok()
+    })
+}
+
+/// Splits rows into the Sheets writes allowed by `sheet_append_page_size`.
+fn sheet_pages<'a>(
+    rows: &'a [Vec<String>],
+    settings: &RuntimeSettings,
+) -> std::slice::Chunks<'a, Vec<String>> {
+    rows.chunks(settings.sheet_append_page_size.unwrap_or(rows.len()).max(1))
+}
+
+/// Retries a Sheets write on the failure classes the retry policy allows.
+async fn retry_sheets_write<F, Fut>(settings: &RuntimeSettings, mut write: F) -> anyhow::Result<()>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = anyhow::Result<()>>,
+{
+    let mut attempt = 0;
+    loop {
+        let err = match write().await {
+            Ok(()) => return Ok(()),
+            Err(err) => err,
+        };
+        let retryable = retry_class(&err).is_some_and(|class| settings.retry_on.allows(class));
+        attempt += 1;
+        if !retryable || attempt >= settings.max_retries {
+            return Err(err);
+        }
+        let backoff_seconds = settings.retry_delay_seconds * 2_f64.powf((attempt - 1) as f64);
+        tokio::time::sleep(Duration::from_secs_f64(backoff_seconds.max(0.1))).await;
+    }
+}
+
+/// Sheet rows held back after Google Sheets ran out of quota, tagged with their destination.
+struct UnwrittenRows {
+    /// Header first; each row starts with the spreadsheet it was meant for.
+    rows: Vec<Vec<String>>,
+    sheets_exhausted: bool,
+    path: Option<std::path::PathBuf>,
+    /// When the job last wrote to Sheets, for `sheet_append_delay_ms` pacing.
+    last_write: Option<Instant>,
+}
+
+impl UnwrittenRows {
+    fn new(header_row: &[String]) -> Self {
+        Self {
+            rows: vec![std::iter::once("Spreadsheet ID".to_string())
+                .chain(header_row.iter().cloned())
+                .collect()],
+            sheets_exhausted: false,
+            path: None,
+            last_write: None,
+        }
+    }
+
+    fn keep(&mut self, sheet_id: &str, rows: &[Vec<String>]) {
+        self.rows.extend(rows.iter().map(|row| {
+            std::iter::once(sheet_id.to_string())
+                .chain(row.iter().cloned())
+                .collect()
+        }));
+    }
+
+    /// Job warning naming how many rows missed Sheets and where they were saved.
+    fn warning(&self) -> Option<String> {
+        let path = self.path.as_ref()?;
+        Some(format!(
+            "Google Sheets quota was exhausted; {} rows could not be written and were saved to {}",
+            self.rows.len() - 1,
+            path.display()
+        ))
+    }
+}
+
+fn retry_class(error: &anyhow::Error) -> Option<RetryClass> {
+    if error
+        .downcast_ref::<tokio::time::error::Elapsed>()
+        .is_some()
+    {
+        return Some(RetryClass::Network);
+    }
+
+    if let Some(core_error) = error.downcast_ref::<CoreError>() {
+        return core_error.retry_class();
+    }
+
+    if let Some(reqwest_error) = error.downcast_ref::<reqwest::Error>() {
+        if reqwest_error.is_timeout() || reqwest_error.is_connect() {
+            return Some(RetryClass::Network);
+        }
+
+        if let Some(status) = reqwest_error.status() {
+            return RetryClass::from_status(status.as_u16());
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::errors::GoogleApiService;
+    use crate::core::models::RetryPolicy;
+    use crate::core::settings_store::SettingsStore;
+    use crate::core::test_support::{zip_with_entry, MockHttpServer, MockResponse};
+    use futures::future::BoxFuture;
+
+    fn batch_request(include: Option<&str>, exclude: Option<&str>) -> BatchParseRequest {
+        BatchParseRequest {
+            folder_id: "folder-1".to_string(),
+            spreadsheet_id: None,
+            name_include_regex: include.map(str::to_string),
+            name_exclude_regex: exclude.map(str::to_string),
+            skip_spreadsheet: false,
+            completion_webhook_url: None,
+            split_multi_resume: false,
+            idempotency_key: None,
+            skip_existing_in_sheet: false,
+            spreadsheet_title: None,
+            flag_possible_duplicates: false,
+            review_spreadsheet_id: None,
+            review_confidence_threshold: None,
+            modified_after: None,
+            allowed_mime_types: Vec::new(),
+            file_ids: Vec::new(),
+        }
+    }
+
+    /// Builds a service wired to mock Drive/Sheets servers, with a valid cached token and
+    /// all state kept under `root`.
+    fn test_service(
+        root: &Path,
+        drive: &MockHttpServer,
+        sheets: &MockHttpServer,
+    ) -> Arc<CoreService> {
+        test_service_with_queue(root, drive, sheets, 100).0
+    }
+
+    fn test_service_with_queue(
+        root: &Path,
+        drive: &MockHttpServer,
+        sheets: &MockHttpServer,
+        queue_capacity: usize,
+    ) -> (Arc<CoreService>, mpsc::Receiver<BatchJobWorkItem>) {
+        let settings = RuntimeSettings {
+            google_client_id: "client-id".to_string(),
+            max_concurrent_requests: 1,
+            max_retries: 1,
+            ..RuntimeSettings::default()
+        };
+        test_service_with_settings(root, drive, sheets, settings, queue_capacity)
+    }
+
+    /// `test_service_with_queue` with the given settings, which the HTTP clients are built from.
+    fn test_service_with_settings(
+        root: &Path,
+        drive: &MockHttpServer,
+        sheets: &MockHttpServer,
+        settings: RuntimeSettings,
+        queue_capacity: usize,
+    ) -> (Arc<CoreService>, mpsc::Receiver<BatchJobWorkItem>) {
+        let token_path = root.join("google-token.json");
+        std::fs::write(
+            &token_path,
+            r#"{"access_token":"test-token","refresh_token":"refresh","expires_at_utc":"2099-01-01T00:00:00Z","email":null,"name":null,"picture":null}"#,
+        )
+        .unwrap();
+
+        let client = build_http_client(&settings).unwrap();
+        let webhook_client = build_webhook_client(&settings).unwrap();
+        let (queue_tx, queue_rx) = mpsc::channel(queue_capacity);
+
+        let service = Arc::new(CoreService {
+            settings_store: SettingsStore::with_path(root.join("settings.json")),
+            client_secret_store: GoogleClientSecretStore::new(),
+            started_with: RuntimeSettings {
+                max_queued_jobs: queue_capacity,
+                ..settings.clone()
+            },
+            settings: RwLock::new(settings),
+            legacy_secret_scrubbed: RwLock::new(false),
+            tesseract_probe: RwLock::new(None),
+            auth: Box::new(GoogleAuthService::with_token_cache_path(
+                client.clone(),
+                token_path,
+            )),
+            drive: GoogleDriveClient::with_endpoint(client.clone(), drive.url("/files")),
+            sheets: GoogleSheetsClient::with_endpoint(client, sheets.url("/spreadsheets")),
+            webhook_client,
+            job_store: Arc::new(JsonJobStore::new_with_root(root.join("jobs"), 24)),
+            queue_tx,
+            queued_jobs: AtomicUsize::new(0),
+            recent_idempotency_keys: Mutex::new(HashMap::new()),
+            active_job_handles: Mutex::new(HashMap::new()),
+            cancellation_tokens: Mutex::new(HashMap::new()),
+            killed_jobs: Mutex::new(HashSet::new()),
+            paused_jobs: Mutex::new(HashSet::new()),
+            pause_changed: Notify::new(),
+            watches: Mutex::new(HashMap::new()),
+            aggregate_stats: Mutex::new(None),
+            event_sink: std::sync::RwLock::new(None),
+            shutdown: CancellationToken::new(),
+        });
+        (service, queue_rx)
+    }
+
+    fn docx_with_paragraphs(paragraphs: &[&str]) -> Vec<u8> {
+        let body = paragraphs
+            .iter()
+            .map(|text| format!("<w:p><w:r><w:t>{text}</w:t></w:r></w:p>"))
+            .collect::<String>();
+        zip_with_entry(
+            "word/document.xml",
+            &format!(
+                r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>{body}</w:body></w:document>"#
+            ),
+        )
+    }
+
+    fn drive_files(names: &[&str]) -> Vec<DriveFileRef> {
+        names
+            .iter()
+            .enumerate()
+            .map(|(index, name)| DriveFileRef {
+                id: format!("file-{index}"),
+                name: name.to_string(),
+                mime_type: "application/pdf".to_string(),
+                size: None,
+            })
+            .collect()
+    }
+
+    /// Pauses `job_id` as soon as the worker has started it.
+    async fn pause_once_processing(service: &CoreService, job_id: &str) {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while !service.pause_job(job_id).await.unwrap() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+    }
+
+    fn names(files: &[DriveFileRef]) -> Vec<&str> {
+        files.iter().map(|file| file.name.as_str()).collect()
+    }
+
+    #[test]
+    fn file_name_filter_keeps_only_included_names() {
+        let filter =
+            FileNameFilter::from_request(&batch_request(Some("(?i)resume|cv"), None)).unwrap();
+        let files = filter.apply(drive_files(&["Resume_Jane.pdf", "JD.pdf", "john_cv.docx"]));
+
+        assert_eq!(names(&files), vec!["Resume_Jane.pdf", "john_cv.docx"]);
+    }
+
+    #[test]
+    fn file_name_filter_drops_excluded_names() {
+        let filter =
+            FileNameFilter::from_request(&batch_request(None, Some(r"(?i)^(jd|offer_letter)")))
+                .unwrap();
+        let files = filter.apply(drive_files(&["JD.pdf", "offer_letter.pdf", "jane.pdf"]));
+
+        assert_eq!(names(&files), vec!["jane.pdf"]);
+    }
+
+    #[test]
+    fn custom_ca_certificate_must_contain_pem_certificate() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("corp-ca.pem");
+        std::fs::write(&path, "not a certificate").unwrap();
+
+        let settings = RuntimeSettings {
+            custom_ca_cert_path: Some(path.display().to_string()),
+            ..RuntimeSettings::default()
+        };
+        let err = build_http_client(&settings).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("does not contain a PEM certificate"));
+
+        let missing = RuntimeSettings {
+            custom_ca_cert_path: Some(temp_dir.path().join("missing.pem").display().to_string()),
+            ..RuntimeSettings::default()
+        };
+        assert!(build_http_client(&missing).is_err());
+        assert!(build_http_client(&RuntimeSettings::default()).is_ok());
+    }
+
+    #[tokio::test]
+    async fn extra_headers_are_sent_without_replacing_the_bearer_token() {
+        let server = MockHttpServer::start(vec![MockResponse::with_content_type(
+            200,
+            "application/pdf",
+            "%PDF-1.4 fake",
+        )]);
+        let settings = RuntimeSettings {
+            extra_headers: HashMap::from([(
+                "X-Goog-User-Project".to_string(),
+                "acme-billing".to_string(),
+            )]),
+            ..RuntimeSettings::default()
+        };
+        let drive = GoogleDriveClient::with_endpoint(
+            build_http_client(&settings).unwrap(),
+            server.url("/files"),
+        );
+
+        drive.download_file("token", "file-1").await.unwrap();
+
+        let request = &server.requests()[0];
+        assert_eq!(request.header("x-goog-user-project"), Some("acme-billing"));
+        assert_eq!(request.header("authorization"), Some("Bearer token"));
+
+        for (name, value) in [
+            ("Authorization", "Bearer other"),
+            ("Bad Name", "x"),
+            ("X-Ok", "a\nb"),
+        ] {
+            let settings = RuntimeSettings {
+                extra_headers: HashMap::from([(name.to_string(), value.to_string())]),
+                ..RuntimeSettings::default()
+            };
+            assert!(
+                build_http_client(&settings).is_err(),
+                "{name} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn possible_duplicates_group_copies_by_normalized_stem() {
+        let files = drive_files(&["cv.pdf", "cv (1).pdf", "Copy of CV.docx", "jane_cv.pdf"]);
+        let warnings = possible_duplicate_warnings(&files);
+
+        assert_eq!(normalized_file_stem("cv (1).pdf"), "cv");
+        assert_eq!(normalized_file_stem("Jane_Doe - Copy.pdf"), "jane doe");
+        assert_eq!(warnings.len(), 3);
+        assert_eq!(warnings["file-0"].code, ParseErrorKind::PossibleDuplicate);
+        assert_eq!(
+            warnings["file-0"].message,
+            "Warning: possible duplicate of cv (1).pdf, Copy of CV.docx"
+        );
+        assert!(warnings["file-1"].message.contains("cv.pdf"));
+        assert!(!warnings.contains_key("file-3"));
+    }
+
+    #[test]
+    fn batch_estimate_counts_types_and_charges_scans_for_ocr() {
+        let file = |name: &str, mime_type: &str, size: u64| DriveFileRef {
+            id: name.to_string(),
+            name: name.to_string(),
+            mime_type: mime_type.to_string(),
+            size: Some(size),
+        };
+        let files = vec![
+            file("text.pdf", "application/pdf", 80 * 1024),
+            file("scan.pdf", "application/pdf", 2 * 1024 * 1024),
+            file(
+                "cv.odt",
+                "application/vnd.oasis.opendocument.text",
+                20 * 1024,
+            ),
+            file("resume.docx", "", 30 * 1024),
+        ];
+        let settings = RuntimeSettings {
+            max_concurrent_requests: 2,
+            ..RuntimeSettings::default()
+        };
+
+        let estimate = estimate_batch_for_files(&files, &settings);
+        assert_eq!(estimate.file_count, 4);
+        assert_eq!(estimate.likely_scanned_pdfs, 1);
+        assert_eq!(
+            estimate.files_by_type.into_iter().collect::<Vec<_>>(),
+            vec![
+                ("docx".to_string(), 1),
+                ("odt".to_string(), 1),
+                ("pdf".to_string(), 2)
+            ]
+        );
+        // Parsing is the slower stage: (3 * 0.5 + 12) / 2 = 6.75s expected.
+        assert_eq!(estimate.estimated_min_seconds, 3);
+        assert_eq!(estimate.estimated_max_seconds, 14);
+
+        let without_ocr = estimate_batch_for_files(
+            &files,
+            &RuntimeSettings {
+                disable_ocr: true,
+                ..settings
+            },
+        );
+        assert!(without_ocr.estimated_max_seconds < estimate.estimated_max_seconds);
+    }
+
+    #[test]
+    fn file_name_filter_rejects_invalid_regex() {
+        let err = FileNameFilter::from_request(&batch_request(Some("(unclosed"), None))
+            .err()
+            .unwrap();
+        assert!(matches!(err, CoreError::InvalidRequest(_)));
+    }
+
+    #[test]
+    fn unattended_runs_need_a_refresh_token() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(Vec::new());
+        let sheets = MockHttpServer::start(Vec::new());
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+        std::fs::write(
+            temp_dir.path().join("google-token.json"),
+            r#"{"access_token":"test-token","refresh_token":null,"expires_at_utc":"2099-01-01T00:00:00Z","email":null,"name":null,"picture":null}"#,
+        )
+        .unwrap();
+
+        assert!(service.google_auth_status().unwrap().signed_in);
+        assert!(!service.can_run_unattended().unwrap());
+
+        let other_dir = tempfile::tempdir().unwrap();
+        let service = test_service(other_dir.path(), &drive, &sheets);
+        assert!(service.can_run_unattended().unwrap());
+    }
+
+    #[tokio::test]
+    async fn parse_single_reports_ocr_use_and_timing() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(Vec::new());
+        let sheets = MockHttpServer::start(Vec::new());
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+
+        let candidate = service
+            .parse_single(
+                "jane.docx".to_string(),
+                docx_with_paragraphs(&["Jane Doe", "jane.doe@example.com"]),
+            )
+            .await
+            .unwrap();
+        assert_eq!(candidate.email.as_deref(), Some("jane.doe@example.com"));
+        assert!(!candidate.ocr_used);
+        assert!(candidate.parse_duration_ms.is_some());
+        assert_eq!(
+            candidate.name_candidates,
+            Some(vec!["Jane Doe".to_string()])
+        );
+
+        // Results stored before these fields existed still load.
+        let stored: ParsedCandidate = serde_json::from_str(
+            r#"{"driveFileId":null,"sourceFile":"a.pdf","name":null,"email":null,"phone":null,"linkedIn":null,"gitHub":null,"confidence":0.5}"#,
+        )
+        .unwrap();
+        assert!(!stored.ocr_used);
+        assert_eq!(stored.parse_duration_ms, None);
+    }
+
+    #[tokio::test]
+    async fn debug_extract_text_returns_raw_text_and_caps_its_length() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(Vec::new());
+        let sheets = MockHttpServer::start(Vec::new());
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+
+        let extracted = service
+            .debug_extract_text(
+                "jane.docx".to_string(),
+                docx_with_paragraphs(&["Jane Doe", "jane.doe@example.com"]),
+            )
+            .await
+            .unwrap();
+        assert!(extracted.text.contains("Jane Doe"));
+        assert!(extracted.text.contains("jane.doe@example.com"));
+        assert!(!extracted.ocr_used);
+        assert!(extracted.errors.is_empty());
+        assert_eq!(extracted.note, None);
+
+        let long_line = "é".repeat(DEBUG_TEXT_MAX_CHARS + 10);
+        let extracted = service
+            .debug_extract_text("long.docx".to_string(), docx_with_paragraphs(&[&long_line]))
+            .await
+            .unwrap();
+        assert_eq!(extracted.text.chars().count(), DEBUG_TEXT_MAX_CHARS);
+        assert!(extracted.note.unwrap().contains("first 50000 of"));
+
+        let unsupported = service
+            .debug_extract_text("notes.txt".to_string(), b"hello".to_vec())
+            .await
+            .unwrap();
+        assert!(unsupported.text.is_empty());
+        assert_eq!(
+            unsupported.errors[0].code,
+            ParseErrorKind::UnsupportedFileType
+        );
+        assert!(service.list_jobs().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn sample_folder_parses_only_the_sampled_files() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let docx = "application/vnd.openxmlformats-officedocument.wordprocessingml.document";
+        let drive = MockHttpServer::start(vec![
+            MockResponse::with_content_type(
+                200,
+                "application/json",
+                format!(
+                    r#"{{"files":[
+                    {{"id":"file-1","name":"jane.docx","mimeType":"{docx}"}},
+                    {{"id":"file-2","name":"john.docx","mimeType":"{docx}"}},
+                    {{"id":"file-3","name":"ann.docx","mimeType":"{docx}"}}]}}"#
+                ),
+            ),
+            MockResponse::with_content_type(
+                200,
+                "application/octet-stream",
+                docx_with_paragraphs(&["Jane Doe", "jane.doe@example.com", "+91 9876543210"]),
+            ),
+            MockResponse::with_content_type(
+                200,
+                "application/octet-stream",
+                docx_with_paragraphs(&["John Smith", "Worked on backend services for years"]),
+            ),
+        ]);
+        let sheets = MockHttpServer::start(Vec::new());
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+
+        let sample = service
+            .sample_folder("folder-1".to_string(), 2)
+            .await
+            .unwrap();
+        assert_eq!(sample.total_files, 3);
+        assert_eq!(sample.sampled_files, 2);
+        assert_eq!(sample.failed_files, 0);
+        assert_eq!(
+            sample.high_confidence + sample.medium_confidence + sample.low_confidence,
+            2
+        );
+        let rate = |field: FieldKind| {
+            sample
+                .field_hit_rates
+                .iter()
+                .find(|hit| hit.field == field)
+                .unwrap()
+                .rate
+        };
+        assert_eq!(rate(FieldKind::Name), 1.0);
+        assert_eq!(rate(FieldKind::Email), 0.5);
+        assert_eq!(rate(FieldKind::GitHub), 0.0);
+
+        let requests = drive.requests();
+        assert_eq!(requests.len(), 3);
+        let downloaded = requests[1..]
+            .iter()
+            .map(|request| request.path.split('?').next().unwrap().to_string())
+            .collect::<HashSet<_>>();
+        assert_eq!(downloaded.len(), 2);
+
+        let error = service
+            .sample_folder("folder-1".to_string(), 0)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("SampleSize"));
+        assert!(service.list_jobs().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn aggregate_stats_cover_every_readable_job_and_are_cached() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(Vec::new());
+        let sheets = MockHttpServer::start(Vec::new());
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+        let candidate = |email: Option<&str>, ocr_used: bool, errors: Vec<ParseError>| {
+            let mut candidate = ParsedCandidate::empty(Some("cv.pdf".to_string()), None, errors);
+            candidate.name = Some("Jane Doe".to_string());
+            candidate.email = email.map(str::to_string);
+            candidate.ocr_used = ocr_used;
+            candidate.confidence = 0.5;
+            candidate
+        };
+        service
+            .job_store
+            .save_results(
+                "job-1",
+                &[
+                    candidate(Some("jane.doe@example.com"), false, Vec::new()),
+                    candidate(None, true, Vec::new()),
+                ],
+            )
+            .await
+            .unwrap();
+        service
+            .job_store
+            .save_results(
+                "job-2",
+                &[
+                    candidate(Some("john@example.org"), true, Vec::new()),
+                    candidate(
+                        None,
+                        false,
+                        vec![ParseError::new(ParseErrorKind::DownloadFailed, "failed")],
+                    ),
+                ],
+            )
+            .await
+            .unwrap();
+        let broken = temp_dir.path().join("jobs/job-3");
+        std::fs::create_dir_all(&broken).unwrap();
+        std::fs::write(broken.join("results.json"), "[{\"truncated").unwrap();
+
+        let stats = service.aggregate_stats().await.unwrap();
+        assert_eq!(stats.job_count, 2);
+        assert_eq!(stats.unreadable_jobs, 1);
+        assert_eq!(stats.total_files, 4);
+        assert_eq!(stats.failed_files, 1);
+        assert_eq!(stats.ocr_rate, 0.5);
+        assert_eq!(stats.average_confidence, 0.5);
+        let rate = |field: FieldKind| {
+            stats
+                .field_hit_rates
+                .iter()
+                .find(|hit| hit.field == field)
+                .unwrap()
+                .rate
+        };
+        assert_eq!(rate(FieldKind::Name), 1.0);
+        assert_eq!(rate(FieldKind::Email), 0.5);
+
+        // Same jobs, so the cached scan is reused until something invalidates it.
+        service
+            .job_store
+            .save_results("job-1", &[candidate(None, false, Vec::new())])
+            .await
+            .unwrap();
+        assert_eq!(service.aggregate_stats().await.unwrap().total_files, 4);
+        service.invalidate_aggregate_stats().await;
+        assert_eq!(service.aggregate_stats().await.unwrap().total_files, 3);
+
+        service
+            .job_store
+            .save_results("job-4", &[candidate(None, false, Vec::new())])
+            .await
+            .unwrap();
+        let stats = service.aggregate_stats().await.unwrap();
+        assert_eq!(stats.job_count, 3);
+        assert_eq!(stats.total_files, 4);
+    }
+
+    #[tokio::test]
+    async fn skip_spreadsheet_keeps_results_without_sheet_calls() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(vec![
+            MockResponse::with_content_type(
+                200,
+                "application/json",
+                r#"{"files":[{"id":"file-1","name":"jane.docx","mimeType":"application/vnd.openxmlformats-officedocument.wordprocessingml.document"}]}"#,
+            ),
+            MockResponse::with_content_type(
+                200,
+                "application/octet-stream",
+                docx_with_paragraphs(&["Jane Doe", "jane.doe@example.com", "+91 9876543210"]),
+            ),
+        ]);
+        let sheets = MockHttpServer::start(Vec::new());
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+
+        let request = BatchParseRequest {
+            skip_spreadsheet: true,
+            ..batch_request(None, None)
+        };
+        service
+            .process_batch_job(BatchJobWorkItem {
+                job_id: "job-1".to_string(),
+                request,
+                checkpoint: None,
+            })
+            .await
+            .unwrap();
+
+        let status = service.get_job_status("job-1").await.unwrap();
+        assert_eq!(
+            status.status,
+            JobProcessingState::Completed,
+            "{:?}",
+            status.error
+        );
+        assert_eq!(status.spreadsheet_id, None);
+        assert_eq!(status.results_count, Some(1));
+
+        let results = service
+            .job_store
+            .load_results("job-1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(results[0].email.as_deref(), Some("jane.doe@example.com"));
+        assert_eq!(drive.requests().len(), 2);
+        assert!(sheets.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn jobs_keep_a_secret_free_config_snapshot_in_their_archive() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(vec![
+            MockResponse::with_content_type(
+                200,
+                "application/json",
+                r#"{"files":[{"id":"file-1","name":"jane.docx","mimeType":"application/vnd.openxmlformats-officedocument.wordprocessingml.document"}]}"#,
+            ),
+            MockResponse::with_content_type(
+                200,
+                "application/octet-stream",
+                docx_with_paragraphs(&["Jane Doe", "jane.doe@example.com"]),
+            ),
+        ]);
+        let sheets = MockHttpServer::start(Vec::new());
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+        {
+            let mut settings = service.settings.write().await;
+            settings.google_client_secret = Some("top-secret".to_string());
+            settings.tesseract_path = "/opt/tesseract/bin/tesseract".to_string();
+        }
+
+        service
+            .process_batch_job(BatchJobWorkItem {
+                job_id: "job-1".to_string(),
+                request: BatchParseRequest {
+                    skip_spreadsheet: true,
+                    ..batch_request(None, None)
+                },
+                checkpoint: None,
+            })
+            .await
+            .unwrap();
+        // Later changes do not rewrite the snapshot of a finished job.
+        service.settings.write().await.max_retries = 7;
+
+        let config = service.get_job_config("job-1").await.unwrap().unwrap();
+        assert_eq!(config.tesseract_path, "/opt/tesseract/bin/tesseract");
+        assert_eq!(config.max_retries, 1);
+        assert!(service.get_job_config("missing").await.is_err());
+
+        let archive_path = temp_dir.path().join("job-1.zip");
+        service
+            .export_job_archive("job-1", archive_path.to_str().unwrap())
+            .await
+            .unwrap();
+        let mut archive =
+            zip::ZipArchive::new(std::fs::File::open(&archive_path).unwrap()).unwrap();
+        let mut snapshot = String::new();
+        std::io::Read::read_to_string(
+            &mut archive.by_name("job-1/config.json").unwrap(),
+            &mut snapshot,
+        )
+        .unwrap();
+        assert!(snapshot.contains("/opt/tesseract/bin/tesseract"));
+        assert!(!snapshot.contains("top-secret"));
+    }
+
+    #[tokio::test]
+    async fn unsupported_files_follow_the_configured_policy() {
+        let listing = r#"{"files":[
+            {"id":"file-1","name":"jane.docx","mimeType":"application/vnd.openxmlformats-officedocument.wordprocessingml.document"},
+            {"id":"file-2","name":"john.txt","mimeType":"text/plain"}]}"#;
+        let run = |policy: UnsupportedFilePolicy| async move {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let drive = MockHttpServer::start(vec![
+                MockResponse::with_content_type(200, "application/json", listing),
+                MockResponse::with_content_type(
+                    200,
+                    "application/octet-stream",
+                    docx_with_paragraphs(&["Jane Doe", "jane.doe@example.com"]),
+                ),
+                MockResponse::with_content_type(
+                    200,
+                    "text/plain",
+                    "John Smith\njohn.smith@example.org",
+                ),
+            ]);
+            let sheets = MockHttpServer::start(Vec::new());
+            let service = test_service(temp_dir.path(), &drive, &sheets);
+            service.settings.write().await.unsupported_file_policy = policy;
+            service
+                .process_batch_job(BatchJobWorkItem {
+                    job_id: "job-1".to_string(),
+                    request: BatchParseRequest {
+                        skip_spreadsheet: true,
+                        ..batch_request(None, None)
+                    },
+                    checkpoint: None,
+                })
+                .await
+                .unwrap();
+            let mut results = service
+                .job_store
+                .load_results("job-1")
+                .await
+                .unwrap()
+                .unwrap();
+            results.sort_by(|a, b| a.drive_file_id.cmp(&b.drive_file_id));
+            (results, drive.requests().len())
+        };
+
+        let (results, requests) = run(UnsupportedFilePolicy::Warn).await;
+        assert_eq!(requests, 2);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[1].source_file.as_deref(), Some("john.txt"));
+        assert_eq!(results[1].errors[0].code, ParseErrorKind::SkippedFileType);
+        assert!(results[1].errors[0].message.contains("text/plain"));
+
+        let (results, requests) = run(UnsupportedFilePolicy::ExtractText).await;
+        assert_eq!(requests, 3);
+        assert_eq!(results[1].email.as_deref(), Some("john.smith@example.org"));
+        assert_eq!(results[1].errors[0].code, ParseErrorKind::PlainTextFallback);
+    }
+
+    #[tokio::test]
+    async fn empty_downloads_are_retried_then_reported() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(vec![
+            MockResponse::with_content_type(200, "application/octet-stream", ""),
+            MockResponse::with_content_type(200, "application/octet-stream", "%PDF-1.4"),
+            MockResponse::with_content_type(200, "application/octet-stream", ""),
+            MockResponse::with_content_type(200, "application/octet-stream", "PK"),
+        ]);
+        let sheets = MockHttpServer::start(Vec::new());
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+        let file = DriveFileRef {
+            id: "file-1".to_string(),
+            name: "jane.pdf".to_string(),
+            mime_type: "application/pdf".to_string(),
+            size: None,
+        };
+        let mut settings = service.settings.read().await.clone();
+        settings.max_retries = 2;
+        settings.retry_delay_seconds = 0.0;
+
+        let (_, bytes) = service
+            .download_file_with_retry(file.clone(), "test-token", &settings, None, None)
+            .await
+            .unwrap();
+        assert_eq!(bytes, b"%PDF-1.4");
+
+        let failed = service
+            .download_file_with_retry(file, "test-token", &settings, None, None)
+            .await
+            .unwrap_err();
+        assert_eq!(drive.requests().len(), 4);
+        assert_eq!(failed.drive_file_id.as_deref(), Some("file-1"));
+        assert_eq!(
+            failed.errors,
+            vec![ParseError::new(
+                ParseErrorKind::DownloadFailed,
+                "Error processing file: Google Drive returned an empty or truncated download (2 bytes)"
+            )]
+        );
+    }
+
+    #[tokio::test]
+    async fn retry_budget_caps_retries_across_files() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let unavailable = || MockResponse::with_content_type(503, "text/plain", "unavailable");
+        let drive = MockHttpServer::start(vec![unavailable(), unavailable(), unavailable()]);
+        let sheets = MockHttpServer::start(Vec::new());
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+        let file = |id: &str| DriveFileRef {
+            id: id.to_string(),
+            name: format!("{id}.pdf"),
+            mime_type: "application/pdf".to_string(),
+            size: None,
+        };
+        let mut settings = service.settings.read().await.clone();
+        settings.max_retries = 3;
+        settings.retry_delay_seconds = 0.0;
+        let budget = AtomicUsize::new(1);
+
+        let first = service
+            .download_file_with_retry(file("file-1"), "test-token", &settings, None, Some(&budget))
+            .await
+            .unwrap_err();
+        assert_eq!(drive.requests().len(), 2);
+        assert!(first.errors[0].message.contains("retry budget"));
+
+        let second = service
+            .download_file_with_retry(file("file-2"), "test-token", &settings, None, Some(&budget))
+            .await
+            .unwrap_err();
+        assert_eq!(drive.requests().len(), 3);
+        assert_eq!(second.errors[0].code, ParseErrorKind::DownloadFailed);
+        assert_eq!(budget.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn a_zero_retry_budget_is_saved_as_no_retries() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(Vec::new());
+        let sheets = MockHttpServer::start(Vec::new());
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+        let update = |budget: serde_json::Value| -> RuntimeSettingsUpdate {
+            serde_json::from_value(serde_json::json!({
+                "tesseractPath": "",
+                "maxConcurrentRequests": 4,
+                "spreadsheetBatchSize": 100,
+                "maxRetries": 2,
+                "retryDelaySeconds": 1.0,
+                "jobRetentionHours": 24,
+                "jobRetryBudget": budget,
+            }))
+            .unwrap()
+        };
+
+        service.save_settings(update(0.into())).await.unwrap();
+        assert_eq!(service.settings.read().await.job_retry_budget, Some(0));
+
+        service
+            .save_settings(update(serde_json::Value::Null))
+            .await
+            .unwrap();
+        assert_eq!(service.settings.read().await.job_retry_budget, None);
+    }
+
+    #[tokio::test]
+    async fn missing_files_fail_without_retrying() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(vec![MockResponse::with_content_type(
+            404,
+            "application/json",
+            r#"{"error":{"code":404,"message":"File not found: file-1."}}"#,
+        )]);
+        let sheets = MockHttpServer::start(Vec::new());
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+        let file = DriveFileRef {
+            id: "file-1".to_string(),
+            name: "jane.pdf".to_string(),
+            mime_type: "application/pdf".to_string(),
+            size: None,
+        };
+        let mut settings = service.settings.read().await.clone();
+        settings.max_retries = 3;
+        settings.retry_delay_seconds = 0.0;
+
+        let failed = service
+            .download_file_with_retry(file, "test-token", &settings, None, None)
+            .await
+            .unwrap_err();
+        assert_eq!(drive.requests().len(), 1);
+        assert_eq!(failed.errors.len(), 1);
+        assert_eq!(failed.errors[0].code, ParseErrorKind::FileUnavailable);
+        assert!(!failed.errors[0].is_warning());
+        assert!(failed.errors[0]
+            .message
+            .contains("no longer available; it may have been deleted or moved to the trash"));
+    }
+
+    #[tokio::test]
+    async fn export_job_resumes_zips_selected_files_and_reports_failures() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(vec![
+            MockResponse::with_content_type(200, "application/pdf", "%PDF-1.4 jane"),
+            MockResponse::with_content_type(200, "application/pdf", "%PDF-1.4 jane again"),
+            MockResponse::with_content_type(
+                404,
+                "application/json",
+                r#"{"error":{"code":404,"message":"File not found"}}"#,
+            ),
+        ]);
+        let sheets = MockHttpServer::start(Vec::new());
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+        let candidate = |id: &str, name: &str| {
+            ParsedCandidate::empty(Some(name.to_string()), Some(id.to_string()), Vec::new())
+        };
+        service
+            .job_store
+            .save_results(
+                "job-1",
+                &[
+                    candidate("file-1", "cv.pdf"),
+                    candidate("file-2", "skipped.pdf"),
+                    candidate("file-3", "CV.pdf"),
+                    candidate("file-4", "gone.pdf"),
+                ],
+            )
+            .await
+            .unwrap();
+        service
+            .job_store
+            .save_status(&JobStatus {
+                job_id: "job-1".to_string(),
+                status: JobProcessingState::Completed,
+                progress: 100,
+                total_files: 4,
+                processed_files: 4,
+                spreadsheet_id: None,
+                results_count: Some(4),
+                error: None,
+                created_at: None,
+                started_at: None,
+                completed_at: None,
+                duration_seconds: None,
+            })
+            .await
+            .unwrap();
+
+        let dest = temp_dir.path().join("exports/resumes.zip");
+        let export = service
+            .export_job_resumes(
+                "job-1",
+                dest.to_str().unwrap(),
+                Some(vec![
+                    "file-1".to_string(),
+                    "file-3".to_string(),
+                    "file-4".to_string(),
+                ]),
+            )
+            .await
+            .unwrap();
+
+        let paths: Vec<String> = drive
+            .requests()
+            .iter()
+            .map(|request| request.path.clone())
+            .collect();
+        assert_eq!(paths.len(), 3);
+        assert!(paths[0].contains("file-1") && paths[1].contains("file-3"));
+        assert_eq!(export.exported_count, 2);
+        assert_eq!(export.failed.len(), 1);
+        assert_eq!(export.failed[0].drive_file_id, "file-4");
+        assert!(export.failed[0].message.contains("no longer available"));
+
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(&dest).unwrap()).unwrap();
+        let names: Vec<String> = archive.file_names().map(str::to_string).collect();
+        assert_eq!(names.len(), 2);
+        let mut second = String::new();
+        std::io::Read::read_to_string(&mut archive.by_name("CV (2).pdf").unwrap(), &mut second)
+            .unwrap();
+        assert_eq!(second, "%PDF-1.4 jane again");
+        assert!(archive.by_name("cv.pdf").is_ok());
+    }
+
+    #[tokio::test]
+    async fn exported_resumes_are_named_with_the_mime_type_drive_listed() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let docx = docx_with_paragraphs(&["Jane Doe", "jane.doe@example.com"]);
+        let drive = MockHttpServer::start(vec![
+            MockResponse::with_content_type(
+                200,
+                "application/json",
+                r#"{"files":[{"id":"file-1","name":"Jane Resume","mimeType":"application/vnd.openxmlformats-officedocument.wordprocessingml.document"}]}"#,
+            ),
+            MockResponse::with_content_type(200, "application/octet-stream", docx.clone()),
+            MockResponse::with_content_type(200, "application/octet-stream", docx),
+        ]);
+        let sheets = MockHttpServer::start(Vec::new());
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+        service
+            .process_batch_job(BatchJobWorkItem {
+                job_id: "job-1".to_string(),
+                request: BatchParseRequest {
+                    skip_spreadsheet: true,
+                    ..batch_request(None, None)
+                },
+                checkpoint: None,
+            })
+            .await
+            .unwrap();
+        let results = service.get_job_results("job-1").await.unwrap();
+        assert_eq!(
+            results[0].mime_type.as_deref(),
+            Some("application/vnd.openxmlformats-officedocument.wordprocessingml.document")
+        );
+
+        let dest = temp_dir.path().join("resumes.zip");
+        let export = service
+            .export_job_resumes("job-1", dest.to_str().unwrap(), None)
+            .await
+            .unwrap();
+
+        assert_eq!(export.exported_count, 1);
+        let archive = zip::ZipArchive::new(std::fs::File::open(&dest).unwrap()).unwrap();
+        assert_eq!(
+            archive.file_names().collect::<Vec<_>>(),
+            vec!["Jane Resume.docx"]
+        );
+    }
+
+    #[tokio::test]
+    async fn resume_export_stops_downloading_once_the_cap_is_reached() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(vec![
+            MockResponse::with_content_type(200, "application/pdf", "%PDF-1.4 jane"),
+            MockResponse::with_content_type(200, "application/pdf", "%PDF-1.4 john, too big"),
+        ]);
+        let sheets = MockHttpServer::start(Vec::new());
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+        let settings = service.settings.read().await.clone();
+        // Drive names need not carry an extension; the MIME type supplies it.
+        let files = ["Jane Resume", "john.pdf", "amy.pdf"]
+            .iter()
+            .enumerate()
+            .map(|(index, name)| DriveFileRef {
+                id: format!("file-{index}"),
+                name: name.to_string(),
+                mime_type: "application/pdf".to_string(),
+                size: None,
+            })
+            .collect();
+
+        let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        let export = service
+            .download_resumes_into_zip(files, "test-token", &settings, 20, &mut zip)
+            .await
+            .unwrap();
+
+        assert_eq!(drive.requests().len(), 2);
+        let archive = zip::ZipArchive::new(zip.finish().unwrap()).unwrap();
+        let names = archive.file_names().collect::<Vec<_>>();
+        assert_eq!(names, vec!["Jane Resume.pdf"]);
+        assert_eq!(export.exported_count, 1);
+        assert_eq!(export.total_bytes, 13);
+        let skipped = export
+            .failed
+            .iter()
+            .map(|failure| failure.drive_file_id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(skipped, vec!["file-1", "file-2"]);
+        assert!(export
+            .failed
+            .iter()
+            .all(|failure| failure.message.starts_with("Skipped: the export is capped")));
+    }
+
+    #[tokio::test]
+    async fn settings_view_flags_startup_settings_that_changed() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(Vec::new());
+        let sheets = MockHttpServer::start(Vec::new());
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+        assert!(!service.get_settings().await.restart_required);
+
+        service.settings.write().await.retry_delay_seconds = 5.0;
+        assert!(!service.get_settings().await.restart_required);
+
+        service.settings.write().await.user_agent_suffix = Some("acme-it".to_string());
+        assert!(service.get_settings().await.restart_required);
+
+        service.settings.write().await.user_agent_suffix = None;
+        service
+            .settings
+            .write()
+            .await
+            .extra_headers
+            .insert("X-Goog-User-Project".to_string(), "acme".to_string());
+        assert!(service.get_settings().await.restart_required);
+    }
+
+    #[tokio::test]
+    async fn periodic_cleanup_follows_the_interval_setting() {
+        let mut settings = RuntimeSettings {
+            cleanup_interval_minutes: 0,
+            ..RuntimeSettings::default()
+        };
+        assert_eq!(cleanup_interval(&settings), None);
+        settings.cleanup_interval_minutes = 90;
+        assert_eq!(
+            cleanup_interval(&settings),
+            Some(Duration::from_secs(90 * 60))
+        );
+        settings.cleanup_interval_minutes = u64::MAX;
+        assert_eq!(
+            cleanup_interval(&settings),
+            Some(Duration::from_secs(u64::MAX))
+        );
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(Vec::new());
+        let sheets = MockHttpServer::start(Vec::new());
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+        let finished_long_ago = Some(Utc::now() - chrono::Duration::days(3));
+        service
+            .job_store
+            .save_status(&JobStatus {
+                job_id: "job-old".to_string(),
+                status: JobProcessingState::Completed,
+                progress: 100,
+                total_files: 1,
+                processed_files: 1,
+                spreadsheet_id: None,
+                results_count: Some(1),
+                error: None,
+                created_at: finished_long_ago,
+                started_at: finished_long_ago,
+                completed_at: finished_long_ago,
+                duration_seconds: Some(1.0),
+            })
+            .await
+            .unwrap();
+
+        service.settings.write().await.cleanup_interval_minutes = 0;
+        service.run_scheduled_cleanup().await;
+        assert!(service.get_job_status("job-old").await.is_ok());
+
+        service.settings.write().await.cleanup_interval_minutes = 60;
+        service.run_scheduled_cleanup().await;
+        assert!(service.get_job_status("job-old").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn blank_spreadsheet_title_template_falls_back_to_the_default() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(Vec::new());
+        let sheets = MockHttpServer::start(Vec::new());
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+        let update = |template: &str| -> RuntimeSettingsUpdate {
+            serde_json::from_value(serde_json::json!({
+                "tesseractPath": "",
+                "maxConcurrentRequests": 4,
+                "spreadsheetBatchSize": 100,
+                "maxRetries": 2,
+                "retryDelaySeconds": 1.0,
+                "jobRetentionHours": 24,
+                "spreadsheetTitleTemplate": template,
+            }))
+            .unwrap()
+        };
+
+        service
+            .save_settings(update("Hiring {date}"))
+            .await
+            .unwrap();
+        assert_eq!(
+            service.settings.read().await.spreadsheet_title_template,
+            "Hiring {date}"
+        );
+
+        service.save_settings(update("   ")).await.unwrap();
+        assert_eq!(
+            service.settings.read().await.spreadsheet_title_template,
+            default_spreadsheet_title_template()
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn download_and_parse_stages_each_keep_to_their_own_limit() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let running = temp_dir.path().join("running");
+        std::fs::create_dir(&running).unwrap();
+        let overlaps_log = temp_dir.path().join("overlaps.log");
+        // Logs how many OCR runs are going on, this one included, then takes a while.
+        let tesseract = temp_dir.path().join("fake-tesseract");
+        std::fs::write(
+            &tesseract,
+            format!(
+                "#!/bin/sh\ntouch '{running}'/$$\nls '{running}' | wc -l >> '{log}'\nsleep 0.3\nrm '{running}'/$$\necho 'Jane Doe'\n",
+                running = running.display(),
+                log = overlaps_log.display(),
+            ),
+        )
+        .unwrap();
+        std::fs::set_permissions(&tesseract, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let files = (1..=4)
+            .map(|index| {
+                serde_json::json!({
+                    "id": format!("file-{index}"),
+                    "name": format!("scan-{index}.pdf"),
+                    "mimeType": "application/pdf",
+                })
+            })
+            .collect::<Vec<_>>();
+        // The PDF text helper is not available in tests, so every file goes to OCR.
+        let drive = MockHttpServer::start_concurrent(
+            std::iter::once(MockResponse::with_content_type(
+                200,
+                "application/json",
+                serde_json::json!({ "files": files }).to_string(),
+            ))
+            .chain((1..=4).map(|_| {
+                MockResponse::with_content_type(200, "application/pdf", "%PDF-1.4")
+                    .delayed(Duration::from_millis(200))
+            }))
+            .collect(),
+        );
+        let sheets = MockHttpServer::start(Vec::new());
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+        {
+            let mut settings = service.settings.write().await;
+            settings.tesseract_path = tesseract.display().to_string();
+            settings.max_concurrent_requests = 10;
+            settings.max_concurrent_downloads = Some(3);
+            settings.max_concurrent_parses = Some(1);
+        }
+
+        service
+            .process_batch_job(BatchJobWorkItem {
+                job_id: "job-1".to_string(),
+                request: BatchParseRequest {
+                    skip_spreadsheet: true,
+                    ..batch_request(None, None)
+                },
+                checkpoint: None,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(drive.requests().len(), 5);
+        assert_eq!(drive.max_in_flight(), 3);
+        let overlaps = std::fs::read_to_string(&overlaps_log)
+            .unwrap()
+            .lines()
+            .map(|line| line.trim().parse::<usize>().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(overlaps, vec![1, 1, 1, 1]);
+        let results = service.get_job_results("job-1").await.unwrap();
+        assert_eq!(results.len(), 4);
+        assert!(results.iter().all(|candidate| candidate.ocr_used));
+    }
+
+    /// Stands in for a provider whose user never signed in.
+    struct SignedOutProvider;
+
+    impl AuthProvider for SignedOutProvider {
+        fn sign_in<'a>(
+            &'a self,
+            _settings: &'a RuntimeSettings,
+        ) -> BoxFuture<'a, anyhow::Result<GoogleSignInResult>> {
+            Box::pin(async { Err(anyhow::anyhow!("sign-in is not supported")) })
+        }
+
+        fn begin_manual_sign_in<'a>(
+            &'a self,
+            _settings: &'a RuntimeSettings,
+        ) -> BoxFuture<'a, anyhow::Result<ManualAuthChallenge>> {
+            Box::pin(async { Err(anyhow::anyhow!("sign-in is not supported")) })
+        }
+
+        fn complete_manual_sign_in<'a>(
+            &'a self,
+            _settings: &'a RuntimeSettings,
+            _request: ManualAuthCompleteRequest,
+        ) -> BoxFuture<'a, anyhow::Result<AuthStatus>> {
+            Box::pin(async { Err(anyhow::anyhow!("sign-in is not supported")) })
+        }
+
+        fn complete_deep_link_sign_in<'a>(
+            &'a self,
+            _settings: &'a RuntimeSettings,
+            _url: &'a str,
+        ) -> BoxFuture<'a, anyhow::Result<AuthStatus>> {
+            Box::pin(async { Err(anyhow::anyhow!("sign-in is not supported")) })
+        }
+
+        fn cancel_sign_in(&self) -> bool {
+            false
+        }
+
+        fn sign_out(&self) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        fn status(&self) -> anyhow::Result<AuthStatus> {
+            Ok(AuthStatus {
+                signed_in: false,
+                email: None,
+                name: None,
+                picture: None,
+                expires_at: None,
+            })
+        }
+
+        fn has_refresh_token(&self) -> anyhow::Result<bool> {
+            Ok(false)
+        }
+
+        fn get_access_token_non_interactive<'a>(
+            &'a self,
+            _settings: &'a RuntimeSettings,
+        ) -> BoxFuture<'a, anyhow::Result<String>> {
+            Box::pin(async {
+                Err(CoreError::auth(AuthErrorCode::SignInRequired, "Sign-in required.").into())
+            })
+        }
+
+        fn set_event_sink(&self, _sink: EventSink) {}
+    }
+
+    #[tokio::test]
+    async fn service_uses_whichever_auth_provider_it_holds() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(Vec::new());
+        let sheets = MockHttpServer::start(Vec::new());
+        let (mut service, _queue_rx) = test_service_with_queue(temp_dir.path(), &drive, &sheets, 1);
+        Arc::get_mut(&mut service).unwrap().auth = Box::new(SignedOutProvider);
+
+        assert!(!service.google_auth_status().unwrap().signed_in);
+        assert!(!service.can_run_unattended().unwrap());
+        let err = service
+            .preview_folder("folder-1".to_string())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<CoreError>(),
+            Some(CoreError::Auth {
+                code: AuthErrorCode::SignInRequired,
+                ..
+            })
+        ));
+        assert!(drive.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn files_without_an_id_fail_with_a_coded_error() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(Vec::new());
+        let sheets = MockHttpServer::start(Vec::new());
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+        let settings = service.settings.read().await.clone();
+        let file = DriveFileRef {
+            id: " ".to_string(),
+            name: "jane.pdf".to_string(),
+            mime_type: "application/pdf".to_string(),
+            size: None,
+        };
+
+        let failed = service
+            .download_file_with_retry(file, "test-token", &settings, None, None)
+            .await
+            .unwrap_err();
+        assert!(drive.requests().is_empty());
+        assert_eq!(failed.source_file.as_deref(), Some("jane.pdf"));
+        assert_eq!(failed.errors[0].code, ParseErrorKind::MissingFileId);
+    }
+
+    #[tokio::test]
+    async fn retry_policy_retries_only_enabled_failure_classes() {
+        let elapsed = tokio::time::timeout(Duration::ZERO, std::future::pending::<()>())
+            .await
+            .unwrap_err();
+        let google = |status: u16| -> anyhow::Error {
+            CoreError::GoogleApi {
+                service: GoogleApiService::Drive,
+                status,
+                body: String::new(),
+            }
+            .into()
+        };
+        let failures = [
+            (google(429), RetryClass::RateLimit),
+            (google(503), RetryClass::ServerError),
+            (anyhow::Error::from(elapsed), RetryClass::Network),
+        ];
+        assert_eq!(retry_class(&google(404)), None);
+
+        for combination in 0..8 {
+            let policy = RetryPolicy {
+                rate_limit: combination & 1 != 0,
+                server_error: combination & 2 != 0,
+                network: combination & 4 != 0,
+            };
+            for (error, class) in &failures {
+                assert_eq!(retry_class(error), Some(*class));
+                let expected = match class {
+                    RetryClass::RateLimit => policy.rate_limit,
+                    RetryClass::ServerError => policy.server_error,
+                    RetryClass::Network => policy.network,
+                };
+                assert_eq!(policy.allows(*class), expected, "{policy:?} {class:?}");
+            }
+        }
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(vec![
+            MockResponse::with_content_type(503, "text/plain", "unavailable"),
+            MockResponse::with_content_type(503, "text/plain", "unavailable"),
+            MockResponse::with_content_type(200, "application/octet-stream", "%PDF-1.4"),
+        ]);
+        let sheets = MockHttpServer::start(Vec::new());
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+        let file = DriveFileRef {
+            id: "file-1".to_string(),
+            name: "jane.pdf".to_string(),
+            mime_type: "application/pdf".to_string(),
+            size: None,
+        };
+        let mut settings = service.settings.read().await.clone();
+        settings.max_retries = 3;
+        settings.retry_delay_seconds = 0.0;
+
+        settings.retry_on.server_error = false;
+        let failed = service
+            .download_file_with_retry(file.clone(), "test-token", &settings, None, None)
+            .await;
+        assert!(failed.is_err());
+        assert_eq!(drive.requests().len(), 1);
+
+        settings.retry_on.server_error = true;
+        let (_, bytes) = service
+            .download_file_with_retry(file, "test-token", &settings, None, None)
+            .await
+            .unwrap();
+        assert_eq!(bytes, b"%PDF-1.4");
+        assert_eq!(drive.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn get_job_candidates_returns_every_result_for_the_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(Vec::new());
+        let sheets = MockHttpServer::start(Vec::new());
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+        service
+            .job_store
+            .save_results(
+                "job-1",
+                &[
+                    ParsedCandidate::empty(
+                        Some("a.pdf".to_string()),
+                        Some("file-a".to_string()),
+                        Vec::new(),
+                    ),
+                    ParsedCandidate {
+                        name: Some("Jane Doe".to_string()),
+                        ..ParsedCandidate::empty(
+                            Some("b.pdf".to_string()),
+                            Some("file-b".to_string()),
+                            Vec::new(),
+                        )
+                    },
+                    ParsedCandidate {
+                        name: Some("John Roe".to_string()),
+                        ..ParsedCandidate::empty(
+                            Some("b.pdf".to_string()),
+                            Some("file-b".to_string()),
+                            Vec::new(),
+                        )
+                    },
+                ],
+            )
+            .await
+            .unwrap();
+
+        let candidates = service.get_job_candidates("job-1", "file-b").await.unwrap();
+        let names: Vec<_> = candidates
+            .iter()
+            .map(|candidate| candidate.name.as_deref())
+            .collect();
+        assert_eq!(names, vec![Some("Jane Doe"), Some("John Roe")]);
+        assert!(service
+            .get_job_candidates("job-1", "file-z")
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(service
+            .get_job_candidates("missing", "file-a")
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn rescore_job_applies_current_weights_to_stored_results() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(vec![
+            MockResponse::with_content_type(
+                200,
+                "application/json",
+                r#"{"files":[{"id":"file-1","name":"jane.docx","mimeType":"application/vnd.openxmlformats-officedocument.wordprocessingml.document"}]}"#,
+            ),
+            MockResponse::with_content_type(
+                200,
+                "application/octet-stream",
+                docx_with_paragraphs(&["Jane Doe", "jane.doe@example.com"]),
+            ),
+        ]);
+        let sheets = MockHttpServer::start(Vec::new());
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+
+        assert!(service.rescore_job("missing").await.is_err());
+
+        let request = BatchParseRequest {
+            skip_spreadsheet: true,
+            ..batch_request(None, None)
+        };
+        service
+            .process_batch_job(BatchJobWorkItem {
+                job_id: "job-1".to_string(),
+                request,
+                checkpoint: None,
+            })
+            .await
+            .unwrap();
+        let before = service.get_job_results("job-1").await.unwrap();
+        assert!((before[0].confidence - 0.6).abs() < 0.01);
+
+        service.settings.write().await.confidence_weights = ConfidenceWeights {
+            email: 0.9,
+            ..ConfidenceWeights::default()
+        };
+        let rescored = service.rescore_job("job-1").await.unwrap();
+        assert!((rescored[0].confidence - 1.0).abs() < 0.01);
+        assert_eq!(rescored[0].email.as_deref(), Some("jane.doe@example.com"));
+
+        let stored = service.get_job_results("job-1").await.unwrap();
+        assert_eq!(stored[0].confidence, rescored[0].confidence);
+        assert_eq!(drive.requests().len(), 2);
+    }
+
+    #[test]
+    fn rescoring_skips_only_candidates_that_failed_to_parse() {
+        let candidate = |errors: Vec<ParseError>| {
+            let mut candidate = ParsedCandidate::empty(
+                Some("jane.pdf".to_string()),
+                Some("file-1".to_string()),
+                errors,
+            );
+            candidate.email = Some("jane@example.com".to_string());
+            candidate
+        };
+        let weights = ConfidenceWeights::default();
+
+        let mut failed = candidate(vec![ParseError::new(
+            ParseErrorKind::DownloadFailed,
+            "download failed",
+        )]);
+        rescore_candidate(&mut failed, &weights);
+        assert_eq!(failed.confidence, 0.0);
+
+        // Scored as zero under earlier weights, e.g. with the email weight set to nothing.
+        let mut unscored = candidate(Vec::new());
+        rescore_candidate(&mut unscored, &weights);
+        assert!(unscored.confidence > 0.0);
+
+        let mut warned = candidate(vec![ParseError::new(
+            ParseErrorKind::Warning,
+            "Warning: little text was extracted",
+        )]);
+        rescore_candidate(&mut warned, &weights);
+        assert_eq!(warned.confidence, unscored.confidence);
+    }
+
+    #[tokio::test]
+    async fn rows_without_required_fields_stay_out_of_the_sheet() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(vec![
+            MockResponse::with_content_type(
+                200,
+                "application/json",
+                r#"{"files":[{"id":"file-1","name":"jane.docx","mimeType":"application/vnd.openxmlformats-officedocument.wordprocessingml.document"},{"id":"file-2","name":"john.docx","mimeType":"application/vnd.openxmlformats-officedocument.wordprocessingml.document"}]}"#,
+            ),
+            MockResponse::with_content_type(
+                200,
+                "application/octet-stream",
+                docx_with_paragraphs(&["Jane Doe", "jane.doe@example.com"]),
+            ),
+            MockResponse::with_content_type(
+                200,
+                "application/octet-stream",
+                docx_with_paragraphs(&["John Smith"]),
+            ),
+        ]);
+        let sheets = MockHttpServer::start(vec![
+            MockResponse::with_content_type(
+                200,
+                "application/json",
+                r#"{"spreadsheetId":"new-sheet"}"#,
+            ),
+            MockResponse::with_content_type(200, "application/json", "{}"),
+        ]);
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+        service.settings.write().await.required_fields = vec![FieldKind::Email];
+
+        service
+            .process_batch_job(BatchJobWorkItem {
+                job_id: "job-1".to_string(),
+                request: batch_request(None, None),
+                checkpoint: None,
+            })
+            .await
+            .unwrap();
+
+        let status = service.get_job_status("job-1").await.unwrap();
+        assert_eq!(
+            status.status,
+            JobProcessingState::Completed,
+            "{:?}",
+            status.error
+        );
+        assert_eq!(status.results_count, Some(2));
+
+        let payload: serde_json::Value =
+            serde_json::from_slice(&sheets.requests()[1].body).unwrap();
+        let values = payload["values"].as_array().unwrap();
+        assert_eq!(values.len(), 2);
+        assert_eq!(values[1][3], "jane.doe@example.com");
+
+        let results = service
+            .job_store
+            .load_results("job-1")
+            .await
+            .unwrap()
+            .unwrap();
+        let john = results
+            .iter()
+            .find(|candidate| candidate.drive_file_id.as_deref() == Some("file-2"))
+            .unwrap();
+        assert_eq!(john.name.as_deref(), Some("John Smith"));
+        assert!(john
+            .errors
+            .iter()
+            .any(|error| error.code == ParseErrorKind::MissingRequiredFields
+                && error.message.contains("required fields (email)")));
+    }
+
+    #[tokio::test]
+    async fn cancelling_a_small_job_writes_its_buffered_rows_and_checkpoint() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(vec![
+            MockResponse::with_content_type(
+                200,
+                "application/json",
+                r#"{"files":[{"id":"file-1","name":"jane.docx","mimeType":"application/vnd.openxmlformats-officedocument.wordprocessingml.document"},{"id":"file-2","name":"john.docx","mimeType":"application/vnd.openxmlformats-officedocument.wordprocessingml.document"}]}"#,
+            ),
+            MockResponse::with_content_type(
+                200,
+                "application/octet-stream",
+                docx_with_paragraphs(&["Jane Doe", "jane.doe@example.com"]),
+            ),
+            // Still downloading when the job is cancelled.
+            MockResponse::with_content_type(
+                200,
+                "application/octet-stream",
+                docx_with_paragraphs(&["John Smith", "john.smith@example.org"]),
+            )
+            .delayed(Duration::from_millis(500)),
+        ]);
+        let sheets = MockHttpServer::start(vec![
+            MockResponse::with_content_type(
+                200,
+                "application/json",
+                r#"{"spreadsheetId":"new-sheet"}"#,
+            ),
+            MockResponse::with_content_type(200, "application/json", "{}"),
+        ]);
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+        service.settings.write().await.spreadsheet_batch_size = 1;
+
+        let job = tokio::spawn({
+            let service = Arc::clone(&service);
+            async move {
+                service
+                    .process_batch_job(BatchJobWorkItem {
+                        job_id: "job-1".to_string(),
+                        request: batch_request(None, None),
+                        checkpoint: None,
+                    })
+                    .await
+            }
+        });
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while service
+                .job_store
+                .load_status("job-1")
+                .await
+                .unwrap()
+                .is_none_or(|status| status.processed_files < 1)
+            {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+        assert!(service.cancel_job("job-1").await.unwrap());
+        job.await.unwrap().unwrap();
+
+        let status = service.get_job_status("job-1").await.unwrap();
+        assert_eq!(status.status, JobProcessingState::Revoked);
+        let requests = sheets.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1].method, "PUT");
+        let payload: serde_json::Value = serde_json::from_slice(&requests[1].body).unwrap();
+        let values = payload["values"].as_array().unwrap();
+        assert_eq!(values.len(), 2);
+        assert_eq!(values[0][0], "Name");
+        assert_eq!(values[1][3], "jane.doe@example.com");
+
+        let checkpoint = service
+            .job_store
+            .load_checkpoint("job-1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(checkpoint.spreadsheet_id.as_deref(), Some("new-sheet"));
+        assert_eq!(checkpoint.processed_file_ids, vec!["file-1".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn cancelling_a_sorted_job_writes_its_rows_so_far_and_can_resume() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(vec![
+            MockResponse::with_content_type(
+                200,
+                "application/json",
+                r#"{"files":[{"id":"file-1","name":"zoe.docx","mimeType":"application/vnd.openxmlformats-officedocument.wordprocessingml.document"},{"id":"file-2","name":"amy.docx","mimeType":"application/vnd.openxmlformats-officedocument.wordprocessingml.document"},{"id":"file-3","name":"bob.docx","mimeType":"application/vnd.openxmlformats-officedocument.wordprocessingml.document"}]}"#,
+            ),
+            MockResponse::with_content_type(
+                200,
+                "application/octet-stream",
+                docx_with_paragraphs(&["Zoe Adams", "zoe@example.com"]),
+            ),
+            MockResponse::with_content_type(
+                200,
+                "application/octet-stream",
+                docx_with_paragraphs(&["Amy Brown", "amy@example.com"]),
+            ),
+            // Still downloading when the job is cancelled.
+            MockResponse::with_content_type(
+                200,
+                "application/octet-stream",
+                docx_with_paragraphs(&["Bob Clark", "bob@example.com"]),
+            )
+            .delayed(Duration::from_millis(500)),
+        ]);
+        let sheets = MockHttpServer::start(vec![
+            // The user's sheet is empty, so the rows are laid out from A1.
+            MockResponse::with_content_type(200, "application/json", "{}"),
+            MockResponse::with_content_type(200, "application/json", "{}"),
+        ]);
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+        {
+            let mut settings = service.settings.write().await;
+            settings.spreadsheet_batch_size = 1;
+            settings.sort_order = SortOrder::NameAsc;
+        }
+
+        let job = tokio::spawn({
+            let service = Arc::clone(&service);
+            async move {
+                service
+                    .process_batch_job(BatchJobWorkItem {
+                        job_id: "job-1".to_string(),
+                        request: BatchParseRequest {
+                            spreadsheet_id: Some("sheet-1".to_string()),
+                            ..batch_request(None, None)
+                        },
+                        checkpoint: None,
+                    })
+                    .await
+            }
+        });
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while service
+                .job_store
+                .load_status("job-1")
+                .await
+                .unwrap()
+                .is_none_or(|status| status.processed_files < 2)
+            {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+        assert!(service.cancel_job("job-1").await.unwrap());
+        job.await.unwrap().unwrap();
+
+        let requests = sheets.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1].method, "PUT");
+        let payload: serde_json::Value = serde_json::from_slice(&requests[1].body).unwrap();
+        let values = payload["values"].as_array().unwrap();
+        assert_eq!(values.len(), 3);
+        assert_eq!(values[1][0], "Amy Brown");
+        assert_eq!(values[2][0], "Zoe Adams");
+
+        let checkpoint = service
+            .job_store
+            .load_checkpoint("job-1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(checkpoint.spreadsheet_id.as_deref(), Some("sheet-1"));
+        assert_eq!(
+            checkpoint.processed_file_ids,
+            vec!["file-1".to_string(), "file-2".to_string()]
+        );
+        assert_eq!(
+            service.get_job_status("job-1").await.unwrap().status,
+            JobProcessingState::Revoked
+        );
+    }
+
+    #[tokio::test]
+    async fn created_sheet_for_small_job_is_written_in_one_request() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(vec![
+            MockResponse::with_content_type(
+                200,
+                "application/json",
+                r#"{"files":[{"id":"file-1","name":"jane.docx","mimeType":"application/vnd.openxmlformats-officedocument.wordprocessingml.document"},{"id":"file-2","name":"john.docx","mimeType":"application/vnd.openxmlformats-officedocument.wordprocessingml.document"}]}"#,
+            ),
+            MockResponse::with_content_type(
+                200,
+                "application/octet-stream",
+                docx_with_paragraphs(&["Jane Doe", "jane.doe@example.com"]),
+            ),
+            MockResponse::with_content_type(
+                200,
+                "application/octet-stream",
+                docx_with_paragraphs(&["John Smith", "john.smith@example.org"]),
+            ),
+        ]);
+        let sheets = MockHttpServer::start(vec![
+            MockResponse::with_content_type(
+                200,
+                "application/json",
+                r#"{"spreadsheetId":"new-sheet"}"#,
+            ),
+            MockResponse::with_content_type(200, "application/json", "{}"),
+        ]);
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+
+        service
+            .process_batch_job(BatchJobWorkItem {
+                job_id: "job-1".to_string(),
+                request: batch_request(None, None),
+                checkpoint: None,
+            })
+            .await
+            .unwrap();
+
+        let status = service.get_job_status("job-1").await.unwrap();
+        assert_eq!(
+            status.status,
+            JobProcessingState::Completed,
+            "{:?}",
+            status.error
+        );
+        assert_eq!(status.spreadsheet_id.as_deref(), Some("new-sheet"));
+
+        let requests = sheets.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[1].method, "PUT");
+        assert_eq!(
+            requests[1].path,
+            "/spreadsheets/new-sheet/values/A1?valueInputOption=USER_ENTERED"
+        );
+        let payload: serde_json::Value = serde_json::from_slice(&requests[1].body).unwrap();
+        let values = payload["values"].as_array().unwrap();
+        assert_eq!(values.len(), 3);
+        assert_eq!(values[0][0], "Name");
+        assert_eq!(values[1][3], "jane.doe@example.com");
+        assert_eq!(values[2][3], "john.smith@example.org");
+    }
+
+    #[tokio::test]
+    async fn sheet_writes_are_split_into_pages() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(vec![
+            MockResponse::with_content_type(
+                200,
+                "application/json",
+                r#"{"files":[{"id":"file-1","name":"jane.docx","mimeType":"application/vnd.openxmlformats-officedocument.wordprocessingml.document"},{"id":"file-2","name":"john.docx","mimeType":"application/vnd.openxmlformats-officedocument.wordprocessingml.document"},{"id":"file-3","name":"ana.docx","mimeType":"application/vnd.openxmlformats-officedocument.wordprocessingml.document"}]}"#,
+            ),
+            MockResponse::with_content_type(
+                200,
+                "application/octet-stream",
+                docx_with_paragraphs(&["Jane Doe", "jane.doe@example.com"]),
+            ),
+            MockResponse::with_content_type(
+                200,
+                "application/octet-stream",
+                docx_with_paragraphs(&["John Smith", "john.smith@example.org"]),
+            ),
+            MockResponse::with_content_type(
+                200,
+                "application/octet-stream",
+                docx_with_paragraphs(&["Ana Lima", "ana.lima@example.net"]),
+            ),
+        ]);
+        let sheets = MockHttpServer::start(vec![
+            MockResponse::with_content_type(
+                200,
+                "application/json",
+                r#"{"spreadsheetId":"new-sheet"}"#,
+            ),
+            MockResponse::with_content_type(200, "application/json", "{}"),
+            MockResponse::with_content_type(200, "application/json", "{}"),
+        ]);
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+        service.settings.write().await.sheet_append_page_size = Some(2);
+
+        service
+            .process_batch_job(BatchJobWorkItem {
+                job_id: "job-1".to_string(),
+                request: batch_request(None, None),
+                checkpoint: None,
+            })
+            .await
+            .unwrap();
+
+        let status = service.get_job_status("job-1").await.unwrap();
+        assert_eq!(
+            status.status,
+            JobProcessingState::Completed,
+            "{:?}",
+            status.error
+        );
+
+        let requests = sheets.requests();
+        let calls = requests
+            .iter()
+            .map(|request| request.method.as_str())
+            .collect::<Vec<_>>();
+        // The created sheet already has its header, so later pages append without reading it.
+        assert_eq!(calls, vec!["POST", "PUT", "POST"]);
+        let first: serde_json::Value = serde_json::from_slice(&requests[1].body).unwrap();
+        assert_eq!(first["values"].as_array().unwrap().len(), 3);
+        assert_eq!(first["values"][0][0], "Name");
+        assert!(requests[2].path.contains(":append"));
+        let rest: serde_json::Value = serde_json::from_slice(&requests[2].body).unwrap();
+        assert_eq!(rest["values"].as_array().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn sheet_writes_are_paced_by_the_append_delay() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(vec![
+            MockResponse::with_content_type(
+                200,
+                "application/json",
+                r#"{"files":[{"id":"file-1","name":"jane.docx","mimeType":"application/vnd.openxmlformats-officedocument.wordprocessingml.document"},{"id":"file-2","name":"john.docx","mimeType":"application/vnd.openxmlformats-officedocument.wordprocessingml.document"}]}"#,
+            ),
+            MockResponse::with_content_type(
+                200,
+                "application/octet-stream",
+                docx_with_paragraphs(&["Jane Doe", "jane.doe@example.com"]),
+            ),
+            MockResponse::with_content_type(
+                200,
+                "application/octet-stream",
+                docx_with_paragraphs(&["John Smith", "john.smith@example.org"]),
+            ),
+        ]);
+        let sheets = MockHttpServer::start(vec![
+            MockResponse::with_content_type(
+                200,
+                "application/json",
+                r#"{"spreadsheetId":"new-sheet"}"#,
+            ),
+            MockResponse::with_content_type(200, "application/json", "{}"),
+            MockResponse::with_content_type(200, "application/json", "{}"),
+        ]);
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+        {
+            let mut settings = service.settings.write().await;
+            settings.sheet_append_page_size = Some(1);
+            settings.sheet_append_delay_ms = 300;
+        }
+
+        let started = Instant::now();
+        service
+            .process_batch_job(BatchJobWorkItem {
+                job_id: "job-1".to_string(),
+                request: batch_request(None, None),
+                checkpoint: None,
+            })
+            .await
+            .unwrap();
+
+        let status = service.get_job_status("job-1").await.unwrap();
+        assert_eq!(
+            status.status,
+            JobProcessingState::Completed,
+            "{:?}",
+            status.error
+        );
+        // Two row writes, so one pause between them.
+        assert!(started.elapsed() >= Duration::from_millis(300));
+        let calls = sheets
+            .requests()
+            .iter()
+            .map(|request| request.method.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(calls, vec!["POST", "PUT", "POST"]);
+    }
+
+    #[tokio::test]
+    async fn confidence_colors_are_applied_once_and_tolerate_failures() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(vec![
+            MockResponse::with_content_type(
+                200,
+                "application/json",
+                r#"{"files":[{"id":"file-1","name":"jane.docx","mimeType":"application/vnd.openxmlformats-officedocument.wordprocessingml.document"}]}"#,
+            ),
+            MockResponse::with_content_type(
+                200,
+                "application/octet-stream",
+                docx_with_paragraphs(&["Jane Doe", "jane.doe@example.com"]),
+            ),
+        ]);
+        let sheets = MockHttpServer::start(vec![
+            MockResponse::with_content_type(
+                200,
+                "application/json",
+                r#"{"spreadsheetId":"new-sheet"}"#,
+            ),
+            MockResponse::with_content_type(200, "application/json", "{}"),
+            MockResponse::with_content_type(500, "application/json", "{}"),
+        ]);
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+        service.settings.write().await.color_by_confidence = true;
+
+        service
+            .process_batch_job(BatchJobWorkItem {
+                job_id: "job-1".to_string(),
+                request: batch_request(None, None),
+                checkpoint: None,
+            })
+            .await
+            .unwrap();
+
+        let status = service.get_job_status("job-1").await.unwrap();
+        assert_eq!(
+            status.status,
+            JobProcessingState::Completed,
+            "{:?}",
+            status.error
+        );
+
+        let requests = sheets.requests();
+        assert_eq!(requests.len(), 3);
+        let payload: serde_json::Value = serde_json::from_slice(&requests[1].body).unwrap();
+        assert_eq!(payload["values"][0][6], "Confidence");
+        assert!(payload["values"][1][6].as_str().unwrap().starts_with("0."));
+
+        assert_eq!(requests[2].path, "/spreadsheets/new-sheet:batchUpdate");
+        let payload: serde_json::Value = serde_json::from_slice(&requests[2].body).unwrap();
+        let rules = payload["requests"].as_array().unwrap();
+        assert_eq!(rules.len(), 3);
+        let rule = &rules[0]["addConditionalFormatRule"]["rule"];
+        assert_eq!(rule["ranges"][0]["endColumnIndex"], 7);
+        assert!(
+            rule["booleanRule"]["condition"]["values"][0]["userEnteredValue"]
+                .as_str()
+                .unwrap()
+                .contains("$G2")
+        );
+    }
+
+    #[tokio::test]
+    async fn confidence_as_percent_adds_the_column_without_colors() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(vec![
+            MockResponse::with_content_type(
+                200,
+                "application/json",
+                r#"{"files":[{"id":"file-1","name":"jane.docx","mimeType":"application/vnd.openxmlformats-officedocument.wordprocessingml.document"}]}"#,
+            ),
+            MockResponse::with_content_type(
+                200,
+                "application/octet-stream",
+                docx_with_paragraphs(&["Jane Doe", "jane.doe@example.com"]),
+            ),
+        ]);
+        let sheets = MockHttpServer::start(vec![
+            MockResponse::with_content_type(
+                200,
+                "application/json",
+                r#"{"spreadsheetId":"new-sheet"}"#,
+            ),
+            MockResponse::with_content_type(200, "application/json", "{}"),
+        ]);
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+        service.settings.write().await.confidence_as_percent = true;
+
+        service
+            .process_batch_job(BatchJobWorkItem {
+                job_id: "job-1".to_string(),
+                request: batch_request(None, None),
+                checkpoint: None,
+            })
+            .await
+            .unwrap();
+
+        let requests = sheets.requests();
+        // No formatting request follows the write.
+        assert_eq!(requests.len(), 2);
+        let payload: serde_json::Value = serde_json::from_slice(&requests[1].body).unwrap();
+        assert_eq!(payload["values"][0][6], "Confidence");
+        assert!(payload["values"][1][6].as_str().unwrap().ends_with('%'));
+    }
+
+    #[tokio::test]
+    async fn custom_fields_get_columns_after_the_layout_in_new_sheets() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(vec![
+            MockResponse::with_content_type(
+                200,
+                "application/json",
+                r#"{"files":[{"id":"file-1","name":"jane.docx","mimeType":"application/vnd.openxmlformats-officedocument.wordprocessingml.document"}]}"#,
+            ),
+            MockResponse::with_content_type(
+                200,
+                "application/octet-stream",
+                docx_with_paragraphs(&["Jane Doe", "jane.doe@example.com", "Visa: H-1B"]),
+            ),
+        ]);
+        let sheets = MockHttpServer::start(vec![
+            MockResponse::with_content_type(
+                200,
+                "application/json",
+                r#"{"spreadsheetId":"new-sheet"}"#,
+            ),
+            MockResponse::with_content_type(200, "application/json", "{}"),
+            MockResponse::with_content_type(200, "application/json", "{}"),
+        ]);
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+        {
+            let mut settings = service.settings.write().await;
+            settings.color_by_confidence = true;
+            settings.custom_fields = vec![
+                CustomFieldRule {
+                    name: "Visa Status".to_string(),
+                    pattern: r"(?i)visa:\s*(\S+)".to_string(),
+                },
+                CustomFieldRule {
+                    name: "Candidate Ref".to_string(),
+                    pattern: r"(CR-\d+)".to_string(),
+                },
+            ];
+        }
+
+        service
+            .process_batch_job(BatchJobWorkItem {
+                job_id: "job-1".to_string(),
+                request: batch_request(None, None),
+                checkpoint: None,
+            })
+            .await
+            .unwrap();
+
+        let results = service.get_job_results("job-1").await.unwrap();
+        assert_eq!(results[0].custom["Visa Status"], "H-1B");
+        assert!(!results[0].custom.contains_key("Candidate Ref"));
+
+        let requests = sheets.requests();
+        let payload: serde_json::Value = serde_json::from_slice(&requests[1].body).unwrap();
+        let header = payload["values"][0].as_array().unwrap();
+        assert_eq!(header.len(), 9);
+        assert_eq!(header[6], "Confidence");
+        assert_eq!(header[7], "Visa Status");
+        assert_eq!(header[8], "Candidate Ref");
+        assert_eq!(payload["values"][1][7], "H-1B");
+        assert_eq!(payload["values"][1][8], "");
+
+        // Whole rows are shaded, still keyed on the Confidence column.
+        let payload: serde_json::Value = serde_json::from_slice(&requests[2].body).unwrap();
+        let rule = &payload["requests"][0]["addConditionalFormatRule"]["rule"];
+        assert_eq!(rule["ranges"][0]["endColumnIndex"], 9);
+        assert!(
+            rule["booleanRule"]["condition"]["values"][0]["userEnteredValue"]
+                .as_str()
+                .unwrap()
+                .contains("$G2")
+        );
+    }
+
+    #[tokio::test]
+    async fn skip_spreadsheet_rejects_explicit_spreadsheet_id() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(Vec::new());
+        let sheets = MockHttpServer::start(Vec::new());
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+
+        let err = service
+            .start_batch_job(BatchParseRequest {
+                spreadsheet_id: Some("sheet-1".to_string()),
+                skip_spreadsheet: true,
+                ..batch_request(None, None)
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<CoreError>(),
+            Some(CoreError::InvalidRequest(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn start_rejects_an_inaccessible_spreadsheet_before_queueing() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(Vec::new());
+        let sheets = MockHttpServer::start(vec![MockResponse::with_content_type(
+            404,
+            "application/json",
+            r#"{"error":{"code":404,"status":"NOT_FOUND"}}"#,
+        )]);
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+
+        let err = service
+            .start_batch_job(BatchParseRequest {
+                spreadsheet_id: Some("missing-sheet".to_string()),
+                completion_webhook_url: Some("ftp://hooks.example".to_string()),
+                ..batch_request(Some("(unclosed"), None)
+            })
+            .await
+            .unwrap_err();
+        let Some(CoreError::InvalidRequest(message)) = err.downcast_ref::<CoreError>() else {
+            panic!("expected an invalid request, got {err}");
+        };
+        assert!(message.contains("nameIncludeRegex"), "{message}");
+        assert!(message.contains("CompletionWebhookUrl"), "{message}");
+        assert!(message.contains("SpreadsheetId missing-sheet"), "{message}");
+
+        let requests = sheets.requests();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].path.starts_with("/spreadsheets/missing-sheet"));
+        assert!(service.list_jobs().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn completion_webhook_receives_final_status_and_sheet_url() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(vec![MockResponse::with_content_type(
+            200,
+            "application/json",
+            r#"{"files":[]}"#,
+        )]);
+        let sheets = MockHttpServer::start(Vec::new());
+        let webhook =
+            MockHttpServer::start(vec![MockResponse::with_content_type(204, "text/plain", "")]);
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+
+        service
+            .process_batch_job(BatchJobWorkItem {
+                job_id: "job-1".to_string(),
+                request: BatchParseRequest {
+                    spreadsheet_id: Some("sheet-1".to_string()),
+                    completion_webhook_url: Some(webhook.url("/hooks/resume-jobs")),
+                    ..batch_request(None, None)
+                },
+                checkpoint: None,
+            })
+            .await
+            .unwrap();
+
+        let requests = webhook.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/hooks/resume-jobs");
+
+        let payload: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(payload["jobId"], "job-1");
+        assert_eq!(payload["status"], "completed");
+        assert_eq!(payload["progress"], 100);
+        assert_eq!(payload["spreadsheetId"], "sheet-1");
+        assert_eq!(
+            payload["spreadsheetUrl"],
+            "https://docs.google.com/spreadsheets/d/sheet-1"
+        );
+    }
+
+    #[tokio::test]
+    async fn completion_webhook_does_not_receive_google_extra_headers() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(vec![MockResponse::with_content_type(
+            200,
+            "application/json",
+            r#"{"files":[]}"#,
+        )]);
+        let sheets = MockHttpServer::start(Vec::new());
+        let webhook =
+            MockHttpServer::start(vec![MockResponse::with_content_type(204, "text/plain", "")]);
+        let settings = RuntimeSettings {
+            google_client_id: "client-id".to_string(),
+            max_retries: 1,
+            extra_headers: HashMap::from([(
+                "X-Goog-User-Project".to_string(),
+                "acme-billing".to_string(),
+            )]),
+            ..RuntimeSettings::default()
+        };
+        let (service, _rx) =
+            test_service_with_settings(temp_dir.path(), &drive, &sheets, settings, 100);
+
+        service
+            .process_batch_job(BatchJobWorkItem {
+                job_id: "job-1".to_string(),
+                request: BatchParseRequest {
+                    spreadsheet_id: Some("sheet-1".to_string()),
+                    completion_webhook_url: Some(webhook.url("/hooks/resume-jobs")),
+                    ..batch_request(None, None)
+                },
+                checkpoint: None,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(
+            drive.requests()[0].header("x-goog-user-project"),
+            Some("acme-billing")
+        );
+        let requests = webhook.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].header("x-goog-user-project"), None);
+        assert!(requests[0]
+            .header("user-agent")
+            .is_some_and(|agent| agent.starts_with("SourceStackDesktop/")));
+    }
+
+    #[tokio::test]
+    async fn finished_jobs_emit_a_terminal_event_with_the_sheet_url() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(vec![MockResponse::with_content_type(
+            200,
+            "application/json",
+            r#"{"files":[]}"#,
+        )]);
+        let sheets = MockHttpServer::start(Vec::new());
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+        let received = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let captured = Arc::clone(&received);
+        service.set_event_sink(Arc::new(move |event, payload| {
+            captured.lock().unwrap().push((event.to_string(), payload));
+        }));
+
+        service
+            .process_batch_job(BatchJobWorkItem {
+                job_id: "job-1".to_string(),
+                request: BatchParseRequest {
+                    spreadsheet_id: Some("sheet-1".to_string()),
+                    ..batch_request(None, None)
+                },
+                checkpoint: None,
+            })
+            .await
+            .unwrap();
+
+        let events = received.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].0, JOB_COMPLETE);
+        assert_eq!(events[0].1["status"]["jobId"], "job-1");
+        assert_eq!(events[0].1["status"]["status"], "completed");
+        assert_eq!(
+            events[0].1["spreadsheetUrl"],
+            "https://docs.google.com/spreadsheets/d/sheet-1"
+        );
+        assert_eq!(events[0].1["parsedCount"], 0);
+    }
+
+    #[test]
+    fn job_finished_event_counts_parsed_failed_and_ocr_results() {
+        let mut parsed = ParsedCandidate::empty(None, None, Vec::new());
+        parsed.ocr_used = true;
+        parsed.errors.push(ParseError::new(
+            ParseErrorKind::PossibleDuplicate,
+            "Warning: possible duplicate of cv.pdf",
+        ));
+        let failed = ParsedCandidate::empty(
+            None,
+            None,
+            vec![ParseError::new(
+                ParseErrorKind::ExtractionFailed,
+                "Parse error: could not read file",
+            )],
+        );
+        let status = JobStatus {
+            job_id: "job-1".to_string(),
+            status: JobProcessingState::Failed,
+            progress: 50,
+            total_files: 4,
+            processed_files: 2,
+            spreadsheet_id: None,
+            results_count: Some(2),
+            error: Some("Sheets append failed".to_string()),
+            created_at: None,
+            started_at: None,
+            completed_at: None,
+            duration_seconds: None,
+        };
+
+        let event = job_finished_event(status, &[parsed, failed]);
+
+        assert_eq!(event.parsed_count, 1);
+        assert_eq!(event.failed_count, 1);
+        assert_eq!(event.ocr_count, 1);
+        assert_eq!(event.spreadsheet_url, None);
+        assert_eq!(event.status.error.as_deref(), Some("Sheets append failed"));
+    }
+
+    #[test]
+    fn completion_webhook_url_must_be_http() {
+        assert!(validate_webhook_url(None).is_ok());
+        assert!(validate_webhook_url(Some("https://hooks.example.com/jobs")).is_ok());
+        assert!(validate_webhook_url(Some("ftp://hooks.example.com/jobs")).is_err());
+        assert!(validate_webhook_url(Some("not a url")).is_err());
+    }
+
+    #[tokio::test]
+    async fn failed_job_resumes_from_checkpoint_page_without_reprocessing_files() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let docx_mime = "application/vnd.openxmlformats-officedocument.wordprocessingml.document";
+        let drive = MockHttpServer::start(vec![
+            MockResponse::with_content_type(
+                200,
+                "application/json",
+                format!(
+                    r#"{{"files":[{{"id":"file-1","name":"jane.docx","mimeType":"{docx_mime}"}}],"nextPageToken":"page-2"}}"#
+                ),
+            ),
+            MockResponse::with_content_type(
+                200,
+                "application/json",
+                format!(
+                    r#"{{"files":[{{"id":"file-2","name":"john.docx","mimeType":"{docx_mime}"}}]}}"#
+                ),
+            ),
+            MockResponse::with_content_type(
+                200,
+                "application/octet-stream",
+                docx_with_paragraphs(&["Jane Doe", "jane.doe@example.com"]),
+            ),
+            MockResponse::with_content_type(
+                200,
+                "application/octet-stream",
+                docx_with_paragraphs(&["John Smith", "john.smith@example.org"]),
+            ),
+            // Resumed run: only the checkpoint page is listed again.
+            MockResponse::with_content_type(
+                200,
+                "application/json",
+                format!(
+                    r#"{{"files":[{{"id":"file-2","name":"john.docx","mimeType":"{docx_mime}"}}]}}"#
+                ),
+            ),
+            MockResponse::with_content_type(
+                200,
+                "application/octet-stream",
+                docx_with_paragraphs(&["John Smith", "john.smith@example.org"]),
+            ),
+        ]);
+        let has_rows = r#"{"values":[["Name"]]}"#;
+        let sheets = MockHttpServer::start(vec![
+            MockResponse::with_content_type(200, "application/json", "{}"),
+            MockResponse::with_content_type(200, "application/json", "{}"),
+            MockResponse::with_content_type(200, "application/json", has_rows),
+            MockResponse::with_content_type(400, "application/json", "{}"),
+            MockResponse::with_content_type(200, "application/json", has_rows),
+            MockResponse::with_content_type(200, "application/json", "{}"),
+        ]);
+        let (service, mut queue_rx) =
+            test_service_with_queue(temp_dir.path(), &drive, &sheets, 100);
+        service.settings.write().await.spreadsheet_batch_size = 1;
+
+        let request = BatchParseRequest {
+            spreadsheet_id: Some("sheet-1".to_string()),
+            ..batch_request(None, None)
+        };
+        service
+            .process_batch_job(BatchJobWorkItem {
+                job_id: "job-1".to_string(),
+                request,
+                checkpoint: None,
+            })
+            .await
+            .unwrap();
+
+        let status = service.get_job_status("job-1").await.unwrap();
+        assert_eq!(status.status, JobProcessingState::Failed);
+        let checkpoint = service
+            .job_store
+            .load_checkpoint("job-1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(checkpoint.page_token.as_deref(), Some("page-2"));
+        assert_eq!(checkpoint.processed_file_ids, vec!["file-1"]);
+        assert_eq!(checkpoint.spreadsheet_id.as_deref(), Some("sheet-1"));
+
+        assert!(service.resume_job("job-1").await.unwrap());
+        let work_item = queue_rx.try_recv().unwrap();
+        service.process_batch_job(work_item).await.unwrap();
+
+        let status = service.get_job_status("job-1").await.unwrap();
+        assert_eq!(
+            status.status,
+            JobProcessingState::Completed,
+            "{:?}",
+            status.error
+        );
+        assert_eq!(status.processed_files, 2);
+        assert_eq!(status.total_files, 2);
+        assert_eq!(status.results_count, Some(2));
+        assert!(drive.requests()[4].path.contains("pageToken=page-2"));
+        assert!(service
+            .job_store
+            .load_checkpoint("job-1")
+            .await
+            .unwrap()
+            .is_none());
+        assert!(!service.resume_job("job-1").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn paused_job_waits_until_resumed() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(Vec::new());
+        let sheets = MockHttpServer::start(Vec::new());
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+        service
+            .job_store
+            .save_status(&JobStatus {
+                job_id: "job-1".to_string(),
+                status: JobProcessingState::Processing,
+                progress: 40,
+                total_files: 5,
+                processed_files: 2,
+                spreadsheet_id: None,
+                results_count: Some(2),
+                error: None,
+                created_at: Some(Utc::now()),
+                started_at: Some(Utc::now()),
+                completed_at: None,
+                duration_seconds: None,
+            })
+            .await
+            .unwrap();
+
+        assert!(service.pause_job("job-1").await.unwrap());
+        let token = CancellationToken::new();
+        let waiter = tokio::spawn({
+            let service = Arc::clone(&service);
+            let token = token.clone();
+            async move { service.wait_while_paused("job-1", &token).await }
+        });
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!waiter.is_finished());
+        let paused = service.get_job_status("job-1").await.unwrap();
+        assert_eq!(paused.status, JobProcessingState::Paused);
+        assert_eq!(paused.processed_files, 2);
+
+        assert!(service.resume_job("job-1").await.unwrap());
+        tokio::time::timeout(Duration::from_secs(5), waiter)
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        let resumed = service.get_job_status("job-1").await.unwrap();
+        assert_eq!(resumed.status, JobProcessingState::Processing);
+    }
+
+    #[tokio::test]
+    async fn abort_and_delete_stops_a_running_job_and_removes_it() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        // The slow listing leaves time to pause the job before its first chunk.
+        let drive = MockHttpServer::start(vec![MockResponse::with_content_type(
+            200,
+            "application/json",
+            r#"{"files":[{"id":"file-1","name":"jane.docx","mimeType":"application/vnd.openxmlformats-officedocument.wordprocessingml.document"}]}"#,
+        )
+        .delayed(Duration::from_millis(500))]);
+        let sheets = MockHttpServer::start(Vec::new());
+        let (service, queue_rx) = test_service_with_queue(temp_dir.path(), &drive, &sheets, 10);
+        let job_id = service
+            .start_batch_job(BatchParseRequest {
+                skip_spreadsheet: true,
+                ..batch_request(None, None)
+            })
+            .await
+            .unwrap();
+        // A queued job cannot be paused yet.
+        assert!(!service.pause_job(&job_id).await.unwrap());
+        // Paused before its first chunk, the job stays running until it is aborted.
+        tokio::spawn(Arc::clone(&service).process_queue(queue_rx));
+        pause_once_processing(&service, &job_id).await;
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while service.get_job_status(&job_id).await.unwrap().status
+                != JobProcessingState::Paused
+            {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        let deletion = service.abort_and_delete_job(&job_id).await.unwrap();
+        assert!(deletion.cancelled);
+        assert!(deletion.deleted);
+        assert!(!temp_dir.path().join("jobs").join(&job_id).exists());
+        assert!(service.get_job_status(&job_id).await.is_err());
+        assert_eq!(service.queue_status().await.running_jobs, 0);
+        assert_eq!(drive.requests().len(), 1);
+
+        service
+            .job_store
+            .save_status(&JobStatus {
+                job_id: "job-done".to_string(),
+                status: JobProcessingState::Completed,
+                progress: 100,
+                total_files: 1,
+                processed_files: 1,
+                spreadsheet_id: None,
+                results_count: Some(1),
+                error: None,
+                created_at: Some(Utc::now()),
+                started_at: Some(Utc::now()),
+                completed_at: Some(Utc::now()),
+                duration_seconds: Some(1.0),
+            })
+            .await
+            .unwrap();
+        let deletion = service.abort_and_delete_job("job-done").await.unwrap();
+        assert!(!deletion.cancelled);
+        assert!(deletion.deleted);
+        assert!(service.abort_and_delete_job("job-done").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn shutdown_stops_a_running_job_with_its_results_and_checkpoint_kept() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(vec![MockResponse::with_content_type(
+            200,
+            "application/json",
+            r#"{"files":[{"id":"file-1","name":"jane.docx","mimeType":"application/vnd.openxmlformats-officedocument.wordprocessingml.document"},{"id":"file-2","name":"john.docx","mimeType":"application/vnd.openxmlformats-officedocument.wordprocessingml.document"}]}"#,
+        )
+        .delayed(Duration::from_millis(500))]);
+        let sheets = MockHttpServer::start(Vec::new());
+        let (service, queue_rx) = test_service_with_queue(temp_dir.path(), &drive, &sheets, 10);
+        // A job that already processed file-1 before the last interruption.
+        let request = BatchParseRequest {
+            skip_spreadsheet: true,
+            ..batch_request(None, None)
+        };
+        service
+            .job_store
+            .save_status(&JobStatus {
+                job_id: "job-1".to_string(),
+                status: JobProcessingState::Failed,
+                progress: 50,
+                total_files: 2,
+                processed_files: 1,
+                spreadsheet_id: None,
+                results_count: Some(1),
+                error: None,
+                created_at: Some(Utc::now()),
+                started_at: Some(Utc::now()),
+                completed_at: Some(Utc::now()),
+                duration_seconds: Some(1.0),
+            })
+            .await
+            .unwrap();
+        service
+            .job_store
+            .save_checkpoint(
+                "job-1",
+                &JobCheckpoint {
+                    request,
+                    spreadsheet_id: None,
+                    page_token: None,
+                    processed_file_ids: vec!["file-1".to_string()],
+                },
+            )
+            .await
+            .unwrap();
+        service
+            .job_store
+            .save_results(
+                "job-1",
+                &[ParsedCandidate::empty(
+                    Some("jane.docx".to_string()),
+                    Some("file-1".to_string()),
+                    Vec::new(),
+                )],
+            )
+            .await
+            .unwrap();
+
+        // Paused at its next chunk, the resumed job is mid-pipeline when the app closes.
+        assert!(service.resume_job("job-1").await.unwrap());
+        tokio::spawn(Arc::clone(&service).process_queue(queue_rx));
+        pause_once_processing(&service, "job-1").await;
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while service.get_job_status("job-1").await.unwrap().status
+                != JobProcessingState::Paused
+            {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        service.shutdown(Duration::from_secs(5)).await;
+
+        assert_eq!(service.queue_status().await.running_jobs, 0);
+        let status = service.get_job_status("job-1").await.unwrap();
+        assert_eq!(status.status, JobProcessingState::Failed);
+        assert_eq!(status.error.as_deref(), Some(SHUTDOWN_MESSAGE));
+        let results = service
+            .job_store
+            .load_results("job-1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].drive_file_id.as_deref(), Some("file-1"));
+        let checkpoint = service
+            .job_store
+            .load_checkpoint("job-1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(checkpoint.processed_file_ids, vec!["file-1".to_string()]);
+        assert_eq!(drive.requests().len(), 1);
+
+        let err = service
+            .start_batch_job(batch_request(None, None))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("shutting down"));
+    }
+
+    #[tokio::test]
+    async fn queue_status_counts_jobs_waiting_for_the_worker() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(Vec::new());
+        let sheets = MockHttpServer::start(Vec::new());
+        let (service, queue_rx) = test_service_with_queue(temp_dir.path(), &drive, &sheets, 100);
+
+        service
+            .start_batch_job(batch_request(None, None))
+            .await
+            .unwrap();
+        service
+            .start_batch_job(batch_request(None, None))
+            .await
+            .unwrap();
+
+        let status = service.queue_status().await;
+        assert_eq!(status.queued_jobs, 2);
+        assert_eq!(status.running_jobs, 0);
+
+        drop(queue_rx);
+        assert!(service
+            .start_batch_job(batch_request(None, None))
+            .await
+            .is_err());
+        assert_eq!(service.queue_status().await.queued_jobs, 2);
+    }
+
+    #[tokio::test]
+    async fn full_queue_rejects_new_jobs_without_saving_them() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(Vec::new());
+        let sheets = MockHttpServer::start(Vec::new());
+        let (service, mut queue_rx) = test_service_with_queue(temp_dir.path(), &drive, &sheets, 2);
+
+        for _ in 0..2 {
+            service
+                .start_batch_job(batch_request(None, None))
+                .await
+                .unwrap();
+        }
+        let err = service
+            .start_batch_job(batch_request(None, None))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<CoreError>(),
+            Some(CoreError::QueueFull(2))
+        ));
+        assert_eq!(service.list_jobs().await.unwrap().len(), 2);
+
+        // Once the worker takes a job, there is room again.
+        queue_rx.recv().await.unwrap();
+        service
+            .start_batch_job(batch_request(None, None))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn repeated_idempotency_key_returns_existing_job() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(Vec::new());
+        let sheets = MockHttpServer::start(Vec::new());
+        let (service, _queue_rx) = test_service_with_queue(temp_dir.path(), &drive, &sheets, 100);
+        let keyed = |key: &str| BatchParseRequest {
+            idempotency_key: Some(key.to_string()),
+            ..batch_request(None, None)
+        };
+
+        let first = service
+            .start_batch_job(keyed("start-click-1"))
+            .await
+            .unwrap();
+        let repeat = service
+            .start_batch_job(keyed("start-click-1"))
+            .await
+            .unwrap();
+        let other = service
+            .start_batch_job(keyed("start-click-2"))
+            .await
+            .unwrap();
+
+        assert_eq!(first, repeat);
+        assert_ne!(first, other);
+        assert_eq!(service.queue_status().await.queued_jobs, 2);
+        assert_eq!(service.list_jobs().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn idempotency_keys_are_claimed_before_queueing_and_released_on_failure() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(Vec::new());
+        let sheets = MockHttpServer::start(Vec::new());
+        let (service, mut queue_rx) = test_service_with_queue(temp_dir.path(), &drive, &sheets, 1);
+        let keyed = |key: &str| BatchParseRequest {
+            idempotency_key: Some(key.to_string()),
+            ..batch_request(None, None)
+        };
+
+        let (first, racing) = tokio::join!(
+            service.start_batch_job(keyed("start-click-1")),
+            service.start_batch_job(keyed("start-click-1")),
+        );
+        assert_eq!(first.unwrap(), racing.unwrap());
+        assert_eq!(service.list_jobs().await.unwrap().len(), 1);
+
+        let full = service.start_batch_job(keyed("start-click-2")).await;
+        assert!(full.is_err());
+
+        queue_rx.recv().await.unwrap();
+        let retried = service
+            .start_batch_job(keyed("start-click-2"))
+            .await
+            .unwrap();
+        assert_eq!(
+            service.get_job_status(&retried).await.unwrap().status,
+            JobProcessingState::Pending
+        );
+    }
+
+    #[test]
+    fn spreadsheet_title_template_fills_placeholders() {
+        let now = chrono::DateTime::parse_from_rfc3339("2026-03-04T05:06:07Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        assert_eq!(
+            render_spreadsheet_title(
+                &RuntimeSettings::default().spreadsheet_title_template,
+                now,
+                3
+            ),
+            "Resume Parse Results - 2026-03-04 05:06:07"
+        );
+        assert_eq!(
+            render_spreadsheet_title("Acme Backend Hiring ({count} resumes) {date}", now, 12),
+            "Acme Backend Hiring (12 resumes) 2026-03-04"
+        );
+    }
+
+    #[test]
+    fn user_agent_includes_version_os_and_optional_suffix() {
+        let base = user_agent(&RuntimeSettings::default());
+        assert_eq!(
+            base,
+            format!(
+                "SourceStackDesktop/{} ({}; {})",
+                env!("CARGO_PKG_VERSION"),
+                std::env::consts::OS,
+                std::env::consts::ARCH
+            )
+        );
+
+        let settings = RuntimeSettings {
+            user_agent_suffix: Some("acme-recruiting".to_string()),
+            ..RuntimeSettings::default()
+        };
+        assert_eq!(user_agent(&settings), format!("{base} acme-recruiting"));
+        assert!(build_http_client(&settings).is_ok());
+    }
+
+    #[tokio::test]
+    async fn skip_existing_in_sheet_only_appends_new_emails() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(vec![
+            MockResponse::with_content_type(
+                200,
+                "application/json",
+                r#"{"files":[{"id":"file-1","name":"jane.docx","mimeType":"application/vnd.openxmlformats-officedocument.wordprocessingml.document"},{"id":"file-2","name":"john.docx","mimeType":"application/vnd.openxmlformats-officedocument.wordprocessingml.document"}]}"#,
+            ),
+            MockResponse::with_content_type(
+                200,
+                "application/octet-stream",
+                docx_with_paragraphs(&["Jane Doe", "Jane.Doe@example.com"]),
+            ),
+            MockResponse::with_content_type(
+                200,
+                "application/octet-stream",
+                docx_with_paragraphs(&["John Smith", "john.smith@example.org"]),
+            ),
+        ]);
+        let sheets = MockHttpServer::start(vec![
+            MockResponse::with_content_type(
+                200,
+                "application/json",
+                r#"{"range":"Resume Data!D1:D3","values":[["Email ID"],["jane.doe@example.com"]]}"#,
+            ),
+            MockResponse::with_content_type(
+                200,
+                "application/json",
+                r#"{"values":[["Name","Resume Link","Phone Number","Email ID","LinkedIn","GitHub"]]}"#,
+            ),
+            MockResponse::with_content_type(200, "application/json", "{}"),
+        ]);
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+
+        service
+            .process_batch_job(BatchJobWorkItem {
+                job_id: "job-1".to_string(),
+                request: BatchParseRequest {
+                    spreadsheet_id: Some("sheet-1".to_string()),
+                    skip_existing_in_sheet: true,
+                    ..batch_request(None, None)
+                },
+                checkpoint: None,
+            })
+            .await
+            .unwrap();
+
+        let status = service.get_job_status("job-1").await.unwrap();
+        assert_eq!(
+            status.status,
+            JobProcessingState::Completed,
+            "{:?}",
+            status.error
+        );
+        assert_eq!(status.results_count, Some(2));
+
+        let requests = sheets.requests();
+        let paths = requests
+            .iter()
+            .map(|request| request.path.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            vec![
+                "/spreadsheets/sheet-1/values/D:D",
+                "/spreadsheets/sheet-1/values/A1:F1",
+                "/spreadsheets/sheet-1/values/A1:F1:append?valueInputOption=USER_ENTERED&insertDataOption=INSERT_ROWS",
+            ]
+        );
+        let appended = String::from_utf8_lossy(&requests[2].body);
+        assert!(appended.contains("john.smith@example.org"));
+        assert!(!appended.contains("jane.doe@example.com"));
+    }
+
+    #[tokio::test]
+    async fn sorted_jobs_write_rows_once_in_confidence_order() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(vec![
+            MockResponse::with_content_type(
+                200,
+                "application/json",
+                r#"{"files":[{"id":"file-1","name":"jane.docx","mimeType":"application/vnd.openxmlformats-officedocument.wordprocessingml.document"},{"id":"file-2","name":"john.docx","mimeType":"application/vnd.openxmlformats-officedocument.wordprocessingml.document"}]}"#,
+            ),
+            MockResponse::with_content_type(
+                200,
+                "application/octet-stream",
+                docx_with_paragraphs(&["Jane Doe"]),
+            ),
+            MockResponse::with_content_type(
+                200,
+                "application/octet-stream",
+                docx_with_paragraphs(&["John Smith", "john.smith@example.org", "+91 9876543210"]),
+            ),
+        ]);
+        let sheets = MockHttpServer::start(vec![
+            MockResponse::with_content_type(
+                200,
+                "application/json",
+                r#"{"values":[["Name","Resume Link","Phone Number","Email ID","LinkedIn","GitHub"]]}"#,
+            ),
+            MockResponse::with_content_type(200, "application/json", "{}"),
+        ]);
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+        {
+            let mut settings = service.settings.write().await;
+            settings.sort_order = SortOrder::ConfidenceDesc;
+            // One file per chunk, which would otherwise append after each file.
+            settings.spreadsheet_batch_size = 1;
+        }
+
+        service
+            .process_batch_job(BatchJobWorkItem {
+                job_id: "job-1".to_string(),
+                request: BatchParseRequest {
+                    spreadsheet_id: Some("sheet-1".to_string()),
+                    ..batch_request(None, None)
+                },
+                checkpoint: None,
+            })
+            .await
+            .unwrap();
+
+        let status = service.get_job_status("job-1").await.unwrap();
+        assert_eq!(
+            status.status,
+            JobProcessingState::Completed,
+            "{:?}",
+            status.error
+        );
+
+        let requests = sheets.requests();
+        assert_eq!(requests.len(), 2);
+        let payload: serde_json::Value = serde_json::from_slice(&requests[1].body).unwrap();
+        let values = payload["values"].as_array().unwrap();
+        assert_eq!(values.len(), 2);
+        assert_eq!(values[0][0], "John Smith");
+        assert_eq!(values[1][0], "Jane Doe");
+
+        let results = service
+            .job_store
+            .load_results("job-1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(results[0].name.as_deref(), Some("John Smith"));
+        assert!(results[0].confidence > results[1].confidence);
+    }
+
+    #[test]
+    fn name_sort_ignores_case_and_puts_missing_names_last() {
+        let candidate = |id: &str, name: Option<&str>| {
+            let mut candidate = ParsedCandidate::empty(None, Some(id.to_string()), Vec::new());
+            candidate.name = name.map(str::to_string);
+            candidate
+        };
+        let mut candidates = vec![
+            candidate("file-1", None),
+            candidate("file-2", Some("bob Stone")),
+            candidate("file-3", Some("  ")),
+            candidate("file-4", Some("Alice Ray")),
+            candidate("file-5", Some("Bob Stone")),
+        ];
+
+        sort_candidates(&mut candidates, SortOrder::NameAsc);
+        let order = candidates
+            .iter()
+            .map(|candidate| candidate.drive_file_id.as_deref().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            order,
+            vec!["file-4", "file-2", "file-5", "file-1", "file-3"]
+        );
+
+        sort_candidates(&mut candidates, SortOrder::None);
+        assert_eq!(candidates[0].drive_file_id.as_deref(), Some("file-4"));
+    }
+
+    #[tokio::test]
+    async fn low_confidence_candidates_are_routed_to_the_review_sheet() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(vec![
+            MockResponse::with_content_type(
+                200,
+                "application/json",
+                r#"{"files":[{"id":"file-1","name":"jane.docx","mimeType":"application/vnd.openxmlformats-officedocument.wordprocessingml.document"},{"id":"file-2","name":"john.docx","mimeType":"application/vnd.openxmlformats-officedocument.wordprocessingml.document"}]}"#,
+            ),
+            MockResponse::with_content_type(
+                200,
+                "application/octet-stream",
+                docx_with_paragraphs(&[
+                    "Jane Doe",
+                    "jane.doe@example.com",
+                    "+1 650 253 0000",
+                    "linkedin.com/in/janedoe",
+                ]),
+            ),
+            MockResponse::with_content_type(
+                200,
+                "application/octet-stream",
+                docx_with_paragraphs(&["John Smith", "john.smith@example.org"]),
+            ),
+        ]);
+        let has_header = r#"{"values":[["Name","Resume Link","Phone Number","Email ID"]]}"#;
+        let sheets = MockHttpServer::start(vec![
+            MockResponse::with_content_type(200, "application/json", has_header),
+            MockResponse::with_content_type(200, "application/json", "{}"),
+            MockResponse::with_content_type(200, "application/json", has_header),
+            MockResponse::with_content_type(200, "application/json", "{}"),
+        ]);
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+
+        service
+            .process_batch_job(BatchJobWorkItem {
+                job_id: "job-1".to_string(),
+                request: BatchParseRequest {
+                    spreadsheet_id: Some("sheet-1".to_string()),
+                    review_spreadsheet_id: Some("review-sheet".to_string()),
+                    review_confidence_threshold: Some(0.8),
+                    ..batch_request(None, None)
+                },
+                checkpoint: None,
+            })
+            .await
+            .unwrap();
+
+        let status = service.get_job_status("job-1").await.unwrap();
+        assert_eq!(
+            status.status,
+            JobProcessingState::Completed,
+            "{:?}",
+            status.error
+        );
+
+        let appends = sheets
+            .requests()
+            .into_iter()
+            .filter(|request| request.path.contains(":append"))
+            .collect::<Vec<_>>();
+        assert_eq!(appends.len(), 2);
+        let body_for = |sheet: &str| {
+            appends
+                .iter()
+                .find(|request| request.path.starts_with(&format!("/spreadsheets/{sheet}/")))
+                .map(|request| String::from_utf8_lossy(&request.body).to_string())
+                .unwrap()
+        };
+        let main = body_for("sheet-1");
+        assert!(main.contains("jane.doe@example.com"));
+        assert!(!main.contains("john.smith@example.org"));
+        let review = body_for("review-sheet");
+        assert!(review.contains("john.smith@example.org"));
+        assert!(!review.contains("Email ID"));
+    }
+
+    #[tokio::test]
+    async fn exhausted_sheets_quota_keeps_rows_locally_and_completes() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(vec![
+            MockResponse::with_content_type(
+                200,
+                "application/json",
+                r#"{"files":[{"id":"file-1","name":"jane.docx","mimeType":"application/vnd.openxmlformats-officedocument.wordprocessingml.document"},{"id":"file-2","name":"john.docx","mimeType":"application/vnd.openxmlformats-officedocument.wordprocessingml.document"}]}"#,
+            ),
+            MockResponse::with_content_type(
+                200,
+                "application/octet-stream",
+                docx_with_paragraphs(&["Jane Doe", "jane.doe@example.com"]),
+            ),
+            MockResponse::with_content_type(
+                200,
+                "application/octet-stream",
+                docx_with_paragraphs(&["John Smith", "john.smith@example.org"]),
+            ),
+        ]);
+        let quota =
+            r#"{"error":{"code":429,"message":"Quota exceeded","status":"RESOURCE_EXHAUSTED"}}"#;
+        let sheets = MockHttpServer::start(vec![MockResponse::with_content_type(
+            429,
+            "application/json",
+            quota,
+        )]);
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+
+        service
+            .process_batch_job(BatchJobWorkItem {
+                job_id: "job-1".to_string(),
+                request: BatchParseRequest {
+                    spreadsheet_id: Some("sheet-1".to_string()),
+                    ..batch_request(None, None)
+                },
+                checkpoint: None,
+            })
+            .await
+            .unwrap();
+
+        let status = service.get_job_status("job-1").await.unwrap();
+        assert_eq!(status.status, JobProcessingState::Completed);
+        assert_eq!(status.results_count, Some(2));
+        let warning = status.error.unwrap();
+        assert!(warning.contains("2 rows could not be written"), "{warning}");
+        // Reading the sheet's first row already hits the quota, so nothing is written.
+        assert_eq!(sheets.requests().len(), 1);
+
+        let saved = std::fs::read_to_string(
+            service
+                .job_store
+                .jobs_root()
+                .join("job-1")
+                .join("unwritten-rows.csv"),
+        )
+        .unwrap();
+        let lines = saved.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("Spreadsheet ID,Name,Resume Link"));
+        assert!(lines[1..].iter().all(|line| line.starts_with("sheet-1,")));
+        assert!(saved.contains("jane.doe@example.com"));
+        assert!(saved.contains("john.smith@example.org"));
+    }
+
+    #[tokio::test]
+    async fn parse_single_as_row_follows_the_requested_column_order() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(Vec::new());
+        let sheets = MockHttpServer::start(Vec::new());
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+        service.settings.write().await.skill_dictionary = vec!["Rust".to_string()];
+        let docx = || docx_with_paragraphs(&["Jane Doe", "jane.doe@example.com", "Skills: Rust"]);
+
+        let row = service
+            .parse_single_as_row(
+                "jane.docx".to_string(),
+                docx(),
+                Some(vec![
+                    SheetColumn::Email,
+                    SheetColumn::Name,
+                    SheetColumn::ResumeLink,
+                    SheetColumn::Skills,
+                ]),
+            )
+            .await
+            .unwrap();
+        assert_eq!(row, vec!["jane.doe@example.com", "Jane Doe", "", "Rust"]);
+
+        service.settings.write().await.color_by_confidence = true;
+        let row = service
+            .parse_single_as_row("jane.docx".to_string(), docx(), None)
+            .await
+            .unwrap();
+        // Skills and Confidence follow the default columns.
+        assert_eq!(row.len(), SheetColumn::DEFAULT_LAYOUT.len() + 2);
+        assert_eq!(row[0], "Jane Doe");
+        assert_eq!(row[3], "jane.doe@example.com");
+        assert_eq!(row[6], "Rust");
+        assert!(row[7].starts_with("0."));
+
+        // Without a dictionary there is no Skills column at all.
+        {
+            let mut settings = service.settings.write().await;
+            settings.skill_dictionary.clear();
+            settings.color_by_confidence = false;
+        }
+        let row = service
+            .parse_single_as_row("jane.docx".to_string(), docx(), None)
+            .await
+            .unwrap();
+        assert_eq!(row.len(), SheetColumn::DEFAULT_LAYOUT.len());
+    }
+
+    #[tokio::test]
+    async fn missing_fields_are_written_as_the_configured_placeholder() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(Vec::new());
+        let sheets = MockHttpServer::start(Vec::new());
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+        service.settings.write().await.missing_value_placeholder = "N/A".to_string();
+
+        let row = service
+            .parse_single_as_row(
+                "jane.docx".to_string(),
+                docx_with_paragraphs(&["Jane Doe", "jane.doe@example.com"]),
+                Some(vec![
+                    SheetColumn::Name,
+                    SheetColumn::Phone,
+                    SheetColumn::Email,
+                    SheetColumn::GitHub,
+                ]),
+            )
+            .await
+            .unwrap();
+        assert_eq!(row, vec!["Jane Doe", "N/A", "jane.doe@example.com", "N/A"]);
+    }
+
+    #[tokio::test]
+    async fn email_domain_column_follows_the_email() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(Vec::new());
+        let sheets = MockHttpServer::start(Vec::new());
+        let service = test_service(temp_dir.path(), &drive, &sheets);
+        let docx = || docx_with_paragraphs(&["Jane Doe", "Jane.Doe@Example.COM"]);
+
+        let row = service
+            .parse_single_as_row(
+                "jane.docx".to_string(),
+                docx(),
+                Some(vec![SheetColumn::Email, SheetColumn::EmailDomain]),
+            )
+            .await
+            .unwrap();
+        assert_eq!(row, vec!["jane.doe@example.com", "example.com"]);
+
+        let settings = RuntimeSettings {
+            email_domain_column: true,
+            education_column: true,
+            ..RuntimeSettings::default()
+        };
+        let layout = sheet_layout(&settings);
+        assert_eq!(
+            &layout[SheetColumn::DEFAULT_LAYOUT.len()..],
+            [SheetColumn::EmailDomain, SheetColumn::Education]
+        );
+        assert_eq!(SheetColumn::EmailDomain.header(), "Email Domain");
+        assert_eq!(
+            serde_json::to_value(SheetColumn::EmailDomain).unwrap(),
+            "emailDomain"
+        );
+    }
+
+    #[test]
+    fn review_routing_needs_both_a_sheet_and_a_valid_threshold() {
+        let request = |sheet: Option<&str>, threshold: Option<f64>| BatchParseRequest {
+            review_spreadsheet_id: sheet.map(str::to_string),
+            review_confidence_threshold: threshold,
+            ..batch_request(None, None)
+        };
+
+        assert!(batch_request_problems(&request(Some("review"), Some(0.6))).is_empty());
+        assert!(batch_request_problems(&request(None, None)).is_empty());
+        assert_eq!(
+            batch_request_problems(&request(Some("review"), None)),
+            vec!["ReviewSpreadsheetId requires a ReviewConfidenceThreshold"]
+        );
+        assert_eq!(
+            batch_request_problems(&request(None, Some(0.6))),
+            vec!["ReviewConfidenceThreshold requires a ReviewSpreadsheetId"]
+        );
+        assert_eq!(
+            batch_request_problems(&request(Some("review"), Some(1.5))),
+            vec!["ReviewConfidenceThreshold must be between 0 and 1"]
+        );
+        assert_eq!(
+            review_route(&request(Some(" review "), Some(0.6))),
+            Some(("review", 0.6))
+        );
+    }
+
+    #[test]
+    fn allowed_mime_types_must_be_supported() {
+        let request = |mime_types: &[&str]| BatchParseRequest {
+            allowed_mime_types: mime_types.iter().map(|value| value.to_string()).collect(),
+            ..batch_request(None, None)
+        };
+
+        assert!(batch_request_problems(&request(&["application/pdf"])).is_empty());
+        assert_eq!(
+            batch_request_problems(&request(&["application/pdf", "image/png"])),
+            vec!["AllowedMimeTypes entry is not a supported type: image/png"]
+        );
+    }
+
+    #[test]
+    fn confidence_formats_as_decimal_or_percent() {
+        assert_eq!(format_confidence(0.853, false), "0.85");
+        assert_eq!(format_confidence(0.853, true), "85%");
+        assert_eq!(format_confidence(1.0, true), "100%");
+    }
+
+    #[test]
+    fn email_column_matches_header_layout() {
+        assert_eq!(email_column_letter(), "D");
+    }
+
+    fn drive_query(request: &crate::core::test_support::RecordedRequest) -> String {
+        Url::parse(&format!("http://localhost{}", request.path))
+            .unwrap()
+            .query_pairs()
+            .find(|(name, _)| name == "q")
+            .map(|(_, value)| value.into_owned())
+            .unwrap_or_default()
+    }
+
+    #[tokio::test]
+    async fn watch_cycles_only_process_files_no_earlier_cycle_processed() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let listing =
+            |body: &'static str| MockResponse::with_content_type(200, "application/json", body);
+        let one_file = r#"{"files":[{"id":"file-1","name":"amy.docx","mimeType":"application/vnd.openxmlformats-officedocument.wordprocessingml.document"}]}"#;
+        // file-2 was uploaded with its original, older modified time.
+        let two_files = r#"{"files":[{"id":"file-1","name":"amy.docx","mimeType":"application/vnd.openxmlformats-officedocument.wordprocessingml.document"},{"id":"file-2","name":"bob.docx","mimeType":"application/vnd.openxmlformats-officedocument.wordprocessingml.document"}]}"#;
+        let drive = MockHttpServer::start(vec![
+            listing(one_file),
+            listing(one_file),
+            MockResponse::with_content_type(
+                200,
+                "application/octet-stream",
+                docx_with_paragraphs(&["Amy Brown", "amy@example.com"]),
+            ),
+            listing(one_file),
+            listing(two_files),
+            listing(two_files),
+            MockResponse::with_content_type(
+                200,
+                "application/octet-stream",
+                docx_with_paragraphs(&["Bob Clark", "bob@example.com"]),
+            ),
+        ]);
+        let sheet_responses = || {
+            vec![
+                MockResponse::with_content_type(
+                    200,
+                    "application/json",
+                    r#"{"spreadsheetId":"sheet-1"}"#,
+                ),
+                MockResponse::with_content_type(200, "application/json", "{}"),
+                MockResponse::with_content_type(200, "application/json", "{}"),
+                MockResponse::with_content_type(200, "application/json", "{}"),
+            ]
+        };
+        let sheets = MockHttpServer::start(
+            sheet_responses()
+                .into_iter()
+                .chain(sheet_responses())
+                .collect(),
+        );
+        let (service, queue_rx) = test_service_with_queue(temp_dir.path(), &drive, &sheets, 10);
+        tokio::spawn(Arc::clone(&service).process_queue(queue_rx));
+        let request = WatchFolderRequest {
+            folder_id: "folder-1".to_string(),
+            interval_seconds: 60,
+            spreadsheet_id: Some("sheet-1".to_string()),
+        };
+        let cancel = CancellationToken::new();
+        let watch_state = || async {
+            service
+                .job_store
+                .load_watch_state("folder-1")
+                .await
+                .unwrap()
+                .unwrap()
+        };
+
+        service.run_watch_cycle(&request, &cancel).await.unwrap();
+        let first = watch_state().await;
+        assert_eq!(first.spreadsheet_id.as_deref(), Some("sheet-1"));
+        assert_eq!(first.seen_file_ids, BTreeSet::from(["file-1".to_string()]));
+
+        // Later cycles keep appending to the watch's sheet without being told which it is.
+        let request = WatchFolderRequest {
+            spreadsheet_id: None,
+            ..request
+        };
+        service.run_watch_cycle(&request, &cancel).await.unwrap();
+        assert_eq!(service.list_jobs().await.unwrap().len(), 1);
+        assert_eq!(watch_state().await.last_job_id, first.last_job_id);
+
+        service.run_watch_cycle(&request, &cancel).await.unwrap();
+        let third = watch_state().await;
+        assert_eq!(third.spreadsheet_id.as_deref(), Some("sheet-1"));
+        assert_eq!(
+            third.seen_file_ids,
+            BTreeSet::from(["file-1".to_string(), "file-2".to_string()])
+        );
+        assert_eq!(service.list_jobs().await.unwrap().len(), 2);
+
+        let drive_requests = drive.requests();
+        assert_eq!(drive_requests.len(), 7);
+        assert!(drive_requests
+            .iter()
+            .filter(|request| request.path.starts_with("/files?"))
+            .all(|request| !drive_query(request).contains("modifiedTime")));
+        assert!(drive_requests[6].path.starts_with("/files/file-2?"));
+        let results = service
+            .get_job_results(third.last_job_id.as_deref().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name.as_deref(), Some("Bob Clark"));
+        assert!(sheets
+            .requests()
+            .iter()
+            .all(|request| request.path.starts_with("/spreadsheets/sheet-1")));
+    }
+
+    #[tokio::test]
+    async fn stopping_a_watch_kills_the_job_of_its_cycle() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let drive = MockHttpServer::start(vec![MockResponse::with_content_type(
+            200,
+            "application/json",
+            r#"{"files":[{"id":"file-1","name":"amy.docx","mimeType":"application/vnd.openxmlformats-officedocument.wordprocessingml.document"}]}"#,
+        )]);
+        let sheets = MockHttpServer::start(Vec::new());
+        // No queue worker runs, so the cycle's job stays pending until the watch stops.
+        let (service, _queue_rx) = test_service_with_queue(temp_dir.path(), &drive, &sheets, 10);
+        let request = |interval_seconds| WatchFolderRequest {
+            folder_id: "folder-1".to_string(),
+            interval_seconds,
+            spreadsheet_id: None,
+        };
+
+        assert!(service.start_watch(request(5)).await.is_err());
+        assert!(service.start_watch(request(60)).await.unwrap());
+        assert!(!service.start_watch(request(60)).await.unwrap());
+
+        let job_id = loop {
+            if let Some(job_id) = service.list_jobs().await.unwrap().pop() {
+                break job_id;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        };
+        assert!(service.stop_watch("folder-1").await.unwrap());
+        assert!(!service.stop_watch("folder-1").await.unwrap());
+
+        let status = loop {
+            let status = service.get_job_status(&job_id).await.unwrap();
+            if status.status != JobProcessingState::Pending {
+                break status;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        };
+        assert_eq!(status.status, JobProcessingState::Revoked);
+        assert!(service
+            .job_store
+            .load_watch_state("folder-1")
+            .await
+            .unwrap()
+            .is_none());
+    }
 }
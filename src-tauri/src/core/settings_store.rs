@@ -1,9 +1,19 @@
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 use anyhow::Context;
+use once_cell::sync::OnceCell;
 use serde::Deserialize;
 
-use super::models::PersistedSettings;
+use super::models::{
+    ConfidenceWeights, CustomFieldRule, EmailPreference, FieldKind, NationalPhonePolicy,
+    PersistedSettings, RetryPolicy, SortOrder, UnsupportedFilePolicy,
+};
+
+/// Environment variable naming the profile; a `--profile <name>` launch argument wins over it.
+pub const PROFILE_ENV_VAR: &str = "SOURCESTACK_PROFILE";
+
+static ACTIVE_PROFILE: OnceCell<Option<String>> = OnceCell::new();
 
 pub struct SettingsStore {
     file_path: PathBuf,
@@ -26,6 +36,10 @@ struct PersistedSettingsRaw {
     #[serde(default)]
     max_concurrent_requests: Option<usize>,
     #[serde(default)]
+    max_concurrent_downloads: Option<usize>,
+    #[serde(default)]
+    max_concurrent_parses: Option<usize>,
+    #[serde(default)]
     spreadsheet_batch_size: Option<usize>,
     #[serde(default)]
     max_retries: Option<usize>,
@@ -33,6 +47,76 @@ struct PersistedSettingsRaw {
     retry_delay_seconds: Option<f64>,
     #[serde(default)]
     job_retention_hours: Option<i64>,
+    #[serde(default)]
+    custom_ca_cert_path: Option<String>,
+    #[serde(default)]
+    email_preference: EmailPreference,
+    #[serde(default)]
+    user_agent_suffix: Option<String>,
+    #[serde(default)]
+    skill_dictionary: Vec<String>,
+    #[serde(default)]
+    disable_ocr: bool,
+    #[serde(default)]
+    status_write_interval_ms: Option<u64>,
+    #[serde(default)]
+    preserve_text_columns: bool,
+    #[serde(default)]
+    spreadsheet_title_template: Option<String>,
+    #[serde(default)]
+    color_by_confidence: bool,
+    #[serde(default)]
+    merge_ocr_fields: bool,
+    #[serde(default)]
+    max_queued_jobs: Option<usize>,
+    #[serde(default)]
+    debug_field_sources: bool,
+    #[serde(default)]
+    required_fields: Vec<FieldKind>,
+    #[serde(default)]
+    confidence_as_percent: bool,
+    #[serde(default)]
+    confidence_weights: ConfidenceWeights,
+    #[serde(default)]
+    name_denylist: Vec<String>,
+    #[serde(default)]
+    keep_ocr_temp: bool,
+    #[serde(default)]
+    retry_on: RetryPolicy,
+    #[serde(default)]
+    cleanup_interval_minutes: u64,
+    #[serde(default)]
+    notice_period_column: bool,
+    #[serde(default)]
+    compact_results: bool,
+    #[serde(default)]
+    sheet_append_page_size: Option<usize>,
+    #[serde(default)]
+    missing_value_placeholder: String,
+    #[serde(default)]
+    unsupported_file_policy: UnsupportedFilePolicy,
+    #[serde(default)]
+    adaptive_concurrency: bool,
+    #[serde(default)]
+    custom_fields: Vec<CustomFieldRule>,
+    #[serde(default)]
+    sort_order: SortOrder,
+    #[serde(default)]
+    job_retry_budget: Option<usize>,
+    #[serde(default)]
+    sheet_append_delay_ms: Option<u64>,
+    #[serde(default)]
+    deobfuscate_emails: Option<bool>,
+    #[serde(default)]
+    education_column: bool,
+    #[serde(default)]
+    extra_headers: HashMap<String, String>,
+    #[serde(default)]
+    require_international_phone: bool,
+    #[serde(default)]
+    national_phone_policy: NationalPhonePolicy,
+    #[serde(default)]
+    email_domain_column: bool,
 }
 
 impl SettingsStore {
@@ -42,6 +126,11 @@ impl SettingsStore {
         }
     }
 
+    #[cfg(test)]
+    pub(crate) fn with_path(file_path: PathBuf) -> Self {
+        Self { file_path }
+    }
+
     pub fn path(&self) -> &PathBuf {
         &self.file_path
     }
@@ -74,6 +163,8 @@ impl SettingsStore {
             max_concurrent_requests: raw
                 .max_concurrent_requests
                 .unwrap_or(defaults.max_concurrent_requests),
+            max_concurrent_downloads: raw.max_concurrent_downloads,
+            max_concurrent_parses: raw.max_concurrent_parses,
             spreadsheet_batch_size: raw
                 .spreadsheet_batch_size
                 .unwrap_or(defaults.spreadsheet_batch_size),
@@ -84,6 +175,49 @@ impl SettingsStore {
             job_retention_hours: raw
                 .job_retention_hours
                 .unwrap_or(defaults.job_retention_hours),
+            custom_ca_cert_path: raw.custom_ca_cert_path,
+            email_preference: raw.email_preference,
+            user_agent_suffix: raw.user_agent_suffix,
+            skill_dictionary: raw.skill_dictionary,
+            disable_ocr: raw.disable_ocr,
+            status_write_interval_ms: raw
+                .status_write_interval_ms
+                .unwrap_or(defaults.status_write_interval_ms),
+            preserve_text_columns: raw.preserve_text_columns,
+            spreadsheet_title_template: raw
+                .spreadsheet_title_template
+                .unwrap_or(defaults.spreadsheet_title_template),
+            color_by_confidence: raw.color_by_confidence,
+            merge_ocr_fields: raw.merge_ocr_fields,
+            max_queued_jobs: raw.max_queued_jobs.unwrap_or(defaults.max_queued_jobs),
+            debug_field_sources: raw.debug_field_sources,
+            required_fields: raw.required_fields,
+            confidence_as_percent: raw.confidence_as_percent,
+            confidence_weights: raw.confidence_weights,
+            name_denylist: raw.name_denylist,
+            keep_ocr_temp: raw.keep_ocr_temp,
+            retry_on: raw.retry_on,
+            cleanup_interval_minutes: raw.cleanup_interval_minutes,
+            notice_period_column: raw.notice_period_column,
+            compact_results: raw.compact_results,
+            sheet_append_page_size: raw.sheet_append_page_size,
+            missing_value_placeholder: raw.missing_value_placeholder,
+            unsupported_file_policy: raw.unsupported_file_policy,
+            adaptive_concurrency: raw.adaptive_concurrency,
+            custom_fields: raw.custom_fields,
+            sort_order: raw.sort_order,
+            job_retry_budget: raw.job_retry_budget,
+            sheet_append_delay_ms: raw
+                .sheet_append_delay_ms
+                .unwrap_or(defaults.sheet_append_delay_ms),
+            deobfuscate_emails: raw
+                .deobfuscate_emails
+                .unwrap_or(defaults.deobfuscate_emails),
+            education_column: raw.education_column,
+            extra_headers: raw.extra_headers,
+            require_international_phone: raw.require_international_phone,
+            national_phone_policy: raw.national_phone_policy,
+            email_domain_column: raw.email_domain_column,
         }
         .sanitized();
 
@@ -118,7 +252,67 @@ fn settings_path() -> PathBuf {
     app_data_root().join("desktop-settings.json")
 }
 
+pub fn logs_root() -> PathBuf {
+    app_data_root().join("logs")
+}
+
+/// Selects the profile whose settings, jobs, and keychain entries this process uses.
+/// Must run before any store is created; the profile cannot change afterwards.
+pub fn set_active_profile(profile: Option<&str>) -> anyhow::Result<()> {
+    let profile = match profile {
+        Some(name) => normalize_profile_name(name)?,
+        None => None,
+    };
+
+    let active = ACTIVE_PROFILE.get_or_init(|| profile.clone());
+    if *active != profile {
+        anyhow::bail!("profile is already set to {active:?}");
+    }
+    Ok(())
+}
+
+pub fn active_profile() -> Option<&'static str> {
+    ACTIVE_PROFILE.get().and_then(|profile| profile.as_deref())
+}
+
+/// Keychain service name for the active profile; the default profile keeps `base`.
+pub fn profile_keyring_service(base: &str) -> String {
+    match active_profile() {
+        Some(profile) => format!("{base}.profiles.{profile}"),
+        None => base.to_string(),
+    }
+}
+
+/// Trims and lowercases a profile name, so `Work` and `work` are the same profile. A blank
+/// name means the default profile.
+pub fn normalize_profile_name(name: &str) -> anyhow::Result<Option<String>> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Ok(None);
+    }
+
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        anyhow::bail!("invalid profile name {name:?}: use only letters, digits, '-' and '_'");
+    }
+
+    Ok(Some(name.to_ascii_lowercase()))
+}
+
+fn profile_data_root(base: PathBuf, profile: Option<&str>) -> PathBuf {
+    match profile {
+        Some(profile) => base.join("profiles").join(profile),
+        None => base,
+    }
+}
+
 pub fn app_data_root() -> PathBuf {
+    profile_data_root(base_data_root(), active_profile())
+}
+
+fn base_data_root() -> PathBuf {
     #[cfg(target_os = "windows")]
     {
         if let Ok(local_app_data) = std::env::var("LOCALAPPDATA") {
@@ -171,4 +365,25 @@ mod tests {
         let written = tokio::fs::read_to_string(store.path()).await.unwrap();
         assert!(!written.contains("googleClientSecret"));
     }
+
+    #[test]
+    fn profiles_nest_under_the_default_root() {
+        let base = PathBuf::from("SourceStack");
+        assert_eq!(profile_data_root(base.clone(), None), base);
+        assert_eq!(
+            profile_data_root(base.clone(), Some("acme")),
+            base.join("profiles").join("acme")
+        );
+
+        assert_eq!(
+            normalize_profile_name("  acme-1 ").unwrap().as_deref(),
+            Some("acme-1")
+        );
+        assert_eq!(
+            normalize_profile_name("Work").unwrap(),
+            normalize_profile_name("work").unwrap()
+        );
+        assert_eq!(normalize_profile_name("  ").unwrap(), None);
+        assert!(normalize_profile_name("../other").is_err());
+    }
 }
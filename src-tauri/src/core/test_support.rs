@@ -0,0 +1,203 @@
+use std::io::{Cursor, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+pub struct MockResponse {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: Vec<u8>,
+    /// How long the server waits before answering.
+    pub delay: Duration,
+}
+
+impl MockResponse {
+    pub fn with_content_type(
+        status: u16,
+        content_type: &'static str,
+        body: impl Into<Vec<u8>>,
+    ) -> Self {
+        Self {
+            status,
+            content_type,
+            body: body.into(),
+            delay: Duration::ZERO,
+        }
+    }
+
+    /// Answers only after `delay`, e.g. to keep a request in flight while a test acts.
+    pub fn delayed(self, delay: Duration) -> Self {
+        Self { delay, ..self }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl RecordedRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Minimal HTTP/1.1 server that answers each incoming connection with the next queued
+/// response and records what the client sent.
+pub struct MockHttpServer {
+    base_url: String,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+    max_in_flight: Arc<AtomicUsize>,
+    _thread_handle: thread::JoinHandle<()>,
+}
+
+impl MockHttpServer {
+    pub fn start(responses: Vec<MockResponse>) -> Self {
+        Self::serve(responses, false)
+    }
+
+    /// Like `start`, but answers each connection on its own thread so a client can keep
+    /// several requests in flight. Responses go out in the order connections arrive.
+    pub fn start_concurrent(responses: Vec<MockResponse>) -> Self {
+        Self::serve(responses, true)
+    }
+
+    fn serve(responses: Vec<MockResponse>, concurrent: bool) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let max_in_flight = Arc::new(AtomicUsize::new(0));
+        let recorded = Arc::clone(&requests);
+        let max_seen = Arc::clone(&max_in_flight);
+        let in_flight = Arc::new(AtomicUsize::new(0));
+
+        let handle = thread::spawn(move || {
+            let mut workers = Vec::new();
+            for response in responses {
+                let Ok((stream, _)) = listener.accept() else {
+                    break;
+                };
+                let recorded = Arc::clone(&recorded);
+                let max_seen = Arc::clone(&max_seen);
+                let in_flight = Arc::clone(&in_flight);
+                let serve_one = move || {
+                    let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_seen.fetch_max(now, Ordering::SeqCst);
+                    answer(stream, response, &recorded);
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+                };
+                if concurrent {
+                    workers.push(thread::spawn(serve_one));
+                } else {
+                    serve_one();
+                }
+            }
+            for worker in workers {
+                let _ = worker.join();
+            }
+        });
+
+        Self {
+            base_url,
+            requests,
+            max_in_flight,
+            _thread_handle: handle,
+        }
+    }
+
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Most requests that were waiting for their response at the same time.
+    pub fn max_in_flight(&self) -> usize {
+        self.max_in_flight.load(Ordering::SeqCst)
+    }
+}
+
+fn answer(mut stream: TcpStream, response: MockResponse, recorded: &Mutex<Vec<RecordedRequest>>) {
+    if let Some(request) = read_request(&mut stream) {
+        recorded.lock().unwrap().push(request);
+    }
+    thread::sleep(response.delay);
+
+    let head = format!(
+        "HTTP/1.1 {} OK\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        response.content_type,
+        response.body.len()
+    );
+    let _ = stream.write_all(head.as_bytes());
+    let _ = stream.write_all(&response.body);
+    let _ = stream.flush();
+}
+
+fn read_request(stream: &mut TcpStream) -> Option<RecordedRequest> {
+    let mut data = Vec::new();
+    let mut buffer = [0u8; 8_192];
+
+    let header_end = loop {
+        let read = stream.read(&mut buffer).ok()?;
+        if read == 0 {
+            return None;
+        }
+        data.extend_from_slice(&buffer[..read]);
+        if let Some(index) = data.windows(4).position(|w| w == b"\r\n\r\n") {
+            break index + 4;
+        }
+    };
+
+    let head = String::from_utf8_lossy(&data[..header_end]).to_string();
+    let headers: Vec<(String, String)> = head
+        .lines()
+        .skip(1)
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+        .collect();
+    let content_length = headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.parse::<usize>().ok())
+        .unwrap_or(0);
+
+    while data.len() < header_end + content_length {
+        let read = stream.read(&mut buffer).ok()?;
+        if read == 0 {
+            break;
+        }
+        data.extend_from_slice(&buffer[..read]);
+    }
+
+    let mut request_line = head.lines().next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or("GET").to_string();
+    let path = request_line.next().unwrap_or("/").to_string();
+    let body_end = data.len().min(header_end + content_length);
+    Some(RecordedRequest {
+        method,
+        path,
+        headers,
+        body: data[header_end..body_end].to_vec(),
+    })
+}
+
+/// Builds an in-memory zip archive holding a single entry, e.g. a DOCX or ODT body.
+pub fn zip_with_entry(entry_name: &str, content: &str) -> Vec<u8> {
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    writer
+        .start_file(entry_name, zip::write::SimpleFileOptions::default())
+        .unwrap();
+    writer.write_all(content.as_bytes()).unwrap();
+    writer.finish().unwrap().into_inner()
+}
@@ -1,55 +1,236 @@
 pub mod core;
 
-use tauri::Manager;
+use std::sync::Arc;
+use std::time::Duration;
 
+use tauri::{AppHandle, Emitter, Manager, RunEvent};
+use tauri_plugin_deep_link::DeepLinkExt;
+
+use core::auth::DEEP_LINK_SCHEME;
 use core::commands::{
-    cancel_job, get_drive_folder_path, get_job_results, get_job_status, get_settings,
-    google_auth_begin_manual, google_auth_complete_manual, google_auth_sign_in,
-    google_auth_sign_out, google_auth_status, kill_job, list_drive_files, list_drive_folders,
-    list_jobs, parse_single, save_settings, start_batch_job, AppState,
+    abort_and_delete_job, aggregate_stats, can_run_unattended, cancel_job, cancel_sign_in,
+    check_folder_access, debug_extract_text, estimate_batch, export_job_archive,
+    export_job_resumes, get_drive_folder_path, get_job_candidates, get_job_config, get_job_results,
+    get_job_status, get_paths, get_queue_status, get_settings, google_auth_begin_manual,
+    google_auth_complete_manual, google_auth_sign_in, google_auth_sign_out, google_auth_status,
+    import_job_archive, kill_job, list_drive_files, list_drive_folders, list_jobs, parse_single,
+    parse_single_as_row, pause_job, preview_folder, rescore_job, resume_job, sample_folder,
+    save_settings, start_batch_job, start_watch, stop_watch, supported_file_types, AppState,
 };
+use core::events::{AUTH_DEEP_LINK_FAILED, PROFILE_LAUNCH_REJECTED};
 use core::service::CoreService;
+use core::settings_store::{
+    active_profile, normalize_profile_name, set_active_profile, PROFILE_ENV_VAR,
+};
+
+/// How long closing the app waits for a running job to save its results and status.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
 
 pub fn try_run_internal_command() -> anyhow::Result<bool> {
     core::pdf::maybe_run_pdf_extract_helper_from_args()
 }
 
+/// Profile from a `--profile <name>` / `--profile=<name>` argument, else the environment.
+fn requested_profile() -> Option<String> {
+    profile_argument(std::env::args().skip(1)).or_else(|| std::env::var(PROFILE_ENV_VAR).ok())
+}
+
+/// Profile named by a `--profile <name>` / `--profile=<name>` argument in `args`.
+fn profile_argument(args: impl IntoIterator<Item = String>) -> Option<String> {
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--profile" {
+            return args.next();
+        }
+        if let Some(profile) = arg.strip_prefix("--profile=") {
+            return Some(profile.to_string());
+        }
+    }
+    None
+}
+
+/// The single-instance plugin hands a second launch's arguments, but not its environment,
+/// to the running instance. A profile picked through the environment is therefore turned
+/// into a `--profile` argument by relaunching before anything else starts.
+fn relaunch_with_profile_argument() {
+    if profile_argument(std::env::args().skip(1)).is_some() {
+        return;
+    }
+    let Some(profile) = std::env::var(PROFILE_ENV_VAR)
+        .ok()
+        .filter(|profile| !profile.trim().is_empty())
+    else {
+        return;
+    };
+    let exe = match std::env::current_exe() {
+        Ok(exe) => exe,
+        Err(err) => {
+            eprintln!("failed to relaunch with --profile: {err}");
+            return;
+        }
+    };
+
+    let mut command = std::process::Command::new(exe);
+    command
+        .args(std::env::args_os().skip(1))
+        .arg(format!("--profile={profile}"));
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        let err = command.exec();
+        eprintln!("failed to relaunch with --profile: {err}");
+    }
+    #[cfg(not(unix))]
+    match command.spawn() {
+        Ok(_) => std::process::exit(0),
+        Err(err) => eprintln!("failed to relaunch with --profile: {err}"),
+    }
+}
+
+/// The single-instance lock is shared by every profile, so a second launch for another
+/// profile ends up here instead of opening. Tell the user rather than silently showing this
+/// profile. Without `--profile` the launch counts as the default profile, unless it carries
+/// a sign-in link.
+fn reject_other_profile_launch(app: &AppHandle, args: &[String]) {
+    let deep_link_prefix = format!("{DEEP_LINK_SCHEME}:");
+    if args.iter().any(|arg| arg.starts_with(&deep_link_prefix)) {
+        return;
+    }
+    // An invalid name can never match the active profile, so it is reported as written.
+    let requested = profile_argument(args.iter().skip(1).cloned()).and_then(|profile| {
+        normalize_profile_name(&profile).unwrap_or_else(|_| Some(profile.trim().to_string()))
+    });
+    let requested = requested.as_deref();
+    let active = active_profile();
+    if requested == active {
+        return;
+    }
+
+    let describe = |profile: Option<&str>| match profile {
+        Some(profile) => format!("profile \"{profile}\""),
+        None => "the default profile".to_string(),
+    };
+    let message = format!(
+        "Cannot open {} while {} is running. Quit the app first.",
+        describe(requested),
+        describe(active)
+    );
+    eprintln!("{message}");
+    if let Err(err) = app.emit(PROFILE_LAUNCH_REJECTED, message) {
+        eprintln!("failed to emit {PROFILE_LAUNCH_REJECTED} event: {err}");
+    }
+}
+
+fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+/// Completes a pending manual sign-in from a `sourcestack://callback?...` link. Success is
+/// reported through the auth status event; failures go to the UI so the user can paste instead.
+fn complete_sign_in_from_deep_link(app: AppHandle, url: String) {
+    tauri::async_runtime::spawn(async move {
+        show_main_window(&app);
+        let state = app.state::<AppState>();
+        if let Err(err) = state.core.google_auth_complete_deep_link(&url).await {
+            eprintln!("deep link sign-in failed: {err}");
+            if let Err(err) = app.emit(AUTH_DEEP_LINK_FAILED, err.to_string()) {
+                eprintln!("failed to emit {AUTH_DEEP_LINK_FAILED} event: {err}");
+            }
+        }
+    });
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    relaunch_with_profile_argument();
     tauri::Builder::default()
-        .plugin(tauri_plugin_opener::init())
-        .plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
-            if let Some(window) = app.get_webview_window("main") {
-                let _ = window.show();
-                let _ = window.set_focus();
-            }
+        // Registered first so a second launch (including one opened by a deep link) is
+        // handed to the running instance.
+        .plugin(tauri_plugin_single_instance::init(|app, args, _cwd| {
+            show_main_window(app);
+            reject_other_profile_launch(app, &args);
         }))
+        .plugin(tauri_plugin_deep_link::init())
+        .plugin(tauri_plugin_opener::init())
         .setup(|app| {
+            set_active_profile(requested_profile().as_deref())
+                .map_err(|err| format!("failed to select profile: {err}"))?;
             let core = tauri::async_runtime::block_on(CoreService::new())
                 .map_err(|err| format!("failed to initialize core service: {err}"))?;
 
+            let app_handle = app.handle().clone();
+            core.set_event_sink(Arc::new(move |event, payload| {
+                if let Err(err) = app_handle.emit(event, payload) {
+                    eprintln!("failed to emit {event} event: {err}");
+                }
+            }));
+
             app.manage(AppState { core });
+
+            // Bundled installers register the scheme; dev and AppImage builds do it here.
+            #[cfg(any(windows, target_os = "linux"))]
+            app.deep_link().register_all()?;
+            let deep_link_handle = app.handle().clone();
+            app.deep_link().on_open_url(move |event| {
+                for url in event.urls() {
+                    complete_sign_in_from_deep_link(deep_link_handle.clone(), url.to_string());
+                }
+            });
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             parse_single,
+            parse_single_as_row,
+            debug_extract_text,
             start_batch_job,
             get_job_status,
+            get_job_config,
             get_job_results,
+            get_job_candidates,
+            rescore_job,
+            export_job_archive,
+            export_job_resumes,
+            import_job_archive,
             list_jobs,
+            aggregate_stats,
+            get_queue_status,
             cancel_job,
+            pause_job,
+            resume_job,
             kill_job,
+            abort_and_delete_job,
+            start_watch,
+            stop_watch,
             google_auth_sign_in,
             google_auth_begin_manual,
             google_auth_complete_manual,
             google_auth_sign_out,
+            cancel_sign_in,
             google_auth_status,
+            can_run_unattended,
             list_drive_folders,
             list_drive_files,
             get_drive_folder_path,
+            check_folder_access,
+            preview_folder,
+            estimate_batch,
+            sample_folder,
             get_settings,
-            save_settings
+            save_settings,
+            get_paths,
+            supported_file_types
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            if let RunEvent::Exit = event {
+                // Without this the worker is dropped mid-chunk with its status half-written.
+                if let Some(state) = app.try_state::<AppState>() {
+                    tauri::async_runtime::block_on(state.core.shutdown(SHUTDOWN_TIMEOUT));
+                }
+            }
+        });
 }
@@ -8,7 +8,9 @@ import { JobsView } from "./components/JobsView";
 import { SettingsView } from "./components/SettingsView";
 import {
   cancelJob,
+  cancelSignIn,
   getDriveFolderPath,
+  getJobCandidates,
   getJobResults,
   getJobStatus,
   getSettings,
@@ -21,9 +23,14 @@ import {
   listDriveFiles,
   listDriveFolders,
   listJobs,
+  onAuthStatusChanged,
+  onDeepLinkSignInFailed,
+  onJobFinished,
+  onProfileLaunchRejected,
   parseSingle,
   saveSettings,
   startBatchJob,
+  supportedFileTypes,
 } from "./lib/api";
 import type {
   AuthStatus,
@@ -114,6 +121,7 @@ export default function App() {
   const [parseLoading, setParseLoading] = useState(false);
   const [parseResult, setParseResult] = useState<ParsedCandidate | null>(null);
   const [parseDragActive, setParseDragActive] = useState(false);
+  const [acceptedFileTypes, setAcceptedFileTypes] = useState(".pdf,.docx,.odt");
 
   const [activeJobId, setActiveJobId] = useState<string | null>(null);
   const [activeJobStatus, setActiveJobStatus] = useState<JobStatus | null>(null);
@@ -169,6 +177,38 @@ export default function App() {
     void refreshWorkspace(false);
   }, []);
 
+  useEffect(() => {
+    supportedFileTypes()
+      .then((types) =>
+        setAcceptedFileTypes(types.map((type) => `.${type.extension}`).join(",")),
+      )
+      .catch(() => undefined);
+  }, []);
+
+  useEffect(() => {
+    const subscription = onJobFinished((event) => {
+      const { status } = event;
+      upsertJobStatus(status);
+      setActiveJobStatus((current) => (current?.jobId === status.jobId ? status : current));
+
+      const jobLabel = truncateMiddle(status.jobId, 18);
+      if (status.status === "completed") {
+        pushStatus(
+          `Job ${jobLabel} completed: ${event.parsedCount} parsed, ${event.failedCount} failed, ${event.ocrCount} OCR`,
+          "success",
+        );
+      } else if (status.status === "failed") {
+        pushStatus(`Job ${jobLabel} failed: ${status.error ?? "unknown error"}`, "error");
+      } else {
+        pushStatus(`Job ${jobLabel} cancelled`, "info");
+      }
+    });
+
+    return () => {
+      void subscription.then((unlisten) => unlisten());
+    };
+  }, []);
+
   useEffect(() => {
     if (workspaceStatus.tone === "neutral" || workspaceStatus.tone === "error") {
       return;
@@ -181,6 +221,32 @@ export default function App() {
     return () => window.clearTimeout(timer);
   }, [workspaceStatus]);
 
+  useEffect(() => {
+    // A sourcestack:// sign-in link finishes the manual flow without pasting.
+    const statusSubscription = onAuthStatusChanged((status) => {
+      setAuth(status);
+      if (status.signedIn) {
+        setManualAuthVisible(false);
+        setManualAuthChallenge(null);
+        setManualAuthInput("");
+        void loadDriveFolder(null, true, false);
+      }
+    });
+    const failureSubscription = onDeepLinkSignInFailed((message) => {
+      setManualAuthError(message);
+      pushStatus(`Sign-in link failed: ${message}`, "error");
+    });
+    const profileSubscription = onProfileLaunchRejected((message) => {
+      pushStatus(message, "error");
+    });
+
+    return () => {
+      void statusSubscription.then((unlisten) => unlisten());
+      void failureSubscription.then((unlisten) => unlisten());
+      void profileSubscription.then((unlisten) => unlisten());
+    };
+  }, []);
+
   useEffect(() => {
     if (!auth.signedIn) {
       setDriveState(emptyDriveState);
@@ -418,12 +484,12 @@ export default function App() {
 
   async function handleParseSingle() {
     if (!selectedFile) {
-      pushStatus("Choose a PDF or DOCX file first.", "error");
+      pushStatus("Choose a PDF, DOCX, or ODT file first.", "error");
       return;
     }
 
     if (!isSupportedResumeFileName(selectedFile.name)) {
-      pushStatus("Only .pdf and .docx files are supported.", "error");
+      pushStatus("Only .pdf, .docx, and .odt files are supported.", "error");
       return;
     }
 
@@ -559,6 +625,14 @@ export default function App() {
     }
   }
 
+  async function handleCancelSignIn() {
+    try {
+      await cancelSignIn();
+    } catch (error) {
+      pushStatus(`Cancel sign-in failed: ${String(error)}`, "error");
+    }
+  }
+
   async function handleSignOut() {
     setBusyAuth(true);
 
@@ -667,10 +741,44 @@ export default function App() {
       const saved = await saveSettings({
         jobRetentionHours: settings.jobRetentionHours,
         maxConcurrentRequests: settings.maxConcurrentRequests,
+        maxConcurrentDownloads: settings.maxConcurrentDownloads ?? null,
+        maxConcurrentParses: settings.maxConcurrentParses ?? null,
+        emailPreference: settings.emailPreference,
+        skillDictionary: settings.skillDictionary,
+        disableOcr: settings.disableOcr,
         maxRetries: settings.maxRetries,
         retryDelaySeconds: settings.retryDelaySeconds,
         spreadsheetBatchSize: settings.spreadsheetBatchSize,
         tesseractPath: settings.tesseractPath,
+        statusWriteIntervalMs: settings.statusWriteIntervalMs,
+        preserveTextColumns: settings.preserveTextColumns,
+        spreadsheetTitleTemplate: settings.spreadsheetTitleTemplate,
+        colorByConfidence: settings.colorByConfidence,
+        mergeOcrFields: settings.mergeOcrFields,
+        maxQueuedJobs: settings.maxQueuedJobs,
+        debugFieldSources: settings.debugFieldSources,
+        requiredFields: settings.requiredFields,
+        confidenceAsPercent: settings.confidenceAsPercent,
+        confidenceWeights: settings.confidenceWeights,
+        nameDenylist: settings.nameDenylist,
+        keepOcrTemp: settings.keepOcrTemp,
+        retryOn: settings.retryOn,
+        cleanupIntervalMinutes: settings.cleanupIntervalMinutes,
+        noticePeriodColumn: settings.noticePeriodColumn,
+        compactResults: settings.compactResults,
+        sheetAppendPageSize: settings.sheetAppendPageSize ?? null,
+        missingValuePlaceholder: settings.missingValuePlaceholder,
+        unsupportedFilePolicy: settings.unsupportedFilePolicy,
+        adaptiveConcurrency: settings.adaptiveConcurrency,
+        customFields: settings.customFields,
+        sortOrder: settings.sortOrder,
+        jobRetryBudget: settings.jobRetryBudget ?? null,
+        sheetAppendDelayMs: settings.sheetAppendDelayMs,
+        deobfuscateEmails: settings.deobfuscateEmails,
+        educationColumn: settings.educationColumn,
+        requireInternationalPhone: settings.requireInternationalPhone,
+        nationalPhonePolicy: settings.nationalPhonePolicy,
+        emailDomainColumn: settings.emailDomainColumn,
       });
       setSettings(saved);
       pushStatus("Settings saved", "success");
@@ -755,6 +863,26 @@ export default function App() {
     }
   }
 
+  async function handleCopyCandidateJson(fileId: string) {
+    if (!selectedJobId) {
+      return;
+    }
+
+    try {
+      const candidates = await getJobCandidates(selectedJobId, fileId);
+      if (candidates.length === 0) {
+        pushStatus("Candidate not found in this job", "error");
+        return;
+      }
+      // A file split into several candidates copies all of them as one array.
+      const payload = candidates.length === 1 ? candidates[0] : candidates;
+      await navigator.clipboard.writeText(JSON.stringify(payload, null, 2));
+      pushStatus("Candidate JSON copied", "success");
+    } catch (error) {
+      pushStatus(`Copy failed: ${String(error)}`, "error");
+    }
+  }
+
   async function handleOpenDriveFile(fileId: string) {
     try {
       await openUrl(`https://drive.google.com/file/d/${fileId}/view`);
@@ -777,6 +905,7 @@ export default function App() {
     switch (status.status) {
       case "pending":
       case "processing":
+      case "paused":
         return "This job is still running. Results will be available after it finishes.";
       case "revoked":
         return "This job was killed before completion, so no final results are available.";
@@ -880,6 +1009,7 @@ export default function App() {
     >
       {activeView === "dashboard" && (
         <DashboardView
+          acceptedFileTypes={acceptedFileTypes}
           activeJobId={activeJobId}
           activeJobStatus={activeJobStatus}
           auth={auth}
@@ -892,6 +1022,7 @@ export default function App() {
           manualAuthReason={manualAuthReason}
           manualAuthVisible={manualAuthVisible}
           onCancelActiveJob={() => void handleCancelActiveJob()}
+          onCancelSignIn={() => void handleCancelSignIn()}
           onClearParseFile={() => {
             setSelectedFile(null);
             setParseResult(null);
@@ -926,6 +1057,9 @@ export default function App() {
           jobActionBusy={jobActionBusyId === activeJobId}
           parseLoading={parseLoading}
           parseResult={parseResult}
+          onPickName={(name) =>
+            setParseResult((current) => (current ? { ...current, name } : current))
+          }
           selectedDriveFolder={selectedDriveFolder}
           selectedFile={selectedFile}
           spreadsheetId={spreadsheetId}
@@ -938,6 +1072,7 @@ export default function App() {
           jobActionBusy={jobActionBusyId === selectedJobId}
           jobs={jobs}
           jobsLoading={jobsLoading}
+          onCopyCandidateJson={(fileId) => void handleCopyCandidateJson(fileId)}
           onKillJob={(jobId) => void handleKillJob(jobId)}
           onOpenDriveFile={(fileId) => void handleOpenDriveFile(fileId)}
           onOpenSpreadsheet={(id) => void handleOpenSpreadsheet(id)}
@@ -55,6 +55,7 @@ interface SelectedDriveFolder {
 }
 
 interface DashboardViewProps {
+  acceptedFileTypes: string;
   activeJobId: string | null;
   activeJobStatus: JobStatus | null;
   auth: AuthStatus;
@@ -68,6 +69,7 @@ interface DashboardViewProps {
   manualAuthReason: string;
   manualAuthVisible: boolean;
   onCancelActiveJob: () => void;
+  onCancelSignIn: () => void;
   onClearParseFile: () => void;
   onCloseManualAuth: () => void;
   onCompleteManualAuth: () => void;
@@ -80,6 +82,7 @@ interface DashboardViewProps {
   onOpenManualAuthUrl: () => void;
   onParse: () => void;
   onPickFile: (file: File | null) => void;
+  onPickName: (name: string) => void;
   onRefreshActiveJob: () => void;
   onSelectDriveFolder: (folder: DriveFolderEntry) => void;
   onSetParseDragActive: (active: boolean) => void;
@@ -132,6 +135,7 @@ const typeBadgeColors: Record<
 };
 
 export function DashboardView({
+  acceptedFileTypes,
   activeJobId,
   activeJobStatus,
   auth,
@@ -145,6 +149,7 @@ export function DashboardView({
   manualAuthReason,
   manualAuthVisible,
   onCancelActiveJob,
+  onCancelSignIn,
   onClearParseFile,
   onCloseManualAuth,
   onCompleteManualAuth,
@@ -157,6 +162,7 @@ export function DashboardView({
   onOpenManualAuthUrl,
   onParse,
   onPickFile,
+  onPickName,
   onRefreshActiveJob,
   onSelectDriveFolder,
   onSetParseDragActive,
@@ -210,6 +216,7 @@ export function DashboardView({
             error={manualAuthError}
             inputValue={manualAuthInput}
             manualVisible={manualAuthVisible}
+            onCancelSignIn={onCancelSignIn}
             onCloseManualAuth={onCloseManualAuth}
             onCompleteManualAuth={onCompleteManualAuth}
             onCopyUrl={onCopyManualAuthUrl}
@@ -227,6 +234,7 @@ export function DashboardView({
         onClearParseFile={onClearParseFile}
         onParse={onParse}
         onPickFile={onPickFile}
+        onPickName={onPickName}
         onSetParseDragActive={onSetParseDragActive}
         parseDragActive={parseDragActive}
         parseLoading={parseLoading}
@@ -561,6 +569,7 @@ function AuthPanel({
   error,
   inputValue,
   manualVisible,
+  onCancelSignIn,
   onCloseManualAuth,
   onCompleteManualAuth,
   onCopyUrl,
@@ -575,6 +584,7 @@ function AuthPanel({
   error: string;
   inputValue: string;
   manualVisible: boolean;
+  onCancelSignIn: () => void;
   onCloseManualAuth: () => void;
   onCompleteManualAuth: () => void;
   onCopyUrl: () => void;
@@ -604,15 +614,25 @@ function AuthPanel({
             onClick={onSignIn}
             type="button"
           >
-            {authBusy ? "Opening browser…" : "Sign in with Google"}
-          </button>
-          <button
-            className="mt-3 text-xs text-[var(--app-muted)] transition-colors hover:text-[var(--app-foreground)]"
-            onClick={() => onStartManualAuth(false)}
-            type="button"
-          >
-            Having trouble? Use manual sign-in →
+            {authBusy ? "Waiting for browser sign-in…" : "Sign in with Google"}
           </button>
+          {authBusy ? (
+            <button
+              className="mt-3 text-xs text-[var(--app-muted)] transition-colors hover:text-[var(--app-foreground)]"
+              onClick={onCancelSignIn}
+              type="button"
+            >
+              Cancel sign-in
+            </button>
+          ) : (
+            <button
+              className="mt-3 text-xs text-[var(--app-muted)] transition-colors hover:text-[var(--app-foreground)]"
+              onClick={() => onStartManualAuth(false)}
+              type="button"
+            >
+              Having trouble? Use manual sign-in →
+            </button>
+          )}
         </div>
       </div>
     );
@@ -726,6 +746,7 @@ function QuickParsePanel({
   onClearParseFile,
   onParse,
   onPickFile,
+  onPickName,
   onSetParseDragActive,
   parseDragActive,
   parseLoading,
@@ -736,6 +757,7 @@ function QuickParsePanel({
   onClearParseFile: () => void;
   onParse: () => void;
   onPickFile: (file: File | null) => void;
+  onPickName: (name: string) => void;
   onSetParseDragActive: (active: boolean) => void;
   parseDragActive: boolean;
   parseLoading: boolean;
@@ -775,7 +797,7 @@ function QuickParsePanel({
           type="button"
         >
           <input
-            accept=".pdf,.docx"
+            accept={acceptedFileTypes}
             className="hidden"
             onChange={handleInputChange}
             ref={fileInputRef}
@@ -838,6 +860,27 @@ function QuickParsePanel({
                 </span>
               </div>
             ))}
+            {(parseResult.nameCandidates?.length ?? 0) > 1 && (
+              <div className="border-b border-white/6 py-2">
+                <span className="text-xs text-[var(--app-muted)]">
+                  Not the name? Pick another line
+                </span>
+                <div className="mt-1 flex flex-wrap gap-1">
+                  {parseResult.nameCandidates
+                    ?.filter((candidate) => candidate !== parseResult.name)
+                    .map((candidate) => (
+                      <button
+                        className="rounded bg-[var(--app-muted-panel)] px-2 py-0.5 font-mono text-[11px] text-[var(--app-foreground)] transition-colors hover:bg-white/10"
+                        key={candidate}
+                        onClick={() => onPickName(candidate)}
+                        type="button"
+                      >
+                        {candidate}
+                      </button>
+                    ))}
+                </div>
+              </div>
+            )}
             <div className="flex items-center justify-between gap-4 border-b border-white/6 py-2">
               <span className="text-xs text-[var(--app-muted)]">Confidence</span>
               <div className="flex items-center gap-2">
@@ -852,11 +895,25 @@ function QuickParsePanel({
                 </span>
               </div>
             </div>
+            <div className="flex items-center justify-between gap-4 border-b border-white/6 py-2">
+              <span className="text-xs text-[var(--app-muted)]">OCR</span>
+              <span className="font-mono text-xs text-[var(--app-foreground)]">
+                {parseResult.ocrUsed ? "Used" : "Not needed"}
+              </span>
+            </div>
+            {parseResult.parseDurationMs != null && (
+              <div className="flex items-center justify-between gap-4 border-b border-white/6 py-2">
+                <span className="text-xs text-[var(--app-muted)]">Parse Time</span>
+                <span className="font-mono text-xs text-[var(--app-foreground)]">
+                  {parseResult.parseDurationMs} ms
+                </span>
+              </div>
+            )}
             {parseResult.errors.length > 0 && (
               <div className="py-2">
-                <span className="text-xs text-[var(--app-muted)]">Errors</span>
+                <span className="text-xs text-[var(--app-muted)]">Warnings</span>
                 <p className="mt-1 text-xs text-amber-200">
-                  {parseResult.errors.join("; ")}
+                  {parseResult.errors.map((error) => error.message).join("; ")}
                 </p>
               </div>
             )}
@@ -2,6 +2,7 @@ import { Fragment, useMemo, useState } from "react";
 import {
   AlertTriangle,
   ArrowUpDown,
+  Copy,
   ExternalLink,
   Search,
 } from "lucide-react";
@@ -24,6 +25,7 @@ interface JobsViewProps {
   jobActionBusy: boolean;
   jobs: JobListItem[];
   jobsLoading: boolean;
+  onCopyCandidateJson: (fileId: string) => void;
   onKillJob: (jobId: string) => void;
   onOpenDriveFile: (fileId: string) => void;
   onOpenSpreadsheet: (spreadsheetId: string) => void;
@@ -39,14 +41,17 @@ type SortColumn =
   | "resume"
   | "phone"
   | "email"
+  | "emailDomain"
   | "linkedIn"
   | "gitHub"
+  | "skills"
   | "confidence";
 
 export function JobsView({
   jobActionBusy,
   jobs,
   jobsLoading,
+  onCopyCandidateJson,
   onKillJob,
   onOpenDriveFile,
   onOpenSpreadsheet,
@@ -186,7 +191,8 @@ export function JobsView({
 
               <div className="flex items-center gap-2">
                 {(selectedJobStatus.status === "pending" ||
-                  selectedJobStatus.status === "processing") && (
+                  selectedJobStatus.status === "processing" ||
+                  selectedJobStatus.status === "paused") && (
                   <button
                     className="flex h-8 items-center gap-1 rounded-md border border-red-500/20 bg-red-500/10 px-3 text-xs font-semibold text-red-200 transition-colors hover:bg-red-500/16 disabled:opacity-50"
                     disabled={jobActionBusy}
@@ -269,8 +275,10 @@ export function JobsView({
                           ["resume", "Resume Link"],
                           ["phone", "Phone Number"],
                           ["email", "Email ID"],
+                          ["emailDomain", "Email Domain"],
                           ["linkedIn", "LinkedIn"],
                           ["gitHub", "GitHub"],
+                          ["skills", "Skills"],
                           ["confidence", "Confidence"],
                         ].map(([column, label]) => (
                           <th
@@ -318,7 +326,9 @@ export function JobsView({
                               </td>
                               <td className="px-3 py-2 text-[var(--app-foreground)]">
                                 <div className="flex items-center gap-2">
-                                  <span>{result.name ?? "—"}</span>
+                                  <span title={result.headline ?? undefined}>
+                                    {result.name ?? "—"}
+                                  </span>
                                   {result.errors.length > 0 && (
                                     <button
                                       className="flex items-center gap-1 text-[11px] text-amber-300"
@@ -338,14 +348,25 @@ export function JobsView({
                               </td>
                               <td className="px-3 py-2">
                                 {result.driveFileId ? (
-                                  <button
-                                    className="flex items-center gap-1 text-[var(--app-primary)] transition-colors hover:text-teal-200"
-                                    onClick={() => onOpenDriveFile(result.driveFileId ?? "")}
-                                    type="button"
-                                  >
-                                    <ExternalLink size={12} />
-                                    Open
-                                  </button>
+                                  <div className="flex items-center gap-3">
+                                    <button
+                                      className="flex items-center gap-1 text-[var(--app-primary)] transition-colors hover:text-teal-200"
+                                      onClick={() => onOpenDriveFile(result.driveFileId ?? "")}
+                                      type="button"
+                                    >
+                                      <ExternalLink size={12} />
+                                      Open
+                                    </button>
+                                    <button
+                                      className="flex items-center gap-1 text-[var(--app-muted)] transition-colors hover:text-[var(--app-foreground)]"
+                                      onClick={() => onCopyCandidateJson(result.driveFileId ?? "")}
+                                      title="Copy as JSON"
+                                      type="button"
+                                    >
+                                      <Copy size={12} />
+                                      JSON
+                                    </button>
+                                  </div>
                                 ) : (
                                   <span className="font-mono text-[11px] text-[var(--app-muted)]">
                                     {truncateMiddle(result.sourceFile, 24)}
@@ -358,12 +379,18 @@ export function JobsView({
                               <td className="px-3 py-2 font-mono text-[var(--app-foreground)]">
                                 {result.email ?? "—"}
                               </td>
+                              <td className="px-3 py-2 font-mono text-[var(--app-muted)]">
+                                {result.emailDomain ?? "—"}
+                              </td>
                               <td className="px-3 py-2 text-[var(--app-muted)]">
                                 {result.linkedIn ?? "—"}
                               </td>
                               <td className="px-3 py-2 text-[var(--app-muted)]">
                                 {result.gitHub ?? "—"}
                               </td>
+                              <td className="px-3 py-2 text-[var(--app-muted)]">
+                                {result.skills?.length ? result.skills.join(", ") : "—"}
+                              </td>
                               <td className="px-3 py-2">
                                 <div className="flex items-center gap-2">
                                   <div className="h-1.5 w-12 overflow-hidden rounded-full bg-[#1a1f2e]">
@@ -386,9 +413,11 @@ export function JobsView({
                             </tr>
                             {expanded && (
                               <tr className="border-b border-white/4">
-                                <td className="px-3 py-2" colSpan={8}>
+                                <td className="px-3 py-2" colSpan={10}>
                                   <div className="rounded-md border border-amber-400/18 bg-amber-400/8 px-3 py-2 text-[11px] text-amber-200">
-                                    {result.errors.join("; ")}
+                                    {result.errors
+                                      .map((error) => error.message)
+                                      .join("; ")}
                                   </div>
                                 </td>
                               </tr>
@@ -465,10 +494,14 @@ function readResultColumn(result: ParsedCandidate, column: SortColumn): string {
       return result.phone ?? "";
     case "email":
       return result.email ?? "";
+    case "emailDomain":
+      return result.emailDomain ?? "";
     case "linkedIn":
       return result.linkedIn ?? "";
     case "gitHub":
       return result.gitHub ?? "";
+    case "skills":
+      return (result.skills ?? []).join(", ");
     case "name":
       return result.name ?? "";
     default:
@@ -4,7 +4,16 @@ import {
   Info,
 } from "lucide-react";
 
-import type { RuntimeSettingsView } from "../lib/types";
+import type {
+  CustomFieldRule,
+  EmailPreference,
+  FieldKind,
+  NationalPhonePolicy,
+  RetryPolicy,
+  RuntimeSettingsView,
+  SortOrder,
+  UnsupportedFilePolicy,
+} from "../lib/types";
 import {
   fromRetentionDays,
   fromRetryDelayMilliseconds,
@@ -12,6 +21,41 @@ import {
   toRetryDelayMilliseconds,
 } from "../lib/utils";
 
+const REQUIRED_FIELD_OPTIONS: { label: string; value: FieldKind }[] = [
+  { label: "Name", value: "name" },
+  { label: "Email", value: "email" },
+  { label: "Phone", value: "phone" },
+  { label: "LinkedIn", value: "linkedIn" },
+  { label: "GitHub", value: "gitHub" },
+  { label: "Skills", value: "skills" },
+];
+
+const DEFAULT_RETRY_POLICY: RetryPolicy = { rateLimit: true, serverError: true, network: true };
+
+const DEFAULT_JOB_RETRY_BUDGET = 50;
+
+const RETRY_CLASS_OPTIONS: { key: keyof RetryPolicy; label: string }[] = [
+  { key: "rateLimit", label: "Rate limits (429)" },
+  { key: "serverError", label: "Server errors (5xx)" },
+  { key: "network", label: "Timeouts and network errors" },
+];
+
+// One "Name = pattern" line per rule; text is kept as typed so editing stays predictable.
+function formatCustomFields(rules: CustomFieldRule[]): string {
+  return rules
+    .map(({ name, pattern }) => (name || pattern ? `${name}=${pattern}` : ""))
+    .join("\n");
+}
+
+function parseCustomFields(text: string): CustomFieldRule[] {
+  return text.split("\n").map((line) => {
+    const separator = line.indexOf("=");
+    return separator < 0
+      ? { name: line, pattern: "" }
+      : { name: line.slice(0, separator), pattern: line.slice(separator + 1) };
+  });
+}
+
 interface SettingsViewProps {
   onChange: (patch: Partial<RuntimeSettingsView>) => void;
   onSave: () => void;
@@ -28,6 +72,15 @@ export function SettingsView({
   const oauthConfigured = Boolean(settings.googleClientId.trim());
   const retentionDays = toRetentionDays(settings.jobRetentionHours);
   const retryDelayMilliseconds = toRetryDelayMilliseconds(settings.retryDelaySeconds);
+  const requiredFields = settings.requiredFields ?? [];
+
+  const toggleRequiredField = (field: FieldKind, required: boolean) => {
+    onChange({
+      requiredFields: required
+        ? [...requiredFields, field]
+        : requiredFields.filter((value) => value !== field),
+    });
+  };
 
   return (
     <div className="h-full overflow-y-auto scrollbar-thin">
@@ -77,7 +130,49 @@ export function SettingsView({
           <p className="mt-2 text-[11px] text-[var(--app-subtle)]">
             Used as OCR fallback for scanned PDFs. Text extraction is attempted first.
           </p>
-          {!settings.tesseractPath.trim() && (
+          <label className="mt-3 flex items-center gap-2 text-xs text-[var(--app-foreground)]">
+            <input
+              checked={settings.disableOcr ?? false}
+              onChange={(event) => onChange({ disableOcr: event.target.checked })}
+              type="checkbox"
+            />
+            Disable OCR fallback
+          </label>
+          <label className="mt-2 flex items-center gap-2 text-xs text-[var(--app-foreground)]">
+            <input
+              checked={settings.mergeOcrFields ?? false}
+              disabled={settings.disableOcr ?? false}
+              onChange={(event) => onChange({ mergeOcrFields: event.target.checked })}
+              type="checkbox"
+            />
+            Fill missing fields with OCR on partly scanned PDFs
+          </label>
+          <label className="mt-2 flex items-center gap-2 text-xs text-[var(--app-foreground)]">
+            <input
+              checked={settings.keepOcrTemp ?? false}
+              disabled={settings.disableOcr ?? false}
+              onChange={(event) => onChange({ keepOcrTemp: event.target.checked })}
+              type="checkbox"
+            />
+            Keep OCR input and output files in the logs folder for debugging (deleted with expired jobs)
+          </label>
+          <label className="mt-2 flex items-center gap-2 text-xs text-[var(--app-foreground)]">
+            <input
+              checked={settings.debugFieldSources ?? false}
+              onChange={(event) => onChange({ debugFieldSources: event.target.checked })}
+              type="checkbox"
+            />
+            Show which extractor matched each field in single-file parses
+          </label>
+          <label className="mt-2 flex items-center gap-2 text-xs text-[var(--app-foreground)]">
+            <input
+              checked={settings.compactResults ?? false}
+              onChange={(event) => onChange({ compactResults: event.target.checked })}
+              type="checkbox"
+            />
+            Save job results as compact JSON to reduce disk usage
+          </label>
+          {!settings.disableOcr && !settings.tesseractPath.trim() && (
             <div className="mt-3">
               <WarningNotice>
                 Tesseract path is empty. OCR fallback will fail for scanned PDFs until a
@@ -85,6 +180,164 @@ export function SettingsView({
               </WarningNotice>
             </div>
           )}
+
+          <div className="mt-4">
+            <FieldLabel>Primary Email</FieldLabel>
+            <select
+              className="surface-muted h-9 w-full rounded-md px-3 text-xs text-[var(--app-foreground)] outline-none"
+              onChange={(event) =>
+                onChange({ emailPreference: event.target.value as EmailPreference })
+              }
+              value={settings.emailPreference ?? "first"}
+            >
+              <option value="first">First address found</option>
+              <option value="preferPersonal">Prefer personal (Gmail, Outlook, …)</option>
+              <option value="preferCorporate">Prefer corporate</option>
+            </select>
+            <p className="mt-2 text-[11px] text-[var(--app-subtle)]">
+              Used when a resume lists more than one email address.
+            </p>
+            <label className="mt-2 flex items-center gap-2 text-xs text-[var(--app-foreground)]">
+              <input
+                checked={settings.deobfuscateEmails ?? true}
+                onChange={(event) => onChange({ deobfuscateEmails: event.target.checked })}
+                type="checkbox"
+              />
+              Read addresses written as "jane (at) example (dot) com"
+            </label>
+          </div>
+
+          <div className="mt-4">
+            <FieldLabel>Phone Numbers</FieldLabel>
+            <label className="flex items-center gap-2 text-xs text-[var(--app-foreground)]">
+              <input
+                checked={settings.requireInternationalPhone ?? false}
+                onChange={(event) =>
+                  onChange({ requireInternationalPhone: event.target.checked })
+                }
+                type="checkbox"
+              />
+              Require a country code (+…) instead of assuming India
+            </label>
+            {settings.requireInternationalPhone && (
+              <select
+                className="surface-muted mt-2 h-9 w-full rounded-md px-3 text-xs text-[var(--app-foreground)] outline-none"
+                onChange={(event) =>
+                  onChange({
+                    nationalPhonePolicy: event.target.value as NationalPhonePolicy,
+                  })
+                }
+                value={settings.nationalPhonePolicy ?? "keep"}
+              >
+                <option value="keep">Keep numbers without a country code as written</option>
+                <option value="drop">Leave the phone empty</option>
+              </select>
+            )}
+          </div>
+
+          <div className="mt-4">
+            <FieldLabel>Other File Types</FieldLabel>
+            <select
+              className="surface-muted h-9 w-full rounded-md px-3 text-xs text-[var(--app-foreground)] outline-none"
+              onChange={(event) =>
+                onChange({
+                  unsupportedFilePolicy: event.target.value as UnsupportedFilePolicy,
+                })
+              }
+              value={settings.unsupportedFilePolicy ?? "warn"}
+            >
+              <option value="skip">Skip silently</option>
+              <option value="warn">Skip and list with a warning</option>
+              <option value="extractText">Try reading them as plain text</option>
+            </select>
+            <p className="mt-2 text-[11px] text-[var(--app-subtle)]">
+              Applies to files in the folder that are not PDF, DOCX, or ODT.
+            </p>
+          </div>
+
+          <div className="mt-4">
+            <FieldLabel>Skill Dictionary</FieldLabel>
+            <textarea
+              className="surface-muted min-h-24 w-full rounded-md px-3 py-2 font-mono text-xs text-[var(--app-foreground)] outline-none"
+              onChange={(event) => onChange({ skillDictionary: event.target.value.split("\n") })}
+              placeholder={"Rust\nTypeScript\nKubernetes"}
+              value={(settings.skillDictionary ?? []).join("\n")}
+            />
+            <p className="mt-2 text-[11px] text-[var(--app-subtle)]">
+              One skill per line. Matches are case-insensitive and fill a Skills column on new sheets.
+            </p>
+          </div>
+
+          <div className="mt-4">
+            <FieldLabel>Name Denylist</FieldLabel>
+            <textarea
+              className="surface-muted min-h-24 w-full rounded-md px-3 py-2 font-mono text-xs text-[var(--app-foreground)] outline-none"
+              onChange={(event) => onChange({ nameDenylist: event.target.value.split("\n") })}
+              placeholder={"Acme Talent Partners\nConfidential Resume"}
+              value={(settings.nameDenylist ?? []).join("\n")}
+            />
+            <p className="mt-2 text-[11px] text-[var(--app-subtle)]">
+              One line per entry. Whole lines matching an entry are never used as a name; common
+              section titles are already skipped.
+            </p>
+          </div>
+
+          <div className="mt-4">
+            <FieldLabel>Custom Fields</FieldLabel>
+            <textarea
+              className="surface-muted min-h-24 w-full rounded-md px-3 py-2 font-mono text-xs text-[var(--app-foreground)] outline-none"
+              onChange={(event) => onChange({ customFields: parseCustomFields(event.target.value) })}
+              placeholder={"Visa Status = (?i)visa status:\\s*(.+)\nCandidate Ref = (CR-\\d+)"}
+              value={formatCustomFields(settings.customFields ?? [])}
+            />
+            <p className="mt-2 text-[11px] text-[var(--app-subtle)]">
+              One "Name = pattern" per line. The pattern's single capture group fills a column of
+              that name in new sheets.
+            </p>
+          </div>
+
+          <div className="mt-4">
+            <FieldLabel>Spreadsheet Title</FieldLabel>
+            <input
+              className="surface-muted h-9 w-full rounded-md px-3 font-mono text-xs text-[var(--app-foreground)] outline-none"
+              onChange={(event) => onChange({ spreadsheetTitleTemplate: event.target.value })}
+              value={settings.spreadsheetTitleTemplate ?? ""}
+            />
+            <p className="mt-2 text-[11px] text-[var(--app-subtle)]">
+              Title for new sheets. Supports {"{date}"}, {"{time}"}, and {"{count}"}.
+            </p>
+          </div>
+
+          <div className="mt-4">
+            <FieldLabel>Missing Value Placeholder</FieldLabel>
+            <input
+              className="surface-muted h-9 w-full rounded-md px-3 font-mono text-xs text-[var(--app-foreground)] outline-none"
+              onChange={(event) => onChange({ missingValuePlaceholder: event.target.value })}
+              placeholder="N/A"
+              value={settings.missingValuePlaceholder ?? ""}
+            />
+            <p className="mt-2 text-[11px] text-[var(--app-subtle)]">
+              Written into sheet cells for fields a resume lacks. Leave empty for blank cells.
+            </p>
+          </div>
+
+          <div className="mt-4">
+            <FieldLabel>Row Order</FieldLabel>
+            <select
+              className="surface-muted h-9 w-full rounded-md px-3 text-xs text-[var(--app-foreground)] outline-none"
+              onChange={(event) => onChange({ sortOrder: event.target.value as SortOrder })}
+              value={settings.sortOrder ?? "none"}
+            >
+              <option value="none">Folder order</option>
+              <option value="confidenceDesc">Highest confidence first</option>
+              <option value="nameAsc">Name (A–Z)</option>
+            </select>
+            <p className="mt-2 text-[11px] text-[var(--app-subtle)]">
+              Sorting holds rows back until every file is parsed, then writes them at once.
+              A cancelled or failed job writes what it has so far and resumes from there; a
+              killed job restarts from its last stop.
+            </p>
+          </div>
         </Section>
 
         <Section title="Performance Tuning">
@@ -114,6 +367,182 @@ export function SettingsView({
               suffix="ms"
               value={retryDelayMilliseconds}
             />
+            <NumberField
+              label="Status Write Interval"
+              onChange={(value) => onChange({ statusWriteIntervalMs: value })}
+              suffix="ms"
+              tooltip="Minimum time between job progress writes to disk. Paused and finished jobs are always written at once."
+              value={settings.statusWriteIntervalMs ?? 1000}
+            />
+            <NumberField
+              label="Max Queued Jobs"
+              onChange={(value) => onChange({ maxQueuedJobs: value })}
+              tooltip="Jobs that can wait behind the running one. Takes effect on next launch."
+              value={settings.maxQueuedJobs ?? 100}
+            />
+            <NumberField
+              label="Cleanup Interval"
+              onChange={(value) => onChange({ cleanupIntervalMinutes: value })}
+              suffix="min"
+              tooltip="How often jobs past retention are deleted in the background. 0 turns this off."
+              value={settings.cleanupIntervalMinutes ?? 60}
+            />
+            {settings.jobRetryBudget != null && (
+              <NumberField
+                label="Job Retry Budget"
+                onChange={(value) => onChange({ jobRetryBudget: value })}
+                tooltip="Most download retries one job may spend across all files, so a folder of failing files cannot run for hours. 0 turns retries off."
+                value={settings.jobRetryBudget}
+              />
+            )}
+            <NumberField
+              label="Sheet Append Delay"
+              onChange={(value) => onChange({ sheetAppendDelayMs: value })}
+              suffix="ms"
+              tooltip="Pause between a job's writes to Google Sheets, to stay under the per-minute write quota on large jobs. 0 writes as fast as Google allows."
+              value={settings.sheetAppendDelayMs ?? 0}
+            />
+          </div>
+          <div className="mt-4">
+            <FieldLabel>Retry On</FieldLabel>
+            <div className="flex flex-wrap gap-4">
+              {RETRY_CLASS_OPTIONS.map(({ key, label }) => (
+                <label
+                  className="flex items-center gap-2 text-xs text-[var(--app-foreground)]"
+                  key={key}
+                >
+                  <input
+                    checked={(settings.retryOn ?? DEFAULT_RETRY_POLICY)[key]}
+                    onChange={(event) =>
+                      onChange({
+                        retryOn: {
+                          ...(settings.retryOn ?? DEFAULT_RETRY_POLICY),
+                          [key]: event.target.checked,
+                        },
+                      })
+                    }
+                    type="checkbox"
+                  />
+                  {label}
+                </label>
+              ))}
+            </div>
+            <p className="mt-2 text-[11px] text-[var(--app-subtle)]">
+              Failures of an unchecked kind fail the file right away instead of retrying.
+            </p>
+          </div>
+          <label className="mt-4 flex items-center gap-2 text-xs text-[var(--app-foreground)]">
+            <input
+              checked={settings.jobRetryBudget != null}
+              onChange={(event) =>
+                onChange({
+                  jobRetryBudget: event.target.checked ? DEFAULT_JOB_RETRY_BUDGET : null,
+                })
+              }
+              type="checkbox"
+            />
+            Cap download retries per job
+          </label>
+          <label className="mt-4 flex items-center gap-2 text-xs text-[var(--app-foreground)]">
+            <input
+              checked={settings.adaptiveConcurrency ?? false}
+              onChange={(event) => onChange({ adaptiveConcurrency: event.target.checked })}
+              type="checkbox"
+            />
+            Tune download concurrency automatically
+          </label>
+          <p className="mt-2 text-[11px] text-[var(--app-subtle)]">
+            Starts at Max Concurrency, halves it when Google rate-limits downloads, and raises it
+            again while requests go through.
+          </p>
+          <label className="mt-4 flex items-center gap-2 text-xs text-[var(--app-foreground)]">
+            <input
+              checked={settings.preserveTextColumns ?? false}
+              onChange={(event) => onChange({ preserveTextColumns: event.target.checked })}
+              type="checkbox"
+            />
+            Write sheet values as plain text
+          </label>
+          <p className="mt-2 text-[11px] text-[var(--app-subtle)]">
+            Keeps phone numbers like +919876543210 intact instead of letting Sheets convert them.
+          </p>
+          <label className="mt-4 flex items-center gap-2 text-xs text-[var(--app-foreground)]">
+            <input
+              checked={settings.colorByConfidence ?? false}
+              onChange={(event) => onChange({ colorByConfidence: event.target.checked })}
+              type="checkbox"
+            />
+            Color rows by confidence
+          </label>
+          <p className="mt-2 text-[11px] text-[var(--app-subtle)]">
+            Adds a Confidence column to new sheets and shades rows green, yellow or red.
+          </p>
+          <label className="mt-2 flex items-center gap-2 text-xs text-[var(--app-foreground)]">
+            <input
+              checked={settings.confidenceAsPercent ?? false}
+              onChange={(event) => onChange({ confidenceAsPercent: event.target.checked })}
+              type="checkbox"
+            />
+            Show confidence as a percentage (85% instead of 0.85)
+          </label>
+          <p className="mt-2 text-[11px] text-[var(--app-subtle)]">
+            Adds the Confidence column to new sheets even when rows are not colored.
+          </p>
+          <label className="mt-4 flex items-center gap-2 text-xs text-[var(--app-foreground)]">
+            <input
+              checked={settings.emailDomainColumn ?? false}
+              onChange={(event) => onChange({ emailDomainColumn: event.target.checked })}
+              type="checkbox"
+            />
+            Add an Email Domain column
+          </label>
+          <p className="mt-2 text-[11px] text-[var(--app-subtle)]">
+            Shows the domain of each email (gmail.com, acme.com, …) on new sheets, for filtering.
+          </p>
+          <label className="mt-4 flex items-center gap-2 text-xs text-[var(--app-foreground)]">
+            <input
+              checked={settings.noticePeriodColumn ?? false}
+              onChange={(event) => onChange({ noticePeriodColumn: event.target.checked })}
+              type="checkbox"
+            />
+            Add a Notice Period column
+          </label>
+          <p className="mt-2 text-[11px] text-[var(--app-subtle)]">
+            Fills it from phrases like "notice period: 30 days" or "available immediately" on new sheets.
+          </p>
+          <label className="mt-4 flex items-center gap-2 text-xs text-[var(--app-foreground)]">
+            <input
+              checked={settings.educationColumn ?? false}
+              onChange={(event) => onChange({ educationColumn: event.target.checked })}
+              type="checkbox"
+            />
+            Add an Education column
+          </label>
+          <p className="mt-2 text-[11px] text-[var(--app-subtle)]">
+            Fills it from the first degree under an Education heading on new sheets.
+          </p>
+
+          <div className="mt-4">
+            <FieldLabel>Required Fields</FieldLabel>
+            <div className="flex flex-wrap gap-x-4 gap-y-2">
+              {REQUIRED_FIELD_OPTIONS.map((option) => (
+                <label
+                  className="flex items-center gap-2 text-xs text-[var(--app-foreground)]"
+                  key={option.value}
+                >
+                  <input
+                    checked={requiredFields.includes(option.value)}
+                    onChange={(event) => toggleRequiredField(option.value, event.target.checked)}
+                    type="checkbox"
+                  />
+                  {option.label}
+                </label>
+              ))}
+            </div>
+            <p className="mt-2 text-[11px] text-[var(--app-subtle)]">
+              Rows with none of the checked fields are left out of the sheet but kept in job
+              results. Leave all unchecked to write every non-empty row.
+            </p>
           </div>
         </Section>
 
@@ -138,6 +567,15 @@ export function SettingsView({
           {saving ? "Saving…" : "Save Settings"}
         </button>
 
+        {settings.restartRequired && (
+          <div className="mt-3">
+            <WarningNotice>
+              Restart the app to apply the changed certificate, user agent, extra header, or
+              queue size settings.
+            </WarningNotice>
+          </div>
+        )}
+
         <div className="mt-6">
           <InfoNotice>
             Settings are stored locally on this device. OAuth tokens are stored in the OS
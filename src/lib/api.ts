@@ -1,19 +1,35 @@
 import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 import type {
+  AggregateStats,
+  AppPaths,
   AuthStatus,
+  BatchEstimate,
   BatchParseRequest,
   CommandOk,
   DriveBrowserFile,
   DriveFolderEntry,
   DrivePathEntry,
+  ExtractedText,
+  FileTypeInfo,
+  FolderAccess,
+  FolderPreviewResponse,
+  FolderSample,
   GoogleSignInResult,
+  JobConfig,
+  JobDeletion,
+  JobFinishedEvent,
   JobStatus,
   ManualAuthChallenge,
   ManualAuthCompleteRequest,
   ParsedCandidate,
+  QueueStatus,
+  ResumeExport,
   RuntimeSettingsUpdate,
+  SheetColumn,
   RuntimeSettingsView,
   StartJobResponse,
+  WatchFolderRequest,
 } from "./types";
 
 export async function parseSingle(
@@ -26,6 +42,28 @@ export async function parseSingle(
   });
 }
 
+export async function debugExtractText(
+  fileName: string,
+  fileBytesBase64: string,
+): Promise<ExtractedText> {
+  return invoke<ExtractedText>("debug_extract_text", {
+    fileName,
+    fileBytesBase64,
+  });
+}
+
+export async function parseSingleAsRow(
+  fileName: string,
+  fileBytesBase64: string,
+  columns?: SheetColumn[],
+): Promise<string[]> {
+  return invoke<string[]>("parse_single_as_row", {
+    fileName,
+    fileBytesBase64,
+    columns,
+  });
+}
+
 export async function startBatchJob(
   request: BatchParseRequest,
 ): Promise<StartJobResponse> {
@@ -36,22 +74,85 @@ export async function getJobStatus(jobId: string): Promise<JobStatus> {
   return invoke<JobStatus>("get_job_status", { jobId });
 }
 
+export async function getJobConfig(jobId: string): Promise<JobConfig | null> {
+  return invoke<JobConfig | null>("get_job_config", { jobId });
+}
+
 export async function getJobResults(jobId: string): Promise<ParsedCandidate[]> {
   return invoke<ParsedCandidate[]>("get_job_results", { jobId });
 }
 
+export async function getJobCandidates(
+  jobId: string,
+  driveFileId: string,
+): Promise<ParsedCandidate[]> {
+  return invoke<ParsedCandidate[]>("get_job_candidates", { jobId, driveFileId });
+}
+
+export async function rescoreJob(jobId: string): Promise<ParsedCandidate[]> {
+  return invoke<ParsedCandidate[]>("rescore_job", { jobId });
+}
+
+export async function exportJobArchive(jobId: string, destPath: string): Promise<CommandOk> {
+  return invoke<CommandOk>("export_job_archive", { jobId, destPath });
+}
+
+export async function exportJobResumes(
+  jobId: string,
+  destPath: string,
+  onlyDriveIds?: string[] | null,
+): Promise<ResumeExport> {
+  return invoke<ResumeExport>("export_job_resumes", {
+    jobId,
+    destPath,
+    onlyDriveIds: onlyDriveIds ?? null,
+  });
+}
+
+export async function importJobArchive(srcPath: string): Promise<StartJobResponse> {
+  return invoke<StartJobResponse>("import_job_archive", { srcPath });
+}
+
 export async function listJobs(): Promise<string[]> {
   return invoke<string[]>("list_jobs");
 }
 
+export async function getAggregateStats(): Promise<AggregateStats> {
+  return invoke<AggregateStats>("aggregate_stats");
+}
+
+export async function getQueueStatus(): Promise<QueueStatus> {
+  return invoke<QueueStatus>("get_queue_status");
+}
+
 export async function cancelJob(jobId: string): Promise<CommandOk> {
   return invoke<CommandOk>("cancel_job", { jobId });
 }
 
+export async function pauseJob(jobId: string): Promise<CommandOk> {
+  return invoke<CommandOk>("pause_job", { jobId });
+}
+
+export async function resumeJob(jobId: string): Promise<CommandOk> {
+  return invoke<CommandOk>("resume_job", { jobId });
+}
+
 export async function killJob(jobId: string): Promise<CommandOk> {
   return invoke<CommandOk>("kill_job", { jobId });
 }
 
+export async function abortAndDeleteJob(jobId: string): Promise<JobDeletion> {
+  return invoke<JobDeletion>("abort_and_delete_job", { jobId });
+}
+
+export async function startWatch(request: WatchFolderRequest): Promise<CommandOk> {
+  return invoke<CommandOk>("start_watch", { request });
+}
+
+export async function stopWatch(folderId: string): Promise<CommandOk> {
+  return invoke<CommandOk>("stop_watch", { folderId });
+}
+
 export async function googleAuthSignIn(): Promise<GoogleSignInResult> {
   return invoke<GoogleSignInResult>("google_auth_sign_in");
 }
@@ -70,10 +171,18 @@ export async function googleAuthSignOut(): Promise<CommandOk> {
   return invoke<CommandOk>("google_auth_sign_out");
 }
 
+export async function cancelSignIn(): Promise<CommandOk> {
+  return invoke<CommandOk>("cancel_sign_in");
+}
+
 export async function googleAuthStatus(): Promise<AuthStatus> {
   return invoke<AuthStatus>("google_auth_status");
 }
 
+export async function canRunUnattended(): Promise<boolean> {
+  return invoke<boolean>("can_run_unattended");
+}
+
 export async function listDriveFolders(
   parentFolderId?: string,
 ): Promise<DriveFolderEntry[]> {
@@ -92,6 +201,27 @@ export async function getDriveFolderPath(
   return invoke<DrivePathEntry[]>("get_drive_folder_path", { folderId });
 }
 
+export async function checkFolderAccess(folderId: string): Promise<FolderAccess> {
+  return invoke<FolderAccess>("check_folder_access", { folderId });
+}
+
+export async function previewFolder(
+  folderId: string,
+): Promise<FolderPreviewResponse> {
+  return invoke<FolderPreviewResponse>("preview_folder", { folderId });
+}
+
+export async function estimateBatch(folderId: string): Promise<BatchEstimate> {
+  return invoke<BatchEstimate>("estimate_batch", { folderId });
+}
+
+export async function sampleFolder(
+  folderId: string,
+  sampleSize: number,
+): Promise<FolderSample> {
+  return invoke<FolderSample>("sample_folder", { folderId, sampleSize });
+}
+
 export async function getSettings(): Promise<RuntimeSettingsView> {
   return invoke<RuntimeSettingsView>("get_settings");
 }
@@ -101,3 +231,46 @@ export async function saveSettings(
 ): Promise<RuntimeSettingsView> {
   return invoke<RuntimeSettingsView>("save_settings", { settings });
 }
+
+export async function getPaths(): Promise<AppPaths> {
+  return invoke<AppPaths>("get_paths");
+}
+
+export async function supportedFileTypes(): Promise<FileTypeInfo[]> {
+  return invoke<FileTypeInfo[]>("supported_file_types");
+}
+
+export async function onAuthStatusChanged(
+  handler: (status: AuthStatus) => void,
+): Promise<UnlistenFn> {
+  return listen<AuthStatus>("auth-status-changed", (event) =>
+    handler(event.payload),
+  );
+}
+
+export async function onDeepLinkSignInFailed(
+  handler: (message: string) => void,
+): Promise<UnlistenFn> {
+  return listen<string>("auth-deep-link-failed", (event) =>
+    handler(event.payload),
+  );
+}
+
+export async function onProfileLaunchRejected(
+  handler: (message: string) => void,
+): Promise<UnlistenFn> {
+  return listen<string>("profile-launch-rejected", (event) =>
+    handler(event.payload),
+  );
+}
+
+export async function onJobFinished(
+  handler: (event: JobFinishedEvent) => void,
+): Promise<UnlistenFn> {
+  const unlisteners = await Promise.all(
+    ["job-complete", "job-failed", "job-cancelled"].map((name) =>
+      listen<JobFinishedEvent>(name, (event) => handler(event.payload)),
+    ),
+  );
+  return () => unlisteners.forEach((unlisten) => unlisten());
+}
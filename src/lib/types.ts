@@ -1,25 +1,125 @@
 export type JobProcessingState =
   | "pending"
   | "processing"
+  | "paused"
   | "completed"
   | "failed"
   | "revoked";
 
+export type ParseErrorKind =
+  | "missing_file_id"
+  | "download_failed"
+  | "file_unavailable"
+  | "timeout"
+  | "unsupported_file_type"
+  | "extraction_failed"
+  | "no_text_extracted"
+  | "other"
+  | "ocr_disabled"
+  | "metadata_fallback"
+  | "ocr_merge_failed"
+  | "split_document"
+  | "notice_period"
+  | "possible_duplicate"
+  | "missing_required_fields"
+  | "skipped_file_type"
+  | "plain_text_fallback"
+  | "name_from_filename"
+  | "education_uncertain"
+  | "warning";
+
+export interface ParseError {
+  code: ParseErrorKind;
+  message: string;
+}
+
 export interface ParsedCandidate {
   driveFileId?: string | null;
   sourceFile?: string | null;
+  mimeType?: string | null;
   name?: string | null;
   email?: string | null;
+  emailDomain?: string | null;
   phone?: string | null;
+  phoneExtension?: string | null;
   linkedIn?: string | null;
   gitHub?: string | null;
+  skills?: string[];
+  headline?: string | null;
+  noticePeriod?: string | null;
+  fieldSources?: Record<string, string> | null;
+  nameCandidates?: string[] | null;
+  ocrUsed?: boolean;
+  parseDurationMs?: number | null;
+  custom?: Record<string, string>;
+  education?: string | null;
   confidence: number;
-  errors: string[];
+  errors: ParseError[];
 }
 
 export interface BatchParseRequest {
   folderId: string;
   spreadsheetId?: string | null;
+  nameIncludeRegex?: string | null;
+  nameExcludeRegex?: string | null;
+  skipSpreadsheet?: boolean;
+  completionWebhookUrl?: string | null;
+  splitMultiResume?: boolean;
+  idempotencyKey?: string | null;
+  skipExistingInSheet?: boolean;
+  spreadsheetTitle?: string | null;
+  flagPossibleDuplicates?: boolean;
+  reviewSpreadsheetId?: string | null;
+  reviewConfidenceThreshold?: number | null;
+  modifiedAfter?: string | null;
+  allowedMimeTypes?: string[];
+  fileIds?: string[];
+}
+
+export interface FileTypeInfo {
+  extension: string;
+  mimeType: string;
+}
+
+export interface WatchFolderRequest {
+  folderId: string;
+  intervalSeconds: number;
+  spreadsheetId?: string | null;
+}
+
+export interface BatchEstimate {
+  fileCount: number;
+  filesByType: Record<string, number>;
+  likelyScannedPdfs: number;
+  estimatedMinSeconds: number;
+  estimatedMaxSeconds: number;
+  note: string;
+}
+
+export interface FieldHitRate {
+  field: FieldKind;
+  rate: number;
+}
+
+export interface AggregateStats {
+  jobCount: number;
+  unreadableJobs: number;
+  totalFiles: number;
+  failedFiles: number;
+  ocrRate: number;
+  averageConfidence: number;
+  fieldHitRates: FieldHitRate[];
+}
+
+export interface FolderSample {
+  totalFiles: number;
+  sampledFiles: number;
+  failedFiles: number;
+  averageConfidence: number;
+  highConfidence: number;
+  mediumConfidence: number;
+  lowConfidence: number;
+  fieldHitRates: FieldHitRate[];
 }
 
 export interface DriveFolderEntry {
@@ -28,6 +128,18 @@ export interface DriveFolderEntry {
   mimeType: string;
 }
 
+export interface DriveFileRef {
+  id: string;
+  name: string;
+  mimeType: string;
+  size?: number | null;
+}
+
+export interface FolderPreviewResponse {
+  fileCount: number;
+  files: DriveFileRef[];
+}
+
 export interface DriveBrowserFile {
   id: string;
   name: string;
@@ -41,6 +153,34 @@ export interface DrivePathEntry {
   name: string;
 }
 
+export type FolderAccessStatus = "accessible" | "notFound" | "notAFolder" | "permissionDenied";
+
+export interface FolderAccess {
+  folderId: string;
+  status: FolderAccessStatus;
+  name?: string | null;
+  message: string;
+}
+
+export interface ResumeExportFailure {
+  driveFileId: string;
+  sourceFile?: string | null;
+  message: string;
+}
+
+export interface ExtractedText {
+  text: string;
+  ocrUsed: boolean;
+  errors: ParseError[];
+  note?: string | null;
+}
+
+export interface ResumeExport {
+  exportedCount: number;
+  totalBytes: number;
+  failed: ResumeExportFailure[];
+}
+
 export interface JobStatus {
   jobId: string;
   status: JobProcessingState;
@@ -56,25 +196,160 @@ export interface JobStatus {
   durationSeconds?: number | null;
 }
 
+export interface JobFinishedEvent {
+  status: JobStatus;
+  spreadsheetUrl?: string | null;
+  parsedCount: number;
+  failedCount: number;
+  ocrCount: number;
+}
+
+export type SheetColumn =
+  | "name"
+  | "resumeLink"
+  | "phone"
+  | "email"
+  | "emailDomain"
+  | "linkedIn"
+  | "gitHub"
+  | "skills"
+  | "noticePeriod"
+  | "education"
+  | "confidence";
+
+export type FieldKind = "name" | "email" | "phone" | "linkedIn" | "gitHub" | "skills";
+
+export interface RetryPolicy {
+  rateLimit: boolean;
+  serverError: boolean;
+  network: boolean;
+}
+
+export interface ConfidenceWeights {
+  email: number;
+  phone: number;
+  name: number;
+  linkedIn: number;
+  gitHub: number;
+  textLayer: number;
+}
+
+export type EmailPreference = "first" | "preferPersonal" | "preferCorporate";
+
+export type NationalPhonePolicy = "keep" | "drop";
+
+export type UnsupportedFilePolicy = "skip" | "warn" | "extractText";
+
+export type SortOrder = "none" | "confidenceDesc" | "nameAsc";
+
+export interface CustomFieldRule {
+  name: string;
+  pattern: string;
+}
+
+export type JobConfig = Omit<
+  RuntimeSettingsView,
+  | "googleClientSecretConfigured"
+  | "legacySecretScrubbed"
+  | "restartRequired"
+  | "tesseractVersion"
+>;
+
 export interface RuntimeSettingsView {
   googleClientId: string;
   googleClientSecretConfigured: boolean;
   legacySecretScrubbed: boolean;
+  restartRequired?: boolean;
   tesseractPath: string;
+  tesseractVersion?: string | null;
   maxConcurrentRequests: number;
+  maxConcurrentDownloads?: number | null;
+  maxConcurrentParses?: number | null;
   spreadsheetBatchSize: number;
   maxRetries: number;
   retryDelaySeconds: number;
   jobRetentionHours: number;
+  customCaCertPath?: string | null;
+  emailPreference?: EmailPreference;
+  userAgentSuffix?: string | null;
+  skillDictionary?: string[];
+  disableOcr?: boolean;
+  statusWriteIntervalMs?: number;
+  preserveTextColumns?: boolean;
+  spreadsheetTitleTemplate?: string;
+  colorByConfidence?: boolean;
+  mergeOcrFields?: boolean;
+  maxQueuedJobs?: number;
+  debugFieldSources?: boolean;
+  requiredFields?: FieldKind[];
+  confidenceAsPercent?: boolean;
+  confidenceWeights?: ConfidenceWeights;
+  nameDenylist?: string[];
+  keepOcrTemp?: boolean;
+  retryOn?: RetryPolicy;
+  cleanupIntervalMinutes?: number;
+  noticePeriodColumn?: boolean;
+  compactResults?: boolean;
+  sheetAppendPageSize?: number | null;
+  missingValuePlaceholder?: string;
+  unsupportedFilePolicy?: UnsupportedFilePolicy;
+  adaptiveConcurrency?: boolean;
+  customFields?: CustomFieldRule[];
+  sortOrder?: SortOrder;
+  jobRetryBudget?: number | null;
+  sheetAppendDelayMs?: number;
+  deobfuscateEmails?: boolean;
+  educationColumn?: boolean;
+  extraHeaders?: Record<string, string>;
+  requireInternationalPhone?: boolean;
+  nationalPhonePolicy?: NationalPhonePolicy;
+  emailDomainColumn?: boolean;
 }
 
 export interface RuntimeSettingsUpdate {
   tesseractPath: string;
   maxConcurrentRequests: number;
+  maxConcurrentDownloads?: number | null;
+  maxConcurrentParses?: number | null;
   spreadsheetBatchSize: number;
   maxRetries: number;
   retryDelaySeconds: number;
   jobRetentionHours: number;
+  customCaCertPath?: string | null;
+  emailPreference?: EmailPreference;
+  userAgentSuffix?: string | null;
+  skillDictionary?: string[];
+  disableOcr?: boolean;
+  statusWriteIntervalMs?: number;
+  preserveTextColumns?: boolean;
+  spreadsheetTitleTemplate?: string;
+  colorByConfidence?: boolean;
+  mergeOcrFields?: boolean;
+  maxQueuedJobs?: number;
+  debugFieldSources?: boolean;
+  requiredFields?: FieldKind[];
+  confidenceAsPercent?: boolean;
+  confidenceWeights?: ConfidenceWeights;
+  nameDenylist?: string[];
+  keepOcrTemp?: boolean;
+  retryOn?: RetryPolicy;
+  cleanupIntervalMinutes?: number;
+  noticePeriodColumn?: boolean;
+  compactResults?: boolean;
+  sheetAppendPageSize?: number | null;
+  missingValuePlaceholder?: string;
+  unsupportedFilePolicy?: UnsupportedFilePolicy;
+  adaptiveConcurrency?: boolean;
+  customFields?: CustomFieldRule[];
+  sortOrder?: SortOrder;
+  jobRetryBudget?: number | null;
+  sheetAppendDelayMs?: number;
+  deobfuscateEmails?: boolean;
+  educationColumn?: boolean;
+  extraHeaders?: Record<string, string>;
+  requireInternationalPhone?: boolean;
+  nationalPhonePolicy?: NationalPhonePolicy;
+  emailDomainColumn?: boolean;
 }
 
 export interface AuthStatus {
@@ -109,6 +384,24 @@ export interface ManualAuthCompleteRequest {
   callbackUrlOrCode: string;
 }
 
+export interface JobDeletion {
+  jobId: string;
+  cancelled: boolean;
+  deleted: boolean;
+}
+
+export interface QueueStatus {
+  queuedJobs: number;
+  runningJobs: number;
+}
+
+export interface AppPaths {
+  dataRoot: string;
+  settingsFile: string;
+  jobsRoot: string;
+  logsDir: string;
+}
+
 export interface StartJobResponse {
   jobId: string;
 }
@@ -104,7 +104,9 @@ export function isTerminalJobState(state?: JobProcessingState | null): boolean {
 
 export function isSupportedResumeFileName(name: string): boolean {
   const lower = name.toLowerCase();
-  return lower.endsWith(".pdf") || lower.endsWith(".docx");
+  return (
+    lower.endsWith(".pdf") || lower.endsWith(".docx") || lower.endsWith(".odt")
+  );
 }
 
 export function isSupportedResumeFile(file: Pick<DriveBrowserFile, "name" | "mimeType">): boolean {
@@ -113,7 +115,8 @@ export function isSupportedResumeFile(file: Pick<DriveBrowserFile, "name" | "mim
     isSupportedResumeFileName(file.name) ||
     mime === "application/pdf" ||
     mime ===
-      "application/vnd.openxmlformats-officedocument.wordprocessingml.document"
+      "application/vnd.openxmlformats-officedocument.wordprocessingml.document" ||
+    mime === "application/vnd.oasis.opendocument.text"
   );
 }
 